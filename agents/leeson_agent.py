@@ -18,6 +18,7 @@ class Agent:
 
     def __init__(self, agent_index: int) -> None:
         self.agent_index = agent_index
+        self.paused = False
 
     # -- Outbound messages (agent → TUI) --
 
@@ -34,6 +35,23 @@ class Agent:
         """Signal that the agent is ready to receive messages."""
         self._send({"type": "ready"})
 
+    def supports_json(self) -> None:
+        """Ask the TUI to send agent risk parameters as JSON."""
+        self._send({"type": "supports_json"})
+
+    def price_alert(self, symbol: str, direction: str, threshold: str) -> None:
+        """Ask to be told once the last price of ``symbol`` is at or
+        ``"above"``/``"below"`` ``threshold``. Fires once via
+        ``on_price_alert``."""
+        self._send(
+            {
+                "type": "price_alert",
+                "symbol": symbol,
+                "direction": direction,
+                "threshold": threshold,
+            }
+        )
+
     def place_order(
         self,
         symbol: str,
@@ -43,7 +61,12 @@ class Agent:
         price: str | None = None,
         cl_ord_id: str | None = None,
     ) -> None:
-        """Submit an order request to the TUI for risk check and execution."""
+        """Submit an order request to the TUI for risk check and execution.
+
+        Does nothing while the operator has paused the agent.
+        """
+        if self.paused:
+            return
         msg: dict = {
             "type": "place_order",
             "symbol": symbol,
@@ -71,9 +94,30 @@ class Agent:
     def on_trade(self, data: list[dict]) -> None:
         """Called on market trades."""
 
+    def on_book(self, symbol: str, book: str) -> None:
+        """Called with a throttled top-of-book snapshot.
+
+        ``book`` is formatted as ``BID:<price>x<qty>,...|ASK:<price>x<qty>,...``.
+        """
+
+    def on_order_book(self, symbol: str, bids: list, asks: list) -> None:
+        """Called with a throttled structured top-of-book (agent 0 only).
+
+        ``bids`` and ``asks`` are ``[price, qty]`` string pairs, best first.
+        """
+
     def on_balance(self, data: list[dict]) -> None:
         """Called on balance changes."""
 
+    def on_position_snapshot(
+        self, positions: dict[str, str], avg_entries: dict[str, str]
+    ) -> None:
+        """Called after every execution with net positions and average entry
+        prices keyed by symbol, as decimal strings."""
+
+    def on_price_alert(self, symbol: str, price: str) -> None:
+        """Called when a requested price alert fires."""
+
     def on_order_response(
         self,
         success: bool,
@@ -87,9 +131,18 @@ class Agent:
     def on_risk_limits(self, description: str) -> None:
         """Called when risk configuration is sent to the agent."""
 
+    def on_risk_limits_json(self, params: dict) -> None:
+        """Called with agent risk parameters once `supports_json` was sent."""
+
     def on_token_state(self, state: str) -> None:
         """Called when the authentication token state changes."""
 
+    def on_pause(self) -> None:
+        """Called when the operator pauses order submissions."""
+
+    def on_resume(self) -> None:
+        """Called when the operator resumes order submissions."""
+
     def on_shutdown(self) -> None:
         """Called when the TUI requests a graceful shutdown."""
 
@@ -116,8 +169,20 @@ class Agent:
                     self.on_ticker(msg.get("data", {}))
                 elif msg_type == "trade_update":
                     self.on_trade(msg.get("data", []))
+                elif msg_type == "book_snapshot":
+                    self.on_book(msg.get("symbol", ""), msg.get("book", ""))
+                elif msg_type == "order_book":
+                    self.on_order_book(
+                        msg.get("symbol", ""), msg.get("bids", []), msg.get("asks", [])
+                    )
                 elif msg_type == "balance_update":
                     self.on_balance(msg.get("data", []))
+                elif msg_type == "position_snapshot":
+                    self.on_position_snapshot(
+                        msg.get("positions", {}), msg.get("avg_entries", {})
+                    )
+                elif msg_type == "price_alert_triggered":
+                    self.on_price_alert(msg.get("symbol", ""), msg.get("price", ""))
                 elif msg_type == "order_response":
                     self.on_order_response(
                         success=msg.get("success", False),
@@ -128,8 +193,16 @@ class Agent:
                     )
                 elif msg_type == "risk_limits":
                     self.on_risk_limits(msg.get("description", ""))
+                elif msg_type == "risk_limits_json":
+                    self.on_risk_limits_json(msg.get("params", {}))
                 elif msg_type == "token_state":
                     self.on_token_state(msg.get("state", ""))
+                elif msg_type == "pause":
+                    self.paused = True
+                    self.on_pause()
+                elif msg_type == "resume":
+                    self.paused = False
+                    self.on_resume()
                 elif msg_type == "shutdown":
                     self.on_shutdown()
                     break
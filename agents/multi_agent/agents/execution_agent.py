@@ -54,6 +54,9 @@ async def place_order(
         price: Limit price (required for limit orders).
         cl_ord_id: Optional client order ID for tracking.
     """
+    if ctx.deps.state.paused:
+        output_to_panel(PANEL, f"[exec] ORDER HELD (paused): {symbol} {side} qty={qty}")
+        return "Order not submitted: the operator has paused order submissions"
     send_place_order(
         symbol=symbol,
         side=side,
@@ -196,6 +196,14 @@ async def _route_stdin_messages(
                 state.token_state = msg.get("state", "unknown")
                 output_to_panel(0, f"Token state: {state.token_state}")
 
+            elif msg_type == "pause":
+                state.paused = True
+                output_to_panel(0, "[system] Order submissions paused")
+
+            elif msg_type == "resume":
+                state.paused = False
+                output_to_panel(0, "[system] Order submissions resumed")
+
             elif msg_type == "shutdown":
                 output_to_panel(0, "[system] Shutdown requested")
                 state.shutting_down = True
@@ -53,6 +53,8 @@ class SharedState:
     pairs_ready: asyncio.Event = field(default_factory=asyncio.Event)
     token_state: str = "unknown"
     shutting_down: bool = False
+    # Set while the operator has paused order submissions
+    paused: bool = False
     # Track last analyzed price per symbol for rate limiting
     last_analyzed_price: dict[str, float] = field(default_factory=dict)
     # Cumulative token usage across all LLM calls
@@ -0,0 +1,69 @@
+//! Compares price-level lookup in the `Vec`-backed book used for level-2
+//! data with the `BTreeMap`-backed [`Level3OrderBook`].
+
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use rust_decimal::Decimal;
+
+use leeson::models::book::PriceLevel;
+use leeson::models::orders::{Level3OrderBook, OrderEntry, OrdersData};
+
+const LEVELS: i64 = 1000;
+
+fn price(level: i64) -> Decimal {
+    Decimal::new(5_000_000 - level * 50, 2)
+}
+
+fn vec_book() -> Vec<PriceLevel> {
+    (0..LEVELS)
+        .map(|level| PriceLevel {
+            price: price(level),
+            qty: Decimal::ONE,
+        })
+        .collect()
+}
+
+fn level3_book() -> Level3OrderBook {
+    let bids = (0..LEVELS)
+        .map(|level| OrderEntry {
+            event: None,
+            order_id: format!("O{level:05}"),
+            limit_price: price(level),
+            order_qty: Decimal::ONE,
+            timestamp: "2024-01-01T00:00:00.000000Z".to_string(),
+        })
+        .collect();
+    let mut book = Level3OrderBook::default();
+    book.apply_snapshot(&OrdersData {
+        symbol: "BTC/USD".to_string(),
+        bids,
+        asks: Vec::new(),
+        checksum: 0,
+        timestamp: "2024-01-01T00:00:00.000000Z".to_string(),
+    });
+    book
+}
+
+fn level_lookup(c: &mut Criterion) {
+    let levels = vec_book();
+    let book = level3_book();
+    // The deepest level is the worst case for a linear scan
+    let target = price(LEVELS - 1);
+
+    let mut group = c.benchmark_group("level_lookup_1000");
+    group.bench_function("vec_linear", |b| {
+        b.iter(|| {
+            black_box(&levels)
+                .iter()
+                .find(|l| l.price == black_box(target))
+        })
+    });
+    group.bench_function("btreemap", |b| {
+        b.iter(|| black_box(&book).bids_at(black_box(target)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, level_lookup);
+criterion_main!(benches);
@@ -3,16 +3,35 @@
 //! Spawns Python agent processes and bridges their stdin/stdout/stderr
 //! with the TUI via JSON-lines over pipes.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rust_decimal::Decimal;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
 use tokio::sync::mpsc;
 
 use crate::models::balance::BalanceData;
+use crate::models::book::PriceLevel;
 use crate::models::execution::ExecutionData;
 use crate::models::ticker::TickerData;
 use crate::models::trade::TradeData;
+use crate::risk::config::AgentRiskParams;
 use crate::tui::Message;
 
+/// Number of levels per side included in a [`AgentCommand::BookSnapshot`].
+pub const BOOK_SNAPSHOT_DEPTH: usize = 10;
+
+/// Commands buffered per agent before senders have to wait.
+const COMMAND_BUFFER: usize = 256;
+
+/// Pending price alerts kept per agent; adding another drops its oldest.
+pub const MAX_ALERTS_PER_AGENT: usize = 20;
+
+/// Index of the multi-agent process. Its sub-agents share all three output
+/// panels, and every order it submits carries this index.
+pub const MULTI_AGENT_INDEX: usize = 0;
+
 /// Commands sent from the TUI to an agent subprocess.
 #[derive(Debug, Clone)]
 pub enum AgentCommand {
@@ -20,6 +39,9 @@ pub enum AgentCommand {
     UserMessage(String),
     /// Risk limits description for the agent's system prompt.
     RiskLimits(String),
+    /// Agent risk parameters as a JSON object, sent instead of their text
+    /// description to agents that announced JSON support.
+    RiskLimitsJson(String),
     /// Structured result of an order placement attempt.
     OrderResponse {
         success: bool,
@@ -36,22 +58,214 @@ pub enum AgentCommand {
     TickerUpdate(TickerData),
     /// Market trades.
     TradeUpdate(Vec<TradeData>),
+    /// Throttled top-of-book levels for a single trading pair, best first.
+    BookSnapshot {
+        symbol: String,
+        bids: Vec<PriceLevel>,
+        asks: Vec<PriceLevel>,
+    },
+    /// Throttled top-of-book levels for a single trading pair as
+    /// `(price, qty)` pairs, best first, for agents that parse the book
+    /// programmatically.
+    OrderBook {
+        symbol: String,
+        bids: Vec<(Decimal, Decimal)>,
+        asks: Vec<(Decimal, Decimal)>,
+    },
     /// Balance changes.
     BalanceUpdate(Vec<BalanceData>),
+    /// Net position and average entry price per symbol, sent after every
+    /// execution.
+    PositionSnapshot {
+        positions: HashMap<String, Decimal>,
+        avg_entries: HashMap<String, Decimal>,
+    },
+    /// A price alert the agent requested has fired at `price`.
+    PriceAlertTriggered { symbol: String, price: Decimal },
     /// Active trading pairs selected by the operator.
     ActivePairs(Vec<String>),
+    /// Stop submitting orders until [`AgentCommand::Resume`] arrives.
+    Pause,
+    /// Resume submitting orders after [`AgentCommand::Pause`].
+    Resume,
     /// Request the agent to shut down gracefully.
     Shutdown,
 }
 
+/// Which way the price has to cross a [`PriceAlert`] threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertDirection {
+    /// Fires once the price is at or above the threshold.
+    Above,
+    /// Fires once the price is at or below the threshold.
+    Below,
+}
+
+impl std::fmt::Display for AlertDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Above => write!(f, "above"),
+            Self::Below => write!(f, "below"),
+        }
+    }
+}
+
+/// One-shot price alert requested by an agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceAlert {
+    /// Agent to notify when the alert fires.
+    pub agent_index: usize,
+    /// Trading pair symbol (e.g., `"BTC/USD"`).
+    pub symbol: String,
+    /// Which side of `threshold` fires the alert.
+    pub direction: AlertDirection,
+    /// Price level to watch.
+    pub threshold: Decimal,
+}
+
+impl PriceAlert {
+    /// Returns `true` if `price` has reached the threshold.
+    #[must_use]
+    pub fn is_triggered_by(&self, price: Decimal) -> bool {
+        match self.direction {
+            AlertDirection::Above => price >= self.threshold,
+            AlertDirection::Below => price <= self.threshold,
+        }
+    }
+}
+
+/// Pending price alerts, checked against every ticker update.
+#[derive(Debug, Default)]
+pub struct PriceAlerts {
+    alerts: Vec<PriceAlert>,
+}
+
+impl PriceAlerts {
+    /// Adds an alert to watch until it fires, dropping the agent's oldest
+    /// pending alert once it has [`MAX_ALERTS_PER_AGENT`].
+    pub fn add(&mut self, alert: PriceAlert) {
+        let pending = self
+            .alerts
+            .iter()
+            .filter(|a| a.agent_index == alert.agent_index)
+            .count();
+        if pending >= MAX_ALERTS_PER_AGENT
+            && let Some(oldest) = self
+                .alerts
+                .iter()
+                .position(|a| a.agent_index == alert.agent_index)
+        {
+            self.alerts.remove(oldest);
+        }
+        self.alerts.push(alert);
+    }
+
+    /// Removes and returns the alerts on `symbol` that `price` triggers.
+    pub fn check(&mut self, symbol: &str, price: Decimal) -> Vec<PriceAlert> {
+        self.alerts
+            .extract_if(.., |alert| {
+                alert.symbol == symbol && alert.is_triggered_by(price)
+            })
+            .collect()
+    }
+
+    /// Drops every pending alert of an agent, e.g. once it has exited.
+    pub fn clear_agent(&mut self, agent_index: usize) {
+        self.alerts.retain(|alert| alert.agent_index != agent_index);
+    }
+
+    /// Drops every pending alert on `symbol`, e.g. once it is unsubscribed.
+    pub fn clear_symbol(&mut self, symbol: &str) {
+        self.alerts.retain(|alert| alert.symbol != symbol);
+    }
+
+    /// Returns the number of pending alerts.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.alerts.len()
+    }
+
+    /// Returns `true` if no alerts are pending.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.alerts.is_empty()
+    }
+}
+
 /// Handle to a running agent subprocess.
 ///
 /// Dropping the handle kills the child process (via `kill_on_drop`).
 pub struct AgentHandle {
     /// Sender for commands to the agent's stdin writer task.
-    pub commands: mpsc::UnboundedSender<AgentCommand>,
+    commands: mpsc::Sender<AgentCommand>,
     /// The child process (kept alive; killed on drop).
-    _child: Child,
+    child: Child,
+    /// Whether the agent announced it can parse structured JSON payloads.
+    supports_json: bool,
+}
+
+impl AgentHandle {
+    /// Returns `true` once the agent has announced JSON support.
+    #[must_use]
+    pub fn supports_json(&self) -> bool {
+        self.supports_json
+    }
+
+    /// Records that the agent can parse structured JSON payloads.
+    pub fn enable_json(&mut self) {
+        self.supports_json = true;
+    }
+
+    /// Queues `cmd` for the agent, waiting up to `timeout` for room in its
+    /// command buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::AgentTimeout`](crate::LeesonError::AgentTimeout)
+    /// if the agent is too slow to drain its buffer, or
+    /// [`LeesonError::Channel`](crate::LeesonError::Channel) if its stdin
+    /// writer has stopped.
+    pub async fn send_timeout(&self, cmd: AgentCommand, timeout: Duration) -> crate::Result<()> {
+        match tokio::time::timeout(timeout, self.commands.send(cmd)).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) => Err(crate::LeesonError::Channel(
+                "agent command channel closed".to_string(),
+            )),
+            Err(_) => Err(crate::LeesonError::AgentTimeout { timeout }),
+        }
+    }
+
+    /// Asks the agent to shut down and waits up to `duration` for it to exit.
+    ///
+    /// If the process is still running once `duration` has elapsed it is
+    /// killed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Io`](crate::LeesonError::Io) if the agent had
+    /// to be killed or waiting on the process failed.
+    pub async fn shutdown_timeout(&mut self, duration: Duration) -> crate::Result<()> {
+        let _ = self.commands.try_send(AgentCommand::Shutdown);
+
+        match tokio::time::timeout(duration, self.child.wait()).await {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(crate::LeesonError::io("failed to wait for agent", e)),
+            Err(_) => {
+                self.child
+                    .kill()
+                    .await
+                    .map_err(|e| crate::LeesonError::io("failed to kill agent", e))?;
+                Err(crate::LeesonError::io(
+                    format!(
+                        "agent did not exit within {}ms and was killed",
+                        duration.as_millis()
+                    ),
+                    std::io::ErrorKind::TimedOut.into(),
+                ))
+            }
+        }
+    }
 }
 
 /// JSON message from a Python agent (stdout).
@@ -87,6 +301,12 @@ enum AgentToTui {
     StreamEnd {
         agent: usize,
     },
+    SupportsJson,
+    PriceAlert {
+        symbol: String,
+        direction: AlertDirection,
+        threshold: Decimal,
+    },
 }
 
 /// JSON message from the TUI to a Python agent (stdin).
@@ -99,6 +319,9 @@ enum TuiToAgent {
     RiskLimits {
         description: String,
     },
+    RiskLimitsJson {
+        params: serde_json::Value,
+    },
     OrderResponse {
         success: bool,
         order_id: Option<String>,
@@ -118,15 +341,70 @@ enum TuiToAgent {
     TradeUpdate {
         data: Vec<TradeData>,
     },
+    BookSnapshot {
+        symbol: String,
+        book: String,
+    },
+    OrderBook {
+        symbol: String,
+        bids: Vec<(Decimal, Decimal)>,
+        asks: Vec<(Decimal, Decimal)>,
+    },
     BalanceUpdate {
         data: Vec<BalanceData>,
     },
+    PositionSnapshot {
+        positions: HashMap<String, Decimal>,
+        avg_entries: HashMap<String, Decimal>,
+    },
+    PriceAlertTriggered {
+        symbol: String,
+        price: Decimal,
+    },
     ActivePairs {
         pairs: Vec<String>,
     },
+    Pause,
+    Resume,
     Shutdown,
 }
 
+/// Builds the commands that deliver risk limits to an agent.
+///
+/// `limits` describes the enforced limits. Agents that support JSON get it
+/// followed by `params` as [`AgentCommand::RiskLimitsJson`]; all others get
+/// a single text description of both.
+#[must_use]
+pub fn risk_limit_commands(
+    limits: &str,
+    params: &AgentRiskParams,
+    supports_json: bool,
+) -> Vec<AgentCommand> {
+    if supports_json {
+        vec![
+            AgentCommand::RiskLimits(limits.to_string()),
+            AgentCommand::RiskLimitsJson(params.to_json()),
+        ]
+    } else {
+        vec![AgentCommand::RiskLimits(format!(
+            "{limits}{}",
+            params.describe()
+        ))]
+    }
+}
+
+/// Formats book levels compactly as `BID:<price>x<qty>,...|ASK:<price>x<qty>,...`.
+fn format_book(bids: &[PriceLevel], asks: &[PriceLevel]) -> String {
+    let side = |levels: &[PriceLevel]| {
+        levels
+            .iter()
+            .map(|level| format!("{}x{}", level.price, level.qty))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    format!("BID:{}|ASK:{}", side(bids), side(asks))
+}
+
 /// Spawns a Python agent subprocess and wires its I/O to the TUI message channel.
 ///
 /// Returns an [`AgentHandle`] that can be used to send commands to the agent.
@@ -144,7 +422,7 @@ pub fn spawn_agent(
         .stderr(std::process::Stdio::piped())
         .kill_on_drop(true)
         .spawn()
-        .map_err(|e| crate::LeesonError::Io(format!("failed to spawn agent {agent_index}: {e}")))?;
+        .map_err(|e| crate::LeesonError::io(format!("failed to spawn agent {agent_index}"), e))?;
 
     wire_agent_io(agent_index, child, tx)
 }
@@ -172,7 +450,7 @@ pub fn spawn_multi_agent(
         .stderr(std::process::Stdio::piped())
         .kill_on_drop(true)
         .spawn()
-        .map_err(|e| crate::LeesonError::Io(format!("failed to spawn multi-agent system: {e}")))?;
+        .map_err(|e| crate::LeesonError::io("failed to spawn multi-agent system", e))?;
 
     wire_agent_io(agent_index, child, tx)
 }
@@ -197,7 +475,7 @@ fn wire_agent_io(
         .expect("stdin piped but missing from child");
 
     // Channel for TUI → agent commands
-    let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<AgentCommand>();
+    let (cmd_tx, cmd_rx) = mpsc::channel::<AgentCommand>(COMMAND_BUFFER);
 
     // Stdout reader: parse JSON-lines, forward to TUI
     spawn_stdout_reader(agent_index, stdout, tx.clone());
@@ -210,7 +488,8 @@ fn wire_agent_io(
 
     Ok(AgentHandle {
         commands: cmd_tx,
-        _child: child,
+        child,
+        supports_json: false,
     })
 }
 
@@ -275,6 +554,21 @@ fn spawn_stdout_reader(
                 Ok(AgentToTui::StreamEnd { agent }) => {
                     let _ = tx.try_send(Message::AgentStreamEnd { agent_index: agent });
                 }
+                Ok(AgentToTui::SupportsJson) => {
+                    let _ = tx.try_send(Message::AgentSupportsJson(agent_index));
+                }
+                Ok(AgentToTui::PriceAlert {
+                    symbol,
+                    direction,
+                    threshold,
+                }) => {
+                    let _ = tx.try_send(Message::AgentPriceAlert(PriceAlert {
+                        agent_index,
+                        symbol,
+                        direction,
+                        threshold,
+                    }));
+                }
                 Err(_) => {
                     // Non-JSON line — pass through as raw output
                     let _ = tx.try_send(Message::AgentOutput { agent_index, line });
@@ -309,7 +603,7 @@ fn spawn_stderr_reader(
 fn spawn_stdin_writer(
     agent_index: usize,
     mut stdin: tokio::process::ChildStdin,
-    mut cmd_rx: mpsc::UnboundedReceiver<AgentCommand>,
+    mut cmd_rx: mpsc::Receiver<AgentCommand>,
     tx: mpsc::Sender<Message>,
 ) {
     tokio::spawn(async move {
@@ -317,6 +611,13 @@ fn spawn_stdin_writer(
             let msg = match cmd {
                 AgentCommand::UserMessage(content) => TuiToAgent::UserMessage { content },
                 AgentCommand::RiskLimits(description) => TuiToAgent::RiskLimits { description },
+                AgentCommand::RiskLimitsJson(json) => match serde_json::from_str(&json) {
+                    Ok(params) => TuiToAgent::RiskLimitsJson { params },
+                    Err(e) => {
+                        tracing::warn!(agent_index, "dropping malformed risk limits JSON: {e}");
+                        continue;
+                    }
+                },
                 AgentCommand::OrderResponse {
                     success,
                     order_id,
@@ -334,8 +635,27 @@ fn spawn_stdin_writer(
                 AgentCommand::ExecutionUpdate(data) => TuiToAgent::ExecutionUpdate { data },
                 AgentCommand::TickerUpdate(data) => TuiToAgent::TickerUpdate { data },
                 AgentCommand::TradeUpdate(data) => TuiToAgent::TradeUpdate { data },
+                AgentCommand::BookSnapshot { symbol, bids, asks } => TuiToAgent::BookSnapshot {
+                    symbol,
+                    book: format_book(&bids, &asks),
+                },
+                AgentCommand::OrderBook { symbol, bids, asks } => {
+                    TuiToAgent::OrderBook { symbol, bids, asks }
+                }
                 AgentCommand::BalanceUpdate(data) => TuiToAgent::BalanceUpdate { data },
+                AgentCommand::PositionSnapshot {
+                    positions,
+                    avg_entries,
+                } => TuiToAgent::PositionSnapshot {
+                    positions,
+                    avg_entries,
+                },
+                AgentCommand::PriceAlertTriggered { symbol, price } => {
+                    TuiToAgent::PriceAlertTriggered { symbol, price }
+                }
                 AgentCommand::ActivePairs(pairs) => TuiToAgent::ActivePairs { pairs },
+                AgentCommand::Pause => TuiToAgent::Pause,
+                AgentCommand::Resume => TuiToAgent::Resume,
                 AgentCommand::Shutdown => TuiToAgent::Shutdown,
             };
             let mut json =
@@ -354,3 +674,257 @@ fn spawn_stdin_writer(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn level(price: rust_decimal::Decimal, qty: rust_decimal::Decimal) -> PriceLevel {
+        PriceLevel { price, qty }
+    }
+
+    fn spawn_shell(script: &str) -> (AgentHandle, mpsc::Receiver<Message>) {
+        let child = Command::new("sh")
+            .args(["-c", script])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let (tx, rx) = mpsc::channel(16);
+        (wire_agent_io(0, child, tx).unwrap(), rx)
+    }
+
+    #[tokio::test]
+    async fn shutdown_timeout_waits_for_exit() {
+        // Exits as soon as the shutdown line arrives on stdin.
+        let (mut handle, _rx) = spawn_shell("read line");
+
+        handle
+            .shutdown_timeout(Duration::from_secs(5))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn shutdown_timeout_kills_unresponsive_agent() {
+        let (mut handle, _rx) = spawn_shell("sleep 30");
+
+        let err = handle
+            .shutdown_timeout(Duration::from_millis(100))
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("killed"), "{err}");
+        assert!(handle.child.try_wait().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn send_timeout_gives_up_on_slow_agent() {
+        let child = Command::new("sleep")
+            .arg("30")
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let (commands, mut cmd_rx) = mpsc::channel(1);
+        let handle = AgentHandle {
+            commands,
+            child,
+            supports_json: false,
+        };
+        // A receiver that is busy long after the deadline
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            while cmd_rx.recv().await.is_some() {}
+        });
+        let timeout = Duration::from_millis(50);
+
+        handle
+            .send_timeout(AgentCommand::Shutdown, timeout)
+            .await
+            .unwrap();
+        let err = handle
+            .send_timeout(AgentCommand::Shutdown, timeout)
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, crate::LeesonError::AgentTimeout { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn format_book_is_compact() {
+        let bids = [level(dec!(50000), dec!(1.5)), level(dec!(49990), dec!(2.0))];
+        let asks = [level(dec!(50010), dec!(0.5))];
+
+        assert_eq!(
+            format_book(&bids, &asks),
+            "BID:50000x1.5,49990x2.0|ASK:50010x0.5"
+        );
+    }
+
+    #[test]
+    fn book_snapshot_serializes_as_compact_string() {
+        let msg = TuiToAgent::BookSnapshot {
+            symbol: "BTC/USD".to_string(),
+            book: format_book(&[], &[level(dec!(50010), dec!(0.5))]),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&msg).unwrap(),
+            r#"{"type":"book_snapshot","symbol":"BTC/USD","book":"BID:|ASK:50010x0.5"}"#
+        );
+    }
+
+    #[test]
+    fn order_book_serializes_levels_as_pairs() {
+        let msg = TuiToAgent::OrderBook {
+            symbol: "BTC/USD".to_string(),
+            bids: vec![(dec!(50000), dec!(1.5))],
+            asks: vec![(dec!(50010), dec!(0.5)), (dec!(50020), dec!(2))],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&msg).unwrap(),
+            r#"{"type":"order_book","symbol":"BTC/USD","bids":[["50000","1.5"]],"asks":[["50010","0.5"],["50020","2"]]}"#
+        );
+    }
+
+    #[test]
+    fn pause_and_resume_serialize_as_bare_types() {
+        assert_eq!(
+            serde_json::to_string(&TuiToAgent::Pause).unwrap(),
+            r#"{"type":"pause"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&TuiToAgent::Resume).unwrap(),
+            r#"{"type":"resume"}"#
+        );
+    }
+
+    #[test]
+    fn risk_limits_stay_text_without_json_support() {
+        let params = AgentRiskParams::default();
+
+        let cmds = risk_limit_commands("Risk limits:\n", &params, false);
+
+        assert_eq!(cmds.len(), 1);
+        let AgentCommand::RiskLimits(ref text) = cmds[0] else {
+            panic!("expected RiskLimits, got {cmds:?}");
+        };
+        assert!(text.starts_with("Risk limits:\n"));
+        assert!(text.contains("Agent risk parameters:"));
+    }
+
+    #[test]
+    fn risk_limits_switch_to_json_when_supported() {
+        let params = AgentRiskParams::default();
+
+        let cmds = risk_limit_commands("Risk limits:\n", &params, true);
+
+        assert!(matches!(cmds[0], AgentCommand::RiskLimits(ref text) if text == "Risk limits:\n"));
+        let AgentCommand::RiskLimitsJson(ref json) = cmds[1] else {
+            panic!("expected RiskLimitsJson, got {cmds:?}");
+        };
+        let msg = TuiToAgent::RiskLimitsJson {
+            params: serde_json::from_str(json).unwrap(),
+        };
+        let wire: serde_json::Value = serde_json::to_value(&msg).unwrap();
+        assert_eq!(wire["type"], "risk_limits_json");
+        assert_eq!(wire["params"]["trades_per_month"], 10);
+    }
+
+    #[test]
+    fn supports_json_message_parses() {
+        let msg: AgentToTui = serde_json::from_str(r#"{"type":"supports_json"}"#).unwrap();
+        assert!(matches!(msg, AgentToTui::SupportsJson));
+    }
+
+    #[test]
+    fn position_snapshot_serializes_maps_by_symbol() {
+        let msg = TuiToAgent::PositionSnapshot {
+            positions: HashMap::from([("BTC/USD".to_string(), dec!(0.5))]),
+            avg_entries: HashMap::from([("BTC/USD".to_string(), dec!(50000))]),
+        };
+
+        let wire: serde_json::Value = serde_json::to_value(&msg).unwrap();
+
+        assert_eq!(wire["type"], "position_snapshot");
+        assert_eq!(wire["positions"]["BTC/USD"], "0.5");
+        assert_eq!(wire["avg_entries"]["BTC/USD"], "50000");
+    }
+
+    fn alert(symbol: &str, direction: AlertDirection, threshold: Decimal) -> PriceAlert {
+        PriceAlert {
+            agent_index: 0,
+            symbol: symbol.to_string(),
+            direction,
+            threshold,
+        }
+    }
+
+    #[test]
+    fn above_alert_fires_once_at_threshold() {
+        let mut alerts = PriceAlerts::default();
+        alerts.add(alert("BTC/USD", AlertDirection::Above, dec!(50000)));
+
+        assert!(alerts.check("BTC/USD", dec!(49999)).is_empty());
+        assert!(alerts.check("ETH/USD", dec!(60000)).is_empty());
+        assert_eq!(alerts.check("BTC/USD", dec!(50000)).len(), 1);
+        assert!(alerts.is_empty());
+        assert!(alerts.check("BTC/USD", dec!(51000)).is_empty());
+    }
+
+    #[test]
+    fn below_alert_fires_when_price_drops() {
+        let mut alerts = PriceAlerts::default();
+        alerts.add(alert("BTC/USD", AlertDirection::Below, dec!(40000)));
+        alerts.add(alert("BTC/USD", AlertDirection::Above, dec!(60000)));
+
+        assert!(alerts.check("BTC/USD", dec!(40001)).is_empty());
+        let fired = alerts.check("BTC/USD", dec!(39000));
+
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].direction, AlertDirection::Below);
+        assert_eq!(alerts.len(), 1);
+    }
+
+    #[test]
+    fn alerts_are_capped_per_agent_and_cleared() {
+        let mut alerts = PriceAlerts::default();
+        for i in 0..=MAX_ALERTS_PER_AGENT {
+            alerts.add(alert("BTC/USD", AlertDirection::Above, Decimal::from(i)));
+        }
+        alerts.add(PriceAlert {
+            agent_index: 1,
+            ..alert("ETH/USD", AlertDirection::Below, dec!(10))
+        });
+
+        assert_eq!(alerts.len(), MAX_ALERTS_PER_AGENT + 1);
+        // The oldest alert (threshold 0) was dropped
+        assert!(alerts.check("BTC/USD", dec!(0)).is_empty());
+
+        alerts.clear_symbol("ETH/USD");
+        assert_eq!(alerts.len(), MAX_ALERTS_PER_AGENT);
+        alerts.clear_agent(0);
+        assert!(alerts.is_empty());
+    }
+
+    #[test]
+    fn price_alert_request_parses() {
+        let msg: AgentToTui = serde_json::from_str(
+            r#"{"type":"price_alert","symbol":"BTC/USD","direction":"below","threshold":"40000.5"}"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            msg,
+            AgentToTui::PriceAlert { direction: AlertDirection::Below, threshold, .. }
+                if threshold == dec!(40000.5)
+        ));
+    }
+}
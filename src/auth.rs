@@ -6,12 +6,12 @@
 //! REST endpoint.  The token is valid for 15 minutes after creation.
 
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use base64::prelude::*;
 use hmac::{Hmac, Mac};
 use sha2::{Digest, Sha256, Sha512};
-use tracing::info;
+use tracing::{info, warn};
 use zeroize::Zeroizing;
 
 /// Tracks the last nonce issued so every call returns a strictly
@@ -22,12 +22,49 @@ static LAST_NONCE: AtomicU64 = AtomicU64::new(0);
 
 use crate::Result;
 
-const TOKEN_URL: &str = "https://api.kraken.com/0/private/GetWebSocketsToken";
-const URL_PATH: &str = "/0/private/GetWebSocketsToken";
+const API_URL: &str = "https://api.kraken.com";
+const TOKEN_PATH: &str = "/0/private/GetWebSocketsToken";
+const TRADE_VOLUME_PATH: &str = "/0/private/TradeVolume";
 
-/// Validates API credentials by attempting to fetch a WebSocket token.
+/// Requests made for a WebSocket token before a run of `503` responses is
+/// reported as [`LeesonError::AuthServiceUnavailable`](crate::LeesonError::AuthServiceUnavailable).
+const TOKEN_ATTEMPTS: u32 = 3;
+
+/// Wait before retrying a `503` token response, doubled on each retry.
+const TOKEN_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Token lifetime assumed when the response omits `expires`.
+pub const TOKEN_LIFETIME: Duration = Duration::from_secs(15 * 60);
+
+/// What a successful credential check learned about the account.
+pub struct CredentialInfo {
+    /// WebSocket token fetched during validation, ready for the first connection.
+    pub token: Zeroizing<String>,
+    /// When the token stops being accepted.
+    pub expires_at: Instant,
+    /// The account's fee tier for XBT/USD, e.g. `taker 0.40% / maker 0.25%`,
+    /// or `unknown` if the key may not query trade volume.
+    pub tier: String,
+}
+
+impl std::fmt::Debug for CredentialInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialInfo")
+            .field("token", &"[REDACTED]")
+            .field("expires_at", &self.expires_at)
+            .field("tier", &self.tier)
+            .finish()
+    }
+}
+
+/// Validates API credentials by fetching a WebSocket token, then looks up
+/// the account's fee tier.
 ///
-/// Returns `Ok(token)` if credentials are valid, or an error describing the failure.
+/// The token request retries `503` responses and maps `429` the same way
+/// [`get_websocket_token`] does.
+///
+/// A failed tier lookup is logged and reported as `unknown`; it does not
+/// fail validation.
 ///
 /// # Errors
 ///
@@ -39,40 +76,226 @@ pub async fn validate_credentials(
     api_key: &str,
     api_secret: &str,
     tls_config: rustls::ClientConfig,
-) -> Result<Zeroizing<String>> {
-    get_websocket_token(api_key, api_secret, tls_config).await
+) -> Result<CredentialInfo> {
+    let requested_at = Instant::now();
+    let (token, result) = request_websocket_token(
+        API_URL,
+        api_key,
+        api_secret,
+        tls_config.clone(),
+        TOKEN_RETRY_DELAY,
+    )
+    .await?;
+    let lifetime = result["expires"]
+        .as_u64()
+        .map_or(TOKEN_LIFETIME, Duration::from_secs);
+
+    let tier = match fetch_fee_tier(api_key, api_secret, tls_config).await {
+        Ok(tier) => tier,
+        Err(e) => {
+            warn!("failed to fetch fee tier: {e}");
+            "unknown".to_string()
+        }
+    };
+
+    Ok(CredentialInfo {
+        token,
+        expires_at: requested_at + lifetime,
+        tier,
+    })
 }
 
 /// Fetches a short-lived WebSocket authentication token from the Kraken REST API.
 ///
+/// `503 Service Unavailable` responses are retried with exponential backoff,
+/// up to three attempts in total.
+///
 /// # Errors
 ///
-/// Returns a [`LeesonError`](crate::LeesonError) if the HTTP request fails,
-/// the response cannot be parsed, or the API returns an error.
+/// Returns [`LeesonError::TokenRefreshFailed`](crate::LeesonError::TokenRefreshFailed)
+/// if the API answers `429 Too Many Requests`,
+/// [`LeesonError::AuthServiceUnavailable`](crate::LeesonError::AuthServiceUnavailable)
+/// if every attempt answers `503`, or another
+/// [`LeesonError`](crate::LeesonError) if the HTTP request fails, the
+/// response cannot be parsed, or the API returns an error.
 pub async fn get_websocket_token(
     api_key: &str,
     api_secret: &str,
     tls_config: rustls::ClientConfig,
 ) -> Result<Zeroizing<String>> {
+    let (token, _) =
+        request_websocket_token(API_URL, api_key, api_secret, tls_config, TOKEN_RETRY_DELAY)
+            .await?;
+    Ok(token)
+}
+
+/// [`get_websocket_token`] against `api_url`, waiting `retry_delay` before
+/// the first retry.
+///
+/// Returns the token along with the full `result` object, which also
+/// carries the token's `expires` lifetime.
+async fn request_websocket_token(
+    api_url: &str,
+    api_key: &str,
+    api_secret: &str,
+    tls_config: rustls::ClientConfig,
+    retry_delay: Duration,
+) -> Result<(Zeroizing<String>, serde_json::Value)> {
+    let mut delay = retry_delay;
+    let mut attempt = 1;
+    let response = loop {
+        let response = send_private_request(
+            api_url,
+            api_key,
+            api_secret,
+            tls_config.clone(),
+            TOKEN_PATH,
+            "",
+        )
+        .await?;
+        if response.status() != reqwest::StatusCode::SERVICE_UNAVAILABLE {
+            break response;
+        }
+        if attempt == TOKEN_ATTEMPTS {
+            return Err(crate::LeesonError::AuthServiceUnavailable { attempts: attempt });
+        }
+        warn!(
+            attempt,
+            "Kraken auth service unavailable, retrying in {}ms",
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    };
+
+    if let Some(e) = rate_limit_error(response.status(), response.headers()) {
+        return Err(e);
+    }
+    let result = read_private_result(response).await?;
+    let token = token_from_result(&result)?;
+    info!("Obtained WebSocket authentication token");
+    Ok((token, result))
+}
+
+/// Looks up the taker and maker fees the account pays on XBT/USD.
+async fn fetch_fee_tier(
+    api_key: &str,
+    api_secret: &str,
+    tls_config: rustls::ClientConfig,
+) -> Result<String> {
+    let result = private_request(
+        api_key,
+        api_secret,
+        tls_config,
+        TRADE_VOLUME_PATH,
+        "&pair=XBTUSD",
+    )
+    .await?;
+    fee_tier_from_result(&result)
+}
+
+/// Formats the XBT/USD fees from a `TradeVolume` result.
+fn fee_tier_from_result(result: &serde_json::Value) -> Result<String> {
+    let fee = |key: &str| {
+        result[key]
+            .as_object()
+            .and_then(|fees| fees.values().next())
+            .and_then(|pair| pair["fee"].as_str())
+            .and_then(|fee| fee.parse::<rust_decimal::Decimal>().ok())
+            .ok_or_else(|| {
+                crate::LeesonError::MalformedMessage(format!(
+                    "missing {key} in TradeVolume response"
+                ))
+            })
+    };
+    Ok(format!(
+        "taker {:.2}% / maker {:.2}%",
+        fee("fees")?,
+        fee("fees_maker")?
+    ))
+}
+
+/// Extracts the token from a `GetWebSocketsToken` result.
+fn token_from_result(result: &serde_json::Value) -> Result<Zeroizing<String>> {
+    let token = result["token"].as_str().ok_or_else(|| {
+        crate::LeesonError::MalformedMessage("missing token in GetWebSocketsToken response".into())
+    })?;
+    Ok(Zeroizing::new(token.to_string()))
+}
+
+/// Returns [`LeesonError::TokenRefreshFailed`](crate::LeesonError::TokenRefreshFailed)
+/// for a `429 Too Many Requests` response, with the `Retry-After` delay if
+/// it is given in seconds.
+fn rate_limit_error(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Option<crate::LeesonError> {
+    if status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let retry_after = headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|secs| secs.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    Some(crate::LeesonError::TokenRefreshFailed {
+        reason: format!("rate limited by Kraken ({status})"),
+        retry_after,
+    })
+}
+
+/// Sends a signed request to a private REST endpoint and returns its
+/// `result` object.
+///
+/// `params` is appended to the form body after the nonce and must start
+/// with `&` when non-empty.
+async fn private_request(
+    api_key: &str,
+    api_secret: &str,
+    tls_config: rustls::ClientConfig,
+    path: &str,
+    params: &str,
+) -> Result<serde_json::Value> {
+    let response =
+        send_private_request(API_URL, api_key, api_secret, tls_config, path, params).await?;
+    read_private_result(response).await
+}
+
+/// Sends a signed request to a private REST endpoint of the API at
+/// `api_url`.
+async fn send_private_request(
+    api_url: &str,
+    api_key: &str,
+    api_secret: &str,
+    tls_config: rustls::ClientConfig,
+    path: &str,
+    params: &str,
+) -> Result<reqwest::Response> {
     let nonce = next_nonce();
-    let post_data = format!("nonce={nonce}");
-    let signature = sign(api_secret, URL_PATH, nonce, &post_data)?;
+    let post_data = format!("nonce={nonce}{params}");
+    let signature = sign(api_secret, path, nonce, &post_data)?;
 
     let client = reqwest::Client::builder()
         .use_preconfigured_tls(tls_config)
         .build()
         .map_err(|e| crate::LeesonError::Tls(format!("failed to build HTTP client: {e}")))?;
     let response = client
-        .post(TOKEN_URL)
+        .post(format!("{api_url}{path}"))
         .header("API-Key", api_key)
         .header("API-Sign", &signature)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .body(post_data)
         .send()
         .await?;
+    Ok(response)
+}
 
+/// Returns the `result` object of a private REST response, or the errors
+/// the API reported.
+async fn read_private_result(response: reqwest::Response) -> Result<serde_json::Value> {
     let response = response.error_for_status()?;
-    let body: serde_json::Value = response.json().await?;
+    let mut body: serde_json::Value = response.json().await?;
 
     let errors = body["error"].as_array();
     if let Some(errors) = errors
@@ -88,17 +311,7 @@ pub async fn get_websocket_token(
         )));
     }
 
-    let token = body["result"]["token"]
-        .as_str()
-        .ok_or_else(|| {
-            crate::LeesonError::MalformedMessage(
-                "missing token in GetWebSocketsToken response".into(),
-            )
-        })?
-        .to_string();
-
-    info!("Obtained WebSocket authentication token");
-    Ok(Zeroizing::new(token))
+    Ok(body["result"].take())
 }
 
 /// Returns a strictly monotonically-increasing nonce with nanosecond resolution.
@@ -148,6 +361,109 @@ fn sign(api_secret: &str, path: &str, nonce: u64, post_data: &str) -> Result<Str
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Base64 of `secret`, a valid signing key for requests to the mock.
+    const TEST_SECRET: &str = "c2VjcmV0";
+
+    /// Serves one canned HTTP response per connection, in order, and
+    /// returns the mock's base URL.
+    async fn mock_api(responses: Vec<&'static str>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut tcp, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut request = [0u8; 4096];
+                    let _ = tcp.read(&mut request).await;
+                    let _ = tcp.write_all(response.as_bytes()).await;
+                    let _ = tcp.shutdown().await;
+                    // Drain the rest of the request so closing does not reset
+                    let _ = tcp.read_to_end(&mut Vec::new()).await;
+                });
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    const TOKEN: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 51\r\nConnection: close\r\n\r\n{\"error\":[],\"result\":{\"token\":\"tok\",\"expires\":900}}";
+
+    fn tls_config() -> rustls::ClientConfig {
+        crate::tls::build_tls_config().unwrap()
+    }
+
+    #[tokio::test]
+    async fn token_request_retries_service_unavailable() {
+        let url = mock_api(vec![UNAVAILABLE, UNAVAILABLE, TOKEN]).await;
+
+        let (token, result) = request_websocket_token(
+            &url,
+            "key",
+            TEST_SECRET,
+            tls_config(),
+            Duration::from_millis(1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(token.as_str(), "tok");
+        assert_eq!(result["expires"], 900);
+    }
+
+    #[tokio::test]
+    async fn token_request_gives_up_after_three_503s() {
+        let url = mock_api(vec![UNAVAILABLE, UNAVAILABLE, UNAVAILABLE]).await;
+
+        let err = request_websocket_token(
+            &url,
+            "key",
+            TEST_SECRET,
+            tls_config(),
+            Duration::from_millis(1),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::LeesonError::AuthServiceUnavailable { attempts: 3 }
+        ));
+    }
+
+    #[test]
+    fn too_many_requests_carries_retry_after() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "30".parse().unwrap());
+
+        let err = rate_limit_error(reqwest::StatusCode::TOO_MANY_REQUESTS, &headers).unwrap();
+        assert!(matches!(
+            err,
+            crate::LeesonError::TokenRefreshFailed {
+                retry_after: Some(d),
+                ..
+            } if d == Duration::from_secs(30)
+        ));
+        assert_eq!(
+            err.to_string(),
+            "token refresh failed: rate limited by Kraken (429 Too Many Requests)"
+        );
+
+        // An HTTP date is not parsed, and other statuses are not rate limits
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            "Wed, 21 Oct 2026 07:28:00 GMT".parse().unwrap(),
+        );
+        assert!(matches!(
+            rate_limit_error(reqwest::StatusCode::TOO_MANY_REQUESTS, &headers),
+            Some(crate::LeesonError::TokenRefreshFailed {
+                retry_after: None,
+                ..
+            })
+        ));
+        assert!(rate_limit_error(reqwest::StatusCode::OK, &headers).is_none());
+    }
 
     #[test]
     fn sign_produces_deterministic_output() {
@@ -156,8 +472,8 @@ mod tests {
         let nonce = 1_000_000_000_000u64;
         let post_data = "nonce=1000000000000";
 
-        let sig1 = sign(&secret, URL_PATH, nonce, post_data).unwrap();
-        let sig2 = sign(&secret, URL_PATH, nonce, post_data).unwrap();
+        let sig1 = sign(&secret, TOKEN_PATH, nonce, post_data).unwrap();
+        let sig2 = sign(&secret, TOKEN_PATH, nonce, post_data).unwrap();
         assert_eq!(sig1, sig2);
 
         // Verify the output is valid base64.
@@ -166,10 +482,37 @@ mod tests {
 
     #[test]
     fn sign_rejects_invalid_base64_secret() {
-        let result = sign("not-valid-base64!!!", URL_PATH, 123, "nonce=123");
+        let result = sign("not-valid-base64!!!", TOKEN_PATH, 123, "nonce=123");
         assert!(result.is_err());
     }
 
+    #[test]
+    fn fee_tier_formats_taker_and_maker_fees() {
+        let result = serde_json::json!({
+            "currency": "ZUSD",
+            "volume": "12000.0000",
+            "fees": { "XXBTZUSD": { "fee": "0.4000", "minfee": "0.1000", "maxfee": "0.4000" } },
+            "fees_maker": { "XXBTZUSD": { "fee": "0.2500", "minfee": "0.0000", "maxfee": "0.2500" } }
+        });
+        assert_eq!(
+            fee_tier_from_result(&result).unwrap(),
+            "taker 0.40% / maker 0.25%"
+        );
+    }
+
+    #[test]
+    fn fee_tier_requires_fees() {
+        let result = serde_json::json!({ "currency": "ZUSD", "volume": "0", "fees": null });
+        assert!(fee_tier_from_result(&result).is_err());
+    }
+
+    #[test]
+    fn token_is_read_from_result() {
+        let result = serde_json::json!({ "token": "abc123", "expires": 900 });
+        assert_eq!(token_from_result(&result).unwrap().as_str(), "abc123");
+        assert!(token_from_result(&serde_json::json!({})).is_err());
+    }
+
     #[test]
     fn next_nonce_is_strictly_monotonic() {
         let mut prev = next_nonce();
@@ -4,9 +4,18 @@
 //! - `KRAKEN_API_KEY` — API key for Kraken authentication
 //! - `KRAKEN_API_SECRET` — API secret for Kraken authentication
 //!
-//! An optional `KRAKEN_WEBSOCKET_URL` overrides the default public endpoint.
+//! An optional `KRAKEN_WEBSOCKET_URL` overrides the default public endpoint,
+//! and `LEESON_MAX_RECONNECT_ATTEMPTS` caps how often a lost connection is
+//! retried before giving up. `LEESON_LOG` sets the tracing level
+//! (`info` by default), following the `RUST_LOG` convention.
+//!
+//! The trading pairs selected in the TUI are kept in `selected_pairs.json`
+//! in the working directory and read back with
+//! [`AppConfig::load_selected_pairs`] on the next start.
 
 use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
 
 use rust_decimal::Decimal;
 use zeroize::Zeroizing;
@@ -14,6 +23,21 @@ use zeroize::Zeroizing;
 /// Default public WebSocket endpoint.
 const DEFAULT_WEBSOCKET_URL: &str = "wss://ws.kraken.com/v2";
 
+/// Default tracing level when `LEESON_LOG` is unset.
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+
+/// Default number of lines kept per agent output panel.
+pub const DEFAULT_AGENT_OUTPUT_LINES: usize = 50;
+
+/// File the selected trading pairs are persisted to.
+pub const SELECTED_PAIRS_PATH: &str = "selected_pairs.json";
+
+/// Smallest accepted `LEESON_AGENT_OUTPUT_LINES` value.
+pub const MIN_AGENT_OUTPUT_LINES: usize = 10;
+
+/// Largest accepted `LEESON_AGENT_OUTPUT_LINES` value.
+pub const MAX_AGENT_OUTPUT_LINES: usize = 10_000;
+
 /// Top-level application configuration.
 #[derive(Debug)]
 pub struct AppConfig {
@@ -24,6 +48,195 @@ pub struct AppConfig {
     pub token_input_cost: Option<Decimal>,
     /// USD cost per million output tokens (from `LEESON_TOKEN_OUTPUT_COST`).
     pub token_output_cost: Option<Decimal>,
+    /// Lines kept per agent output panel (from `LEESON_AGENT_OUTPUT_LINES`),
+    /// clamped to [`MIN_AGENT_OUTPUT_LINES`]..=[`MAX_AGENT_OUTPUT_LINES`].
+    pub agent_output_lines: usize,
+    /// Consecutive reconnect attempts before the connection manager gives
+    /// up (from `LEESON_MAX_RECONNECT_ATTEMPTS`). `None` retries forever.
+    pub max_reconnect_attempts: Option<u32>,
+    /// Trading pairs subscribed at startup, restored by
+    /// [`load_selected_pairs`](Self::load_selected_pairs).
+    pub selected_pairs: Vec<String>,
+    /// File the selected pairs are loaded from and saved to.
+    pub selected_pairs_path: PathBuf,
+    /// Tracing level name (from `LEESON_LOG`), checked by
+    /// [`validate`](Self::validate).
+    pub log_level: String,
+}
+
+impl AppConfig {
+    /// Reads every configuration field from environment variables, without
+    /// validating the values.
+    ///
+    /// The WebSocket URL defaults to `wss://ws.kraken.com/v2` and can be
+    /// overridden with `KRAKEN_WEBSOCKET_URL`. API credentials are optional
+    /// (unauthenticated mode) but when one is set both must be present.
+    /// Unset or unparsable optional values fall back to their defaults.
+    ///
+    /// No pairs are selected; [`load_selected_pairs`](Self::load_selected_pairs)
+    /// reads them from [`SELECTED_PAIRS_PATH`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Config`](crate::LeesonError::Config) if only
+    /// one of the two credential variables is set.
+    pub fn from_env() -> crate::Result<Self> {
+        let websocket_url = non_empty_var("KRAKEN_WEBSOCKET_URL")
+            .unwrap_or_else(|| DEFAULT_WEBSOCKET_URL.to_string());
+
+        let api_key = non_empty_var("KRAKEN_API_KEY");
+        let api_secret = non_empty_var("KRAKEN_API_SECRET");
+
+        match (&api_key, &api_secret) {
+            (Some(_), None) => {
+                return Err(crate::LeesonError::Config(
+                    "KRAKEN_API_KEY is set but KRAKEN_API_SECRET is missing".to_string(),
+                ));
+            }
+            (None, Some(_)) => {
+                return Err(crate::LeesonError::Config(
+                    "KRAKEN_API_SECRET is set but KRAKEN_API_KEY is missing".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let simulation =
+            non_empty_var("LEESON_SIMULATION").is_some_and(|v| v == "true" || v == "1");
+
+        let token_input_cost =
+            non_empty_var("LEESON_TOKEN_INPUT_COST").and_then(|v| v.parse::<Decimal>().ok());
+        let token_output_cost =
+            non_empty_var("LEESON_TOKEN_OUTPUT_COST").and_then(|v| v.parse::<Decimal>().ok());
+
+        let agent_output_lines = non_empty_var("LEESON_AGENT_OUTPUT_LINES")
+            .and_then(|v| v.parse::<usize>().ok())
+            .map_or(DEFAULT_AGENT_OUTPUT_LINES, |lines| {
+                lines.clamp(MIN_AGENT_OUTPUT_LINES, MAX_AGENT_OUTPUT_LINES)
+            });
+
+        let max_reconnect_attempts =
+            non_empty_var("LEESON_MAX_RECONNECT_ATTEMPTS").and_then(|v| v.parse::<u32>().ok());
+
+        let log_level =
+            non_empty_var("LEESON_LOG").unwrap_or_else(|| DEFAULT_LOG_LEVEL.to_string());
+
+        Ok(Self {
+            kraken: KrakenConfig {
+                websocket_url,
+                api_key: api_key.map(Zeroizing::new),
+                api_secret: api_secret.map(Zeroizing::new),
+            },
+            simulation,
+            token_input_cost,
+            token_output_cost,
+            agent_output_lines,
+            max_reconnect_attempts,
+            selected_pairs: Vec::new(),
+            selected_pairs_path: PathBuf::from(SELECTED_PAIRS_PATH),
+            log_level,
+        })
+    }
+
+    /// Returns the tracing level named by [`log_level`](Self::log_level).
+    ///
+    /// Names are matched case-insensitively (`"debug"`, `"WARN"`, ...).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Config`](crate::LeesonError::Config) if the
+    /// name is not a recognized tracing level.
+    pub fn tracing_level(&self) -> crate::Result<tracing::Level> {
+        tracing::Level::from_str(&self.log_level)
+            .map_err(|_| crate::LeesonError::Config(log_level_problem(&self.log_level)))
+    }
+
+    /// Reads the selected pairs from
+    /// [`selected_pairs_path`](Self::selected_pairs_path). A missing file
+    /// leaves no pairs selected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Config`](crate::LeesonError::Config) if the
+    /// file cannot be read, or [`LeesonError::Json`](crate::LeesonError::Json)
+    /// if it does not hold a JSON array of symbols, so a corrupt file is
+    /// reported instead of being overwritten with an empty list.
+    pub fn load_selected_pairs(&mut self) -> crate::Result<()> {
+        let json = match std::fs::read_to_string(&self.selected_pairs_path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(crate::LeesonError::Config(format!(
+                    "failed to read {}: {e}",
+                    self.selected_pairs_path.display()
+                )));
+            }
+        };
+        self.selected_pairs = serde_json::from_str(&json)?;
+        Ok(())
+    }
+
+    /// Replaces the selected pairs and writes them to
+    /// [`selected_pairs_path`](Self::selected_pairs_path).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Config`](crate::LeesonError::Config) if the
+    /// pairs cannot be serialized or the file cannot be written.
+    pub fn save_selected_pairs(&mut self, pairs: &[String]) -> crate::Result<()> {
+        self.selected_pairs = pairs.to_vec();
+        let json = serde_json::to_string_pretty(&self.selected_pairs).map_err(|e| {
+            crate::LeesonError::Config(format!("failed to serialize selected pairs: {e}"))
+        })?;
+        std::fs::write(&self.selected_pairs_path, json).map_err(|e| {
+            crate::LeesonError::Config(format!(
+                "failed to write {}: {e}",
+                self.selected_pairs_path.display()
+            ))
+        })
+    }
+
+    /// Checks that the WebSocket URL is a `wss://` URL with a host, that
+    /// the token cost rates are not negative and that the log level is a
+    /// recognized tracing level.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::ConfigValidation`](crate::LeesonError::ConfigValidation)
+    /// describing every failed check.
+    pub fn validate(&self) -> crate::Result<()> {
+        let mut problems = Vec::new();
+
+        let url = &self.kraken.websocket_url;
+        match url.strip_prefix("wss://") {
+            None => problems.push(format!(
+                "KRAKEN_WEBSOCKET_URL must start with wss://, got {url}"
+            )),
+            Some(rest) if rest.split('/').next().is_none_or(str::is_empty) => {
+                problems.push(format!("KRAKEN_WEBSOCKET_URL has no host: {url}"));
+            }
+            Some(_) => {}
+        }
+
+        for (name, cost) in [
+            ("LEESON_TOKEN_INPUT_COST", self.token_input_cost),
+            ("LEESON_TOKEN_OUTPUT_COST", self.token_output_cost),
+        ] {
+            if let Some(cost) = cost.filter(|cost| *cost < Decimal::ZERO) {
+                problems.push(format!("{name} must not be negative, got {cost}"));
+            }
+        }
+
+        if self.tracing_level().is_err() {
+            problems.push(log_level_problem(&self.log_level));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::LeesonError::ConfigValidation(problems.join("; ")))
+        }
+    }
 }
 
 /// Kraken-specific configuration values.
@@ -49,54 +262,25 @@ impl fmt::Debug for KrakenConfig {
     }
 }
 
-/// Loads the application configuration from environment variables.
+/// Loads and validates the application configuration.
 ///
-/// The WebSocket URL defaults to `wss://ws.kraken.com/v2` and can be
-/// overridden with `KRAKEN_WEBSOCKET_URL`. API credentials are optional
-/// (unauthenticated mode) but when one is set both must be present.
+/// See [`AppConfig::from_env`] for the variables read.
 ///
 /// # Errors
 ///
 /// Returns [`LeesonError::Config`](crate::LeesonError::Config) if only
-/// one of the two credential variables is set.
+/// one of the two credential variables is set, or
+/// [`LeesonError::ConfigValidation`](crate::LeesonError::ConfigValidation)
+/// if [`AppConfig::validate`] fails.
 pub fn fetch_config() -> crate::Result<AppConfig> {
-    let websocket_url =
-        non_empty_var("KRAKEN_WEBSOCKET_URL").unwrap_or_else(|| DEFAULT_WEBSOCKET_URL.to_string());
-
-    let api_key = non_empty_var("KRAKEN_API_KEY");
-    let api_secret = non_empty_var("KRAKEN_API_SECRET");
-
-    match (&api_key, &api_secret) {
-        (Some(_), None) => {
-            return Err(crate::LeesonError::Config(
-                "KRAKEN_API_KEY is set but KRAKEN_API_SECRET is missing".to_string(),
-            ));
-        }
-        (None, Some(_)) => {
-            return Err(crate::LeesonError::Config(
-                "KRAKEN_API_SECRET is set but KRAKEN_API_KEY is missing".to_string(),
-            ));
-        }
-        _ => {}
-    }
+    let config = AppConfig::from_env()?;
+    config.validate()?;
+    Ok(config)
+}
 
-    let simulation = non_empty_var("LEESON_SIMULATION").is_some_and(|v| v == "true" || v == "1");
-
-    let token_input_cost =
-        non_empty_var("LEESON_TOKEN_INPUT_COST").and_then(|v| v.parse::<Decimal>().ok());
-    let token_output_cost =
-        non_empty_var("LEESON_TOKEN_OUTPUT_COST").and_then(|v| v.parse::<Decimal>().ok());
-
-    Ok(AppConfig {
-        kraken: KrakenConfig {
-            websocket_url,
-            api_key: api_key.map(Zeroizing::new),
-            api_secret: api_secret.map(Zeroizing::new),
-        },
-        simulation,
-        token_input_cost,
-        token_output_cost,
-    })
+/// Describes an unrecognized `LEESON_LOG` value.
+fn log_level_problem(level: &str) -> String {
+    format!("LEESON_LOG must be one of trace, debug, info, warn, error, got {level}")
 }
 
 /// Returns the value of an environment variable if it exists and is non-empty.
@@ -279,6 +463,128 @@ mod tests {
         );
     }
 
+    #[test]
+    fn agent_output_lines_defaults_when_unset_or_invalid() {
+        for value in [None, Some("lots")] {
+            with_env(
+                &[
+                    ("KRAKEN_API_KEY", None),
+                    ("KRAKEN_API_SECRET", None),
+                    ("LEESON_AGENT_OUTPUT_LINES", value),
+                ],
+                || {
+                    let config = fetch_config().unwrap();
+                    assert_eq!(config.agent_output_lines, DEFAULT_AGENT_OUTPUT_LINES);
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn agent_output_lines_clamped_to_range() {
+        for (value, expected) in [
+            ("500", 500),
+            ("1", MIN_AGENT_OUTPUT_LINES),
+            ("1000000", MAX_AGENT_OUTPUT_LINES),
+        ] {
+            with_env(
+                &[
+                    ("KRAKEN_API_KEY", None),
+                    ("KRAKEN_API_SECRET", None),
+                    ("LEESON_AGENT_OUTPUT_LINES", Some(value)),
+                ],
+                || {
+                    let config = fetch_config().unwrap();
+                    assert_eq!(config.agent_output_lines, expected);
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn max_reconnect_attempts_from_env() {
+        for (value, expected) in [(None, None), (Some("5"), Some(5)), (Some("-1"), None)] {
+            with_env(
+                &[
+                    ("KRAKEN_API_KEY", None),
+                    ("KRAKEN_API_SECRET", None),
+                    ("LEESON_MAX_RECONNECT_ATTEMPTS", value),
+                ],
+                || {
+                    let config = fetch_config().unwrap();
+                    assert_eq!(config.max_reconnect_attempts, expected);
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_non_wss_websocket_url() {
+        with_env(
+            &[
+                ("KRAKEN_API_KEY", None),
+                ("KRAKEN_API_SECRET", None),
+                ("KRAKEN_WEBSOCKET_URL", Some("http://ws.kraken.com/v2")),
+            ],
+            || {
+                let err = fetch_config().unwrap_err();
+                assert!(matches!(err, crate::LeesonError::ConfigValidation(_)));
+                assert!(err.to_string().contains("must start with wss://"));
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_websocket_url_without_host() {
+        with_env(
+            &[
+                ("KRAKEN_API_KEY", None),
+                ("KRAKEN_API_SECRET", None),
+                ("KRAKEN_WEBSOCKET_URL", Some("wss:///v2")),
+            ],
+            || {
+                let err = fetch_config().unwrap_err();
+                assert!(err.to_string().contains("has no host"));
+            },
+        );
+    }
+
+    #[test]
+    fn validation_reports_every_failure() {
+        with_env(
+            &[
+                ("KRAKEN_API_KEY", None),
+                ("KRAKEN_API_SECRET", None),
+                ("KRAKEN_WEBSOCKET_URL", Some("ws://localhost")),
+                ("LEESON_TOKEN_INPUT_COST", Some("-1")),
+                ("LEESON_TOKEN_OUTPUT_COST", Some("-0.5")),
+            ],
+            || {
+                let message = fetch_config().unwrap_err().to_string();
+                assert!(message.contains("KRAKEN_WEBSOCKET_URL"));
+                assert!(message.contains("LEESON_TOKEN_INPUT_COST must not be negative"));
+                assert!(message.contains("LEESON_TOKEN_OUTPUT_COST must not be negative"));
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_zero_token_costs() {
+        with_env(
+            &[
+                ("KRAKEN_API_KEY", None),
+                ("KRAKEN_API_SECRET", None),
+                ("KRAKEN_WEBSOCKET_URL", None),
+                ("LEESON_TOKEN_INPUT_COST", Some("0")),
+                ("LEESON_TOKEN_OUTPUT_COST", Some("0")),
+            ],
+            || {
+                let config = fetch_config().unwrap();
+                assert_eq!(config.token_input_cost, Some(Decimal::ZERO));
+            },
+        );
+    }
+
     #[test]
     fn empty_values_treated_as_absent() {
         with_env(
@@ -295,4 +601,125 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn from_env_reads_every_field_without_validating() {
+        with_env(
+            &[
+                ("KRAKEN_WEBSOCKET_URL", Some("ws://localhost:9000")),
+                ("KRAKEN_API_KEY", Some("env-key")),
+                ("KRAKEN_API_SECRET", Some("env-secret")),
+                ("LEESON_SIMULATION", Some("1")),
+                ("LEESON_TOKEN_INPUT_COST", Some("3")),
+                ("LEESON_TOKEN_OUTPUT_COST", Some("15")),
+                ("LEESON_AGENT_OUTPUT_LINES", Some("200")),
+                ("LEESON_MAX_RECONNECT_ATTEMPTS", Some("7")),
+            ],
+            || {
+                let config = AppConfig::from_env().unwrap();
+                assert_eq!(config.kraken.websocket_url, "ws://localhost:9000");
+                assert_eq!(
+                    config.kraken.api_key.as_deref().map(String::as_str),
+                    Some("env-key")
+                );
+                assert!(config.simulation);
+                assert_eq!(config.token_input_cost, Some(Decimal::from(3)));
+                assert_eq!(config.token_output_cost, Some(Decimal::from(15)));
+                assert_eq!(config.agent_output_lines, 200);
+                assert_eq!(config.max_reconnect_attempts, Some(7));
+
+                // The plain ws:// URL is only rejected by fetch_config
+                assert!(fetch_config().is_err());
+            },
+        );
+    }
+
+    #[test]
+    fn log_level_maps_to_tracing_level() {
+        for (value, expected) in [
+            (None, tracing::Level::INFO),
+            (Some("debug"), tracing::Level::DEBUG),
+            (Some("WARN"), tracing::Level::WARN),
+        ] {
+            with_env(
+                &[
+                    ("KRAKEN_API_KEY", None),
+                    ("KRAKEN_API_SECRET", None),
+                    ("KRAKEN_WEBSOCKET_URL", None),
+                    ("LEESON_LOG", value),
+                ],
+                || {
+                    let config = fetch_config().unwrap();
+                    assert_eq!(config.tracing_level().unwrap(), expected);
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_log_level() {
+        with_env(
+            &[
+                ("KRAKEN_API_KEY", None),
+                ("KRAKEN_API_SECRET", None),
+                ("KRAKEN_WEBSOCKET_URL", None),
+                ("LEESON_LOG", Some("verbose")),
+            ],
+            || {
+                let err = fetch_config().unwrap_err();
+                assert!(matches!(err, crate::LeesonError::ConfigValidation(_)));
+                assert!(err.to_string().contains("LEESON_LOG"));
+            },
+        );
+    }
+
+    /// A default configuration keeping its selected pairs at `path`.
+    fn test_config(path: PathBuf) -> AppConfig {
+        AppConfig {
+            kraken: KrakenConfig {
+                websocket_url: DEFAULT_WEBSOCKET_URL.to_string(),
+                api_key: None,
+                api_secret: None,
+            },
+            simulation: false,
+            token_input_cost: None,
+            token_output_cost: None,
+            agent_output_lines: DEFAULT_AGENT_OUTPUT_LINES,
+            max_reconnect_attempts: None,
+            selected_pairs: Vec::new(),
+            selected_pairs_path: path,
+            log_level: DEFAULT_LOG_LEVEL.to_string(),
+        }
+    }
+
+    #[test]
+    fn selected_pairs_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = test_config(dir.path().join("selected_pairs.json"));
+        let pairs = vec!["BTC/USD".to_string(), "ETH/USD".to_string()];
+
+        config.save_selected_pairs(&pairs).unwrap();
+        assert_eq!(config.selected_pairs, pairs);
+
+        config.selected_pairs.clear();
+        config.load_selected_pairs().unwrap();
+        assert_eq!(config.selected_pairs, pairs);
+    }
+
+    #[test]
+    fn load_selected_pairs_reports_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("selected_pairs.json");
+        let mut config = test_config(path.clone());
+
+        config.load_selected_pairs().unwrap();
+        assert!(config.selected_pairs.is_empty());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            config.load_selected_pairs(),
+            Err(crate::LeesonError::Json(_))
+        ));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "not json");
+    }
 }
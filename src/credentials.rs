@@ -93,6 +93,42 @@ pub fn save(key: CredentialKey, value: &str) -> crate::Result<()> {
         .map_err(|e| crate::LeesonError::Config(format!("failed to save to keychain: {e}")))
 }
 
+/// Replaces a credential in the keychain, returning the previous value.
+///
+/// The old value is handed back so callers can revoke it before it is
+/// dropped (and zeroed). Returns `Ok(None)` if nothing was stored.
+///
+/// # Errors
+///
+/// Returns [`LeesonError::Config`](crate::LeesonError::Config) if the
+/// existing entry cannot be read or the new value cannot be written. A
+/// failed read leaves the stored value untouched.
+pub fn rotate(key: CredentialKey, new_value: &str) -> crate::Result<Option<Zeroizing<String>>> {
+    let entry = keyring::Entry::new(SERVICE, key.keyring_id())
+        .map_err(|e| crate::LeesonError::Config(format!("keyring entry error: {e}")))?;
+    rotate_entry(&entry, new_value)
+}
+
+/// Reads the current password of `entry`, then overwrites it with `new_value`.
+fn rotate_entry(
+    entry: &keyring::Entry,
+    new_value: &str,
+) -> crate::Result<Option<Zeroizing<String>>> {
+    let old = match entry.get_password() {
+        Ok(password) => Some(Zeroizing::new(password)),
+        Err(keyring::Error::NoEntry) => None,
+        Err(e) => {
+            return Err(crate::LeesonError::Config(format!(
+                "failed to read keychain entry: {e}"
+            )));
+        }
+    };
+    entry
+        .set_password(new_value)
+        .map_err(|e| crate::LeesonError::Config(format!("failed to save to keychain: {e}")))?;
+    Ok(old)
+}
+
 /// Checks whether a credential exists in the keychain.
 pub fn is_set(key: CredentialKey) -> bool {
     load(key).is_some()
@@ -115,3 +151,45 @@ pub fn populate_env_from_keychain() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use keyring::mock::MockCredential;
+
+    fn mock_entry() -> keyring::Entry {
+        keyring::Entry::new_with_credential(Box::new(MockCredential::default()))
+    }
+
+    #[test]
+    fn rotate_without_old_value_returns_none() {
+        let entry = mock_entry();
+
+        let old = rotate_entry(&entry, "first").unwrap();
+
+        assert!(old.is_none());
+        assert_eq!(entry.get_password().unwrap(), "first");
+    }
+
+    #[test]
+    fn rotate_returns_previous_value() {
+        let entry = mock_entry();
+        rotate_entry(&entry, "first").unwrap();
+
+        let old = rotate_entry(&entry, "second").unwrap();
+
+        assert_eq!(old.as_deref().map(String::as_str), Some("first"));
+        assert_eq!(entry.get_password().unwrap(), "second");
+    }
+
+    #[test]
+    fn rotate_keeps_old_value_when_read_fails() {
+        let entry = mock_entry();
+        entry.set_password("current").unwrap();
+        let mock: &MockCredential = entry.get_credential().downcast_ref().unwrap();
+        mock.set_error(keyring::Error::PlatformFailure("locked".into()));
+
+        assert!(rotate_entry(&entry, "next").is_err());
+        assert_eq!(entry.get_password().unwrap(), "current");
+    }
+}
@@ -14,6 +14,11 @@ pub enum LeesonError {
     #[error("configuration error: {0}")]
     Config(String),
 
+    /// One or more configuration values are malformed; the message lists
+    /// every failure.
+    #[error("invalid configuration: {0}")]
+    ConfigValidation(String),
+
     /// A WebSocket operation (connect, send, receive) failed.
     #[error("websocket error: {0}")]
     WebSocket(#[from] tungstenite::Error),
@@ -35,10 +40,196 @@ pub enum LeesonError {
     Tls(String),
 
     /// An I/O operation (e.g. terminal setup) failed.
-    #[error("io error: {0}")]
-    Io(String),
+    #[error("io error: {context}: {source}")]
+    Io {
+        /// What was being attempted, e.g. `failed to write session.json`.
+        context: String,
+        /// The underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
 
     /// A channel send operation failed.
     #[error("channel error: {0}")]
     Channel(String),
+
+    /// The risk guard rejected an order.
+    #[error(transparent)]
+    RiskCheckFailed(#[from] crate::risk::RiskCheckError),
+
+    /// The server closed the WebSocket connection with a close frame.
+    #[error("websocket closed: {}", describe_close(*code, reason.as_deref()))]
+    WebSocketClosed {
+        /// Close code from the frame, if one was sent.
+        code: Option<u16>,
+        /// Close reason from the frame, if non-empty.
+        reason: Option<String>,
+    },
+
+    /// The connection manager gave up after the configured number of
+    /// reconnect attempts.
+    #[error("max retries exceeded after {attempts} reconnect attempts")]
+    MaxRetriesExceeded {
+        /// Reconnect attempts made before giving up.
+        attempts: u32,
+    },
+
+    /// A WebSocket response did not arrive before the deadline.
+    #[error("timed out: {0}")]
+    Timeout(String),
+
+    /// An order request builder rejected its parameters.
+    #[error("invalid order: {0}")]
+    OrderBuilder(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// Fetching a fresh WebSocket auth token was refused by the exchange.
+    #[error("token refresh failed: {reason}")]
+    TokenRefreshFailed {
+        /// Why the request was refused.
+        reason: String,
+        /// How long the exchange asked to wait before retrying, if it said.
+        retry_after: Option<std::time::Duration>,
+    },
+
+    /// Kraken's REST API kept answering `503 Service Unavailable` (e.g.
+    /// during maintenance) while fetching a WebSocket auth token.
+    #[error("auth service unavailable after {attempts} attempts")]
+    AuthServiceUnavailable {
+        /// Requests made before giving up.
+        attempts: u32,
+    },
+
+    /// An agent did not accept a command before the deadline.
+    #[error("agent did not accept command within {}ms", timeout.as_millis())]
+    AgentTimeout {
+        /// How long the send waited.
+        timeout: std::time::Duration,
+    },
+}
+
+impl LeesonError {
+    /// Builds a [`LeesonError::Io`] describing what was being attempted.
+    #[must_use]
+    pub fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Builds a [`LeesonError::WebSocketClosed`] from a received close frame.
+    #[must_use]
+    pub fn from_close_frame(frame: Option<&tungstenite::protocol::CloseFrame>) -> Self {
+        Self::WebSocketClosed {
+            code: frame.map(|f| u16::from(f.code)),
+            reason: frame
+                .map(|f| f.reason.to_string())
+                .filter(|reason| !reason.is_empty()),
+        }
+    }
+}
+
+impl From<std::io::Error> for LeesonError {
+    fn from(source: std::io::Error) -> Self {
+        Self::io("i/o operation failed", source)
+    }
+}
+
+/// Implements `From<$error> for LeesonError` for each order builder error,
+/// wrapping it in [`LeesonError::OrderBuilder`].
+macro_rules! from_order_builder_error {
+    ($($error:ty),* $(,)?) => {
+        $(
+            impl From<$error> for LeesonError {
+                fn from(e: $error) -> Self {
+                    Self::OrderBuilder(Box::new(e))
+                }
+            }
+        )*
+    };
+}
+
+from_order_builder_error!(
+    crate::models::AddOrderError,
+    crate::models::AmendOrderError,
+    crate::models::BatchAddError,
+    crate::models::BatchCancelError,
+    crate::models::CancelOrderError,
+    crate::models::EditOrderError,
+);
+
+/// Formats a close code and reason as e.g. `maintenance (1001)`.
+pub(crate) fn describe_close(code: Option<u16>, reason: Option<&str>) -> String {
+    match (code, reason) {
+        (Some(code), Some(reason)) => format!("{reason} ({code})"),
+        (Some(code), None) => format!("code {code}"),
+        (None, Some(reason)) => reason.to_string(),
+        (None, None) => "no reason given".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BatchCancelBuilder, CancelOrderBuilder, CancelOrderError};
+
+    fn cancel_with_no_orders() -> Result<()> {
+        CancelOrderBuilder::by_order_id(Vec::new()).build("token")?;
+        Ok(())
+    }
+
+    #[test]
+    fn order_builder_errors_convert_with_source() {
+        let err = cancel_with_no_orders().unwrap_err();
+
+        assert!(
+            err.to_string()
+                .starts_with("invalid order: at least one of")
+        );
+        let source = std::error::Error::source(&err).expect("builder error as source");
+        assert_eq!(
+            source.downcast_ref::<CancelOrderError>(),
+            Some(&CancelOrderError::NoOrderIdentifier)
+        );
+    }
+
+    #[test]
+    fn batch_cancel_error_converts() {
+        let err = LeesonError::from(
+            BatchCancelBuilder::with_orders(vec!["O1".to_string()])
+                .build("token")
+                .unwrap_err(),
+        );
+
+        assert!(matches!(err, LeesonError::OrderBuilder(_)));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn json_error_keeps_serde_error_as_source() {
+        let err = LeesonError::from(serde_json::from_str::<u32>("not json").unwrap_err());
+
+        let source = std::error::Error::source(&err).expect("serde error as source");
+        assert!(source.downcast_ref::<serde_json::Error>().is_some());
+    }
+
+    #[test]
+    fn io_error_keeps_context_and_source() {
+        let err = LeesonError::io(
+            "failed to read session.json",
+            std::io::Error::from(std::io::ErrorKind::PermissionDenied),
+        );
+
+        assert_eq!(
+            err.to_string(),
+            "io error: failed to read session.json: permission denied"
+        );
+        let source = std::error::Error::source(&err).expect("io error as source");
+        assert_eq!(
+            source
+                .downcast_ref::<std::io::Error>()
+                .map(std::io::Error::kind),
+            Some(std::io::ErrorKind::PermissionDenied)
+        );
+    }
 }
@@ -1,36 +1,50 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use rust_decimal::Decimal;
 use tokio::sync::mpsc;
 
 use leeson::LeesonError;
-use leeson::agent::{AgentCommand, AgentHandle, spawn_multi_agent};
+use leeson::agent::{
+    AgentCommand, AgentHandle, BOOK_SNAPSHOT_DEPTH, MULTI_AGENT_INDEX, PriceAlerts,
+    risk_limit_commands, spawn_multi_agent,
+};
 use leeson::auth::validate_credentials;
-use leeson::config::fetch_config;
+use leeson::config::{AppConfig, fetch_config};
 use leeson::credentials::{self, CredentialKey};
 use leeson::models::Channel;
-use leeson::models::add_order::AddOrderRequest;
 use leeson::models::book::BookDepth;
-use leeson::risk::RiskGuard;
+use leeson::models::{AddOrderParams, CancelOrderBuilder, CancelOrderParams};
 use leeson::risk::config::{AgentRiskParams, RiskConfig};
+use leeson::risk::{RiskGuard, RiskVerdict};
 use leeson::simulation::SimulationEngine;
 use leeson::tls::build_tls_config;
 use leeson::tui::app::{ApiKeysEditState, Mode, PendingOrder, SimulationStats};
 use leeson::tui::{self, App, Message};
 use leeson::websocket::{
-    ConnectionCommand, ConnectionManager, add_order, subscribe, subscribe_book, unsubscribe,
+    ConnectionCommand, ConnectionManager, subscribe, subscribe_book, subscribe_candles,
+    unsubscribe, unsubscribe_candles,
 };
 
+/// How long to wait for a busy agent to accept a command before dropping it.
+const AGENT_SEND_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// File tracing output is written to, so it does not draw over the TUI.
+const LOG_PATH: &str = "leeson.log";
+
 #[tokio::main]
 async fn main() -> Result<(), LeesonError> {
     credentials::populate_env_from_keychain();
-    let app_config = fetch_config()?;
+    let mut app_config = fetch_config()?;
+    app_config.load_selected_pairs()?;
+    init_tracing(&app_config)?;
     let tls_config = Arc::new(build_tls_config()?);
 
     // Load risk configuration (required — running without risk limits is a hard error)
-    let risk_config = RiskConfig::load(Path::new("risk.json"))?;
+    let risk_config_path = Path::new("risk.json");
+    let risk_config = RiskConfig::load(risk_config_path)?;
     let mut risk_guard = RiskGuard::new(risk_config);
 
     let mut sim_engine: Option<SimulationEngine> = if app_config.simulation {
@@ -51,16 +65,17 @@ async fn main() -> Result<(), LeesonError> {
 
     // Validate API credentials only when all creds are present;
     // when some are missing the overlay will open first.
-    let (credentials_valid, auth_error) = if !any_credentials_missing && has_credentials {
+    let (credential_info, auth_error) = if !any_credentials_missing && has_credentials {
         let key = app_config.kraken.api_key.as_deref().unwrap();
         let secret = app_config.kraken.api_secret.as_deref().unwrap();
         match validate_credentials(key, secret, (*tls_config).clone()).await {
-            Ok(_) => (true, None),
-            Err(e) => (false, Some(e.to_string())),
+            Ok(info) => (Some(info), None),
+            Err(e) => (None, Some(e.to_string())),
         }
     } else {
-        (false, None)
+        (None, None)
     };
+    let credentials_valid = credential_info.is_some();
 
     // Use authenticated endpoint if credentials are valid, otherwise use configured URL.
     // In simulation mode, always use the public endpoint (no auth needed for data-only).
@@ -76,16 +91,20 @@ async fn main() -> Result<(), LeesonError> {
     let agent_risk_path = Path::new("agent_risk.json");
     let agent_risk_params = AgentRiskParams::load(agent_risk_path)?;
 
-    // Setup terminal
-    let mut terminal = tui::setup_terminal()?;
+    // Setup terminal (restored when `terminal` is dropped)
+    let mut terminal = tui::Tui::new()?;
 
     // Create application state
-    let mut app = App::new();
+    let mut app = App::new_with_config(&app_config);
     app.agent_risk_params = agent_risk_params;
     app.authenticated = credentials_valid;
-    app.simulation = app_config.simulation;
-    app.token_usage.input_cost_per_million = app_config.token_input_cost;
-    app.token_usage.output_cost_per_million = app_config.token_output_cost;
+    app.account_tier = credential_info.as_ref().map(|info| info.tier.clone());
+
+    // Restore the layout of the previous session (fresh start if none)
+    let session_path = Path::new("session.json");
+    if let Err(e) = app.restore_session_state(session_path) {
+        app.show_error(format!("Session not restored: {e}"));
+    }
 
     // Show auth error if credentials were provided but invalid
     if let Some(error) = auth_error {
@@ -118,15 +137,22 @@ async fn main() -> Result<(), LeesonError> {
     if setup_complete {
         // Spawn the connection manager — credentials move into the manager,
         // which is the sole owner for the rest of the process lifetime.
-        let manager = ConnectionManager::new(
+        let mut manager = ConnectionManager::new(
             url,
             tls_config.clone(),
-            app_config.kraken.api_key,
-            app_config.kraken.api_secret,
+            app_config.kraken.api_key.take(),
+            app_config.kraken.api_secret.take(),
             tx.clone(),
             writer.clone(),
             cmd_rx,
-        );
+        )
+        .with_max_reconnect_attempts(app_config.max_reconnect_attempts)
+        .with_timeframe(app.chart_timeframe);
+        if let Some(info) = credential_info {
+            manager = manager.with_credential_info(info);
+        }
+        // Pairs restored from the previous session
+        manager.set_subscribed_pairs(app.selected_pairs.clone());
         tokio::spawn(async move { manager.run().await });
     } else {
         deferred_cmd_rx = Some(cmd_rx);
@@ -141,8 +167,8 @@ async fn main() -> Result<(), LeesonError> {
     // Spawn agent subprocesses (deferred when credentials are missing)
     let mut agents: [Option<AgentHandle>; 3] = [None, None, None];
     if setup_complete {
-        match spawn_multi_agent(0, tx.clone()) {
-            Ok(handle) => agents[0] = Some(handle),
+        match spawn_multi_agent(MULTI_AGENT_INDEX, tx.clone()) {
+            Ok(handle) => agents[MULTI_AGENT_INDEX] = Some(handle),
             Err(e) => app.show_error(format!("Failed to spawn multi-agent system: {e}")),
         }
     }
@@ -151,6 +177,13 @@ async fn main() -> Result<(), LeesonError> {
     let mut ticker_last_sent: HashMap<String, Instant> = HashMap::new();
     const TICKER_THROTTLE: Duration = Duration::from_secs(5);
 
+    // Per-symbol throttle for order book snapshots to agents (max once per 10 seconds)
+    let mut book_last_sent: HashMap<String, Instant> = HashMap::new();
+    const BOOK_THROTTLE: Duration = Duration::from_secs(10);
+
+    // One-shot price alerts requested by agents, checked on every ticker
+    let mut price_alerts = PriceAlerts::default();
+
     // Main event loop
     loop {
         // Snapshot simulation stats before rendering
@@ -166,9 +199,7 @@ async fn main() -> Result<(), LeesonError> {
         }
 
         // Render UI
-        terminal
-            .draw(|frame| tui::render(frame, &mut app))
-            .map_err(|e| LeesonError::Io(e.to_string()))?;
+        terminal.draw(|frame| tui::render(frame, &mut app))?;
 
         // Check if we should quit
         if app.should_quit {
@@ -187,11 +218,7 @@ async fn main() -> Result<(), LeesonError> {
                     } else {
                         state.label()
                     };
-                    for handle in agents.iter().flatten() {
-                        let _ = handle
-                            .commands
-                            .send(AgentCommand::TokenState(label.to_string()));
-                    }
+                    broadcast(&agents, &AgentCommand::TokenState(label.to_string())).await;
                     tui::event::update(&mut app, Message::TokenState(state));
                     continue;
                 }
@@ -203,38 +230,39 @@ async fn main() -> Result<(), LeesonError> {
                 Message::AgentReady(agent_index) => {
                     app.add_agent_output(agent_index, "[agent ready]".to_string());
                     if let Some(ref handle) = agents[agent_index] {
-                        let mut desc = risk_guard.config().describe_limits();
-                        desc.push_str(&app.agent_risk_params.describe());
-                        let _ = handle.commands.send(AgentCommand::RiskLimits(desc));
+                        let limits = risk_guard.config().describe_limits();
+                        send_risk_limits(handle, &limits, &app.agent_risk_params).await;
                         if !app.selected_pairs.is_empty() {
-                            let _ = handle
-                                .commands
-                                .send(AgentCommand::ActivePairs(app.selected_pairs.clone()));
+                            let cmd = AgentCommand::ActivePairs(app.selected_pairs.clone());
+                            send_to_agent(handle, cmd).await;
                         }
                     }
                     continue;
                 }
+                // Re-send the limits in the structured form the agent now accepts
+                Message::AgentSupportsJson(agent_index) => {
+                    if let Some(handle) = agents.get_mut(agent_index).and_then(Option::as_mut) {
+                        handle.enable_json();
+                        let limits = risk_guard.config().describe_limits();
+                        send_risk_limits(handle, &limits, &app.agent_risk_params).await;
+                    }
+                    continue;
+                }
                 other => other,
             };
 
             // Forward data streams to agents (message passes through to TUI unchanged)
             if let Message::Execution(ref response) = message {
                 let cmd = AgentCommand::ExecutionUpdate(response.data.clone());
-                for handle in agents.iter().flatten() {
-                    let _ = handle.commands.send(cmd.clone());
-                }
+                broadcast(&agents, &cmd).await;
             }
             if let Message::Balance(ref response) = message {
                 let cmd = AgentCommand::BalanceUpdate(response.data.clone());
-                for handle in agents.iter().flatten() {
-                    let _ = handle.commands.send(cmd.clone());
-                }
+                broadcast(&agents, &cmd).await;
             }
             if let Message::Trade(ref response) = message {
                 let cmd = AgentCommand::TradeUpdate(response.data.clone());
-                for handle in agents.iter().flatten() {
-                    let _ = handle.commands.send(cmd.clone());
-                }
+                broadcast(&agents, &cmd).await;
             }
             if let Message::OrderPlaced(ref response) = message {
                 let cmd = AgentCommand::OrderResponse {
@@ -244,28 +272,80 @@ async fn main() -> Result<(), LeesonError> {
                     order_userref: response.result.as_ref().and_then(|r| r.order_userref),
                     error: response.error.clone(),
                 };
-                for handle in agents.iter().flatten() {
-                    let _ = handle.commands.send(cmd.clone());
+                broadcast(&agents, &cmd).await;
+            }
+            if let Message::BatchOrderResult(ref response) = message {
+                // Agents submit orders one at a time, so report each batched order individually
+                match response.result {
+                    Some(ref results) if response.success => {
+                        for result in results {
+                            let cmd = AgentCommand::OrderResponse {
+                                success: true,
+                                order_id: Some(result.order_id.clone()),
+                                cl_ord_id: result.cl_ord_id.clone(),
+                                order_userref: result.order_userref,
+                                error: None,
+                            };
+                            broadcast(&agents, &cmd).await;
+                        }
+                    }
+                    _ => {
+                        let cmd = AgentCommand::OrderResponse {
+                            success: false,
+                            order_id: None,
+                            cl_ord_id: None,
+                            order_userref: None,
+                            error: response.error.clone(),
+                        };
+                        broadcast(&agents, &cmd).await;
+                    }
                 }
             }
+            if let Message::AgentPriceAlert(ref alert) = message {
+                price_alerts.add(alert.clone());
+            }
+            if let Message::AgentExited { agent_index, .. } = message {
+                price_alerts.clear_agent(agent_index);
+            }
             if let Message::Ticker(ref response) = message {
                 let now = Instant::now();
                 for data in &response.data {
+                    for alert in price_alerts.check(&data.symbol, data.last) {
+                        if let Some(handle) = agents.get(alert.agent_index).and_then(Option::as_ref)
+                        {
+                            let cmd = AgentCommand::PriceAlertTriggered {
+                                symbol: alert.symbol,
+                                price: data.last,
+                            };
+                            send_to_agent(handle, cmd).await;
+                        }
+                    }
                     let should_send = ticker_last_sent
                         .get(&data.symbol)
                         .is_none_or(|last| now.duration_since(*last) >= TICKER_THROTTLE);
                     if should_send {
                         ticker_last_sent.insert(data.symbol.clone(), now);
                         let cmd = AgentCommand::TickerUpdate(data.clone());
-                        for handle in agents.iter().flatten() {
-                            let _ = handle.commands.send(cmd.clone());
-                        }
+                        broadcast(&agents, &cmd).await;
                     }
                 }
             }
 
+            let book_symbols: Vec<String> = match message {
+                Message::Book(ref response) => {
+                    response.data.iter().map(|d| d.symbol.clone()).collect()
+                }
+                _ => Vec::new(),
+            };
+
+            let is_execution = matches!(message, Message::Execution(_));
+            let action = tui::event::update(&mut app, message);
+            if is_execution {
+                broadcast(&agents, &position_snapshot(sim_engine.as_ref(), &app)).await;
+            }
+
             // Handle actions that require WebSocket writes
-            if let Some(action) = tui::event::update(&mut app, message) {
+            if let Some(action) = action {
                 match action {
                     tui::event::Action::SubscribePair(symbol) => {
                         let mut guard = writer.lock().await;
@@ -273,27 +353,43 @@ async fn main() -> Result<(), LeesonError> {
                             let symbols = vec![symbol.clone()];
                             let _ = subscribe(w, &Channel::Ticker, &symbols, None).await;
                             let _ = subscribe_book(w, &symbols, BookDepth::D25, None).await;
-                            let _ = subscribe(w, &Channel::Candles, &symbols, None).await;
+                            let _ = subscribe_candles(w, &symbols, app.chart_timeframe).await;
                             let _ = subscribe(w, &Channel::Trades, &symbols, None).await;
                         }
                         if let Err(e) = cmd_tx.try_send(ConnectionCommand::PairSubscribed(symbol)) {
                             tracing::warn!("command channel full, dropping PairSubscribed: {e}");
                         }
+                        if let Err(e) = app_config.save_selected_pairs(&app.selected_pairs) {
+                            app.show_error(format!("Failed to save pairs: {e}"));
+                        }
                         // Forward updated active pairs to all agents
                         let cmd = AgentCommand::ActivePairs(app.selected_pairs.clone());
-                        for handle in agents.iter().flatten() {
-                            let _ = handle.commands.send(cmd.clone());
-                        }
+                        broadcast(&agents, &cmd).await;
                     }
                     tui::event::Action::UnsubscribePair(symbol) => {
+                        price_alerts.clear_symbol(&symbol);
                         if let Err(e) = cmd_tx.try_send(ConnectionCommand::PairUnsubscribed(symbol))
                         {
                             tracing::warn!("command channel full, dropping PairUnsubscribed: {e}");
                         }
+                        if let Err(e) = app_config.save_selected_pairs(&app.selected_pairs) {
+                            app.show_error(format!("Failed to save pairs: {e}"));
+                        }
                         // Forward updated active pairs to all agents
                         let cmd = AgentCommand::ActivePairs(app.selected_pairs.clone());
-                        for handle in agents.iter().flatten() {
-                            let _ = handle.commands.send(cmd.clone());
+                        broadcast(&agents, &cmd).await;
+                    }
+                    tui::event::Action::ChangeTimeframe { from, to } => {
+                        let mut guard = writer.lock().await;
+                        if let Some(ref mut w) = *guard
+                            && !app.selected_pairs.is_empty()
+                        {
+                            let symbols = app.selected_pairs.clone();
+                            let _ = unsubscribe_candles(w, &symbols, from).await;
+                            let _ = subscribe_candles(w, &symbols, to).await;
+                        }
+                        if let Err(e) = cmd_tx.try_send(ConnectionCommand::SetTimeframe(to)) {
+                            tracing::warn!("command channel full, dropping SetTimeframe: {e}");
                         }
                     }
                     tui::event::Action::ResyncBook(symbol) => {
@@ -308,21 +404,24 @@ async fn main() -> Result<(), LeesonError> {
                     tui::event::Action::SendToAgent1(message) => {
                         app.add_agent_output(0, format!("You: {message}"));
                         if let Some(ref handle) = agents[0] {
-                            let _ = handle.commands.send(AgentCommand::UserMessage(message));
+                            send_to_agent(handle, AgentCommand::UserMessage(message)).await;
                         } else {
                             app.add_agent_output(0, "[agent not running]".to_string());
                         }
                     }
                     tui::event::Action::SubmitOrder(boxed_params) => {
-                        use leeson::risk::RiskVerdict;
-                        let params = *boxed_params;
-                        let symbol = params.symbol.clone();
-                        match risk_guard.check_order(&params) {
-                            Ok(RiskVerdict::Approved) => {
+                        let positions = match sim_engine {
+                            Some(ref sim) => sim.positions().clone(),
+                            None => app.spot_positions(),
+                        };
+                        match review_order(&mut app, &mut risk_guard, *boxed_params, &positions) {
+                            Ok(Some(params)) => {
+                                let symbol = params.symbol.clone();
                                 if let Some(ref mut sim) = sim_engine {
                                     let ticker = app.tickers.get(&symbol);
+                                    let book = app.orderbooks.get(&symbol).filter(|b| !b.is_stale);
                                     let (order_resp, exec_resp) =
-                                        sim.execute_order(&params, ticker);
+                                        sim.execute_order_with_book(&params, ticker, book);
                                     // Forward synthesized responses to agents
                                     let cmd = AgentCommand::OrderResponse {
                                         success: order_resp.success,
@@ -340,40 +439,29 @@ async fn main() -> Result<(), LeesonError> {
                                             .and_then(|r| r.order_userref),
                                         error: order_resp.error.clone(),
                                     };
-                                    for handle in agents.iter().flatten() {
-                                        let _ = handle.commands.send(cmd.clone());
-                                    }
+                                    broadcast(&agents, &cmd).await;
                                     if let Some(ref exec) = exec_resp {
                                         let exec_cmd =
                                             AgentCommand::ExecutionUpdate(exec.data.clone());
-                                        for handle in agents.iter().flatten() {
-                                            let _ = handle.commands.send(exec_cmd.clone());
-                                        }
+                                        broadcast(&agents, &exec_cmd).await;
                                     }
                                     // Feed through TUI state update
                                     tui::event::update(&mut app, Message::OrderPlaced(order_resp));
                                     if let Some(exec) = exec_resp {
                                         tui::event::update(&mut app, Message::Execution(exec));
+                                        let cmd = position_snapshot(Some(sim), &app);
+                                        broadcast(&agents, &cmd).await;
                                     }
-                                    risk_guard.record_submission(&symbol);
-                                } else {
-                                    let request = AddOrderRequest::new(params, None);
-                                    let mut ws = writer.lock().await;
-                                    if let Some(ref mut w) = *ws {
-                                        let _ = add_order(w, request).await;
-                                        risk_guard.record_submission(&symbol);
-                                        let _ = cmd_tx.try_send(ConnectionCommand::TokenUsed);
-                                    }
+                                } else if let Err(e) = cmd_tx
+                                    .try_send(ConnectionCommand::SubmitOrder(Box::new(params)))
+                                {
+                                    app.show_error("Order not sent: command channel full");
+                                    tracing::warn!(
+                                        "command channel full, dropping SubmitOrder: {e}"
+                                    );
                                 }
                             }
-                            Ok(RiskVerdict::RequiresConfirmation { reason }) => {
-                                app.pending_order = Some(PendingOrder {
-                                    params,
-                                    reason: reason.clone(),
-                                });
-                                app.mode = Mode::Confirm;
-                                tracing::info!(%reason, "order requires confirmation");
-                            }
+                            Ok(None) => {}
                             Err(e) => {
                                 app.show_error(format!("Order rejected: {e}"));
                                 tracing::warn!(%e, "order rejected by risk guard");
@@ -383,10 +471,12 @@ async fn main() -> Result<(), LeesonError> {
                     tui::event::Action::ConfirmOrder => {
                         if let Some(pending) = app.pending_order.take() {
                             let symbol = pending.params.symbol.clone();
+                            let side = pending.params.side;
                             if let Some(ref mut sim) = sim_engine {
                                 let ticker = app.tickers.get(&symbol);
+                                let book = app.orderbooks.get(&symbol).filter(|b| !b.is_stale);
                                 let (order_resp, exec_resp) =
-                                    sim.execute_order(&pending.params, ticker);
+                                    sim.execute_order_with_book(&pending.params, ticker, book);
                                 let cmd = AgentCommand::OrderResponse {
                                     success: order_resp.success,
                                     order_id: order_resp
@@ -403,43 +493,139 @@ async fn main() -> Result<(), LeesonError> {
                                         .and_then(|r| r.order_userref),
                                     error: order_resp.error.clone(),
                                 };
-                                for handle in agents.iter().flatten() {
-                                    let _ = handle.commands.send(cmd.clone());
-                                }
+                                broadcast(&agents, &cmd).await;
                                 if let Some(ref exec) = exec_resp {
                                     let exec_cmd = AgentCommand::ExecutionUpdate(exec.data.clone());
-                                    for handle in agents.iter().flatten() {
-                                        let _ = handle.commands.send(exec_cmd.clone());
-                                    }
+                                    broadcast(&agents, &exec_cmd).await;
                                 }
                                 tui::event::update(&mut app, Message::OrderPlaced(order_resp));
                                 if let Some(exec) = exec_resp {
                                     tui::event::update(&mut app, Message::Execution(exec));
+                                    let cmd = position_snapshot(Some(sim), &app);
+                                    broadcast(&agents, &cmd).await;
                                 }
-                                risk_guard.record_submission(&symbol);
+                                risk_guard.record_submission(&symbol, side);
                             } else {
-                                let request = AddOrderRequest::new(pending.params, None);
-                                let mut ws = writer.lock().await;
-                                if let Some(ref mut w) = *ws {
-                                    let _ = add_order(w, request).await;
-                                    risk_guard.record_submission(&symbol);
-                                    let _ = cmd_tx.try_send(ConnectionCommand::TokenUsed);
+                                let order = Box::new(pending.params);
+                                match cmd_tx.try_send(ConnectionCommand::SubmitOrder(order)) {
+                                    Ok(()) => risk_guard.record_submission(&symbol, side),
+                                    Err(e) => {
+                                        app.show_error("Order not sent: command channel full");
+                                        tracing::warn!(
+                                            "command channel full, dropping SubmitOrder: {e}"
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    tui::event::Action::CancelOrder(order_id) => {
+                        if sim_engine.is_some() {
+                            app.show_error("order cancellation is unavailable in simulation mode");
+                        } else {
+                            match cancel_order_params(&order_id) {
+                                Ok(params) => {
+                                    if let Err(e) = cmd_tx
+                                        .try_send(ConnectionCommand::CancelOrder(Box::new(params)))
+                                    {
+                                        tracing::warn!(
+                                            "command channel full, dropping CancelOrder: {e}"
+                                        );
+                                        app.show_error("cancel not sent: command queue full");
+                                    }
+                                }
+                                Err(e) => app.show_error(format!("cancel not sent: {e}")),
+                            }
+                        }
+                    }
+                    tui::event::Action::CancelAllVisible(symbol) => {
+                        if sim_engine.is_some() {
+                            app.show_error("order cancellation is unavailable in simulation mode");
+                        } else {
+                            for order_ids in app.open_order_id_batches(&symbol) {
+                                if let Err(e) =
+                                    cmd_tx.try_send(ConnectionCommand::BatchCancel(order_ids))
+                                {
+                                    tracing::warn!(
+                                        "command channel full, dropping BatchCancel: {e}"
+                                    );
+                                    app.show_error("batch cancel not sent: command queue full");
+                                    break;
                                 }
                             }
                         }
                     }
-                    tui::event::Action::CancelOrder(_order_id) => {
-                        // TODO: Implement order cancellation
+                    tui::event::Action::EditOrder(params) => {
+                        if sim_engine.is_some() {
+                            app.show_error("order editing is unavailable in simulation mode");
+                        } else if let Err(e) = cmd_tx.try_send(ConnectionCommand::EditOrder(params))
+                        {
+                            tracing::warn!("command channel full, dropping EditOrder: {e}");
+                            app.show_error("order edit not sent: command queue full");
+                        }
+                    }
+                    tui::event::Action::SetCancelAfter(timeout) => {
+                        if sim_engine.is_some() {
+                            app.cancel_after_timeout = None;
+                            app.show_error("dead man's switch is unavailable in simulation mode");
+                        } else if let Err(e) =
+                            cmd_tx.try_send(ConnectionCommand::SetCancelAfter(timeout))
+                        {
+                            tracing::warn!("command channel full, dropping SetCancelAfter: {e}");
+                        }
+                    }
+                    tui::event::Action::SetAgentPaused {
+                        agent_index,
+                        paused,
+                    } => {
+                        if let Some(handle) = agents.get(agent_index).and_then(Option::as_ref) {
+                            let cmd = if paused {
+                                AgentCommand::Pause
+                            } else {
+                                AgentCommand::Resume
+                            };
+                            send_to_agent(handle, cmd).await;
+                        }
                     }
                     tui::event::Action::SaveRiskParams(params) => {
                         if let Err(e) = params.save(agent_risk_path) {
                             app.show_error(format!("Failed to save risk params: {e}"));
                         }
                         // Re-send combined limits to all running agents
-                        let mut desc = risk_guard.config().describe_limits();
-                        desc.push_str(&params.describe());
+                        let limits = risk_guard.config().describe_limits();
                         for handle in agents.iter().flatten() {
-                            let _ = handle.commands.send(AgentCommand::RiskLimits(desc.clone()));
+                            send_risk_limits(handle, &limits, &params).await;
+                        }
+                    }
+                    tui::event::Action::ExportTradeHistory => {
+                        let timestamp = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0);
+                        let path = Path::new(".").join(format!("trades_{timestamp}.json"));
+                        match app.export_trade_history(&path) {
+                            Ok(()) => app.show_info(format!(
+                                "Exported {} trade(s) to {}",
+                                app.executed_trades_all.len(),
+                                path.display()
+                            )),
+                            Err(e) => app.show_error(format!("Trade export failed: {e}")),
+                        }
+                    }
+                    tui::event::Action::ReloadRiskConfig => {
+                        match risk_guard.config_mut().reload(risk_config_path) {
+                            Ok(()) => {
+                                let limits = risk_guard.config().describe_limits();
+                                for handle in agents.iter().flatten() {
+                                    send_risk_limits(handle, &limits, &app.agent_risk_params).await;
+                                }
+                                app.show_info("Risk limits reloaded");
+                                tracing::info!("risk config reloaded");
+                            }
+                            Err(e) => {
+                                app.show_error(format!("Risk config reload failed: {e}"));
+                                tracing::warn!(%e, "risk config reload failed");
+                            }
                         }
                     }
                     tui::event::Action::SaveApiKeys { values } => {
@@ -471,7 +657,7 @@ async fn main() -> Result<(), LeesonError> {
                         if !errors.is_empty() {
                             app.show_error(format!("Keychain errors: {}", errors.join(", ")));
                         } else if saved > 0 {
-                            app.show_error(format!("{saved} API key(s) saved"));
+                            app.show_info(format!("{saved} API key(s) saved"));
                         }
 
                         // If Kraken credentials changed, tell the connection manager
@@ -494,6 +680,43 @@ async fn main() -> Result<(), LeesonError> {
                 }
             }
 
+            // Forward throttled book snapshots once the update has been applied
+            let now = Instant::now();
+            for symbol in book_symbols {
+                let should_send = book_last_sent
+                    .get(&symbol)
+                    .is_none_or(|last| now.duration_since(*last) >= BOOK_THROTTLE);
+                let Some(book) = app.orderbooks.get(&symbol) else {
+                    continue;
+                };
+                if should_send && !book.is_stale {
+                    book_last_sent.insert(symbol.clone(), now);
+                    let bids: Vec<_> = book.bids.iter().take(BOOK_SNAPSHOT_DEPTH).collect();
+                    let asks: Vec<_> = book.asks.iter().take(BOOK_SNAPSHOT_DEPTH).collect();
+                    // The primary trading agent gets the structured book,
+                    // the others the compact text form
+                    for (index, handle) in agents.iter().enumerate() {
+                        let Some(handle) = handle else {
+                            continue;
+                        };
+                        let cmd = if index == 0 {
+                            AgentCommand::OrderBook {
+                                symbol: symbol.clone(),
+                                bids: bids.iter().map(|l| (l.price, l.qty)).collect(),
+                                asks: asks.iter().map(|l| (l.price, l.qty)).collect(),
+                            }
+                        } else {
+                            AgentCommand::BookSnapshot {
+                                symbol: symbol.clone(),
+                                bids: bids.iter().copied().cloned().collect(),
+                                asks: asks.iter().copied().cloned().collect(),
+                            }
+                        };
+                        send_to_agent(handle, cmd).await;
+                    }
+                }
+            }
+
             // Complete deferred setup once the API keys overlay is dismissed
             if !setup_complete && app.mode != Mode::ApiKeys {
                 setup_complete = true;
@@ -503,6 +726,7 @@ async fn main() -> Result<(), LeesonError> {
                 let api_secret = credentials::load(CredentialKey::KrakenApiSecret);
 
                 let has_creds = api_key.is_some() && api_secret.is_some();
+                let mut credential_info = None;
 
                 // Determine the WebSocket URL based on available credentials
                 let url = if app_config.simulation {
@@ -511,7 +735,11 @@ async fn main() -> Result<(), LeesonError> {
                     // Validate credentials before connecting
                     if let (Some(k), Some(s)) = (&api_key, &api_secret) {
                         match validate_credentials(k, s, (*tls_config).clone()).await {
-                            Ok(_) => app.authenticated = true,
+                            Ok(info) => {
+                                app.authenticated = true;
+                                app.account_tier = Some(info.tier.clone());
+                                credential_info = Some(info);
+                            }
                             Err(e) => {
                                 app.show_error(format!("Auth failed: {e}"));
                             }
@@ -528,7 +756,7 @@ async fn main() -> Result<(), LeesonError> {
 
                 // Spawn the connection manager with the deferred cmd_rx
                 if let Some(cmd_rx) = deferred_cmd_rx.take() {
-                    let manager = ConnectionManager::new(
+                    let mut manager = ConnectionManager::new(
                         url,
                         tls_config.clone(),
                         api_key,
@@ -536,26 +764,139 @@ async fn main() -> Result<(), LeesonError> {
                         tx.clone(),
                         writer.clone(),
                         cmd_rx,
-                    );
+                    )
+                    .with_max_reconnect_attempts(app_config.max_reconnect_attempts)
+                    .with_timeframe(app.chart_timeframe);
+                    if let Some(info) = credential_info {
+                        manager = manager.with_credential_info(info);
+                    }
+                    // Pairs selected while the setup overlay was open
+                    manager.set_subscribed_pairs(app.selected_pairs.clone());
                     tokio::spawn(async move { manager.run().await });
                 }
 
                 // Spawn agent subprocesses
-                match spawn_multi_agent(0, tx.clone()) {
-                    Ok(handle) => agents[0] = Some(handle),
+                match spawn_multi_agent(MULTI_AGENT_INDEX, tx.clone()) {
+                    Ok(handle) => agents[MULTI_AGENT_INDEX] = Some(handle),
                     Err(e) => app.show_error(format!("Failed to spawn multi-agent system: {e}")),
                 }
             }
         }
     }
 
-    // Shut down running agents
-    for handle in agents.iter().flatten() {
-        let _ = handle.commands.send(AgentCommand::Shutdown);
+    if let Err(e) = app.save_session_state(session_path) {
+        tracing::warn!("failed to save session state: {e}");
     }
 
-    // Restore terminal
-    tui::restore_terminal(&mut terminal)?;
+    // Shut down running agents before the terminal is restored
+    const AGENT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+    for (index, handle) in agents.iter_mut().enumerate() {
+        if let Some(handle) = handle
+            && let Err(e) = handle.shutdown_timeout(AGENT_SHUTDOWN_TIMEOUT).await
+        {
+            tracing::warn!("agent {index} shutdown: {e}");
+        }
+    }
+
+    // Restore terminal, reporting any failure instead of only logging it
+    terminal.restore()?;
+
+    Ok(())
+}
 
+/// Installs the global tracing subscriber at the configured level, writing
+/// to [`LOG_PATH`].
+fn init_tracing(config: &AppConfig) -> Result<(), LeesonError> {
+    let level = config.tracing_level()?;
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(LOG_PATH)
+        .map_err(|e| LeesonError::io(format!("failed to open {LOG_PATH}"), e))?;
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_ansi(false)
+        .with_writer(Arc::new(file))
+        .init();
     Ok(())
 }
+
+/// Sends `cmd` to one agent, dropping it with a warning if the agent does
+/// not accept it within [`AGENT_SEND_TIMEOUT`].
+async fn send_to_agent(handle: &AgentHandle, cmd: AgentCommand) {
+    if let Err(e) = handle.send_timeout(cmd, AGENT_SEND_TIMEOUT).await {
+        tracing::warn!("dropping agent command: {e}");
+    }
+}
+
+/// Sends the enforced `limits` and the agent risk `params` to one agent, as
+/// JSON if it supports it.
+async fn send_risk_limits(handle: &AgentHandle, limits: &str, params: &AgentRiskParams) {
+    for cmd in risk_limit_commands(limits, params, handle.supports_json()) {
+        send_to_agent(handle, cmd).await;
+    }
+}
+
+/// Builds the position snapshot sent to agents after an execution.
+///
+/// Uses the simulated positions when simulating, otherwise the account's
+/// spot holdings with entry prices from the fills of open positions.
+fn position_snapshot(sim_engine: Option<&SimulationEngine>, app: &App) -> AgentCommand {
+    match sim_engine {
+        Some(sim) => AgentCommand::PositionSnapshot {
+            positions: sim.positions().clone(),
+            avg_entries: sim.avg_entry_prices().clone(),
+        },
+        None => AgentCommand::PositionSnapshot {
+            positions: app.spot_positions(),
+            avg_entries: app
+                .fill_positions
+                .iter()
+                .filter(|(_, position)| !position.qty.is_zero())
+                .map(|(symbol, position)| (symbol.clone(), position.avg_price))
+                .collect(),
+        },
+    }
+}
+
+/// Builds the cancel request for one order.
+fn cancel_order_params(order_id: &str) -> leeson::Result<CancelOrderParams> {
+    // Placeholder token; the connection stamps the real one before sending
+    Ok(CancelOrderBuilder::by_single_order_id(order_id).build("pending")?)
+}
+
+/// Sends `cmd` to every running agent.
+async fn broadcast(agents: &[Option<AgentHandle>], cmd: &AgentCommand) {
+    for handle in agents.iter().flatten() {
+        send_to_agent(handle, cmd.clone()).await;
+    }
+}
+
+/// Runs the risk checks for an order from the TUI or an agent.
+///
+/// `positions` are the current net holdings, valued with the app's
+/// tickers for the portfolio exposure cap.
+///
+/// Returns the order when it may be sent right away, already recorded
+/// against the rate limits, or `None` once it has been parked for operator
+/// confirmation.
+///
+/// # Errors
+///
+/// Returns [`LeesonError::RiskCheckFailed`] if the risk guard rejects the order.
+fn review_order(
+    app: &mut App,
+    risk_guard: &mut RiskGuard,
+    params: AddOrderParams,
+    positions: &HashMap<String, Decimal>,
+) -> Result<Option<AddOrderParams>, LeesonError> {
+    match risk_guard.submit_with_check(&params, positions, &app.tickers)? {
+        RiskVerdict::Approved => Ok(Some(params)),
+        RiskVerdict::RequiresConfirmation { reason } => {
+            tracing::info!(%reason, "order requires confirmation");
+            app.pending_order = Some(PendingOrder { params, reason });
+            app.mode = Mode::Confirm;
+            Ok(None)
+        }
+    }
+}
@@ -39,6 +39,41 @@ pub enum OrderSide {
     Sell,
 }
 
+impl TryFrom<&str> for OrderType {
+    type Error = ParseOrderTypeError;
+
+    /// Parses the Kraken wire name (e.g. `"stop-loss-limit"`), ignoring case.
+    /// Underscores are accepted in place of hyphens.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().replace('_', "-").as_str() {
+            "limit" => Ok(Self::Limit),
+            "market" => Ok(Self::Market),
+            "iceberg" => Ok(Self::Iceberg),
+            "stop-loss" => Ok(Self::StopLoss),
+            "stop-loss-limit" => Ok(Self::StopLossLimit),
+            "take-profit" => Ok(Self::TakeProfit),
+            "take-profit-limit" => Ok(Self::TakeProfitLimit),
+            "trailing-stop" => Ok(Self::TrailingStop),
+            "trailing-stop-limit" => Ok(Self::TrailingStopLimit),
+            "settle-position" => Ok(Self::SettlePosition),
+            _ => Err(ParseOrderTypeError(value.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&str> for OrderSide {
+    type Error = ParseOrderSideError;
+
+    /// Parses `"buy"` or `"sell"`, ignoring case.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "buy" => Ok(Self::Buy),
+            "sell" => Ok(Self::Sell),
+            _ => Err(ParseOrderSideError(value.to_string())),
+        }
+    }
+}
+
 /// Time in force specifying how long the order remains active.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -304,6 +339,7 @@ pub struct AddOrderBuilder {
     fee_preference: Option<FeeCurrencyPreference>,
     no_mpp: Option<bool>,
     req_id: Option<u64>,
+    pair_info: Option<super::instrument::PairInfo>,
 }
 
 impl AddOrderBuilder {
@@ -409,6 +445,7 @@ impl AddOrderBuilder {
             fee_preference: None,
             no_mpp: None,
             req_id: None,
+            pair_info: None,
         }
     }
 
@@ -524,12 +561,27 @@ impl AddOrderBuilder {
         self
     }
 
+    /// Sets the pair's reference data, used by [`build`](Self::build) to
+    /// snap the limit price and quantity to valid increments.
+    #[must_use]
+    pub fn with_pair_info(mut self, pair: super::instrument::PairInfo) -> Self {
+        self.pair_info = Some(pair);
+        self
+    }
+
     /// Validates and builds the order parameters.
     ///
+    /// With [`with_pair_info`](Self::with_pair_info) set, the quantity is
+    /// rounded down to the pair's `qty_increment` and the limit price is
+    /// snapped to its `price_increment`: up for buys, down for sells. The
+    /// snapped values are what gets validated.
+    ///
     /// # Errors
     ///
-    /// Returns an error if required fields are missing for the order type.
-    pub fn build(self, token: &str) -> Result<AddOrderParams, AddOrderError> {
+    /// Returns an error if required fields are missing for the order type,
+    /// or if the snapped quantity is zero or below the pair's `qty_min`.
+    pub fn build(mut self, token: &str) -> Result<AddOrderParams, AddOrderError> {
+        self.snap_to_increments();
         self.validate()?;
 
         Ok(AddOrderParams {
@@ -588,9 +640,52 @@ impl AddOrderBuilder {
             return Err(AddOrderError::InvalidDisplayQty);
         }
 
+        // Validate the snapped quantity is still tradable on the pair
+        if let Some(ref pair) = self.pair_info
+            && (self.order_qty.is_zero() || self.order_qty < pair.qty_min)
+        {
+            return Err(AddOrderError::QtyBelowMinimum {
+                qty: self.order_qty,
+                qty_min: pair.qty_min,
+            });
+        }
+
         Ok(())
     }
 
+    fn snap_to_increments(&mut self) {
+        let Some(ref pair) = self.pair_info else {
+            return;
+        };
+
+        let qty = pair.round_qty_to_increment(self.order_qty);
+        if qty != self.order_qty {
+            tracing::warn!(
+                symbol = %self.symbol,
+                requested = %self.order_qty,
+                snapped = %qty,
+                "Order quantity snapped to qty_increment"
+            );
+            self.order_qty = qty;
+        }
+
+        if let Some(requested) = self.limit_price {
+            let mut price = pair.tick_size_for_price(requested);
+            if self.side == OrderSide::Buy && price < requested {
+                price += pair.price_increment;
+            }
+            if price != requested {
+                tracing::warn!(
+                    symbol = %self.symbol,
+                    requested = %requested,
+                    snapped = %price,
+                    "Limit price snapped to price_increment"
+                );
+                self.limit_price = Some(price);
+            }
+        }
+    }
+
     fn requires_limit_price(&self) -> bool {
         matches!(
             self.order_type,
@@ -626,6 +721,9 @@ pub enum AddOrderError {
     MissingExpireTime,
     /// Display quantity is only valid for iceberg orders.
     InvalidDisplayQty,
+    /// Quantity, after rounding down to the pair's `qty_increment`, is zero
+    /// or below the pair's minimum.
+    QtyBelowMinimum { qty: Decimal, qty_min: Decimal },
 }
 
 impl std::fmt::Display for AddOrderError {
@@ -635,12 +733,39 @@ impl std::fmt::Display for AddOrderError {
             Self::MissingTriggers(ot) => write!(f, "triggers required for {:?} orders", ot),
             Self::MissingExpireTime => write!(f, "expire_time required for GTD orders"),
             Self::InvalidDisplayQty => write!(f, "display_qty only valid for iceberg orders"),
+            Self::QtyBelowMinimum { qty, qty_min } => {
+                write!(f, "order_qty {qty} is below the pair minimum {qty_min}")
+            }
         }
     }
 }
 
 impl std::error::Error for AddOrderError {}
 
+/// Error returned when a string is not a known [`OrderType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOrderTypeError(pub String);
+
+impl std::fmt::Display for ParseOrderTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown order type: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseOrderTypeError {}
+
+/// Error returned when a string is not a known [`OrderSide`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOrderSideError(pub String);
+
+impl std::fmt::Display for ParseOrderSideError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid side: {} (expected buy or sell)", self.0)
+    }
+}
+
+impl std::error::Error for ParseOrderSideError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -699,6 +824,59 @@ mod tests {
         assert_eq!(value["params"]["triggers"]["price"], "40000");
     }
 
+    #[test]
+    fn pair_info_snaps_buys_up_and_sells_down() {
+        let pair = crate::models::instrument::tests::pair(dec!(0.5), dec!(0.001));
+        let build = |side| {
+            AddOrderBuilder::limit(side, "BTC/USD", dec!(0.12345), dec!(100.2))
+                .with_pair_info(pair.clone())
+                .build("token")
+                .unwrap()
+        };
+
+        let buy = build(OrderSide::Buy);
+        assert_eq!(buy.limit_price, Some(dec!(100.5)));
+        assert_eq!(buy.order_qty, dec!(0.123));
+
+        let sell = build(OrderSide::Sell);
+        assert_eq!(sell.limit_price, Some(dec!(100.0)));
+        assert_eq!(sell.order_qty, dec!(0.123));
+
+        let on_tick = AddOrderBuilder::limit(OrderSide::Buy, "BTC/USD", dec!(1), dec!(100.5))
+            .with_pair_info(pair)
+            .build("token")
+            .unwrap();
+        assert_eq!(on_tick.limit_price, Some(dec!(100.5)));
+    }
+
+    #[test]
+    fn pair_info_rejects_qty_snapped_below_minimum() {
+        let pair = crate::models::instrument::tests::pair(dec!(0.5), dec!(0.001));
+        let build = |qty| {
+            AddOrderBuilder::market(OrderSide::Buy, "BTC/USD", qty)
+                .with_pair_info(pair.clone())
+                .build("token")
+        };
+
+        assert_eq!(
+            build(dec!(0.0009)).unwrap_err(),
+            AddOrderError::QtyBelowMinimum {
+                qty: dec!(0.000),
+                qty_min: dec!(0.0001),
+            }
+        );
+
+        let mut coarse = pair.clone();
+        coarse.qty_min = dec!(0.01);
+        let err = AddOrderBuilder::market(OrderSide::Buy, "BTC/USD", dec!(0.0099))
+            .with_pair_info(coarse)
+            .build("token")
+            .unwrap_err();
+        assert!(matches!(err, AddOrderError::QtyBelowMinimum { .. }));
+
+        assert_eq!(build(dec!(0.0019)).unwrap().order_qty, dec!(0.001));
+    }
+
     #[test]
     fn validate_limit_requires_price() {
         let result = AddOrderBuilder::new(OrderType::Limit, OrderSide::Buy, "BTC/USD", dec!(1.0))
@@ -799,4 +977,38 @@ mod tests {
         assert_eq!(value["params"]["order_type"], "iceberg");
         assert_eq!(value["params"]["display_qty"], "1");
     }
+
+    #[test]
+    fn order_side_try_from_str() {
+        assert_eq!(OrderSide::try_from("buy"), Ok(OrderSide::Buy));
+        assert_eq!(OrderSide::try_from("SELL"), Ok(OrderSide::Sell));
+        assert_eq!(
+            OrderSide::try_from("hold"),
+            Err(ParseOrderSideError("hold".to_string()))
+        );
+    }
+
+    #[test]
+    fn order_type_try_from_str() {
+        let cases = [
+            ("limit", OrderType::Limit),
+            ("market", OrderType::Market),
+            ("iceberg", OrderType::Iceberg),
+            ("stop-loss", OrderType::StopLoss),
+            ("stop-loss-limit", OrderType::StopLossLimit),
+            ("take-profit", OrderType::TakeProfit),
+            ("take-profit-limit", OrderType::TakeProfitLimit),
+            ("trailing-stop", OrderType::TrailingStop),
+            ("trailing-stop-limit", OrderType::TrailingStopLimit),
+            ("settle-position", OrderType::SettlePosition),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(OrderType::try_from(input), Ok(expected), "{input}");
+        }
+        assert_eq!(OrderType::try_from("Stop_Loss"), Ok(OrderType::StopLoss));
+        assert_eq!(
+            OrderType::try_from("fill-or-kill"),
+            Err(ParseOrderTypeError("fill-or-kill".to_string()))
+        );
+    }
 }
@@ -51,6 +51,29 @@ pub struct AmendOrderParams {
     pub token: super::RedactedToken,
 }
 
+/// Basis points in one whole (100%).
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+impl AmendOrderParams {
+    /// Returns the order quantity after changing `current_qty` by `delta`,
+    /// for use with [`AmendOrderBuilder::with_order_qty`].
+    ///
+    /// A negative `delta` shrinks the order; the result never drops below
+    /// zero.
+    #[must_use]
+    pub fn delta_qty(current_qty: Decimal, delta: Decimal) -> Decimal {
+        (current_qty + delta).max(Decimal::ZERO)
+    }
+
+    /// Returns `current_price` moved by `bps` basis points, e.g. `25` for
+    /// +0.25% or `-10` for -0.10%, for use with
+    /// [`AmendOrderBuilder::with_limit_price`].
+    #[must_use]
+    pub fn delta_price(current_price: Decimal, bps: Decimal) -> Decimal {
+        current_price + current_price * bps / BPS_PER_UNIT
+    }
+}
+
 /// The amend_order request message.
 #[derive(Debug, Clone, Serialize)]
 pub struct AmendOrderRequest {
@@ -106,7 +129,7 @@ pub struct AmendOrderResponse {
 }
 
 /// Builder for constructing amend_order requests with validation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct AmendOrderBuilder {
     order_id: Option<String>,
     cl_ord_id: Option<String>,
@@ -123,42 +146,38 @@ pub struct AmendOrderBuilder {
 }
 
 impl AmendOrderBuilder {
+    /// Creates a new empty builder; set the target with
+    /// [`with_order_id`](Self::with_order_id) or
+    /// [`with_cl_ord_id`](Self::with_cl_ord_id).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     /// Creates a new builder targeting an order by its Kraken order ID.
     #[must_use]
     pub fn by_order_id(order_id: &str) -> Self {
-        Self {
-            order_id: Some(order_id.to_string()),
-            cl_ord_id: None,
-            order_qty: None,
-            limit_price: None,
-            limit_price_type: None,
-            display_qty: None,
-            post_only: None,
-            trigger_price: None,
-            trigger_price_type: None,
-            symbol: None,
-            deadline: None,
-            req_id: None,
-        }
+        Self::new().with_order_id(order_id)
     }
 
     /// Creates a new builder targeting an order by its client order ID.
     #[must_use]
     pub fn by_cl_ord_id(cl_ord_id: &str) -> Self {
-        Self {
-            order_id: None,
-            cl_ord_id: Some(cl_ord_id.to_string()),
-            order_qty: None,
-            limit_price: None,
-            limit_price_type: None,
-            display_qty: None,
-            post_only: None,
-            trigger_price: None,
-            trigger_price_type: None,
-            symbol: None,
-            deadline: None,
-            req_id: None,
-        }
+        Self::new().with_cl_ord_id(cl_ord_id)
+    }
+
+    /// Sets the Kraken order ID of the order to amend.
+    #[must_use]
+    pub fn with_order_id(mut self, order_id: &str) -> Self {
+        self.order_id = Some(order_id.to_string());
+        self
+    }
+
+    /// Sets the client order ID of the order to amend.
+    #[must_use]
+    pub fn with_cl_ord_id(mut self, cl_ord_id: &str) -> Self {
+        self.cl_ord_id = Some(cl_ord_id.to_string());
+        self
     }
 
     /// Sets the new order quantity.
@@ -313,6 +332,43 @@ mod tests {
     use super::*;
     use rust_decimal_macros::dec;
 
+    #[test]
+    fn delta_qty_adds_signed_delta() {
+        assert_eq!(AmendOrderParams::delta_qty(dec!(1.5), dec!(0.5)), dec!(2.0));
+        assert_eq!(
+            AmendOrderParams::delta_qty(dec!(1.5), dec!(-0.5)),
+            dec!(1.0)
+        );
+        assert_eq!(
+            AmendOrderParams::delta_qty(dec!(1.5), Decimal::ZERO),
+            dec!(1.5)
+        );
+    }
+
+    #[test]
+    fn delta_qty_never_goes_negative() {
+        assert_eq!(
+            AmendOrderParams::delta_qty(dec!(1), dec!(-2)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn delta_price_moves_by_basis_points() {
+        assert_eq!(
+            AmendOrderParams::delta_price(dec!(50000), dec!(25)),
+            dec!(50125)
+        );
+        assert_eq!(
+            AmendOrderParams::delta_price(dec!(50000), dec!(-10)),
+            dec!(49950)
+        );
+        assert_eq!(
+            AmendOrderParams::delta_price(dec!(50000), Decimal::ZERO),
+            dec!(50000)
+        );
+    }
+
     #[test]
     fn serialize_amend_by_order_id() {
         let params = AmendOrderBuilder::by_order_id("OAIYAU-LGI3M-PFM5VW")
@@ -431,6 +487,47 @@ mod tests {
         assert!(matches!(result, Err(AmendOrderError::NoAmendmentFields)));
     }
 
+    #[test]
+    fn builder_sets_identifier_and_req_id() {
+        let request = AmendOrderBuilder::new()
+            .with_cl_ord_id("my-order")
+            .with_order_qty(dec!(2))
+            .with_req_id(7)
+            .build_request("token")
+            .unwrap();
+
+        assert_eq!(request.req_id(), Some(7));
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["params"]["cl_ord_id"], "my-order");
+        assert_eq!(value["params"]["order_qty"], "2");
+        assert!(value["params"].get("order_id").is_none());
+    }
+
+    #[test]
+    fn builder_new_requires_identifier() {
+        let result = AmendOrderBuilder::new()
+            .with_limit_price(dec!(100))
+            .build("token");
+
+        assert!(matches!(
+            result,
+            Err(AmendOrderError::MissingOrderIdentifier)
+        ));
+    }
+
+    #[test]
+    fn validate_post_only_alone_is_an_amendment() {
+        let params = AmendOrderBuilder::new()
+            .with_order_id("OTEST")
+            .with_post_only(true)
+            .build("token")
+            .unwrap();
+
+        assert_eq!(params.post_only, Some(true));
+        assert!(params.order_qty.is_none());
+        assert!(params.limit_price.is_none());
+    }
+
     #[test]
     fn deserialize_success_response() {
         let json = r#"{
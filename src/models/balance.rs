@@ -1,8 +1,12 @@
 //! Balance channel models.
 
+use std::collections::HashMap;
+
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use super::ticker::TickerData;
+
 /// Response from the balances channel (snapshot or update).
 #[derive(Debug, Clone, Deserialize)]
 pub struct BalanceResponse {
@@ -72,3 +76,122 @@ pub struct BalanceUpdateData {
     /// Wallet identifier.
     pub wallet_id: String,
 }
+
+/// Balance for a single asset, aggregated across wallets.
+#[derive(Clone, Debug)]
+pub struct AssetBalance {
+    /// Asset symbol (e.g., "BTC", "USD").
+    pub asset: String,
+    /// Total balance across all wallets.
+    pub total: Decimal,
+    /// Balance in spot wallet.
+    pub spot: Decimal,
+    /// Balance in earn wallet.
+    pub earn: Decimal,
+}
+
+/// Account balances valued in USD.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BalanceSnapshot {
+    /// Sum of all priced balances in USD.
+    pub total_usd_equivalent: Decimal,
+    /// Assets without a `{ASSET}/USD` ticker, left out of the total.
+    pub unpriced_assets: Vec<String>,
+}
+
+impl BalanceSnapshot {
+    /// Values `balances` in USD using the last traded price of each
+    /// `{ASSET}/USD` pair in `prices`, keyed by symbol.
+    ///
+    /// USD balances count at face value. Assets with no matching ticker are
+    /// listed in [`unpriced_assets`](Self::unpriced_assets).
+    #[must_use]
+    pub fn from(
+        balances: &HashMap<String, AssetBalance>,
+        prices: &HashMap<String, TickerData>,
+    ) -> Self {
+        let mut snapshot = Self::default();
+
+        for balance in balances.values() {
+            if balance.asset == "USD" {
+                snapshot.total_usd_equivalent += balance.total;
+                continue;
+            }
+            match prices.get(&format!("{}/USD", balance.asset)) {
+                Some(ticker) => snapshot.total_usd_equivalent += balance.total * ticker.last,
+                None if balance.total.is_zero() => {}
+                None => snapshot.unpriced_assets.push(balance.asset.clone()),
+            }
+        }
+
+        snapshot.unpriced_assets.sort();
+        snapshot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn balance(asset: &str, total: Decimal) -> (String, AssetBalance) {
+        let balance = AssetBalance {
+            asset: asset.to_string(),
+            total,
+            spot: total,
+            earn: Decimal::ZERO,
+        };
+        (asset.to_string(), balance)
+    }
+
+    fn ticker(symbol: &str, last: Decimal) -> (String, TickerData) {
+        let ticker = TickerData {
+            symbol: symbol.to_string(),
+            bid: last,
+            bid_qty: Decimal::ONE,
+            ask: last,
+            ask_qty: Decimal::ONE,
+            last,
+            volume: Some(Decimal::ZERO),
+            vwap: Some(last),
+            low: Some(last),
+            high: Some(last),
+            change: Some(Decimal::ZERO),
+            change_pct: Some(Decimal::ZERO),
+        };
+        (symbol.to_string(), ticker)
+    }
+
+    #[test]
+    fn sums_usd_and_priced_assets() {
+        let balances = HashMap::from([
+            balance("USD", dec!(1000)),
+            balance("BTC", dec!(0.5)),
+            balance("ETH", dec!(2)),
+        ]);
+        let prices = HashMap::from([
+            ticker("BTC/USD", dec!(50000)),
+            ticker("ETH/USD", dec!(3000)),
+        ]);
+
+        let snapshot = BalanceSnapshot::from(&balances, &prices);
+
+        assert_eq!(snapshot.total_usd_equivalent, dec!(32000));
+        assert!(snapshot.unpriced_assets.is_empty());
+    }
+
+    #[test]
+    fn skips_assets_without_usd_ticker() {
+        let balances = HashMap::from([
+            balance("USD", dec!(100)),
+            balance("DOT", dec!(10)),
+            balance("SOL", Decimal::ZERO),
+        ]);
+        let prices = HashMap::from([ticker("DOT/EUR", dec!(5))]);
+
+        let snapshot = BalanceSnapshot::from(&balances, &prices);
+
+        assert_eq!(snapshot.total_usd_equivalent, dec!(100));
+        assert_eq!(snapshot.unpriced_assets, vec!["DOT".to_string()]);
+    }
+}
@@ -15,8 +15,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    ConditionalOrder, FeeCurrencyPreference, OrderSide, OrderType, StpType, TimeInForce,
-    TriggerParams,
+    AddOrderParams, ConditionalOrder, FeeCurrencyPreference, OrderSide, OrderType, StpType,
+    TimeInForce, TriggerParams,
 };
 
 /// Minimum number of orders in a batch.
@@ -202,6 +202,34 @@ impl BatchOrderEntry {
     }
 }
 
+impl From<AddOrderParams> for BatchOrderEntry {
+    /// Converts single-order parameters into a batch entry.
+    ///
+    /// `symbol` and `token` move to the batch level; `validate` and `no_mpp`
+    /// have no per-entry equivalent and are dropped.
+    fn from(params: AddOrderParams) -> Self {
+        Self {
+            order_type: params.order_type,
+            side: params.side,
+            order_qty: params.order_qty,
+            limit_price: params.limit_price,
+            time_in_force: params.time_in_force,
+            expire_time: params.expire_time,
+            post_only: params.post_only,
+            reduce_only: params.reduce_only,
+            margin: params.margin,
+            cl_ord_id: params.cl_ord_id,
+            order_userref: params.order_userref,
+            triggers: params.triggers,
+            conditional: params.conditional,
+            display_qty: params.display_qty,
+            stp_type: params.stp_type,
+            fee_preference: params.fee_preference,
+            cash_order_qty: None,
+        }
+    }
+}
+
 /// Parameters for the batch_add request.
 #[derive(Debug, Clone, Serialize)]
 pub struct BatchAddParams {
@@ -336,6 +364,18 @@ impl BatchAddBuilder {
         self
     }
 
+    /// Adds a market order for the batch symbol.
+    #[must_use]
+    pub fn add_market(self, side: OrderSide, qty: Decimal) -> Self {
+        self.add_order(BatchOrderEntry::market(side, qty))
+    }
+
+    /// Adds a limit order for the batch symbol.
+    #[must_use]
+    pub fn add_limit(self, side: OrderSide, qty: Decimal, price: Decimal) -> Self {
+        self.add_order(BatchOrderEntry::limit(side, qty, price))
+    }
+
     /// Adds multiple orders to the batch.
     #[must_use]
     pub fn add_orders(mut self, orders: Vec<BatchOrderEntry>) -> Self {
@@ -428,6 +468,7 @@ impl std::error::Error for BatchAddError {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::AddOrderBuilder;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -455,6 +496,23 @@ mod tests {
         assert_eq!(value["params"]["orders"][1]["stp_type"], "cancel_both");
     }
 
+    #[test]
+    fn batch_entry_from_add_order_params() {
+        let params = AddOrderBuilder::limit(OrderSide::Sell, "BTC/USD", dec!(0.5), dec!(60000))
+            .with_cl_ord_id("abc")
+            .build("test_token")
+            .unwrap();
+
+        let entry = BatchOrderEntry::from(params);
+        let value = serde_json::to_value(&entry).unwrap();
+
+        assert_eq!(value["side"], "sell");
+        assert_eq!(value["limit_price"], "60000");
+        assert_eq!(value["cl_ord_id"], "abc");
+        assert!(value.get("symbol").is_none());
+        assert!(value.get("token").is_none());
+    }
+
     #[test]
     fn serialize_batch_with_deadline_and_validate() {
         let orders = vec![
@@ -517,6 +575,27 @@ mod tests {
         assert_eq!(request.req_id(), Some(42));
     }
 
+    #[test]
+    fn builder_accepts_size_boundaries() {
+        let min = BatchAddBuilder::new("BTC/USD")
+            .add_market(OrderSide::Buy, dec!(1))
+            .add_limit(OrderSide::Sell, dec!(1), dec!(55000))
+            .build("token")
+            .unwrap();
+        assert_eq!(min.order_count(), MIN_BATCH_SIZE);
+        assert_eq!(min.params.orders[0].order_type, OrderType::Market);
+        assert_eq!(min.params.orders[1].limit_price, Some(dec!(55000)));
+
+        let mut builder = BatchAddBuilder::new("BTC/USD");
+        for _ in 0..MAX_BATCH_SIZE {
+            builder = builder.add_market(OrderSide::Buy, dec!(1));
+        }
+        assert_eq!(
+            builder.build("token").unwrap().order_count(),
+            MAX_BATCH_SIZE
+        );
+    }
+
     #[test]
     fn deserialize_success_response() {
         let json = r#"{
@@ -35,6 +35,14 @@ pub struct PriceLevel {
     pub qty: Decimal,
 }
 
+impl PriceLevel {
+    /// Returns the quote-currency value resting at this level (`price * qty`).
+    #[must_use]
+    pub fn notional(&self) -> Decimal {
+        self.price * self.qty
+    }
+}
+
 /// Available depth levels for order book subscriptions.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(
@@ -93,6 +101,29 @@ fn format_level_for_checksum(level: &PriceLevel) -> String {
 /// 5. Computing CRC32 on the resulting string
 ///
 /// Note: The checksum always uses the top 10 levels regardless of subscription depth.
+///
+/// Prices and quantities must keep the precision Kraken sent them with:
+/// `0.10000000` contributes `10000000`, while `0.1` contributes `1`.
+///
+/// # Examples
+///
+/// ```
+/// use leeson::models::book::{PriceLevel, calculate_checksum};
+/// use rust_decimal::Decimal;
+///
+/// let level = |price: &str, qty: &str| PriceLevel {
+///     price: price.parse::<Decimal>().unwrap(),
+///     qty: qty.parse::<Decimal>().unwrap(),
+/// };
+/// let asks = [level("0.05005", "0.00000500")];
+/// let bids = [level("0.05004", "0.00000500")];
+///
+/// // CRC32 of "5005500" + "5004500"
+/// assert_eq!(
+///     calculate_checksum(&asks, &bids),
+///     crc32fast::hash(b"50055005004500")
+/// );
+/// ```
 #[must_use]
 pub fn calculate_checksum(asks: &[PriceLevel], bids: &[PriceLevel]) -> u32 {
     let mut checksum_str = String::new();
@@ -115,3 +146,92 @@ pub fn calculate_checksum(asks: &[PriceLevel], bids: &[PriceLevel]) -> u32 {
 pub fn verify_checksum(book: &BookData) -> bool {
     calculate_checksum(&book.asks, &book.bids) == book.checksum
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Book snapshot from Kraken's "Spot WebSocket Book v2" checksum guide.
+    const KRAKEN_REFERENCE_BOOK: &str = r#"{
+        "symbol": "BTC/USD",
+        "bids": [
+            {"price": 45283.5, "qty": 0.10000000},
+            {"price": 45283.4, "qty": 1.54582015},
+            {"price": 45282.1, "qty": 0.10000000},
+            {"price": 45281.0, "qty": 0.10000000},
+            {"price": 45280.3, "qty": 1.54592586},
+            {"price": 45279.0, "qty": 0.07990000},
+            {"price": 45277.6, "qty": 0.03310103},
+            {"price": 45277.5, "qty": 0.30000000},
+            {"price": 45277.3, "qty": 1.54602737},
+            {"price": 45276.6, "qty": 0.15445238}
+        ],
+        "asks": [
+            {"price": 45285.2, "qty": 0.00100000},
+            {"price": 45286.4, "qty": 1.54571953},
+            {"price": 45286.6, "qty": 1.54571109},
+            {"price": 45289.6, "qty": 1.54560911},
+            {"price": 45290.2, "qty": 0.15890660},
+            {"price": 45291.8, "qty": 1.54553491},
+            {"price": 45294.7, "qty": 0.04454749},
+            {"price": 45296.1, "qty": 0.35380000},
+            {"price": 45297.5, "qty": 0.09945542},
+            {"price": 45299.5, "qty": 0.18772827}
+        ],
+        "checksum": 3310070434,
+        "timestamp": "2023-10-06T17:35:55.440295Z"
+    }"#;
+
+    #[test]
+    fn notional_is_price_times_qty() {
+        let level = PriceLevel {
+            price: Decimal::new(500005, 1),
+            qty: Decimal::new(25, 1),
+        };
+        assert_eq!(level.notional(), Decimal::new(12500125, 2));
+    }
+
+    #[test]
+    fn kraken_reference_checksum() {
+        let book: BookData = serde_json::from_str(KRAKEN_REFERENCE_BOOK).unwrap();
+        assert_eq!(calculate_checksum(&book.asks, &book.bids), 3310070434);
+        assert!(verify_checksum(&book));
+    }
+
+    #[test]
+    fn checksum_ignores_levels_beyond_ten() {
+        let mut book: BookData = serde_json::from_str(KRAKEN_REFERENCE_BOOK).unwrap();
+        let extra = PriceLevel {
+            price: Decimal::new(453000, 1),
+            qty: Decimal::ONE,
+        };
+        book.asks.push(extra.clone());
+        book.bids.push(extra);
+        assert!(verify_checksum(&book));
+    }
+
+    fn price_level() -> impl Strategy<Value = PriceLevel> {
+        (1i64..10_000_000, 0u32..8, 1i64..1_000_000_000).prop_map(|(price, scale, qty)| {
+            PriceLevel {
+                price: Decimal::new(price, scale),
+                qty: Decimal::new(qty, 8),
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn checksum_is_stable_for_identical_books(
+            asks in prop::collection::vec(price_level(), 0..15),
+            bids in prop::collection::vec(price_level(), 0..15),
+        ) {
+            let copy_asks = asks.clone();
+            let copy_bids = bids.clone();
+            prop_assert_eq!(
+                calculate_checksum(&asks, &bids),
+                calculate_checksum(&copy_asks, &copy_bids)
+            );
+        }
+    }
+}
@@ -3,8 +3,13 @@
 //! Provides types for cancelling orders via the Kraken WebSocket V2 API.
 //! Like `add_order`, this is an RPC-style one-shot request/response command.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::batch_cancel::MAX_BATCH_CANCEL_SIZE;
+use super::execution::ExecutionData;
+
 /// Parameters for the cancel_order request.
 #[derive(Debug, Clone, Serialize)]
 pub struct CancelOrderParams {
@@ -102,6 +107,33 @@ impl CancelOrderBuilder {
         Self::by_order_id(vec![order_id.to_string()])
     }
 
+    /// Creates a builder to cancel every open order for `symbol` in
+    /// `open_orders`, keyed by symbol as in the TUI's order state.
+    ///
+    /// A symbol without open orders yields a builder that fails to build
+    /// with [`CancelOrderError::NoOrderIdentifier`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CancelOrderError::TooManyOrders`] if the symbol has more
+    /// than [`MAX_BATCH_CANCEL_SIZE`] open orders.
+    pub fn for_symbol(
+        symbol: &str,
+        open_orders: &HashMap<String, Vec<ExecutionData>>,
+    ) -> Result<Self, CancelOrderError> {
+        let order_ids: Vec<String> = open_orders
+            .get(symbol)
+            .map(|orders| orders.iter().map(|o| o.order_id.clone()).collect())
+            .unwrap_or_default();
+        if order_ids.len() > MAX_BATCH_CANCEL_SIZE {
+            return Err(CancelOrderError::TooManyOrders {
+                count: order_ids.len(),
+                max: MAX_BATCH_CANCEL_SIZE,
+            });
+        }
+        Ok(Self::by_order_id(order_ids))
+    }
+
     /// Creates a builder to cancel orders by client order ID.
     #[must_use]
     pub fn by_cl_ord_id(cl_ord_ids: Vec<String>) -> Self {
@@ -205,6 +237,8 @@ impl CancelOrderBuilder {
 pub enum CancelOrderError {
     /// At least one order identifier must be provided.
     NoOrderIdentifier,
+    /// More orders than a single cancel request accepts.
+    TooManyOrders { count: usize, max: usize },
 }
 
 impl std::fmt::Display for CancelOrderError {
@@ -214,6 +248,9 @@ impl std::fmt::Display for CancelOrderError {
                 f,
                 "at least one of order_id, cl_ord_id, or order_userref must be provided"
             ),
+            Self::TooManyOrders { count, max } => {
+                write!(f, "{} orders to cancel, maximum is {}", count, max)
+            }
         }
     }
 }
@@ -293,6 +330,67 @@ mod tests {
         );
     }
 
+    fn open_orders(symbol: &str, count: usize) -> HashMap<String, Vec<ExecutionData>> {
+        let orders = (0..count)
+            .map(|i| {
+                serde_json::from_value(serde_json::json!({
+                    "exec_type": "new",
+                    "order_id": format!("O{i}"),
+                    "symbol": symbol,
+                    "side": "buy",
+                    "order_type": "limit",
+                    "order_qty": "1",
+                    "order_status": "new",
+                    "timestamp": "2024-01-01T00:00:00Z"
+                }))
+                .unwrap()
+            })
+            .collect();
+        HashMap::from([(symbol.to_string(), orders)])
+    }
+
+    #[test]
+    fn for_symbol_cancels_every_open_order() {
+        let params = CancelOrderBuilder::for_symbol("BTC/USD", &open_orders("BTC/USD", 2))
+            .unwrap()
+            .build("token")
+            .unwrap();
+
+        assert_eq!(
+            params.order_id,
+            Some(vec!["O0".to_string(), "O1".to_string()])
+        );
+    }
+
+    #[test]
+    fn for_symbol_without_orders_does_not_build() {
+        let result = CancelOrderBuilder::for_symbol("ETH/USD", &open_orders("BTC/USD", 2))
+            .unwrap()
+            .build("token");
+
+        assert!(matches!(result, Err(CancelOrderError::NoOrderIdentifier)));
+    }
+
+    #[test]
+    fn for_symbol_rejects_too_many_orders() {
+        let orders = open_orders("BTC/USD", MAX_BATCH_CANCEL_SIZE + 1);
+
+        assert_eq!(
+            CancelOrderBuilder::for_symbol("BTC/USD", &orders).unwrap_err(),
+            CancelOrderError::TooManyOrders {
+                count: MAX_BATCH_CANCEL_SIZE + 1,
+                max: MAX_BATCH_CANCEL_SIZE,
+            }
+        );
+        assert!(
+            CancelOrderBuilder::for_symbol(
+                "BTC/USD",
+                &open_orders("BTC/USD", MAX_BATCH_CANCEL_SIZE)
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn validate_requires_identifier() {
         let result = CancelOrderBuilder::new().build("token");
@@ -41,3 +41,95 @@ pub struct CandleData {
     /// End timestamp of this candle.
     pub timestamp: String,
 }
+
+/// Body-to-range ratio below which a candle counts as a doji.
+const DOJI_BODY_RATIO: Decimal = Decimal::from_parts(1, 0, 0, false, 1);
+
+impl CandleData {
+    /// Returns `true` if the candle's body is under a tenth of its range,
+    /// i.e. it opened and closed at almost the same price.
+    ///
+    /// A candle without range is a doji only if it opened and closed at the
+    /// same price.
+    #[must_use]
+    pub fn is_doji(&self) -> bool {
+        let range = self.high - self.low;
+        let body = (self.open - self.close).abs();
+        if range.is_zero() {
+            return body.is_zero();
+        }
+        body / range < DOJI_BODY_RATIO
+    }
+
+    /// Returns `true` if this candle's body engulfs the opposite-colored
+    /// body of `prev`, either bullish (a rise after a fall) or bearish (a
+    /// fall after a rise).
+    #[must_use]
+    pub fn is_engulfing(&self, prev: &CandleData) -> bool {
+        let bullish = prev.close < prev.open
+            && self.close > self.open
+            && self.close > prev.open
+            && self.open < prev.close;
+        let bearish = prev.close > prev.open
+            && self.close < self.open
+            && self.close < prev.open
+            && self.open > prev.close;
+        bullish || bearish
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn candle(open: Decimal, high: Decimal, low: Decimal, close: Decimal) -> CandleData {
+        CandleData {
+            symbol: "BTC/USD".to_string(),
+            open,
+            high,
+            low,
+            close,
+            vwap: close,
+            trades: 1,
+            volume: Decimal::ONE,
+            interval_begin: "2024-01-01T00:00:00Z".to_string(),
+            interval: 1,
+            timestamp: "2024-01-01T00:01:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn doji_has_a_small_body_for_its_range() {
+        assert!(candle(dec!(100), dec!(110), dec!(90), dec!(101)).is_doji());
+        assert!(!candle(dec!(100), dec!(110), dec!(90), dec!(102)).is_doji());
+        assert!(!candle(dec!(90), dec!(110), dec!(90), dec!(110)).is_doji());
+    }
+
+    #[test]
+    fn doji_without_range_needs_equal_open_and_close() {
+        assert!(candle(dec!(100), dec!(100), dec!(100), dec!(100)).is_doji());
+    }
+
+    #[test]
+    fn bullish_engulfing_follows_a_smaller_falling_candle() {
+        let prev = candle(dec!(105), dec!(106), dec!(99), dec!(100));
+
+        assert!(candle(dec!(99), dec!(108), dec!(98), dec!(107)).is_engulfing(&prev));
+        // Rises but does not close above the previous open
+        assert!(!candle(dec!(99), dec!(105), dec!(98), dec!(104)).is_engulfing(&prev));
+        // Falls again
+        assert!(!candle(dec!(107), dec!(108), dec!(98), dec!(99)).is_engulfing(&prev));
+    }
+
+    #[test]
+    fn bearish_engulfing_follows_a_smaller_rising_candle() {
+        let prev = candle(dec!(100), dec!(106), dec!(99), dec!(105));
+
+        assert!(candle(dec!(106), dec!(107), dec!(98), dec!(99)).is_engulfing(&prev));
+        // Opens inside the previous body
+        assert!(!candle(dec!(104), dec!(105), dec!(98), dec!(99)).is_engulfing(&prev));
+        // Rises again
+        assert!(!candle(dec!(99), dec!(107), dec!(98), dec!(106)).is_engulfing(&prev));
+    }
+}
@@ -240,9 +240,21 @@ impl EditOrderBuilder {
     ///
     /// Returns an error if no editable fields are provided.
     pub fn build(self, token: &str) -> Result<EditOrderRequest, EditOrderError> {
+        let req_id = self.req_id;
+        let params = self.build_params(token)?;
+        Ok(EditOrderRequest::new(params, req_id))
+    }
+
+    /// Validates and builds the request parameters without wrapping them in
+    /// a request, e.g. to have the token and `req_id` filled in later.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no editable fields are provided.
+    pub fn build_params(self, token: &str) -> Result<EditOrderParams, EditOrderError> {
         self.validate()?;
 
-        let params = EditOrderParams {
+        Ok(EditOrderParams {
             order_id: self.order_id,
             symbol: self.symbol,
             token: super::RedactedToken::new(token),
@@ -256,9 +268,7 @@ impl EditOrderBuilder {
             deadline: self.deadline,
             triggers: self.triggers,
             validate: self.validate,
-        };
-
-        Ok(EditOrderRequest::new(params, self.req_id))
+        })
     }
 
     fn validate(&self) -> Result<(), EditOrderError> {
@@ -353,6 +363,31 @@ mod tests {
         assert_eq!(value["params"]["validate"], true);
     }
 
+    #[test]
+    fn build_params_then_wrap_in_request() {
+        let params = EditOrderBuilder::new("OFGKYQ-FHPCQ-HUQFEK", "BTC/USD")
+            .with_limit_price(dec!(51000))
+            .build_params("pending")
+            .unwrap();
+        assert_eq!(params.order_id, "OFGKYQ-FHPCQ-HUQFEK");
+        assert_eq!(params.limit_price, Some(dec!(51000)));
+        assert_eq!(params.order_qty, None);
+
+        let request = EditOrderRequest::new(params, Some(7));
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value["method"], "edit_order");
+        assert_eq!(value["req_id"], 7);
+        assert_eq!(value["params"]["limit_price"], "51000");
+        assert!(value["params"].get("order_qty").is_none());
+    }
+
+    #[test]
+    fn build_params_requires_edit_field() {
+        let result = EditOrderBuilder::new("ORDER123", "BTC/USD").build_params("token");
+
+        assert!(matches!(result, Err(EditOrderError::NoEditFields)));
+    }
+
     #[test]
     fn validate_requires_edit_field() {
         let result = EditOrderBuilder::new("ORDER123", "BTC/USD").build("token");
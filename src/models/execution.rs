@@ -88,6 +88,68 @@ pub struct ExecutionData {
     pub sender_sub_id: Option<String>,
 }
 
+impl ExecutionData {
+    /// Returns true if this execution's side adds to `current_position`,
+    /// the net quantity held before it: a buy while flat or long, or a sell
+    /// while flat or short.
+    #[must_use]
+    pub fn is_opening_trade(&self, current_position: Decimal) -> bool {
+        match self.side.as_str() {
+            "buy" => current_position >= Decimal::ZERO,
+            "sell" => current_position <= Decimal::ZERO,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this execution's side reduces `current_position`: a
+    /// buy while short or a sell while long.
+    ///
+    /// A fill larger than the position closes it and opens the rest on the
+    /// other side.
+    #[must_use]
+    pub fn is_closing_trade(&self, current_position: Decimal) -> bool {
+        match self.side.as_str() {
+            "buy" => current_position < Decimal::ZERO,
+            "sell" => current_position > Decimal::ZERO,
+            _ => false,
+        }
+    }
+
+    /// Sum of every fee charged on this execution, zero if none are listed.
+    ///
+    /// Fees are summed as-is, so the result is only meaningful when they are
+    /// all charged in the quote currency.
+    #[must_use]
+    pub fn total_fees(&self) -> Decimal {
+        self.fees.iter().flatten().map(|fee| fee.qty).sum()
+    }
+
+    /// Returns the cash this execution moved after fees: `cum_cost - fees`
+    /// received for a sell, `-cum_cost - fees` paid for a buy.
+    ///
+    /// `None` if `cum_cost` is absent or the side is unknown.
+    #[must_use]
+    pub fn net_proceeds(&self) -> Option<Decimal> {
+        let cum_cost = self.cum_cost?;
+        let gross = match self.side.as_str() {
+            "buy" => -cum_cost,
+            "sell" => cum_cost,
+            _ => return None,
+        };
+        Some(gross - self.total_fees())
+    }
+
+    /// Returns the fees as a fraction of `cum_cost`, e.g. `0.0026` for 0.26%.
+    ///
+    /// `None` if `cum_cost` is absent or zero.
+    #[must_use]
+    pub fn fee_rate(&self) -> Option<Decimal> {
+        self.cum_cost
+            .filter(|cost| !cost.is_zero())
+            .map(|cost| self.total_fees() / cost)
+    }
+}
+
 /// Fee charged on a trade event.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "python", pyo3::pyclass(frozen, get_all, from_py_object))]
@@ -120,3 +182,98 @@ pub struct Contingent {
     pub limit_price: Option<Decimal>,
     pub limit_price_type: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn execution(side: &str) -> ExecutionData {
+        serde_json::from_value(serde_json::json!({
+            "order_id": "O1",
+            "symbol": "BTC/USD",
+            "side": side,
+            "order_type": "market",
+            "order_qty": "1",
+            "order_status": "filled",
+            "exec_type": "trade",
+            "timestamp": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn buy_opens_long_and_closes_short() {
+        let buy = execution("buy");
+        assert!(buy.is_opening_trade(dec!(2)));
+        assert!(!buy.is_closing_trade(dec!(2)));
+        assert!(buy.is_closing_trade(dec!(-2)));
+        assert!(!buy.is_opening_trade(dec!(-2)));
+    }
+
+    #[test]
+    fn sell_opens_short_and_closes_long() {
+        let sell = execution("sell");
+        assert!(sell.is_opening_trade(dec!(-2)));
+        assert!(!sell.is_closing_trade(dec!(-2)));
+        assert!(sell.is_closing_trade(dec!(2)));
+        assert!(!sell.is_opening_trade(dec!(2)));
+    }
+
+    #[test]
+    fn any_side_opens_from_flat() {
+        assert!(execution("buy").is_opening_trade(Decimal::ZERO));
+        assert!(execution("sell").is_opening_trade(Decimal::ZERO));
+        assert!(!execution("sell").is_closing_trade(Decimal::ZERO));
+    }
+
+    fn filled(side: &str, cum_cost: Option<Decimal>, fees: &[Decimal]) -> ExecutionData {
+        ExecutionData {
+            cum_cost,
+            fees: Some(
+                fees.iter()
+                    .map(|qty| Fee {
+                        asset: "USD".to_string(),
+                        qty: *qty,
+                    })
+                    .collect(),
+            ),
+            ..execution(side)
+        }
+    }
+
+    #[test]
+    fn net_proceeds_subtracts_fees_from_sells() {
+        let sell = filled("sell", Some(dec!(1000)), &[dec!(2), dec!(0.6)]);
+        assert_eq!(sell.total_fees(), dec!(2.6));
+        assert_eq!(sell.net_proceeds(), Some(dec!(997.4)));
+    }
+
+    #[test]
+    fn net_proceeds_is_outflow_for_buys() {
+        let buy = filled("buy", Some(dec!(1000)), &[dec!(2.6)]);
+        assert_eq!(buy.net_proceeds(), Some(dec!(-1002.6)));
+    }
+
+    #[test]
+    fn net_proceeds_requires_cum_cost() {
+        assert_eq!(filled("sell", None, &[dec!(1)]).net_proceeds(), None);
+        assert_eq!(execution("buy").net_proceeds(), None);
+    }
+
+    #[test]
+    fn fee_rate_divides_fees_by_cost() {
+        let sell = filled("sell", Some(dec!(1000)), &[dec!(2), dec!(0.6)]);
+        assert_eq!(sell.fee_rate(), Some(dec!(0.0026)));
+        assert_eq!(filled("sell", Some(Decimal::ZERO), &[]).fee_rate(), None);
+        assert_eq!(filled("sell", None, &[]).fee_rate(), None);
+    }
+
+    #[test]
+    fn missing_fees_count_as_zero() {
+        let mut sell = execution("sell");
+        sell.cum_cost = Some(dec!(500));
+        assert_eq!(sell.net_proceeds(), Some(dec!(500)));
+        assert_eq!(sell.fee_rate(), Some(Decimal::ZERO));
+    }
+}
@@ -65,3 +65,73 @@ pub struct PairInfo {
     /// Whether a price index is available for this pair.
     pub has_index: bool,
 }
+
+impl PairInfo {
+    /// Rounds `price` toward zero to a multiple of `price_increment`.
+    ///
+    /// Returns `price` unchanged if the increment is not positive.
+    #[must_use]
+    pub fn tick_size_for_price(&self, price: Decimal) -> Decimal {
+        round_toward_zero(price, self.price_increment)
+    }
+
+    /// Rounds `qty` toward zero to a multiple of `qty_increment`.
+    ///
+    /// Returns `qty` unchanged if the increment is not positive.
+    #[must_use]
+    pub fn round_qty_to_increment(&self, qty: Decimal) -> Decimal {
+        round_toward_zero(qty, self.qty_increment)
+    }
+}
+
+fn round_toward_zero(value: Decimal, increment: Decimal) -> Decimal {
+    if increment <= Decimal::ZERO {
+        return value;
+    }
+    (value / increment).trunc() * increment
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    pub(crate) fn pair(price_increment: Decimal, qty_increment: Decimal) -> PairInfo {
+        PairInfo {
+            symbol: "BTC/USD".to_string(),
+            base: "BTC".to_string(),
+            quote: "USD".to_string(),
+            status: "online".to_string(),
+            qty_precision: 8,
+            qty_increment,
+            price_precision: 1,
+            price_increment,
+            cost_precision: 5,
+            cost_min: dec!(0.5),
+            qty_min: dec!(0.0001),
+            marginable: true,
+            margin_initial: None,
+            position_limit_long: None,
+            position_limit_short: None,
+            has_index: true,
+        }
+    }
+
+    #[test]
+    fn prices_and_quantities_round_toward_zero() {
+        let pair = pair(dec!(0.5), dec!(0.001));
+
+        assert_eq!(pair.tick_size_for_price(dec!(100.74)), dec!(100.5));
+        assert_eq!(pair.tick_size_for_price(dec!(100.5)), dec!(100.5));
+        assert_eq!(pair.tick_size_for_price(dec!(-100.74)), dec!(-100.5));
+        assert_eq!(pair.round_qty_to_increment(dec!(0.12345)), dec!(0.123));
+    }
+
+    #[test]
+    fn zero_increment_leaves_values_unchanged() {
+        let pair = pair(Decimal::ZERO, Decimal::ZERO);
+
+        assert_eq!(pair.tick_size_for_price(dec!(100.74)), dec!(100.74));
+        assert_eq!(pair.round_qty_to_increment(dec!(0.12345)), dec!(0.12345));
+    }
+}
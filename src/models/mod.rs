@@ -22,14 +22,17 @@ pub mod trade;
 
 pub use add_order::{
     AddOrderBuilder, AddOrderError, AddOrderParams, AddOrderRequest, AddOrderResponse,
-    AddOrderResult, ConditionalOrder, FeeCurrencyPreference, OrderSide, OrderType, StpType,
-    TimeInForce, TriggerParams, TriggerPriceType, TriggerReference,
+    AddOrderResult, ConditionalOrder, FeeCurrencyPreference, OrderSide, OrderType,
+    ParseOrderSideError, ParseOrderTypeError, StpType, TimeInForce, TriggerParams,
+    TriggerPriceType, TriggerReference,
 };
 pub use amend_order::{
     AmendOrderBuilder, AmendOrderError, AmendOrderParams, AmendOrderRequest, AmendOrderResponse,
     AmendOrderResult, PriceType,
 };
-pub use balance::{BalanceData, BalanceResponse, BalanceUpdateData, WalletBalance};
+pub use balance::{
+    AssetBalance, BalanceData, BalanceResponse, BalanceSnapshot, BalanceUpdateData, WalletBalance,
+};
 pub use batch_add::{
     BatchAddBuilder, BatchAddError, BatchAddOrderResult, BatchAddRequest, BatchAddResponse,
     BatchOrderEntry, MAX_BATCH_SIZE, MIN_BATCH_SIZE,
@@ -71,11 +74,35 @@ impl RedactedToken {
         Self(token.into())
     }
 
+    /// Reads the token from the environment variable `var`.
+    ///
+    /// Returns `None` if the variable is unset, empty, or not valid
+    /// Unicode.
+    #[must_use]
+    pub fn from_env(var: &str) -> Option<Self> {
+        std::env::var(var)
+            .ok()
+            .filter(|token| !token.is_empty())
+            .map(Self)
+    }
+
     /// Returns the inner token value.
     #[must_use]
     pub fn into_inner(self) -> String {
         self.0
     }
+
+    /// Returns true if the token is the empty string.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the token length in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
 }
 
 impl fmt::Debug for RedactedToken {
@@ -145,6 +172,8 @@ impl Channel {
 pub struct SubscribeRequest {
     method: String,
     params: Params,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    req_id: Option<u64>,
 }
 
 impl SubscribeRequest {
@@ -154,8 +183,23 @@ impl SubscribeRequest {
         Self {
             method: "subscribe".to_string(),
             params: Params::new(channel, symbols, token),
+            req_id: None,
         }
     }
+
+    /// Sets the request ID echoed back in the subscription response.
+    #[must_use]
+    pub fn with_req_id(mut self, req_id: u64) -> Self {
+        self.req_id = Some(req_id);
+        self
+    }
+
+    /// Sets the candle interval in minutes (`ohlc` channel only).
+    #[must_use]
+    pub fn with_interval(mut self, interval: i32) -> Self {
+        self.params.interval = Some(interval);
+        self
+    }
 }
 
 /// An `unsubscribe` request sent to the Kraken WebSocket API.
@@ -174,6 +218,13 @@ impl UnsubscribeRequest {
             params: Params::new(channel, symbols, token),
         }
     }
+
+    /// Sets the candle interval in minutes (`ohlc` channel only).
+    #[must_use]
+    pub fn with_interval(mut self, interval: i32) -> Self {
+        self.params.interval = Some(interval);
+        self
+    }
 }
 
 /// Channel and symbol parameters used in subscribe/unsubscribe requests.
@@ -181,6 +232,9 @@ impl UnsubscribeRequest {
 pub struct Params {
     channel: String,
     symbol: Vec<String>,
+    /// Candle interval in minutes, only used by the `ohlc` channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    interval: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     token: Option<RedactedToken>,
 }
@@ -192,6 +246,7 @@ impl Params {
         Self {
             channel: channel.as_str().to_string(),
             symbol: symbols.to_vec(),
+            interval: None,
             token: token.map(RedactedToken::new),
         }
     }
@@ -225,6 +280,8 @@ impl BookParams {
 pub struct BookSubscribeRequest {
     method: String,
     params: BookParams,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    req_id: Option<u64>,
 }
 
 impl BookSubscribeRequest {
@@ -234,8 +291,16 @@ impl BookSubscribeRequest {
         Self {
             method: "subscribe".to_string(),
             params: BookParams::new(symbols, depth, token),
+            req_id: None,
         }
     }
+
+    /// Sets the request ID echoed back in the subscription response.
+    #[must_use]
+    pub fn with_req_id(mut self, req_id: u64) -> Self {
+        self.req_id = Some(req_id);
+        self
+    }
 }
 
 /// An `unsubscribe` request for the book channel with depth parameter.
@@ -391,6 +456,66 @@ impl BalancesUnsubscribeRequest {
     }
 }
 
+/// Server acknowledgement of a `subscribe` or `unsubscribe` request.
+///
+/// Successful responses carry the channel and symbol in `result`; failed
+/// ones report the symbol at the top level alongside `error`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubscribeResponse {
+    /// `"subscribe"` or `"unsubscribe"`.
+    pub method: String,
+    pub success: bool,
+    #[serde(default)]
+    pub result: Option<SubscribeResult>,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Symbol the request was for, present on failures.
+    #[serde(default)]
+    pub symbol: Option<String>,
+    #[serde(default)]
+    pub time_in: Option<String>,
+    #[serde(default)]
+    pub time_out: Option<String>,
+    #[serde(default)]
+    pub req_id: Option<u64>,
+}
+
+impl SubscribeResponse {
+    /// Returns `true` for responses to an `unsubscribe` request.
+    #[must_use]
+    pub fn is_unsubscribe(&self) -> bool {
+        self.method == "unsubscribe"
+    }
+
+    /// Returns the channel name, if the response carries a result.
+    #[must_use]
+    pub fn channel(&self) -> Option<&str> {
+        self.result.as_ref().map(|r| r.channel.as_str())
+    }
+
+    /// Returns the symbol the response refers to, whether it succeeded or not.
+    #[must_use]
+    pub fn symbol(&self) -> Option<&str> {
+        self.result
+            .as_ref()
+            .and_then(|r| r.symbol.as_deref())
+            .or(self.symbol.as_deref())
+    }
+}
+
+/// Details of an accepted subscription.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubscribeResult {
+    /// Wire name of the channel (e.g., `"ticker"`, `"ohlc"`).
+    pub channel: String,
+    /// Subscribed symbol; absent for channels such as `instrument`.
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Whether a snapshot was requested.
+    #[serde(default)]
+    pub snapshot: Option<bool>,
+}
+
 /// A `ping` request used to test connection liveness.
 #[derive(Debug, Clone, Serialize)]
 pub struct PingRequest {
@@ -449,3 +574,24 @@ pub struct StatusData {
     /// Server software version.
     pub version: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_token_len_and_is_empty() {
+        let token = RedactedToken::new("abc123");
+        assert_eq!(token.len(), 6);
+        assert!(!token.is_empty());
+
+        let empty = RedactedToken::new("");
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn redacted_token_from_unset_env_var_is_none() {
+        assert!(RedactedToken::from_env("LEESON_TEST_UNSET_TOKEN_VAR").is_none());
+    }
+}
@@ -1,5 +1,7 @@
 //! Level-3 (individual orders) channel models.
 
+use std::collections::{BTreeMap, HashMap};
+
 use rust_decimal::Decimal;
 use serde::Deserialize;
 
@@ -39,3 +41,290 @@ pub struct OrderEntry {
     pub order_qty: Decimal,
     pub timestamp: String,
 }
+
+/// One resting order at a price level of a [`Level3OrderBook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct L3Order {
+    pub order_id: String,
+    pub qty: Decimal,
+    pub timestamp: String,
+}
+
+/// Which side of the book an order rests on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BookSide {
+    Bid,
+    Ask,
+}
+
+/// Level-3 order book keyed by price for O(log n) level lookup.
+///
+/// Orders within a level keep their arrival order. An index from order ID
+/// to side and price lets modifies and deletes find their level without
+/// scanning the book.
+#[derive(Debug, Clone, Default)]
+pub struct Level3OrderBook {
+    bids: BTreeMap<Decimal, Vec<L3Order>>,
+    asks: BTreeMap<Decimal, Vec<L3Order>>,
+    index: HashMap<String, (BookSide, Decimal)>,
+}
+
+impl Level3OrderBook {
+    /// Replaces the whole book with the orders in a snapshot.
+    pub fn apply_snapshot(&mut self, data: &OrdersData) {
+        self.bids.clear();
+        self.asks.clear();
+        self.index.clear();
+        for entry in &data.bids {
+            self.insert(BookSide::Bid, entry);
+        }
+        for entry in &data.asks {
+            self.insert(BookSide::Ask, entry);
+        }
+    }
+
+    /// Applies the `add`, `modify`, and `delete` events in an update.
+    ///
+    /// Entries without an event are treated as adds. Events for unknown
+    /// order IDs are ignored, except adds.
+    pub fn apply_update(&mut self, data: &OrdersData) {
+        for (side, entries) in [(BookSide::Bid, &data.bids), (BookSide::Ask, &data.asks)] {
+            for entry in entries {
+                match entry.event.as_deref() {
+                    Some("delete") => {
+                        self.remove(&entry.order_id);
+                    }
+                    Some("modify") => self.modify(side, entry),
+                    _ => {
+                        self.remove(&entry.order_id);
+                        self.insert(side, entry);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the highest bid price and the total quantity resting there.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<(Decimal, Decimal)> {
+        self.bids
+            .last_key_value()
+            .map(|(price, orders)| (*price, total_qty(orders)))
+    }
+
+    /// Returns the lowest ask price and the total quantity resting there.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<(Decimal, Decimal)> {
+        self.asks
+            .first_key_value()
+            .map(|(price, orders)| (*price, total_qty(orders)))
+    }
+
+    /// Returns the bids resting at `price`, oldest first.
+    #[must_use]
+    pub fn bids_at(&self, price: Decimal) -> Option<&[L3Order]> {
+        self.bids.get(&price).map(Vec::as_slice)
+    }
+
+    /// Returns the asks resting at `price`, oldest first.
+    #[must_use]
+    pub fn asks_at(&self, price: Decimal) -> Option<&[L3Order]> {
+        self.asks.get(&price).map(Vec::as_slice)
+    }
+
+    /// Returns the number of resting orders on both sides.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if no orders rest on either side.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    fn levels_mut(&mut self, side: BookSide) -> &mut BTreeMap<Decimal, Vec<L3Order>> {
+        match side {
+            BookSide::Bid => &mut self.bids,
+            BookSide::Ask => &mut self.asks,
+        }
+    }
+
+    fn insert(&mut self, side: BookSide, entry: &OrderEntry) {
+        self.index
+            .insert(entry.order_id.clone(), (side, entry.limit_price));
+        self.levels_mut(side)
+            .entry(entry.limit_price)
+            .or_default()
+            .push(L3Order {
+                order_id: entry.order_id.clone(),
+                qty: entry.order_qty,
+                timestamp: entry.timestamp.clone(),
+            });
+    }
+
+    /// Applies a modify event to a known order.
+    ///
+    /// A quantity change at the same price is made in place, so the order
+    /// keeps its place in the queue; a price change re-queues it at the
+    /// back of its new level. A zero quantity removes it.
+    fn modify(&mut self, side: BookSide, entry: &OrderEntry) {
+        let Some(&(current_side, price)) = self.index.get(&entry.order_id) else {
+            return;
+        };
+        if entry.order_qty.is_zero() {
+            self.remove(&entry.order_id);
+        } else if current_side == side && price == entry.limit_price {
+            if let Some(order) = self
+                .levels_mut(side)
+                .get_mut(&price)
+                .and_then(|orders| orders.iter_mut().find(|o| o.order_id == entry.order_id))
+            {
+                order.qty = entry.order_qty;
+            }
+        } else {
+            self.remove(&entry.order_id);
+            self.insert(side, entry);
+        }
+    }
+
+    /// Removes an order, dropping its level once empty. Returns `false` if
+    /// the order was not in the book.
+    fn remove(&mut self, order_id: &str) -> bool {
+        let Some((side, price)) = self.index.remove(order_id) else {
+            return false;
+        };
+        let levels = self.levels_mut(side);
+        if let Some(orders) = levels.get_mut(&price) {
+            orders.retain(|o| o.order_id != order_id);
+            if orders.is_empty() {
+                levels.remove(&price);
+            }
+        }
+        true
+    }
+}
+
+fn total_qty(orders: &[L3Order]) -> Decimal {
+    orders.iter().map(|o| o.qty).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn entry(event: Option<&str>, order_id: &str, price: Decimal, qty: Decimal) -> OrderEntry {
+        OrderEntry {
+            event: event.map(str::to_string),
+            order_id: order_id.to_string(),
+            limit_price: price,
+            order_qty: qty,
+            timestamp: "2024-01-01T00:00:00.000000Z".to_string(),
+        }
+    }
+
+    fn data(bids: Vec<OrderEntry>, asks: Vec<OrderEntry>) -> OrdersData {
+        OrdersData {
+            symbol: "BTC/USD".to_string(),
+            bids,
+            asks,
+            checksum: 0,
+            timestamp: "2024-01-01T00:00:00.000000Z".to_string(),
+        }
+    }
+
+    fn snapshot_book() -> Level3OrderBook {
+        let mut book = Level3OrderBook::default();
+        book.apply_snapshot(&data(
+            vec![
+                entry(None, "B1", dec!(100), dec!(1)),
+                entry(None, "B2", dec!(100), dec!(2)),
+                entry(None, "B3", dec!(99), dec!(5)),
+            ],
+            vec![entry(None, "A1", dec!(101), dec!(0.5))],
+        ));
+        book
+    }
+
+    #[test]
+    fn snapshot_aggregates_best_levels() {
+        let book = snapshot_book();
+
+        assert_eq!(book.len(), 4);
+        assert_eq!(book.best_bid(), Some((dec!(100), dec!(3))));
+        assert_eq!(book.best_ask(), Some((dec!(101), dec!(0.5))));
+        let ids: Vec<&str> = book
+            .bids_at(dec!(100))
+            .unwrap()
+            .iter()
+            .map(|o| o.order_id.as_str())
+            .collect();
+        assert_eq!(ids, ["B1", "B2"]);
+    }
+
+    #[test]
+    fn update_adds_modifies_and_deletes() {
+        let mut book = snapshot_book();
+
+        book.apply_update(&data(
+            vec![
+                entry(Some("delete"), "B1", dec!(100), dec!(0)),
+                entry(Some("delete"), "B2", dec!(100), dec!(0)),
+                entry(Some("modify"), "B3", dec!(99), dec!(4)),
+            ],
+            vec![entry(Some("add"), "A2", dec!(100.5), dec!(1))],
+        ));
+
+        assert_eq!(book.bids_at(dec!(100)), None);
+        assert_eq!(book.best_bid(), Some((dec!(99), dec!(4))));
+        assert_eq!(book.best_ask(), Some((dec!(100.5), dec!(1))));
+        assert_eq!(book.len(), 3);
+    }
+
+    #[test]
+    fn modify_keeps_queue_position_unless_price_changes() {
+        let mut book = snapshot_book();
+        let ids = |book: &Level3OrderBook, price| -> Vec<String> {
+            book.bids_at(price)
+                .unwrap()
+                .iter()
+                .map(|o| o.order_id.clone())
+                .collect()
+        };
+
+        // A smaller quantity at the same price keeps B1 ahead of B2
+        book.apply_update(&data(
+            vec![entry(Some("modify"), "B1", dec!(100), dec!(0.4))],
+            Vec::new(),
+        ));
+        assert_eq!(ids(&book, dec!(100)), ["B1", "B2"]);
+        assert_eq!(book.best_bid(), Some((dec!(100), dec!(2.4))));
+
+        // A new price sends B3 to the back of the 100 level
+        book.apply_update(&data(
+            vec![entry(Some("modify"), "B3", dec!(100), dec!(5))],
+            Vec::new(),
+        ));
+        assert_eq!(ids(&book, dec!(100)), ["B1", "B2", "B3"]);
+        assert_eq!(book.bids_at(dec!(99)), None);
+        assert_eq!(book.len(), 4);
+    }
+
+    #[test]
+    fn unknown_order_events_are_ignored() {
+        let mut book = snapshot_book();
+
+        book.apply_update(&data(
+            vec![
+                entry(Some("delete"), "NOPE", dec!(100), dec!(0)),
+                entry(Some("modify"), "NOPE", dec!(100), dec!(9)),
+            ],
+            Vec::new(),
+        ));
+
+        assert_eq!(book.len(), 4);
+        assert_eq!(book.best_bid(), Some((dec!(100), dec!(3))));
+    }
+}
@@ -14,6 +14,9 @@ pub struct TickerUpdateResponse {
 }
 
 /// Real-time ticker snapshot for a single trading pair.
+///
+/// Kraken occasionally omits the 24-hour statistics, for example during
+/// maintenance, so those fields are optional; the prices are required.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "python", pyo3::pyclass(frozen, get_all, from_py_object))]
 pub struct TickerData {
@@ -30,15 +33,130 @@ pub struct TickerData {
     /// Last traded price.
     pub last: Decimal,
     /// 24-hour rolling trade volume.
-    pub volume: Decimal,
+    pub volume: Option<Decimal>,
     /// Volume-weighted average price.
-    pub vwap: Decimal,
+    pub vwap: Option<Decimal>,
     /// 24-hour rolling low price.
-    pub low: Decimal,
+    pub low: Option<Decimal>,
     /// 24-hour rolling high price.
-    pub high: Decimal,
+    pub high: Option<Decimal>,
     /// Absolute price change over the last 24 hours.
-    pub change: Decimal,
+    pub change: Option<Decimal>,
     /// Price change as a percentage over the last 24 hours.
-    pub change_pct: Decimal,
+    pub change_pct: Option<Decimal>,
+}
+
+impl TickerData {
+    /// Percentage change of the last price since `other`.
+    ///
+    /// Returns zero if `other` has no last price.
+    #[must_use]
+    pub fn price_change_since(&self, other: &TickerData) -> Decimal {
+        percent_change(self.last, other.last)
+    }
+
+    /// Percentage by which the last price sits above (positive) or below
+    /// (negative) the 24-hour VWAP.
+    ///
+    /// Returns zero if the VWAP is zero or missing.
+    #[must_use]
+    pub fn vwap_deviation(&self) -> Decimal {
+        self.vwap
+            .map_or(Decimal::ZERO, |vwap| percent_change(self.last, vwap))
+    }
+
+    /// Returns true if none of the optional 24-hour statistics are missing.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        [
+            self.volume,
+            self.vwap,
+            self.low,
+            self.high,
+            self.change,
+            self.change_pct,
+        ]
+        .iter()
+        .all(Option::is_some)
+    }
+}
+
+/// `(value - base) / base * 100`, or zero when `base` is zero.
+fn percent_change(value: Decimal, base: Decimal) -> Decimal {
+    if base.is_zero() {
+        return Decimal::ZERO;
+    }
+    (value - base) / base * Decimal::ONE_HUNDRED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn ticker(last: Decimal, vwap: Decimal) -> TickerData {
+        TickerData {
+            symbol: "BTC/USD".to_string(),
+            bid: last,
+            bid_qty: Decimal::ONE,
+            ask: last,
+            ask_qty: Decimal::ONE,
+            last,
+            volume: Some(Decimal::ONE),
+            vwap: Some(vwap),
+            low: Some(last),
+            high: Some(last),
+            change: Some(Decimal::ZERO),
+            change_pct: Some(Decimal::ZERO),
+        }
+    }
+
+    #[test]
+    fn price_change_since_is_a_percentage() {
+        let earlier = ticker(dec!(200), dec!(200));
+
+        assert_eq!(
+            ticker(dec!(210), dec!(0)).price_change_since(&earlier),
+            dec!(5)
+        );
+        assert_eq!(
+            ticker(dec!(190), dec!(0)).price_change_since(&earlier),
+            dec!(-5)
+        );
+        assert_eq!(
+            earlier.price_change_since(&ticker(Decimal::ZERO, dec!(0))),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn vwap_deviation_is_relative_to_vwap() {
+        assert_eq!(ticker(dec!(102), dec!(100)).vwap_deviation(), dec!(2));
+        assert_eq!(ticker(dec!(99), dec!(100)).vwap_deviation(), dec!(-1));
+        assert_eq!(
+            ticker(dec!(99), Decimal::ZERO).vwap_deviation(),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn missing_statistics_do_not_fail_parsing() {
+        let response: TickerUpdateResponse = serde_json::from_value(serde_json::json!({
+            "channel": "ticker",
+            "type": "update",
+            "data": [{
+                "symbol": "BTC/USD",
+                "bid": "100", "bid_qty": "1", "ask": "101", "ask_qty": "2",
+                "last": "100.5", "volume": "10", "vwap": "100",
+                "low": "99", "high": "102", "change": "1"
+            }]
+        }))
+        .unwrap();
+
+        let ticker = &response.data[0];
+        assert_eq!(ticker.last, dec!(100.5));
+        assert_eq!(ticker.change_pct, None);
+        assert!(!ticker.is_complete());
+        assert!(self::ticker(dec!(100), dec!(100)).is_complete());
+    }
 }
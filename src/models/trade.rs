@@ -1,5 +1,7 @@
 //! Trade channel models.
 
+use std::collections::BTreeMap;
+
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
@@ -31,3 +33,184 @@ pub struct TradeData {
     /// Execution timestamp.
     pub timestamp: String,
 }
+
+impl TradeData {
+    /// Returns true if a market order triggered this trade, i.e. the taker
+    /// crossed the spread rather than resting in the book.
+    #[must_use]
+    pub fn is_aggressive(&self) -> bool {
+        self.ord_type == "market"
+    }
+
+    /// Returns the quote-currency value traded (`price * qty`).
+    #[must_use]
+    pub fn dollar_volume(&self) -> Decimal {
+        self.price * self.qty
+    }
+}
+
+/// OHLCV summary of the trades within one time interval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeBar {
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// Total traded quantity.
+    pub volume: Decimal,
+    /// Quantity traded by aggressive buyers.
+    pub buy_volume: Decimal,
+    /// Quantity traded by aggressive sellers.
+    pub sell_volume: Decimal,
+    pub trade_count: u64,
+    /// Start of the interval, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl TradeBar {
+    /// Groups `trades` into bars of `interval_secs` seconds, oldest first.
+    ///
+    /// Within a bar, `open` and `close` follow the order of `trades`.
+    /// Trades with an unparseable timestamp are skipped.
+    #[must_use]
+    pub fn from_trades(trades: &[TradeData], interval_secs: u64) -> Vec<TradeBar> {
+        let interval = interval_secs.max(1);
+        let mut bars: BTreeMap<u64, TradeBar> = BTreeMap::new();
+
+        for trade in trades {
+            let Some(secs) = epoch_seconds(&trade.timestamp) else {
+                continue;
+            };
+            let start = secs - secs % interval;
+            let bar = bars.entry(start).or_insert_with(|| TradeBar {
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: Decimal::ZERO,
+                buy_volume: Decimal::ZERO,
+                sell_volume: Decimal::ZERO,
+                trade_count: 0,
+                timestamp: start,
+            });
+            bar.high = bar.high.max(trade.price);
+            bar.low = bar.low.min(trade.price);
+            bar.close = trade.price;
+            bar.volume += trade.qty;
+            if trade.side.eq_ignore_ascii_case("buy") {
+                bar.buy_volume += trade.qty;
+            } else {
+                bar.sell_volume += trade.qty;
+            }
+            bar.trade_count += 1;
+        }
+
+        bars.into_values().collect()
+    }
+}
+
+/// Parses an RFC 3339 UTC timestamp (e.g. `2024-01-15T10:30:00.123456Z`)
+/// into whole seconds since the Unix epoch.
+fn epoch_seconds(timestamp: &str) -> Option<u64> {
+    let field = |range: std::ops::Range<usize>| timestamp.get(range)?.parse::<u64>().ok();
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hours, minutes, seconds) = (field(11..13)?, field(14..16)?, field(17..19)?);
+    if !(1..=12).contains(&month) || year < 1970 {
+        return None;
+    }
+
+    // Days since epoch from a civil date (algorithm from Howard Hinnant)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = (era * 146097 + doe).checked_sub(719468)?;
+
+    Some(days * 86400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn trade(side: &str, price: Decimal, qty: Decimal, timestamp: &str) -> TradeData {
+        TradeData {
+            symbol: "BTC/USD".to_string(),
+            side: side.to_string(),
+            price,
+            qty,
+            ord_type: "market".to_string(),
+            trade_id: 1,
+            timestamp: timestamp.to_string(),
+        }
+    }
+
+    #[test]
+    fn market_orders_are_aggressive() {
+        let mut t = trade("buy", dec!(100), dec!(1), "1970-01-01T00:00:00Z");
+        assert!(t.is_aggressive());
+
+        t.ord_type = "limit".to_string();
+        assert!(!t.is_aggressive());
+    }
+
+    #[test]
+    fn dollar_volume_is_price_times_qty() {
+        let t = trade("sell", dec!(50000.5), dec!(0.02), "1970-01-01T00:00:00Z");
+        assert_eq!(t.dollar_volume(), dec!(1000.01));
+    }
+
+    #[test]
+    fn epoch_seconds_parses_rfc3339() {
+        assert_eq!(epoch_seconds("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            epoch_seconds("2024-01-15T10:30:00.123456Z"),
+            Some(1_705_314_600)
+        );
+        assert_eq!(epoch_seconds("2024-02-29T00:00:01Z"), Some(1_709_164_801));
+        assert_eq!(epoch_seconds("not a timestamp"), None);
+    }
+
+    #[test]
+    fn trades_group_into_bars() {
+        let trades = [
+            trade("buy", dec!(100), dec!(1), "2024-01-15T10:30:05.000000Z"),
+            trade("sell", dec!(98), dec!(2), "2024-01-15T10:30:40.000000Z"),
+            trade("buy", dec!(103), dec!(0.5), "2024-01-15T10:30:59.999999Z"),
+            trade("sell", dec!(101), dec!(1.5), "2024-01-15T10:31:10.000000Z"),
+            trade("buy", dec!(102), dec!(1), "2024-01-15T10:33:00.000000Z"),
+        ];
+
+        let bars = TradeBar::from_trades(&trades, 60);
+
+        assert_eq!(bars.len(), 3);
+        assert_eq!(
+            bars[0],
+            TradeBar {
+                open: dec!(100),
+                high: dec!(103),
+                low: dec!(98),
+                close: dec!(103),
+                volume: dec!(3.5),
+                buy_volume: dec!(1.5),
+                sell_volume: dec!(2),
+                trade_count: 3,
+                timestamp: 1_705_314_600,
+            }
+        );
+        assert_eq!(bars[1].timestamp, 1_705_314_660);
+        assert_eq!(bars[1].sell_volume, dec!(1.5));
+        // The quiet minute in between produces no bar
+        assert_eq!(bars[2].timestamp, 1_705_314_780);
+        assert_eq!(bars[2].open, dec!(102));
+    }
+
+    #[test]
+    fn unparseable_timestamps_are_skipped() {
+        let trades = [trade("buy", dec!(100), dec!(1), "garbage")];
+        assert!(TradeBar::from_trades(&trades, 60).is_empty());
+    }
+}
@@ -4,14 +4,21 @@
 //! `EditOrder`) mirror the internal Rust params but omit the auth token.
 //! Python agents construct order intents; the Rust integration layer adds
 //! the token when executing.
+//!
+//! [`RiskGuard`] is exposed so scripts can check an `AddOrder` against
+//! `risk.json` limits before handing it to an agent.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
 
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use rust_decimal::Decimal;
 
-use crate::models::Channel;
 use crate::models::add_order::{
-    AddOrderResponse, AddOrderResult, ConditionalOrder, FeeCurrencyPreference, OrderSide,
-    OrderType, StpType, TimeInForce, TriggerParams, TriggerPriceType, TriggerReference,
+    AddOrderParams, AddOrderResponse, AddOrderResult, ConditionalOrder, FeeCurrencyPreference,
+    OrderSide, OrderType, StpType, TimeInForce, TriggerParams, TriggerPriceType, TriggerReference,
 };
 use crate::models::amend_order::{AmendOrderResponse, AmendOrderResult, PriceType};
 use crate::models::balance::{BalanceData, BalanceUpdateData, WalletBalance};
@@ -28,6 +35,9 @@ use crate::models::instrument::{AssetInfo, InstrumentData, PairInfo};
 use crate::models::orders::{OrderEntry, OrdersData};
 use crate::models::ticker::TickerData;
 use crate::models::trade::TradeData;
+use crate::models::{Channel, RedactedToken};
+use crate::risk::config::RiskConfig;
+use crate::risk::{RiskGuard, RiskVerdict};
 
 // ---------------------------------------------------------------------------
 // Python-facing order parameter types (no auth token)
@@ -129,6 +139,35 @@ impl AddOrder {
     }
 }
 
+impl AddOrder {
+    /// Converts the order intent into [`AddOrderParams`] with an empty token,
+    /// which is all the risk guard needs.
+    fn to_params(&self) -> AddOrderParams {
+        AddOrderParams {
+            order_type: self.order_type,
+            side: self.side,
+            symbol: self.symbol.clone(),
+            order_qty: self.order_qty,
+            limit_price: self.limit_price,
+            time_in_force: self.time_in_force,
+            expire_time: self.expire_time.clone(),
+            post_only: self.post_only,
+            reduce_only: self.reduce_only,
+            margin: self.margin,
+            cl_ord_id: self.cl_ord_id.clone(),
+            order_userref: self.order_userref,
+            validate: self.validate,
+            triggers: self.triggers.clone(),
+            conditional: self.conditional.clone(),
+            display_qty: self.display_qty,
+            stp_type: self.stp_type,
+            fee_preference: self.fee_preference,
+            no_mpp: self.no_mpp,
+            token: RedactedToken::new(""),
+        }
+    }
+}
+
 /// Parameters for cancelling orders.
 #[pyclass(frozen, get_all, from_py_object)]
 #[derive(Debug, Clone)]
@@ -287,6 +326,81 @@ impl EditOrder {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Risk guard
+// ---------------------------------------------------------------------------
+
+#[pymethods]
+impl RiskConfig {
+    /// Parses and validates a configuration in the `risk.json` format.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let config: Self =
+            serde_json::from_str(json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        config
+            .validate()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(config)
+    }
+
+    /// Loads and validates a `risk.json` file.
+    #[staticmethod]
+    #[pyo3(name = "load")]
+    fn py_load(path: PathBuf) -> PyResult<Self> {
+        let config = Self::load(&path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        config
+            .validate()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(config)
+    }
+
+    fn __str__(&self) -> String {
+        self.describe_limits()
+    }
+}
+
+#[pymethods]
+impl RiskGuard {
+    #[new]
+    fn py_new(config: RiskConfig) -> Self {
+        Self::new(config)
+    }
+
+    /// Checks `order` against the configured limits.
+    ///
+    /// Returns `{"verdict": "approved" | "requires_confirmation" | "rejected",
+    /// "reason": str | None}`. Open positions are not known here, so the
+    /// portfolio exposure cap is not evaluated. The verdict is returned as a
+    /// dict because [`RiskVerdict`] has a unit variant, which PyO3 cannot
+    /// expose alongside the data-carrying one.
+    #[pyo3(name = "check_order")]
+    fn py_check_order<'py>(
+        &self,
+        py: Python<'py>,
+        order: &AddOrder,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let (verdict, reason) =
+            match self.check_order(&order.to_params(), &HashMap::new(), &HashMap::new()) {
+                Ok(RiskVerdict::Approved) => ("approved", None),
+                Ok(RiskVerdict::RequiresConfirmation { reason }) => {
+                    ("requires_confirmation", Some(reason))
+                }
+                Err(e) => ("rejected", Some(e.to_string())),
+            };
+        let result = PyDict::new(py);
+        result.set_item("verdict", verdict)?;
+        result.set_item("reason", reason)?;
+        Ok(result)
+    }
+
+    /// Records a submitted `side` order for `symbol` against the trade
+    /// rate and side concentration limits.
+    #[pyo3(name = "record_submission")]
+    fn py_record_submission(&mut self, symbol: &str, side: OrderSide) {
+        self.record_submission(symbol, side);
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Module registration
 // ---------------------------------------------------------------------------
@@ -340,6 +454,10 @@ fn leeson(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<AmendOrder>()?;
     m.add_class::<EditOrder>()?;
 
+    // Risk guard
+    m.add_class::<RiskConfig>()?;
+    m.add_class::<RiskGuard>()?;
+
     // Order responses
     m.add_class::<AddOrderResult>()?;
     m.add_class::<AddOrderResponse>()?;
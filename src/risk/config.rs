@@ -3,12 +3,16 @@
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::path::Path;
+use std::str::FromStr;
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
 /// Risk limits configuration loaded from `risk.json`.
-#[derive(Debug, Clone, Deserialize)]
+///
+/// [`Default`] gives conservative limits for embedding without a file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "python", pyo3::pyclass(frozen, from_py_object))]
 pub struct RiskConfig {
     /// Default limits applied to all symbols unless overridden.
     pub defaults: SymbolLimits,
@@ -18,7 +22,7 @@ pub struct RiskConfig {
 }
 
 /// Complete set of limits (used as global defaults). All fields required.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SymbolLimits {
     pub max_order_qty: Decimal,
     pub max_notional_value: Decimal,
@@ -26,10 +30,19 @@ pub struct SymbolLimits {
     pub max_trades_per_day: u32,
     pub max_trades_per_week: u32,
     pub max_trades_per_month: u32,
+    /// Cap on the total USD value of open positions across all symbols.
+    /// Only meaningful in `defaults`; absent means no cap.
+    #[serde(default)]
+    pub max_portfolio_exposure: Option<Decimal>,
+    /// Largest share, in percent, of a symbol's session trades allowed on
+    /// one side, above 50 and at most 100. Only orders on the side that
+    /// already leads are checked. Absent means no limit.
+    #[serde(default)]
+    pub max_side_concentration_pct: Option<Decimal>,
 }
 
 /// Per-symbol overrides. Every field optional; missing inherits from defaults.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SymbolOverrides {
     pub max_order_qty: Option<Decimal>,
     pub max_notional_value: Option<Decimal>,
@@ -37,9 +50,47 @@ pub struct SymbolOverrides {
     pub max_trades_per_day: Option<u32>,
     pub max_trades_per_week: Option<u32>,
     pub max_trades_per_month: Option<u32>,
+    pub max_side_concentration_pct: Option<Decimal>,
+}
+
+impl Default for RiskConfig {
+    /// Small limits for trying the library out: 0.01 units or 1000 in
+    /// notional per order, five trades a day, and confirmation above 500.
+    fn default() -> Self {
+        Self {
+            defaults: SymbolLimits {
+                max_order_qty: Decimal::new(1, 2),
+                max_notional_value: Decimal::from(1000),
+                confirm_above_notional: Decimal::from(500),
+                max_trades_per_day: 5,
+                max_trades_per_week: 25,
+                max_trades_per_month: 100,
+                max_portfolio_exposure: None,
+                max_side_concentration_pct: None,
+            },
+            symbols: HashMap::new(),
+        }
+    }
+}
+
+impl FromStr for RiskConfig {
+    type Err = crate::LeesonError;
+
+    /// Parses a configuration in the `risk.json` format, without
+    /// validating it.
+    fn from_str(json: &str) -> crate::Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
 }
 
 impl RiskConfig {
+    /// Serializes the configuration in the `risk.json` format, readable by
+    /// [`from_str`](Self::from_str).
+    #[must_use]
+    pub fn to_str(&self) -> String {
+        serde_json::to_string(self).expect("RiskConfig serialization should not fail")
+    }
+
     /// Loads risk configuration from a JSON file.
     ///
     /// # Errors
@@ -53,6 +104,72 @@ impl RiskConfig {
         Ok(config)
     }
 
+    /// Re-reads the configuration from `path`, replacing `self` only if the
+    /// new file parses and passes [`validate`](Self::validate).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, parsed, or validated.
+    /// The current configuration is left untouched in that case.
+    pub fn reload(&mut self, path: &Path) -> crate::Result<()> {
+        let config = Self::load(path)?;
+        config.validate()?;
+        *self = config;
+        Ok(())
+    }
+
+    /// Checks that no limit is negative and that every side concentration
+    /// cap lies in (50, 100].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Config`](crate::LeesonError::Config) naming the
+    /// first offending field.
+    pub fn validate(&self) -> crate::Result<()> {
+        let check = |scope: &str, field: &str, value: Option<Decimal>| {
+            if value.is_some_and(|v| v.is_sign_negative()) {
+                return Err(crate::LeesonError::Config(format!(
+                    "invalid risk config: {scope}.{field} is negative"
+                )));
+            }
+            Ok(())
+        };
+        // At or below 50% the check would reject both sides of a balanced
+        // session, and above 100% it could never trigger.
+        let check_pct = |scope: &str, value: Option<Decimal>| {
+            if value.is_some_and(|v| v <= Decimal::from(50) || v > Decimal::ONE_HUNDRED) {
+                return Err(crate::LeesonError::Config(format!(
+                    "invalid risk config: {scope}.max_side_concentration_pct \
+                     must be above 50 and at most 100"
+                )));
+            }
+            Ok(())
+        };
+
+        let d = &self.defaults;
+        check("defaults", "max_order_qty", Some(d.max_order_qty))?;
+        check("defaults", "max_notional_value", Some(d.max_notional_value))?;
+        check(
+            "defaults",
+            "confirm_above_notional",
+            Some(d.confirm_above_notional),
+        )?;
+        check(
+            "defaults",
+            "max_portfolio_exposure",
+            d.max_portfolio_exposure,
+        )?;
+        check_pct("defaults", d.max_side_concentration_pct)?;
+
+        for (symbol, o) in &self.symbols {
+            check(symbol, "max_order_qty", o.max_order_qty)?;
+            check(symbol, "max_notional_value", o.max_notional_value)?;
+            check(symbol, "confirm_above_notional", o.confirm_above_notional)?;
+            check_pct(symbol, o.max_side_concentration_pct)?;
+        }
+        Ok(())
+    }
+
     /// Returns the effective limits for a symbol, merging overrides with defaults.
     pub fn limits_for(&self, symbol: &str) -> SymbolLimits {
         match self.symbols.get(symbol) {
@@ -75,6 +192,10 @@ impl RiskConfig {
                 max_trades_per_month: overrides
                     .max_trades_per_month
                     .unwrap_or(self.defaults.max_trades_per_month),
+                max_portfolio_exposure: self.defaults.max_portfolio_exposure,
+                max_side_concentration_pct: overrides
+                    .max_side_concentration_pct
+                    .or(self.defaults.max_side_concentration_pct),
             },
             None => self.defaults.clone(),
         }
@@ -111,6 +232,12 @@ impl RiskConfig {
             "    max_trades_per_month: {}",
             self.defaults.max_trades_per_month
         );
+        if let Some(v) = self.defaults.max_portfolio_exposure {
+            let _ = writeln!(out, "    max_portfolio_exposure: {v}");
+        }
+        if let Some(v) = self.defaults.max_side_concentration_pct {
+            let _ = writeln!(out, "    max_side_concentration_pct: {v}");
+        }
 
         for (symbol, overrides) in &self.symbols {
             let _ = writeln!(out, "  {symbol}:");
@@ -132,6 +259,9 @@ impl RiskConfig {
             if let Some(v) = overrides.max_trades_per_month {
                 let _ = writeln!(out, "    max_trades_per_month: {v}");
             }
+            if let Some(v) = overrides.max_side_concentration_pct {
+                let _ = writeln!(out, "    max_side_concentration_pct: {v}");
+            }
         }
 
         out
@@ -173,6 +303,23 @@ impl Default for AgentRiskParams {
 }
 
 impl AgentRiskParams {
+    /// Serializes the parameters as a JSON object for the agent protocol.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("AgentRiskParams serialization should not fail")
+    }
+
+    /// Parses parameters from a JSON object produced by
+    /// [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Json`](crate::LeesonError::Json) if `s` is not
+    /// a valid parameter object.
+    pub fn from_json(s: &str) -> crate::Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
     /// Loads agent risk parameters from a JSON file.
     ///
     /// Returns [`Default`] if the file does not exist.
@@ -207,6 +354,56 @@ impl AgentRiskParams {
         })
     }
 
+    /// Checks that the parameters describe a usable configuration.
+    ///
+    /// Trade size and stop loss must be positive, token costs must not be
+    /// negative, `trades_per_month` must lie in `1..=10000`, and the stop
+    /// loss must be below the total monthly trade volume.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the first rule that is violated.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=10_000).contains(&self.trades_per_month) {
+            return Err(format!(
+                "trades_per_month must be between 1 and 10000, got {}",
+                self.trades_per_month
+            ));
+        }
+        if self.trade_size_eur <= Decimal::ZERO {
+            return Err(format!(
+                "trade_size_eur must be positive, got {}",
+                self.trade_size_eur
+            ));
+        }
+        if self.stop_loss_eur <= Decimal::ZERO {
+            return Err(format!(
+                "stop_loss_eur must be positive, got {}",
+                self.stop_loss_eur
+            ));
+        }
+        if self.cost_per_1m_input_tokens.is_sign_negative() {
+            return Err(format!(
+                "cost_per_1m_input_tokens must not be negative, got {}",
+                self.cost_per_1m_input_tokens
+            ));
+        }
+        if self.cost_per_1m_output_tokens.is_sign_negative() {
+            return Err(format!(
+                "cost_per_1m_output_tokens must not be negative, got {}",
+                self.cost_per_1m_output_tokens
+            ));
+        }
+        let monthly_volume = self.trade_size_eur * Decimal::from(self.trades_per_month);
+        if self.stop_loss_eur >= monthly_volume {
+            return Err(format!(
+                "stop_loss_eur ({}) must be less than trade_size_eur * trades_per_month ({monthly_volume})",
+                self.stop_loss_eur
+            ));
+        }
+        Ok(())
+    }
+
     /// Returns a human-readable description for agent system prompts.
     pub fn describe(&self) -> String {
         let mut out = String::from("Agent risk parameters:\n");
@@ -260,6 +457,47 @@ mod tests {
         }"#
     }
 
+    #[test]
+    fn default_config_is_conservative_and_valid() {
+        let config = RiskConfig::default();
+        assert_eq!(config.defaults.max_order_qty, dec!(0.01));
+        assert_eq!(config.defaults.max_notional_value, dec!(1000));
+        assert_eq!(config.defaults.max_trades_per_day, 5);
+        assert!(config.symbols.is_empty());
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn default_config_round_trips_through_json() {
+        let json = RiskConfig::default().to_str();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["defaults"].is_object());
+
+        let parsed = RiskConfig::from_str(&json).unwrap();
+        assert_eq!(parsed.defaults.max_order_qty, dec!(0.01));
+        assert_eq!(parsed.defaults.max_trades_per_month, 100);
+    }
+
+    #[test]
+    fn from_str_round_trips_overrides() {
+        let config = RiskConfig::from_str(sample_json()).unwrap();
+        let parsed = RiskConfig::from_str(&config.to_str()).unwrap();
+
+        assert_eq!(parsed.limits_for("BTC/USD").max_order_qty, dec!(0.5));
+        assert_eq!(
+            parsed.limits_for("ETH/USD").max_notional_value,
+            dec!(100000)
+        );
+    }
+
+    #[test]
+    fn from_str_rejects_malformed_json() {
+        assert!(matches!(
+            RiskConfig::from_str("{\"defaults\": {}}"),
+            Err(crate::LeesonError::Json(_))
+        ));
+    }
+
     #[test]
     fn parse_valid_config() {
         let config: RiskConfig = serde_json::from_str(sample_json()).unwrap();
@@ -339,6 +577,76 @@ mod tests {
         assert!(desc.contains("max_order_qty: 0.5"));
     }
 
+    #[test]
+    fn reload_replaces_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("risk.json");
+        std::fs::write(&path, sample_json()).unwrap();
+        let mut config = RiskConfig::load(&path).unwrap();
+
+        std::fs::write(&path, sample_json().replace(r#""1.0""#, r#""2.5""#)).unwrap();
+        config.reload(&path).unwrap();
+
+        assert_eq!(config.defaults.max_order_qty, dec!(2.5));
+    }
+
+    #[test]
+    fn reload_bad_json_keeps_old_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("risk.json");
+        std::fs::write(&path, sample_json()).unwrap();
+        let mut config = RiskConfig::load(&path).unwrap();
+
+        std::fs::write(&path, "{ \"defaults\": ").unwrap();
+        assert!(config.reload(&path).is_err());
+
+        assert_eq!(config.defaults.max_order_qty, dec!(1.0));
+        assert_eq!(config.symbols.len(), 2);
+    }
+
+    #[test]
+    fn reload_rejects_negative_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("risk.json");
+        std::fs::write(&path, sample_json()).unwrap();
+        let mut config = RiskConfig::load(&path).unwrap();
+
+        std::fs::write(&path, sample_json().replace(r#""0.5""#, r#""-0.5""#)).unwrap();
+        let err = config.reload(&path).unwrap_err();
+
+        assert!(err.to_string().contains("BTC/USD.max_order_qty"));
+        assert_eq!(config.limits_for("BTC/USD").max_order_qty, dec!(0.5));
+    }
+
+    #[test]
+    fn validate_rejects_side_concentration_outside_range() {
+        let mut config = RiskConfig::default();
+        for pct in [dec!(50), dec!(100.5)] {
+            config.defaults.max_side_concentration_pct = Some(pct);
+            let err = config.validate().unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("defaults.max_side_concentration_pct")
+            );
+        }
+
+        config.defaults.max_side_concentration_pct = Some(dec!(100));
+        config.validate().unwrap();
+
+        config.symbols.insert(
+            "BTC/USD".to_string(),
+            SymbolOverrides {
+                max_side_concentration_pct: Some(dec!(40)),
+                ..SymbolOverrides::default()
+            },
+        );
+        let err = config.validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("BTC/USD.max_side_concentration_pct")
+        );
+    }
+
     #[test]
     fn agent_risk_params_defaults() {
         let params = AgentRiskParams::default();
@@ -392,4 +700,104 @@ mod tests {
         assert!(desc.contains("cost_per_1m_input_tokens: 0"));
         assert!(desc.contains("cost_per_1m_output_tokens: 0"));
     }
+
+    #[test]
+    fn agent_risk_params_default_is_valid() {
+        assert!(AgentRiskParams::default().validate().is_ok());
+    }
+
+    #[test]
+    fn agent_risk_params_rejects_trades_per_month_out_of_range() {
+        let mut params = AgentRiskParams {
+            trades_per_month: 0,
+            ..AgentRiskParams::default()
+        };
+        let err = params.validate().unwrap_err();
+        assert!(err.contains("trades_per_month"));
+
+        params.trades_per_month = 10_001;
+        assert!(params.validate().unwrap_err().contains("trades_per_month"));
+
+        params.trades_per_month = 10_000;
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn agent_risk_params_rejects_non_positive_trade_size() {
+        let params = AgentRiskParams {
+            trade_size_eur: Decimal::ZERO,
+            ..AgentRiskParams::default()
+        };
+        assert!(params.validate().unwrap_err().contains("trade_size_eur"));
+    }
+
+    #[test]
+    fn agent_risk_params_rejects_non_positive_stop_loss() {
+        let params = AgentRiskParams {
+            stop_loss_eur: dec!(-5),
+            ..AgentRiskParams::default()
+        };
+        assert!(params.validate().unwrap_err().contains("stop_loss_eur"));
+    }
+
+    #[test]
+    fn agent_risk_params_rejects_negative_token_costs() {
+        let params = AgentRiskParams {
+            cost_per_1m_input_tokens: dec!(-1),
+            ..AgentRiskParams::default()
+        };
+        assert!(
+            params
+                .validate()
+                .unwrap_err()
+                .contains("cost_per_1m_input_tokens")
+        );
+
+        let params = AgentRiskParams {
+            cost_per_1m_output_tokens: dec!(-1),
+            ..AgentRiskParams::default()
+        };
+        assert!(
+            params
+                .validate()
+                .unwrap_err()
+                .contains("cost_per_1m_output_tokens")
+        );
+    }
+
+    #[test]
+    fn agent_risk_params_rejects_stop_loss_above_monthly_volume() {
+        let params = AgentRiskParams {
+            trades_per_month: 2,
+            trade_size_eur: dec!(100),
+            stop_loss_eur: dec!(200),
+            ..AgentRiskParams::default()
+        };
+        assert!(params.validate().unwrap_err().contains("must be less than"));
+
+        let params = AgentRiskParams {
+            stop_loss_eur: dec!(199.99),
+            ..params
+        };
+        assert!(params.validate().is_ok());
+    }
+
+    #[test]
+    fn agent_risk_params_json_round_trip() {
+        let params = AgentRiskParams {
+            intraday: true,
+            stop_loss_eur: dec!(12.5),
+            ..AgentRiskParams::default()
+        };
+
+        let json = params.to_json();
+        assert!(json.contains(r#""stop_loss_eur":"12.5""#), "{json}");
+
+        let parsed = AgentRiskParams::from_json(&json).unwrap();
+        assert!(parsed.intraday);
+        assert_eq!(parsed.stop_loss_eur, dec!(12.5));
+        assert_eq!(parsed.trades_per_month, params.trades_per_month);
+
+        assert!(AgentRiskParams::from_json("{}").is_err());
+    }
 }
@@ -1,8 +1,8 @@
 //! Risk validation layer for order submission.
 //!
 //! Enforces configurable per-symbol limits on order quantity, notional value,
-//! and trade frequency. Acts as a safety net between order creation and
-//! WebSocket submission.
+//! and trade frequency, plus an optional cap on total portfolio exposure.
+//! Acts as a safety net between order creation and WebSocket submission.
 
 pub mod config;
 
@@ -12,7 +12,8 @@ use std::time::{Duration, Instant};
 
 use rust_decimal::Decimal;
 
-use crate::models::add_order::AddOrderParams;
+use crate::models::add_order::{AddOrderParams, OrderSide};
+use crate::models::ticker::TickerData;
 use config::RiskConfig;
 
 /// Result of a successful risk check.
@@ -46,6 +47,16 @@ pub enum RiskCheckError {
         count: u32,
         max: u32,
     },
+    PortfolioExposureExceeded {
+        exposure: Decimal,
+        max: Decimal,
+    },
+    SideConcentration {
+        symbol: String,
+        side: OrderSide,
+        pct: Decimal,
+        max: Decimal,
+    },
 }
 
 impl fmt::Display for RiskCheckError {
@@ -75,29 +86,66 @@ impl fmt::Display for RiskCheckError {
                     "{symbol}: {count} trades in {period} exceeds limit of {max}"
                 )
             }
+            Self::PortfolioExposureExceeded { exposure, max } => {
+                write!(f, "portfolio exposure {exposure} exceeds max {max}")
+            }
+            Self::SideConcentration {
+                symbol,
+                side,
+                pct,
+                max,
+            } => {
+                let side = match side {
+                    OrderSide::Buy => "buy",
+                    OrderSide::Sell => "sell",
+                };
+                write!(
+                    f,
+                    "{symbol}: {side} trades would be {pct}% of the session, exceeds max {max}%"
+                )
+            }
         }
     }
 }
 
 impl std::error::Error for RiskCheckError {}
 
-/// Tracks order submission timestamps per symbol for rate limiting.
+/// Tracks order submission timestamps per symbol for rate limiting, and
+/// the session's buy and sell counts for the side concentration limit.
 struct RateTracker {
     submissions: HashMap<String, Vec<Instant>>,
+    sides: HashMap<String, SideCounts>,
+}
+
+/// Buy and sell submissions for one symbol this session.
+#[derive(Debug, Clone, Copy, Default)]
+struct SideCounts {
+    buys: u32,
+    sells: u32,
 }
 
 impl RateTracker {
     fn new() -> Self {
         Self {
             submissions: HashMap::new(),
+            sides: HashMap::new(),
         }
     }
 
-    fn record(&mut self, symbol: &str) {
+    fn record(&mut self, symbol: &str, side: OrderSide) {
         self.submissions
             .entry(symbol.to_string())
             .or_default()
             .push(Instant::now());
+        let counts = self.sides.entry(symbol.to_string()).or_default();
+        match side {
+            OrderSide::Buy => counts.buys += 1,
+            OrderSide::Sell => counts.sells += 1,
+        }
+    }
+
+    fn side_counts(&self, symbol: &str) -> SideCounts {
+        self.sides.get(symbol).copied().unwrap_or_default()
     }
 
     fn count_within(&self, symbol: &str, duration: Duration) -> u32 {
@@ -129,6 +177,7 @@ const SECS_PER_WEEK: u64 = 7 * SECS_PER_DAY;
 const SECS_PER_MONTH: u64 = 30 * SECS_PER_DAY;
 
 /// Validates orders against configurable risk limits before submission.
+#[cfg_attr(feature = "python", pyo3::pyclass)]
 pub struct RiskGuard {
     config: RiskConfig,
     tracker: RateTracker,
@@ -148,11 +197,26 @@ impl RiskGuard {
         &self.config
     }
 
+    /// Returns a mutable reference to the risk configuration (e.g. for
+    /// [`RiskConfig::reload`]). Rate-limit history is preserved.
+    pub fn config_mut(&mut self) -> &mut RiskConfig {
+        &mut self.config
+    }
+
     /// Validates an order against all risk limits.
     ///
-    /// Does NOT record the submission — call [`record_submission`] after
-    /// the order is successfully sent to the exchange.
-    pub fn check_order(&self, params: &AddOrderParams) -> Result<RiskVerdict, RiskCheckError> {
+    /// `positions` holds the current net quantity per symbol and `prices`
+    /// the latest tickers, used for the portfolio exposure cap.
+    ///
+    /// Does NOT record the submission. This is meant for a read-only
+    /// preview. Use [`submit_with_check`](Self::submit_with_check) for an
+    /// order that is about to be sent.
+    pub fn check_order(
+        &self,
+        params: &AddOrderParams,
+        positions: &HashMap<String, Decimal>,
+        prices: &HashMap<String, TickerData>,
+    ) -> Result<RiskVerdict, RiskCheckError> {
         let symbol = &params.symbol;
         let qty = params.order_qty;
         let limits = self.config.limits_for(symbol);
@@ -220,7 +284,42 @@ impl RiskGuard {
             });
         }
 
-        // 5. Check if confirmation is needed (only if limit_price is present)
+        // 5. Check side concentration (only when the order's side already
+        //    leads, so the lagging side can always rebalance)
+        if let Some(max) = limits.max_side_concentration_pct {
+            let counts = self.tracker.side_counts(symbol);
+            let (own, other) = match params.side {
+                OrderSide::Buy => (counts.buys, counts.sells),
+                OrderSide::Sell => (counts.sells, counts.buys),
+            };
+            if own > other {
+                let placed = Decimal::from(own + 1);
+                let total = Decimal::from(own + other + 1);
+                let pct = (placed * Decimal::ONE_HUNDRED / total).round_dp(2);
+                if pct > max {
+                    return Err(RiskCheckError::SideConcentration {
+                        symbol: symbol.clone(),
+                        side: params.side,
+                        pct,
+                        max,
+                    });
+                }
+            }
+        }
+
+        // 6. Check portfolio exposure (only if the order grows the position)
+        let current = positions.get(symbol).copied().unwrap_or(Decimal::ZERO);
+        let projected = match params.side {
+            OrderSide::Buy => current + qty,
+            OrderSide::Sell => current - qty,
+        };
+        if projected.abs() > current.abs() {
+            let mut after = positions.clone();
+            after.insert(symbol.clone(), projected);
+            self.check_portfolio_exposure(&after, prices)?;
+        }
+
+        // 7. Check if confirmation is needed (only if limit_price is present)
         if let Some(price) = params.limit_price {
             let notional = qty * price;
             if notional > limits.confirm_above_notional {
@@ -233,13 +332,69 @@ impl RiskGuard {
             }
         }
 
-        // 6. Approved
+        // 8. Approved
         Ok(RiskVerdict::Approved)
     }
 
-    /// Records a successful order submission for rate limiting.
-    pub fn record_submission(&mut self, symbol: &str) {
-        self.tracker.record(symbol);
+    /// Checks an order like [`check_order`](Self::check_order) and, if it
+    /// is approved, records the submission in the same call.
+    ///
+    /// No other order can be checked between this check and the record,
+    /// so a burst of orders cannot all slip under a rate limit. An order
+    /// that needs confirmation is not recorded. Call
+    /// [`record_submission`](Self::record_submission) once the operator
+    /// confirms it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`RiskCheckError`] of the first failed check; nothing is
+    /// recorded in that case.
+    pub fn submit_with_check(
+        &mut self,
+        params: &AddOrderParams,
+        positions: &HashMap<String, Decimal>,
+        prices: &HashMap<String, TickerData>,
+    ) -> Result<RiskVerdict, RiskCheckError> {
+        let verdict = self.check_order(params, positions, prices)?;
+        if verdict == RiskVerdict::Approved {
+            self.record_submission(&params.symbol, params.side);
+        }
+        Ok(verdict)
+    }
+
+    /// Checks the total USD value of `positions` against the configured
+    /// portfolio exposure cap.
+    ///
+    /// Each position is valued at the last price of its ticker in `prices`;
+    /// positions without a ticker are left out.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RiskCheckError::PortfolioExposureExceeded`] if the total
+    /// exceeds `max_portfolio_exposure`.
+    pub fn check_portfolio_exposure(
+        &self,
+        positions: &HashMap<String, Decimal>,
+        prices: &HashMap<String, TickerData>,
+    ) -> Result<(), RiskCheckError> {
+        let Some(max) = self.config.defaults.max_portfolio_exposure else {
+            return Ok(());
+        };
+
+        let exposure: Decimal = positions
+            .iter()
+            .filter_map(|(symbol, qty)| prices.get(symbol).map(|t| qty.abs() * t.last))
+            .sum();
+        if exposure > max {
+            return Err(RiskCheckError::PortfolioExposureExceeded { exposure, max });
+        }
+        Ok(())
+    }
+
+    /// Records a successful order submission for rate limiting and side
+    /// concentration.
+    pub fn record_submission(&mut self, symbol: &str, side: OrderSide) {
+        self.tracker.record(symbol, side);
     }
 
     /// Prunes rate tracker entries older than 30 days.
@@ -313,7 +468,7 @@ mod tests {
     fn reject_zero_qty() {
         let guard = RiskGuard::new(test_config());
         let params = make_params("BTC/USD", dec!(0), Some(dec!(50000)));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert!(matches!(
             result,
             Err(RiskCheckError::NonPositiveQuantity { .. })
@@ -324,7 +479,7 @@ mod tests {
     fn reject_negative_qty() {
         let guard = RiskGuard::new(test_config());
         let params = make_params("BTC/USD", dec!(-0.1), Some(dec!(50000)));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert!(matches!(
             result,
             Err(RiskCheckError::NonPositiveQuantity { .. })
@@ -336,7 +491,7 @@ mod tests {
         let guard = RiskGuard::new(test_config());
         // BTC/USD has max_order_qty of 0.5
         let params = make_params("BTC/USD", dec!(0.6), Some(dec!(50000)));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert!(matches!(
             result,
             Err(RiskCheckError::QuantityExceeded { .. })
@@ -348,7 +503,7 @@ mod tests {
         let guard = RiskGuard::new(test_config());
         // ETH/USD defaults: max_notional = 100000, qty=1.0 * price=200000 = 200000 > 100000
         let params = make_params("ETH/USD", dec!(1.0), Some(dec!(200000)));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert!(matches!(
             result,
             Err(RiskCheckError::NotionalExceeded { .. })
@@ -360,7 +515,7 @@ mod tests {
         let guard = RiskGuard::new(test_config());
         // Default confirm_above_notional is 50000; qty=0.4 * price=130000 = 52000 > 50000
         let params = make_params("BTC/USD", dec!(0.4), Some(dec!(130000)));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert!(matches!(
             result,
             Ok(RiskVerdict::RequiresConfirmation { .. })
@@ -372,7 +527,7 @@ mod tests {
         let guard = RiskGuard::new(test_config());
         // qty=0.1 * price=50000 = 5000 — well within all limits
         let params = make_params("BTC/USD", dec!(0.1), Some(dec!(50000)));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert_eq!(result, Ok(RiskVerdict::Approved));
     }
 
@@ -383,27 +538,230 @@ mod tests {
 
         // Submit 3 orders (daily limit)
         for _ in 0..3 {
-            assert_eq!(guard.check_order(&params), Ok(RiskVerdict::Approved));
-            guard.record_submission("BTC/USD");
+            assert_eq!(
+                guard.check_order(&params, &HashMap::new(), &HashMap::new()),
+                Ok(RiskVerdict::Approved)
+            );
+            guard.record_submission("BTC/USD", OrderSide::Buy);
         }
 
         // 4th should be rejected
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert!(matches!(
             result,
             Err(RiskCheckError::RateLimitExceeded { .. })
         ));
     }
 
+    #[test]
+    fn submit_with_check_records_until_rate_limit() {
+        let mut guard = RiskGuard::new(test_config());
+        let params = make_params("BTC/USD", dec!(0.1), Some(dec!(50000)));
+
+        for _ in 0..3 {
+            assert_eq!(
+                guard.submit_with_check(&params, &HashMap::new(), &HashMap::new()),
+                Ok(RiskVerdict::Approved)
+            );
+        }
+
+        let result = guard.submit_with_check(&params, &HashMap::new(), &HashMap::new());
+        assert!(matches!(
+            result,
+            Err(RiskCheckError::RateLimitExceeded {
+                count: 3,
+                max: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn submit_with_check_skips_orders_awaiting_confirmation() {
+        let mut guard = RiskGuard::new(test_config());
+        let params = make_params("BTC/USD", dec!(0.4), Some(dec!(130000)));
+
+        for _ in 0..4 {
+            assert!(matches!(
+                guard.submit_with_check(&params, &HashMap::new(), &HashMap::new()),
+                Ok(RiskVerdict::RequiresConfirmation { .. })
+            ));
+        }
+    }
+
     #[test]
     fn market_orders_skip_notional_checks() {
         let guard = RiskGuard::new(test_config());
         // Market order with qty within limit but would exceed notional if price were checked
         let params = make_market_params("ETH/USD", dec!(0.5));
-        let result = guard.check_order(&params);
+        let result = guard.check_order(&params, &HashMap::new(), &HashMap::new());
         assert_eq!(result, Ok(RiskVerdict::Approved));
     }
 
+    fn make_ticker(symbol: &str, last: Decimal) -> (String, TickerData) {
+        let ticker = TickerData {
+            symbol: symbol.to_string(),
+            bid: last,
+            bid_qty: dec!(1),
+            ask: last,
+            ask_qty: dec!(1),
+            last,
+            volume: Some(dec!(0)),
+            vwap: Some(last),
+            low: Some(last),
+            high: Some(last),
+            change: Some(dec!(0)),
+            change_pct: Some(dec!(0)),
+        };
+        (symbol.to_string(), ticker)
+    }
+
+    fn capped_config(max: Decimal) -> RiskConfig {
+        let mut config = test_config();
+        config.defaults.max_portfolio_exposure = Some(max);
+        config
+    }
+
+    #[test]
+    fn portfolio_cap_spans_symbols() {
+        let guard = RiskGuard::new(capped_config(dec!(30000)));
+        let prices = HashMap::from([
+            make_ticker("BTC/USD", dec!(50000)),
+            make_ticker("ETH/USD", dec!(2000)),
+        ]);
+
+        // 0.4 BTC = 20000 is fine on its own
+        let positions = HashMap::from([("BTC/USD".to_string(), dec!(0.4))]);
+        assert!(guard.check_portfolio_exposure(&positions, &prices).is_ok());
+
+        // Buying one more ETH passes every per-symbol limit, but takes
+        // 0.4 BTC + 6 ETH to 32000
+        let params = make_params("ETH/USD", dec!(1.0), Some(dec!(2000)));
+        let positions = HashMap::from([
+            ("BTC/USD".to_string(), dec!(0.4)),
+            ("ETH/USD".to_string(), dec!(5)),
+        ]);
+        assert_eq!(
+            guard.check_order(&params, &positions, &prices),
+            Err(RiskCheckError::PortfolioExposureExceeded {
+                exposure: dec!(32000),
+                max: dec!(30000),
+            })
+        );
+    }
+
+    #[test]
+    fn portfolio_cap_allows_reducing_orders() {
+        let guard = RiskGuard::new(capped_config(dec!(10000)));
+        let prices = HashMap::from([make_ticker("BTC/USD", dec!(50000))]);
+        let positions = HashMap::from([("BTC/USD".to_string(), dec!(0.5))]);
+
+        // Already over the cap, but selling shrinks the position
+        let sell = AddOrderParams {
+            side: OrderSide::Sell,
+            ..make_params("BTC/USD", dec!(0.1), Some(dec!(50000)))
+        };
+        assert_eq!(
+            guard.check_order(&sell, &positions, &prices),
+            Ok(RiskVerdict::Approved)
+        );
+
+        let buy = make_params("BTC/USD", dec!(0.1), Some(dec!(50000)));
+        assert!(matches!(
+            guard.check_order(&buy, &positions, &prices),
+            Err(RiskCheckError::PortfolioExposureExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn side_concentration_skipped_without_limit() {
+        let mut guard = RiskGuard::new(test_config());
+        guard.record_submission("ETH/USD", OrderSide::Buy);
+        guard.record_submission("ETH/USD", OrderSide::Buy);
+
+        let params = make_params("ETH/USD", dec!(0.5), Some(dec!(1000)));
+        assert_eq!(
+            guard.check_order(&params, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
+    }
+
+    #[test]
+    fn side_concentration_rejects_one_sided_session() {
+        let mut config = test_config();
+        config.defaults.max_side_concentration_pct = Some(dec!(75));
+        let mut guard = RiskGuard::new(config);
+        let buy = make_params("ETH/USD", dec!(0.5), Some(dec!(1000)));
+
+        // The first trade of a session is not judged on its own
+        assert_eq!(
+            guard.check_order(&buy, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
+        guard.record_submission("ETH/USD", OrderSide::Buy);
+
+        assert_eq!(
+            guard.check_order(&buy, &HashMap::new(), &HashMap::new()),
+            Err(RiskCheckError::SideConcentration {
+                symbol: "ETH/USD".to_string(),
+                side: OrderSide::Buy,
+                pct: dec!(100),
+                max: dec!(75),
+            })
+        );
+
+        // Trading the other side brings the session back in balance
+        let sell = AddOrderParams {
+            side: OrderSide::Sell,
+            ..buy.clone()
+        };
+        assert_eq!(
+            guard.check_order(&sell, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
+    }
+
+    #[test]
+    fn side_concentration_allows_either_side_when_balanced() {
+        let mut config = test_config();
+        config.defaults.max_side_concentration_pct = Some(dec!(60));
+        config.defaults.max_trades_per_day = 10;
+        let mut guard = RiskGuard::new(config);
+        guard.record_submission("ETH/USD", OrderSide::Buy);
+        guard.record_submission("ETH/USD", OrderSide::Sell);
+
+        // Either order would reach 66.67%, but neither side leads yet
+        let buy = make_params("ETH/USD", dec!(0.5), Some(dec!(1000)));
+        let sell = AddOrderParams {
+            side: OrderSide::Sell,
+            ..buy.clone()
+        };
+        assert_eq!(
+            guard.check_order(&buy, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
+        assert_eq!(
+            guard.check_order(&sell, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
+
+        // Once buys lead, another buy is rejected but a sell still passes
+        guard.record_submission("ETH/USD", OrderSide::Buy);
+        assert_eq!(
+            guard.check_order(&buy, &HashMap::new(), &HashMap::new()),
+            Err(RiskCheckError::SideConcentration {
+                symbol: "ETH/USD".to_string(),
+                side: OrderSide::Buy,
+                pct: dec!(75),
+                max: dec!(60),
+            })
+        );
+        assert_eq!(
+            guard.check_order(&sell, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
+    }
+
     #[test]
     fn prune_does_not_panic_on_empty() {
         let mut guard = RiskGuard::new(test_config());
@@ -416,12 +774,15 @@ mod tests {
 
         // Fill BTC/USD daily limit
         for _ in 0..3 {
-            guard.record_submission("BTC/USD");
+            guard.record_submission("BTC/USD", OrderSide::Buy);
         }
 
         // ETH/USD should still be allowed
         let params = make_params("ETH/USD", dec!(0.5), Some(dec!(1000)));
-        assert_eq!(guard.check_order(&params), Ok(RiskVerdict::Approved));
+        assert_eq!(
+            guard.check_order(&params, &HashMap::new(), &HashMap::new()),
+            Ok(RiskVerdict::Approved)
+        );
     }
 
     #[test]
@@ -436,4 +797,22 @@ mod tests {
         };
         assert_eq!(err.to_string(), "BTC/USD: quantity 2.0 exceeds max 1.0");
     }
+
+    #[test]
+    fn risk_check_error_converts_to_leeson_error() {
+        let err = RiskCheckError::QuantityExceeded {
+            qty: dec!(2),
+            max: dec!(1),
+            symbol: "BTC/USD".to_string(),
+        };
+        let expected = err.to_string();
+
+        let converted = crate::LeesonError::from(err.clone());
+
+        assert_eq!(converted.to_string(), expected);
+        assert!(matches!(
+            converted,
+            crate::LeesonError::RiskCheckFailed(inner) if inner == err
+        ));
+    }
 }
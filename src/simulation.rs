@@ -2,24 +2,35 @@
 //!
 //! When simulation mode is active, the engine intercepts orders that would
 //! normally be sent to the Kraken exchange and fills them locally using the
-//! current bid/ask from the ticker stream. Agents receive the same
-//! [`AddOrderResponse`] and [`ExecutionUpdateResponse`] messages they would
-//! from a real exchange, so they remain completely unaware of the simulation.
+//! best bid/ask from the live order book (falling back to the ticker stream),
+//! walking the book depth for market orders when one is available. Agents
+//! receive the same [`AddOrderResponse`] and [`ExecutionUpdateResponse`]
+//! messages they would from a real exchange, so they remain completely
+//! unaware of the simulation.
 
 use std::collections::HashMap;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 
 use crate::models::add_order::{
     AddOrderParams, AddOrderResponse, AddOrderResult, OrderSide, OrderType,
 };
 use crate::models::execution::{ExecutionData, ExecutionUpdateResponse};
 use crate::models::ticker::TickerData;
+use crate::tui::app::OrderBookState;
 
 /// Kraken taker fee rate (0.26%).
 const DEFAULT_FEE_RATE: Decimal = Decimal::from_parts(26, 0, 0, false, 4);
 
+/// Basis points of market impact for an order equal to the average daily
+/// volume.
+const DEFAULT_IMPACT_FACTOR: Decimal = Decimal::TEN;
+
+/// Basis points per unit of price.
+const BPS_PER_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
 /// A completed simulated fill.
 #[derive(Debug, Clone)]
 pub struct SimulatedFill {
@@ -43,7 +54,13 @@ pub struct SimulationEngine {
     trade_history: Vec<SimulatedFill>,
     realized_pnl: Decimal,
     fee_rate: Decimal,
+    impact_factor: Decimal,
+    average_daily_volumes: HashMap<String, Decimal>,
+    /// Largest long and short position allowed per symbol.
+    position_limits: HashMap<String, (Decimal, Decimal)>,
     session_start: Instant,
+    /// Whether the engine replays recorded fills and rejects new orders.
+    playback: bool,
 }
 
 impl SimulationEngine {
@@ -59,33 +76,119 @@ impl SimulationEngine {
             trade_history: Vec::new(),
             realized_pnl: Decimal::ZERO,
             fee_rate: DEFAULT_FEE_RATE,
+            impact_factor: DEFAULT_IMPACT_FACTOR,
+            average_daily_volumes: HashMap::new(),
+            position_limits: HashMap::new(),
             session_start: Instant::now(),
+            playback: false,
         }
     }
 
-    /// Executes an order against current ticker data.
+    /// Creates an engine that replays a recorded session.
+    ///
+    /// Positions and average entry prices are rebuilt by applying `fills`
+    /// in order, and realized P&L is the sum of their recorded P&L. Every
+    /// new order is rejected with a failed response, so the UI and agents
+    /// see the recorded state only.
+    #[must_use]
+    pub fn playback_mode(fills: Vec<SimulatedFill>) -> Self {
+        let mut engine = Self::new();
+        for fill in &fills {
+            engine.update_position(&fill.symbol, &fill.side, fill.qty, fill.fill_price);
+            engine.realized_pnl += fill.realized_pnl;
+        }
+        engine.trade_history = fills;
+        engine.playback = true;
+        engine
+    }
+
+    /// Returns `true` for an engine created by
+    /// [`playback_mode`](Self::playback_mode).
+    #[must_use]
+    pub fn is_playback(&self) -> bool {
+        self.playback
+    }
+
+    /// Sets the market impact factor, in basis points for an order equal to
+    /// the average daily volume. Defaults to 10.
+    #[must_use]
+    pub fn with_impact_factor(mut self, impact_factor: Decimal) -> Self {
+        self.impact_factor = impact_factor;
+        self
+    }
+
+    /// Sets the average daily volume used to model market impact for
+    /// `symbol`. Symbols without one fill without market impact.
+    pub fn set_average_daily_volume(&mut self, symbol: &str, adv: Decimal) {
+        self.average_daily_volumes.insert(symbol.to_string(), adv);
+    }
+
+    /// Caps the position held in `symbol` at `max_long` long and
+    /// `max_short` short, both given as positive quantities.
+    ///
+    /// Orders that would take the position past either limit are rejected
+    /// without a fill.
+    pub fn set_position_limit(&mut self, symbol: &str, max_long: Decimal, max_short: Decimal) {
+        self.position_limits
+            .insert(symbol.to_string(), (max_long, max_short));
+    }
+
+    /// Executes an order against current ticker data only.
+    ///
+    /// Shorthand for [`execute_order_with_book`](Self::execute_order_with_book)
+    /// without an order book.
+    pub fn execute_order(
+        &mut self,
+        params: &AddOrderParams,
+        ticker: Option<&TickerData>,
+    ) -> (AddOrderResponse, Option<ExecutionUpdateResponse>) {
+        self.execute_order_with_book(params, ticker, None)
+    }
+
+    /// Executes an order against the live order book, falling back to the
+    /// ticker.
     ///
     /// Returns synthesized exchange responses identical in shape to what
     /// the real Kraken WebSocket would produce.
     ///
-    /// If no ticker data is available for the symbol, returns a failed
-    /// `AddOrderResponse` with an error message.
-    pub fn execute_order(
+    /// The best bid/ask comes from the [`top_of_book`](OrderBookState::top_of_book)
+    /// of `book` when both sides are populated, otherwise from `ticker`. If
+    /// neither is available, returns a failed `AddOrderResponse` with an
+    /// error message.
+    ///
+    /// When `book` is provided, market orders fill at the volume-weighted
+    /// price of the depth they consume (see
+    /// [`apply_slippage`](Self::apply_slippage)); otherwise they fill flat
+    /// at the best bid/ask. Market orders on a symbol with an
+    /// [average daily volume](Self::set_average_daily_volume) then move a
+    /// further [`apply_market_impact`](Self::apply_market_impact) basis
+    /// points against the order.
+    ///
+    /// Orders that would breach a [position limit](Self::set_position_limit),
+    /// and every order in [playback mode](Self::playback_mode), return a
+    /// failed `AddOrderResponse` instead.
+    pub fn execute_order_with_book(
         &mut self,
         params: &AddOrderParams,
         ticker: Option<&TickerData>,
+        book: Option<&OrderBookState>,
     ) -> (AddOrderResponse, Option<ExecutionUpdateResponse>) {
-        let ticker = match ticker {
-            Some(t) => t,
-            None => {
-                return (
-                    self.make_failed_response(format!("no ticker data for {}", params.symbol)),
-                    None,
-                );
-            }
+        if self.playback {
+            return (self.make_failed_response("playback mode".to_string()), None);
+        }
+
+        let quote = book
+            .and_then(OrderBookState::top_of_book)
+            .map(|(bid, ask)| (bid.price, ask.price))
+            .or_else(|| ticker.map(|t| (t.bid, t.ask)));
+        let Some((bid, ask)) = quote else {
+            return (
+                self.make_failed_response(format!("no ticker or book data for {}", params.symbol)),
+                None,
+            );
         };
 
-        let fill_price = match self.determine_fill_price(params, ticker) {
+        let fill_price = match self.determine_fill_price(params, bid, ask) {
             Some(p) => p,
             None => {
                 return (
@@ -98,6 +201,32 @@ impl SimulationEngine {
             }
         };
 
+        let fill_price = match book {
+            Some(book) if params.order_type == OrderType::Market => {
+                Self::apply_slippage(params.side, fill_price, params.order_qty, book)
+            }
+            _ => fill_price,
+        };
+
+        if self.exceeds_position_limit(params) {
+            return (
+                self.make_failed_response(format!("position limit exceeded for {}", params.symbol)),
+                None,
+            );
+        }
+
+        let fill_price = match self.average_daily_volumes.get(&params.symbol) {
+            Some(adv) if params.order_type == OrderType::Market => {
+                let impact =
+                    fill_price * self.apply_market_impact(params.order_qty, *adv) / BPS_PER_UNIT;
+                match params.side {
+                    OrderSide::Buy => fill_price + impact,
+                    OrderSide::Sell => fill_price - impact,
+                }
+            }
+            _ => fill_price,
+        };
+
         let order_id = self.next_order_id();
         let exec_id = self.next_exec_id();
         let timestamp = iso_timestamp();
@@ -203,6 +332,58 @@ impl SimulationEngine {
         (order_response, Some(execution))
     }
 
+    /// Returns the volume-weighted average price of filling `qty` against
+    /// the order book.
+    ///
+    /// Buys walk the asks and sells walk the bids, from best to worst, until
+    /// `qty` is consumed. Quantity beyond the visible depth fills at the
+    /// worst visible level. Returns `base_price` if that side of the book is
+    /// empty or `qty` is not positive.
+    #[must_use]
+    pub fn apply_slippage(
+        side: OrderSide,
+        base_price: Decimal,
+        qty: Decimal,
+        depth: &OrderBookState,
+    ) -> Decimal {
+        let levels = match side {
+            OrderSide::Buy => &depth.asks,
+            OrderSide::Sell => &depth.bids,
+        };
+        if levels.is_empty() || qty <= Decimal::ZERO {
+            return base_price;
+        }
+
+        let mut remaining = qty;
+        let mut notional = Decimal::ZERO;
+        let mut worst = base_price;
+        for level in levels {
+            let take = remaining.min(level.qty);
+            notional += take * level.price;
+            remaining -= take;
+            worst = level.price;
+            if remaining.is_zero() {
+                break;
+            }
+        }
+        notional += remaining * worst;
+
+        notional / qty
+    }
+
+    /// Returns the market impact, in basis points, of an order for `qty`
+    /// on a market trading `adv` a day: `sqrt(qty / adv) * impact_factor`.
+    ///
+    /// Returns zero if either `qty` or `adv` is not positive.
+    #[must_use]
+    pub fn apply_market_impact(&self, qty: Decimal, adv: Decimal) -> Decimal {
+        if qty <= Decimal::ZERO || adv <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        let participation = (qty / adv).to_f64().unwrap_or(0.0);
+        Decimal::from_f64(participation.sqrt()).unwrap_or(Decimal::ZERO) * self.impact_factor
+    }
+
     /// Returns cumulative realized P&L (after fees).
     #[must_use]
     pub fn realized_pnl(&self) -> Decimal {
@@ -287,42 +468,54 @@ impl SimulationEngine {
     fn determine_fill_price(
         &self,
         params: &AddOrderParams,
-        ticker: &TickerData,
+        bid: Decimal,
+        ask: Decimal,
     ) -> Option<Decimal> {
         match params.order_type {
             OrderType::Market => match params.side {
-                OrderSide::Buy => Some(ticker.ask),
-                OrderSide::Sell => Some(ticker.bid),
+                OrderSide::Buy => Some(ask),
+                OrderSide::Sell => Some(bid),
             },
             OrderType::Limit => {
                 let limit = params.limit_price?;
                 match params.side {
                     OrderSide::Buy => {
                         // Marketable if limit >= ask; fill at best available
-                        if limit >= ticker.ask {
-                            Some(ticker.ask)
-                        } else {
-                            Some(limit)
-                        }
+                        if limit >= ask { Some(ask) } else { Some(limit) }
                     }
                     OrderSide::Sell => {
                         // Marketable if limit <= bid; fill at best available
-                        if limit <= ticker.bid {
-                            Some(ticker.bid)
-                        } else {
-                            Some(limit)
-                        }
+                        if limit <= bid { Some(bid) } else { Some(limit) }
                     }
                 }
             }
             // Unsupported order types fill at market price as a fallback
             _ => match params.side {
-                OrderSide::Buy => Some(ticker.ask),
-                OrderSide::Sell => Some(ticker.bid),
+                OrderSide::Buy => Some(ask),
+                OrderSide::Sell => Some(bid),
             },
         }
     }
 
+    /// Returns `true` if filling `params` would take the position past the
+    /// symbol's long or short limit.
+    fn exceeds_position_limit(&self, params: &AddOrderParams) -> bool {
+        let Some(&(max_long, max_short)) = self.position_limits.get(&params.symbol) else {
+            return false;
+        };
+        let current = self
+            .positions
+            .get(&params.symbol)
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+        // Buys can only breach the long limit and sells the short one, so
+        // orders that shrink an oversized position still go through
+        match params.side {
+            OrderSide::Buy => current + params.order_qty > max_long,
+            OrderSide::Sell => params.order_qty - current > max_short,
+        }
+    }
+
     /// Updates position tracking and returns realized P&L (before fees).
     fn update_position(
         &mut self,
@@ -455,12 +648,12 @@ mod tests {
             ask,
             ask_qty: dec!(10),
             last: (bid + ask) / dec!(2),
-            volume: dec!(1000),
-            vwap: (bid + ask) / dec!(2),
-            low: bid - dec!(100),
-            high: ask + dec!(100),
-            change: dec!(0),
-            change_pct: dec!(0),
+            volume: Some(dec!(1000)),
+            vwap: Some((bid + ask) / dec!(2)),
+            low: Some(bid - dec!(100)),
+            high: Some(ask + dec!(100)),
+            change: Some(dec!(0)),
+            change_pct: Some(dec!(0)),
         }
     }
 
@@ -488,6 +681,34 @@ mod tests {
             .unwrap()
     }
 
+    /// Two levels per side: 1 @ 50010 / 2 @ 50020 asks, 1 @ 50000 / 2 @ 49990 bids.
+    fn make_two_level_book() -> OrderBookState {
+        use crate::models::book::PriceLevel;
+        OrderBookState {
+            bids: vec![
+                PriceLevel {
+                    price: dec!(50000),
+                    qty: dec!(1),
+                },
+                PriceLevel {
+                    price: dec!(49990),
+                    qty: dec!(2),
+                },
+            ],
+            asks: vec![
+                PriceLevel {
+                    price: dec!(50010),
+                    qty: dec!(1),
+                },
+                PriceLevel {
+                    price: dec!(50020),
+                    qty: dec!(2),
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn market_buy_fills_at_ask() {
         let mut engine = SimulationEngine::new();
@@ -607,6 +828,37 @@ mod tests {
         assert_eq!(engine.realized_pnl(), dec!(737.4000));
     }
 
+    #[test]
+    fn playback_reproduces_recorded_session() {
+        let mut engine = SimulationEngine::new();
+        let orders = [
+            (make_market_buy("BTC/USD", dec!(2)), dec!(50000)),
+            (make_market_buy("ETH/USD", dec!(5)), dec!(3000)),
+            (make_market_sell("BTC/USD", dec!(1)), dec!(52000)),
+            (make_market_sell("ETH/USD", dec!(8)), dec!(2900)),
+        ];
+        for (order, price) in &orders {
+            let ticker = make_ticker(&order.symbol, *price, *price);
+            engine.execute_order(order, Some(&ticker));
+        }
+
+        let mut playback = SimulationEngine::playback_mode(engine.trade_history().to_vec());
+
+        assert!(playback.is_playback());
+        assert_eq!(playback.positions(), engine.positions());
+        assert_eq!(playback.avg_entry_prices(), engine.avg_entry_prices());
+        assert_eq!(playback.realized_pnl(), engine.realized_pnl());
+        assert_eq!(playback.trade_count(), engine.trade_count());
+
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50000));
+        let (response, execution) =
+            playback.execute_order(&make_market_buy("BTC/USD", dec!(1)), Some(&ticker));
+        assert!(!response.success);
+        assert_eq!(response.error.as_deref(), Some("playback mode"));
+        assert!(execution.is_none());
+        assert_eq!(playback.trade_count(), engine.trade_count());
+    }
+
     #[test]
     fn partial_close_keeps_position() {
         let mut engine = SimulationEngine::new();
@@ -736,4 +988,199 @@ mod tests {
         assert_eq!(&ts[16..17], ":");
         assert_eq!(&ts[19..20], ".");
     }
+
+    #[test]
+    fn slippage_within_best_level_is_flat() {
+        let book = make_two_level_book();
+        let price = SimulationEngine::apply_slippage(OrderSide::Buy, dec!(50010), dec!(0.5), &book);
+        assert_eq!(price, dec!(50010));
+    }
+
+    #[test]
+    fn slippage_walks_asks_for_buys() {
+        let book = make_two_level_book();
+        // 1 @ 50010 + 1 @ 50020 = 100030 / 2
+        let price = SimulationEngine::apply_slippage(OrderSide::Buy, dec!(50010), dec!(2), &book);
+        assert_eq!(price, dec!(50015));
+    }
+
+    #[test]
+    fn slippage_walks_bids_for_sells() {
+        let book = make_two_level_book();
+        // 1 @ 50000 + 2 @ 49990 = 149980 / 3
+        let price = SimulationEngine::apply_slippage(OrderSide::Sell, dec!(50000), dec!(3), &book);
+        assert_eq!(price.round_dp(4), dec!(49993.3333));
+    }
+
+    #[test]
+    fn slippage_beyond_depth_fills_at_worst_level() {
+        let book = make_two_level_book();
+        // 1 @ 50010 + 3 @ 50020 = 200070 / 4
+        let price = SimulationEngine::apply_slippage(OrderSide::Buy, dec!(50010), dec!(4), &book);
+        assert_eq!(price, dec!(50017.5));
+    }
+
+    #[test]
+    fn slippage_on_empty_book_uses_base_price() {
+        let book = OrderBookState::default();
+        let price = SimulationEngine::apply_slippage(OrderSide::Buy, dec!(50010), dec!(2), &book);
+        assert_eq!(price, dec!(50010));
+    }
+
+    #[test]
+    fn market_order_with_book_fills_at_vwap() {
+        let mut engine = SimulationEngine::new();
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50010));
+        let book = make_two_level_book();
+        let params = make_market_buy("BTC/USD", dec!(2));
+
+        let (_, exec) = engine.execute_order_with_book(&params, Some(&ticker), Some(&book));
+        assert_eq!(exec.unwrap().data[0].avg_price, Some(dec!(50015)));
+    }
+
+    #[test]
+    fn limit_order_ignores_book_depth() {
+        let mut engine = SimulationEngine::new();
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50010));
+        let book = make_two_level_book();
+        let params = make_limit_buy("BTC/USD", dec!(2), dec!(50100));
+
+        let (_, exec) = engine.execute_order_with_book(&params, Some(&ticker), Some(&book));
+        assert_eq!(exec.unwrap().data[0].avg_price, Some(dec!(50010)));
+    }
+
+    #[test]
+    fn book_top_overrides_stale_ticker() {
+        let mut engine = SimulationEngine::new();
+        // Ticker lags the book: its ask is 100 below the best ask
+        let ticker = make_ticker("BTC/USD", dec!(49900), dec!(49910));
+        let book = make_two_level_book();
+        let params = make_limit_buy("BTC/USD", dec!(1), dec!(50100));
+
+        let (_, exec) = engine.execute_order_with_book(&params, Some(&ticker), Some(&book));
+        assert_eq!(exec.unwrap().data[0].avg_price, Some(dec!(50010)));
+    }
+
+    #[test]
+    fn empty_book_falls_back_to_ticker() {
+        let mut engine = SimulationEngine::new();
+        let ticker = make_ticker("BTC/USD", dec!(49900), dec!(49910));
+        let book = OrderBookState::default();
+        let params = make_limit_sell("BTC/USD", dec!(1), dec!(49000));
+
+        let (_, exec) = engine.execute_order_with_book(&params, Some(&ticker), Some(&book));
+        assert_eq!(exec.unwrap().data[0].avg_price, Some(dec!(49900)));
+    }
+
+    #[test]
+    fn book_without_ticker_still_fills() {
+        let mut engine = SimulationEngine::new();
+        let book = make_two_level_book();
+        let params = make_market_sell("BTC/USD", dec!(1));
+
+        let (resp, exec) = engine.execute_order_with_book(&params, None, Some(&book));
+        assert!(resp.success);
+        assert_eq!(exec.unwrap().data[0].avg_price, Some(dec!(50000)));
+    }
+
+    #[test]
+    fn top_of_book_needs_both_sides() {
+        let mut book = make_two_level_book();
+        let (bid, ask) = book.top_of_book().unwrap();
+        assert_eq!((bid.price, ask.price), (dec!(50000), dec!(50010)));
+
+        book.asks.clear();
+        assert!(book.top_of_book().is_none());
+    }
+
+    #[test]
+    fn market_impact_grows_with_share_of_adv() {
+        let engine = SimulationEngine::new();
+
+        assert_eq!(engine.apply_market_impact(dec!(100), dec!(100)), dec!(10));
+        assert!(engine.apply_market_impact(dec!(1), dec!(100)) < dec!(1.01));
+        assert_eq!(engine.apply_market_impact(dec!(1), dec!(0)), dec!(0));
+    }
+
+    #[test]
+    fn ten_percent_of_adv_pays_market_impact() {
+        let mut engine = SimulationEngine::new();
+        engine.set_average_daily_volume("BTC/USD", dec!(10));
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50010));
+
+        let (resp, _) = engine.execute_order(&make_market_buy("BTC/USD", dec!(1)), Some(&ticker));
+        assert!(resp.success);
+
+        // sqrt(0.1) * 10 ≈ 3.16 bps on 50010 ≈ 15.8
+        let price = engine.trade_history()[0].fill_price;
+        assert!(price > dec!(50025) && price < dec!(50026), "{price}");
+
+        let (_, exec) = engine.execute_order(&make_market_sell("BTC/USD", dec!(1)), Some(&ticker));
+        assert!(exec.unwrap().data[0].avg_price.unwrap() < dec!(49985));
+    }
+
+    #[test]
+    fn limit_orders_pay_no_market_impact() {
+        let mut engine = SimulationEngine::new().with_impact_factor(dec!(50));
+        engine.set_average_daily_volume("BTC/USD", dec!(1));
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50010));
+
+        engine.execute_order(
+            &make_limit_buy("BTC/USD", dec!(1), dec!(50020)),
+            Some(&ticker),
+        );
+
+        assert_eq!(engine.trade_history()[0].fill_price, dec!(50010));
+    }
+
+    #[test]
+    fn buy_past_long_limit_is_rejected() {
+        let mut engine = SimulationEngine::new();
+        engine.set_position_limit("BTC/USD", dec!(2), dec!(1));
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50010));
+
+        let (resp, _) = engine.execute_order(&make_market_buy("BTC/USD", dec!(2)), Some(&ticker));
+        assert!(resp.success);
+
+        let (resp, exec) =
+            engine.execute_order(&make_market_buy("BTC/USD", dec!(0.5)), Some(&ticker));
+        assert!(!resp.success);
+        assert!(exec.is_none());
+        assert_eq!(
+            resp.error.as_deref(),
+            Some("position limit exceeded for BTC/USD")
+        );
+        assert_eq!(engine.positions().get("BTC/USD"), Some(&dec!(2)));
+        assert_eq!(engine.trade_count(), 1);
+
+        // Reducing the position is still allowed
+        let (resp, _) =
+            engine.execute_order(&make_market_sell("BTC/USD", dec!(2.5)), Some(&ticker));
+        assert!(resp.success);
+    }
+
+    #[test]
+    fn sell_past_short_limit_is_rejected() {
+        let mut engine = SimulationEngine::new();
+        engine.set_position_limit("BTC/USD", dec!(5), dec!(1));
+        let ticker = make_ticker("BTC/USD", dec!(50000), dec!(50010));
+
+        let (resp, _) = engine.execute_order(&make_market_buy("BTC/USD", dec!(1)), Some(&ticker));
+        assert!(resp.success);
+
+        // Flipping from 1 long to 1.5 short breaches the short limit
+        let (resp, _) =
+            engine.execute_order(&make_market_sell("BTC/USD", dec!(2.5)), Some(&ticker));
+        assert!(!resp.success);
+        assert_eq!(engine.positions().get("BTC/USD"), Some(&dec!(1)));
+
+        let (resp, _) = engine.execute_order(&make_market_sell("BTC/USD", dec!(2)), Some(&ticker));
+        assert!(resp.success);
+        assert_eq!(engine.positions().get("BTC/USD"), Some(&dec!(-1)));
+
+        // Other symbols are unlimited
+        let eth = make_ticker("ETH/USD", dec!(3000), dec!(3001));
+        let (resp, _) = engine.execute_order(&make_market_sell("ETH/USD", dec!(100)), Some(&eth));
+        assert!(resp.success);
+    }
 }
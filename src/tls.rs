@@ -3,8 +3,13 @@
 //! Builds a [`rustls::ClientConfig`] that trusts only the GTS Root R4
 //! certificate authority, which signs the TLS chain for both
 //! `ws.kraken.com` and `api.kraken.com`.
+//!
+//! Users behind TLS-inspecting proxies can instead trust their own CA via
+//! [`build_tls_config_from_pem`] or the OS store via
+//! [`build_tls_config_with_system_roots`].
 
 use rustls::ClientConfig;
+use tracing::warn;
 
 use crate::Result;
 
@@ -19,17 +24,95 @@ static GTS_ROOT_R4_PEM: &[u8] = include_bytes!("../certs/gts_root_r4.pem");
 /// Returns [`LeesonError::Tls`](crate::LeesonError::Tls) if the embedded
 /// PEM cannot be parsed.
 pub fn build_tls_config() -> Result<ClientConfig> {
-    let mut root_store = rustls::RootCertStore::empty();
+    build_tls_config_from_pem(GTS_ROOT_R4_PEM)
+}
 
-    let certs: Vec<_> = rustls_pemfile::certs(&mut &GTS_ROOT_R4_PEM[..])
+/// Builds a [`ClientConfig`] that trusts the CA certificates in `pem`.
+///
+/// # Errors
+///
+/// Returns [`LeesonError::Tls`](crate::LeesonError::Tls) if the PEM is
+/// malformed or contains no usable certificate.
+pub fn build_tls_config_from_pem(pem: &[u8]) -> Result<ClientConfig> {
+    let certs: Vec<_> = rustls_pemfile::certs(&mut &pem[..])
         .collect::<std::result::Result<Vec<_>, _>>()
         .map_err(|e| crate::LeesonError::Tls(format!("failed to parse CA PEM: {e}")))?;
 
-    root_store.add_parsable_certificates(certs);
+    let mut root_store = rustls::RootCertStore::empty();
+    let (added, _ignored) = root_store.add_parsable_certificates(certs);
+    if added == 0 {
+        return Err(crate::LeesonError::Tls(
+            "no valid CA certificate found in PEM".to_string(),
+        ));
+    }
+
+    Ok(with_roots(root_store))
+}
+
+/// Builds a [`ClientConfig`] that trusts the operating system's
+/// certificate store.
+///
+/// Certificates the OS store fails to load are logged and skipped.
+///
+/// # Errors
+///
+/// Returns [`LeesonError::Tls`](crate::LeesonError::Tls) if no usable
+/// certificate could be loaded.
+pub fn build_tls_config_with_system_roots() -> Result<ClientConfig> {
+    let native = rustls_native_certs::load_native_certs();
+    for error in &native.errors {
+        warn!("failed to load system certificate: {error}");
+    }
+
+    let mut root_store = rustls::RootCertStore::empty();
+    let (added, _ignored) = root_store.add_parsable_certificates(native.certs);
+    if added == 0 {
+        return Err(crate::LeesonError::Tls(
+            "no usable certificates in system store".to_string(),
+        ));
+    }
+
+    Ok(with_roots(root_store))
+}
 
-    let config = ClientConfig::builder()
+/// Finishes a client config around `root_store`, without client auth.
+fn with_roots(root_store: rustls::RootCertStore) -> ClientConfig {
+    ClientConfig::builder()
         .with_root_certificates(root_store)
-        .with_no_client_auth();
+        .with_no_client_auth()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embedded_ca_builds_config() {
+        assert!(build_tls_config().is_ok());
+    }
+
+    #[test]
+    fn custom_pem_builds_config() {
+        assert!(build_tls_config_from_pem(GTS_ROOT_R4_PEM).is_ok());
+    }
+
+    #[test]
+    fn pem_without_certificates_is_rejected() {
+        let err = build_tls_config_from_pem(b"not a certificate").unwrap_err();
+        assert!(err.to_string().contains("no valid CA certificate"));
+    }
+
+    #[test]
+    fn malformed_certificate_body_is_rejected() {
+        let pem = b"-----BEGIN CERTIFICATE-----\n!!!not base64!!!\n-----END CERTIFICATE-----\n";
+        let err = build_tls_config_from_pem(pem).unwrap_err();
+        assert!(err.to_string().contains("failed to parse CA PEM"));
+    }
 
-    Ok(config)
+    #[test]
+    fn undecodable_certificate_is_rejected() {
+        // Valid base64 framing, but the DER is not an X.509 certificate
+        let pem = b"-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n";
+        assert!(build_tls_config_from_pem(pem).is_err());
+    }
 }
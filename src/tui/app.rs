@@ -1,23 +1,38 @@
 //! Application state for the TUI.
 
+use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
+use std::path::Path;
 use std::time::Instant;
 
+use ratatui::text::Line;
+use ratatui::widgets::{Paragraph, Wrap};
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 
+use crate::config::{AppConfig, DEFAULT_AGENT_OUTPUT_LINES};
 use crate::models::add_order::AddOrderParams;
+use crate::models::balance::BalanceSnapshot;
+use crate::models::batch_cancel::MAX_BATCH_CANCEL_SIZE;
 use crate::models::book::PriceLevel;
 use crate::models::candle::CandleData;
 use crate::models::execution::ExecutionData;
+use crate::models::orders::Level3OrderBook;
 use crate::models::ticker::TickerData;
 use crate::models::trade::TradeData;
 use crate::risk::config::AgentRiskParams;
+use crate::tui::candle_aggregator::CandleAggregator;
+use crate::tui::input::history::InputHistory;
+use crate::tui::input::text_input::TextInput;
+
+pub use crate::models::balance::AssetBalance;
 
 /// Maximum number of items to keep in history buffers.
 const MAX_HISTORY_SIZE: usize = 100;
 
-/// Maximum number of agent output lines per panel.
-const MAX_AGENT_OUTPUT_LINES: usize = 50;
+/// Cursor appended to an agent's in-progress streaming line, rendered
+/// blinking by the agent panel.
+pub const STREAM_CURSOR: char = '_';
 
 /// Maximum number of order book snapshots to retain in history.
 pub const MAX_ORDERBOOK_HISTORY: usize = 20;
@@ -25,6 +40,15 @@ pub const MAX_ORDERBOOK_HISTORY: usize = 20;
 /// Maximum number of order book levels retained per side (bids/asks).
 pub const MAX_BOOK_DEPTH: usize = 1000;
 
+/// Maximum number of P&L samples kept for the status bar sparkline.
+pub const MAX_PNL_HISTORY: usize = 60;
+
+/// Minimum interval between message rate samples.
+const MESSAGE_RATE_WINDOW: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Maximum number of past notifications kept for the notification log.
+pub const MAX_NOTIFICATION_HISTORY: usize = 50;
+
 /// Central application state container.
 pub struct App {
     // -- Tab State --
@@ -40,14 +64,24 @@ pub struct App {
     // -- Agent State --
     /// Output streams for three agent panels.
     pub agent_outputs: [VecDeque<String>; 3],
+    /// Maximum number of lines kept in each agent output panel.
+    pub agent_output_lines: usize,
     /// Scroll state for each agent output panel.
     pub agent_scroll: [ScrollState; 3],
     /// Partial-line buffers for streaming agent output.
     pub agent_stream_buffers: [String; 3],
+    /// Keyword filter for each agent output panel (case-insensitive).
+    pub agent_output_filter: [Option<String>; 3],
+    /// Lines added to each agent panel since the agent tab was last active.
+    pub unread_agent_lines: [usize; 3],
+    /// Agents whose order requests are held back by the operator.
+    pub agent_paused: [bool; 3],
     /// Current text in the agent input field.
     pub agent_input: String,
     /// Cursor position in the agent input field.
     pub agent_input_cursor: usize,
+    /// Commands previously submitted from the agent input field.
+    pub input_history: InputHistory,
 
     // -- Account State --
     /// Account balance in USD.
@@ -60,16 +94,22 @@ pub struct App {
     pub pnl_today: Decimal,
     /// Total profit/loss.
     pub pnl_total: Decimal,
+    /// Today's P&L sampled on each execution update, oldest first.
+    pub pnl_history: VecDeque<(Instant, Decimal)>,
     /// All executed trades across all pairs.
     pub executed_trades_all: VecDeque<ExecutedTrade>,
     /// Per-asset balances from the balances channel.
     pub asset_balances: HashMap<String, AssetBalance>,
+    /// Net position per symbol built from fills, used for realized P&L.
+    pub fill_positions: HashMap<String, FillPosition>,
 
     // -- Per-Symbol Market Data --
     /// Latest ticker data per symbol.
     pub tickers: HashMap<String, TickerData>,
     /// Order book state per symbol.
     pub orderbooks: HashMap<String, OrderBookState>,
+    /// Level-3 (individual order) books per symbol.
+    pub level3_books: HashMap<String, Level3OrderBook>,
     /// Recent trades per symbol.
     pub recent_trades: HashMap<String, VecDeque<TradeData>>,
     /// Candle data per symbol.
@@ -90,12 +130,33 @@ pub struct App {
     pub chart_type: ChartType,
     /// Chart timeframe.
     pub chart_timeframe: Timeframe,
+    /// Selected chart candle, counted back from the newest (0 = newest).
+    pub chart_cursor: Option<usize>,
+    /// Operator notes on chart candles, keyed by `(symbol, interval_begin)`.
+    pub annotation_store: HashMap<(String, String), String>,
+    /// Input buffer for the candle annotation prompt.
+    pub annotation_input: TextInput,
+    /// `(symbol, interval_begin)` of the candle being annotated.
+    pub annotation_target: Option<(String, String)>,
     /// Orders view (open or executed).
     pub orders_view: OrdersView,
+    /// Order book display (depth bars or heat-map).
+    pub book_view: BookView,
+    /// ID of the selected open order per pair, looked up on each use so the
+    /// selection stays on the same order while the list changes.
+    pub selected_orders: HashMap<String, String>,
+    /// State for the order edit overlay.
+    pub order_edit: Option<EditOrderState>,
     /// Index in the pair selector.
     pub pair_selector_index: usize,
+    /// Query typed into the pair search overlay.
+    pub pair_search: TextInput,
+    /// Query typed into the global search overlay.
+    pub global_search: TextInput,
     /// Error message to display (clears after timeout).
     pub error_message: Option<ErrorDisplay>,
+    /// Recent notifications, oldest first, for the notification log.
+    pub notification_history: VecDeque<ErrorDisplay>,
 
     // -- Connection State --
     /// WebSocket connection status.
@@ -108,6 +169,16 @@ pub struct App {
     pub authenticated: bool,
     /// Whether the private WebSocket is currently connected.
     pub private_connected: bool,
+    /// Fee tier reported when the credentials were validated.
+    pub account_tier: Option<String>,
+    /// Latest reconnect and uptime counters from the connection manager.
+    pub connection_stats: Option<crate::websocket::ConnectionStats>,
+    /// Market data messages received since `last_rate_sample`.
+    pub message_counter: u64,
+    /// When `message_rate` was last sampled.
+    pub last_rate_sample: Instant,
+    /// Market data messages per second over the last sample window.
+    pub message_rate: f64,
 
     // -- Risk State --
     /// Order pending operator confirmation.
@@ -118,6 +189,10 @@ pub struct App {
     pub risk_edit: Option<RiskEditState>,
     /// State for the API keys edit overlay.
     pub api_keys_edit: Option<ApiKeysEditState>,
+    /// Input buffer for the dead man's switch timeout prompt.
+    pub cancel_after_input: TextInput,
+    /// Active dead man's switch timeout in seconds.
+    pub cancel_after_timeout: Option<u32>,
 
     // -- Token Usage --
     /// Cumulative token usage from agent LLM calls.
@@ -153,25 +228,33 @@ impl App {
             ],
 
             agent_outputs: [
-                VecDeque::with_capacity(MAX_AGENT_OUTPUT_LINES),
-                VecDeque::with_capacity(MAX_AGENT_OUTPUT_LINES),
-                VecDeque::with_capacity(MAX_AGENT_OUTPUT_LINES),
+                VecDeque::with_capacity(DEFAULT_AGENT_OUTPUT_LINES),
+                VecDeque::with_capacity(DEFAULT_AGENT_OUTPUT_LINES),
+                VecDeque::with_capacity(DEFAULT_AGENT_OUTPUT_LINES),
             ],
+            agent_output_lines: DEFAULT_AGENT_OUTPUT_LINES,
             agent_scroll: [ScrollState::default(); 3],
             agent_stream_buffers: Default::default(),
+            agent_output_filter: Default::default(),
+            unread_agent_lines: [0; 3],
+            agent_paused: [false; 3],
             agent_input: String::new(),
             agent_input_cursor: 0,
+            input_history: InputHistory::new(),
 
             balance: Decimal::ZERO,
             equity: Decimal::ZERO,
             margin_used: Decimal::ZERO,
             pnl_today: Decimal::ZERO,
             pnl_total: Decimal::ZERO,
+            pnl_history: VecDeque::with_capacity(MAX_PNL_HISTORY),
             executed_trades_all: VecDeque::with_capacity(MAX_HISTORY_SIZE),
             asset_balances: HashMap::new(),
+            fill_positions: HashMap::new(),
 
             tickers: HashMap::new(),
             orderbooks: HashMap::new(),
+            level3_books: HashMap::new(),
             recent_trades: HashMap::new(),
             candles: HashMap::new(),
 
@@ -182,20 +265,37 @@ impl App {
             mode: Mode::Normal,
             chart_type: ChartType::Candle,
             chart_timeframe: Timeframe::M1,
+            chart_cursor: None,
+            annotation_store: HashMap::new(),
+            annotation_input: TextInput::new(),
+            annotation_target: None,
             orders_view: OrdersView::Open,
+            book_view: BookView::Depth,
+            selected_orders: HashMap::new(),
+            order_edit: None,
             pair_selector_index: 0,
+            pair_search: TextInput::new(),
+            global_search: TextInput::new(),
             error_message: None,
+            notification_history: VecDeque::with_capacity(MAX_NOTIFICATION_HISTORY),
 
             pending_order: None,
             agent_risk_params: AgentRiskParams::default(),
             risk_edit: None,
             api_keys_edit: None,
+            cancel_after_input: TextInput::new(),
+            cancel_after_timeout: None,
 
             connection_status: ConnectionStatus::Disconnected,
             token_state: TokenState::Unavailable,
             last_heartbeat: None,
             authenticated: false,
             private_connected: false,
+            account_tier: None,
+            connection_stats: None,
+            message_counter: 0,
+            last_rate_sample: Instant::now(),
+            message_rate: 0.0,
 
             token_usage: TokenUsageStats::default(),
 
@@ -206,6 +306,23 @@ impl App {
         }
     }
 
+    /// Creates a new App instance using settings from `config`.
+    pub fn new_with_config(config: &AppConfig) -> Self {
+        let mut app = Self::new();
+        app.agent_output_lines = config.agent_output_lines;
+        app.agent_outputs =
+            std::array::from_fn(|_| VecDeque::with_capacity(config.agent_output_lines));
+        app.simulation = config.simulation;
+        app.token_usage.input_cost_per_million = config.token_input_cost;
+        app.token_usage.output_cost_per_million = config.token_output_cost;
+        for symbol in &config.selected_pairs {
+            if !app.selected_pairs.contains(symbol) {
+                app.toggle_pair(symbol);
+            }
+        }
+        app
+    }
+
     /// Returns the currently active tab.
     pub fn current_tab(&self) -> &Tab {
         &self.tabs[self.active_tab]
@@ -230,15 +347,62 @@ impl App {
         }
     }
 
-    /// Updates focus when switching tabs.
+    /// Returns the position in `selected_pairs` of the active trading-pair
+    /// tab, or `None` when the agent tab is active.
+    pub fn selected_pair_index(&self) -> Option<usize> {
+        match self.current_tab() {
+            Tab::TradingPair(symbol) => self.selected_pairs.iter().position(|s| s == symbol),
+            Tab::Agent => None,
+        }
+    }
+
+    /// Switches to the tab of the `index`-th selected trading pair.
+    ///
+    /// Does nothing if fewer pairs are selected.
+    pub fn select_pair_tab(&mut self, index: usize) {
+        let Some(symbol) = self.selected_pairs.get(index) else {
+            return;
+        };
+        if let Some(tab) = self
+            .tabs
+            .iter()
+            .position(|t| matches!(t, Tab::TradingPair(s) if s == symbol))
+        {
+            self.active_tab = tab;
+            self.update_focus_for_tab();
+        }
+    }
+
+    /// Updates focus when switching tabs, marking agent output as read when
+    /// the agent tab becomes active.
     fn update_focus_for_tab(&mut self) {
         match self.current_tab() {
-            Tab::Agent => self.focus = Focus::AgentInput,
+            Tab::Agent => {
+                self.focus = Focus::AgentInput;
+                self.unread_agent_lines = [0; 3];
+            }
             Tab::TradingPair(_) => self.focus = Focus::OrderBook,
         }
+        self.selected_orders.clear();
+    }
+
+    /// Number of open orders for `symbol`.
+    pub fn open_order_count(&self, symbol: &str) -> usize {
+        self.open_orders.get(symbol).map_or(0, Vec::len)
+    }
+
+    /// Count shown next to a tab's title: unread agent lines for the agent
+    /// tab, open orders for a trading pair.
+    pub fn tab_badge(&self, tab: &Tab) -> usize {
+        match tab {
+            Tab::Agent => self.unread_agent_lines.iter().sum(),
+            Tab::TradingPair(symbol) => self.open_order_count(symbol),
+        }
     }
 
     /// Toggles selection of a trading pair.
+    ///
+    /// A pair is never selected twice and never gets a second tab.
     pub fn toggle_pair(&mut self, symbol: &str) {
         if let Some(pos) = self.selected_pairs.iter().position(|s| s == symbol) {
             // Remove pair and its tab
@@ -257,33 +421,491 @@ impl App {
         } else {
             // Add pair and its tab
             self.selected_pairs.push(symbol.to_string());
-            self.tabs.push(Tab::TradingPair(symbol.to_string()));
+            let has_tab = self
+                .tabs
+                .iter()
+                .any(|t| matches!(t, Tab::TradingPair(s) if s == symbol));
+            debug_assert!(!has_tab, "{symbol} has a tab but was not selected");
+            if !has_tab {
+                self.tabs.push(Tab::TradingPair(symbol.to_string()));
+            }
+        }
+    }
+
+    /// Deselects the pair of the active trading-pair tab and removes its
+    /// tab, returning the pair's symbol.
+    ///
+    /// Does nothing on the agent tab, which cannot be closed.
+    pub fn close_current_pair_tab(&mut self) -> Option<String> {
+        let Tab::TradingPair(symbol) = self.current_tab().clone() else {
+            return None;
+        };
+        self.toggle_pair(&symbol);
+        self.update_focus_for_tab();
+        Some(symbol)
+    }
+
+    /// Stores `note` on the candle starting at `timestamp`, replacing any
+    /// existing note.
+    pub fn add_annotation(&mut self, symbol: &str, timestamp: &str, note: impl Into<String>) {
+        self.annotation_store
+            .insert((symbol.to_string(), timestamp.to_string()), note.into());
+    }
+
+    /// Removes and returns the note on a candle, if any.
+    pub fn remove_annotation(&mut self, symbol: &str, timestamp: &str) -> Option<String> {
+        self.annotation_store
+            .remove(&(symbol.to_string(), timestamp.to_string()))
+    }
+
+    /// Returns the note on a candle, if any.
+    pub fn get_annotation(&self, symbol: &str, timestamp: &str) -> Option<&str> {
+        self.annotation_store
+            .get(&(symbol.to_string(), timestamp.to_string()))
+            .map(String::as_str)
+    }
+
+    /// Returns the selected open order for `symbol`, if any.
+    pub fn selected_open_order(&self, symbol: &str) -> Option<&ExecutionData> {
+        let order_id = self.selected_orders.get(symbol)?;
+        self.open_orders
+            .get(symbol)?
+            .iter()
+            .find(|o| &o.order_id == order_id)
+    }
+
+    /// Returns the row of the selected open order for `symbol`, if any.
+    pub fn selected_order_index(&self, symbol: &str) -> Option<usize> {
+        let order_id = self.selected_orders.get(symbol)?;
+        self.open_orders
+            .get(symbol)?
+            .iter()
+            .position(|o| &o.order_id == order_id)
+    }
+
+    /// Returns the IDs of every open order for `symbol`, split into
+    /// batches of at most [`MAX_BATCH_CANCEL_SIZE`] for `batch_cancel`.
+    pub fn open_order_id_batches(&self, symbol: &str) -> Vec<Vec<String>> {
+        let ids: Vec<String> = self
+            .open_orders
+            .get(symbol)
+            .map(|orders| orders.iter().map(|o| o.order_id.clone()).collect())
+            .unwrap_or_default();
+        ids.chunks(MAX_BATCH_CANCEL_SIZE)
+            .map(<[String]>::to_vec)
+            .collect()
+    }
+
+    /// Returns the exchange ID of the selected open order for `symbol`.
+    pub fn selected_order_id(&self, symbol: &str) -> Option<String> {
+        self.selected_open_order(symbol).map(|o| o.order_id.clone())
+    }
+
+    /// Moves the order selection by `delta` rows, clamped to `symbol`'s open orders.
+    pub fn move_order_selection(&mut self, symbol: &str, delta: isize) {
+        let orders = self.open_orders.get(symbol).map_or(&[][..], Vec::as_slice);
+        let index = match (self.selected_order_index(symbol), orders.len()) {
+            (_, 0) => None,
+            (None, _) => Some(0),
+            (Some(i), count) => Some(i.saturating_add_signed(delta).min(count - 1)),
+        };
+        match index.map(|i| orders[i].order_id.clone()) {
+            Some(order_id) => self.selected_orders.insert(symbol.to_string(), order_id),
+            None => self.selected_orders.remove(symbol),
+        };
+    }
+
+    /// Clears the order selection on `symbol` if it is `order_id`, which is
+    /// no longer open.
+    pub fn deselect_order(&mut self, symbol: &str, order_id: &str) {
+        if self
+            .selected_orders
+            .get(symbol)
+            .is_some_and(|id| id == order_id)
+        {
+            self.selected_orders.remove(symbol);
+        }
+    }
+
+    /// Returns the candles charted for `symbol` at the current timeframe.
+    ///
+    /// 1-minute candles are down-sampled with [`CandleAggregator`] when a
+    /// coarser timeframe is selected.
+    pub fn chart_candles(&self, symbol: &str) -> Option<Cow<'_, VecDeque<CandleData>>> {
+        let candles = self.candles.get(symbol)?;
+        let target = u64::try_from(self.chart_timeframe.interval()).unwrap_or(1);
+        if candles.iter().any(|c| c.interval < target) {
+            let bars = CandleAggregator::aggregate(candles, self.chart_timeframe);
+            Some(Cow::Owned(bars.into()))
+        } else {
+            Some(Cow::Borrowed(candles))
         }
     }
 
+    /// Returns the candle under the chart cursor for `symbol`.
+    pub fn selected_candle(&self, symbol: &str) -> Option<CandleData> {
+        let candles = self.chart_candles(symbol)?;
+        let offset = self.chart_cursor?;
+        candles.iter().rev().nth(offset).cloned()
+    }
+
     /// Checks if a pair is currently selected.
     pub fn is_pair_selected(&self, symbol: &str) -> bool {
         self.selected_pairs.iter().any(|s| s == symbol)
     }
 
+    /// Returns the lines of an agent output panel that match its filter.
+    ///
+    /// All lines are returned when no filter (or an empty one) is set.
+    pub fn filtered_agent_output(&self, agent_index: usize) -> Vec<&str> {
+        let Some(output) = self.agent_outputs.get(agent_index) else {
+            return Vec::new();
+        };
+        match self.agent_output_filter[agent_index].as_deref() {
+            Some(filter) if !filter.is_empty() => {
+                let filter = filter.to_lowercase();
+                output
+                    .iter()
+                    .filter(|line| line.to_lowercase().contains(&filter))
+                    .map(String::as_str)
+                    .collect()
+            }
+            _ => output.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Returns the lines shown in an agent output panel: the filtered output
+    /// plus, while pinned, the in-progress stream line with its cursor.
+    pub fn agent_panel_lines(&self, agent_index: usize) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .filtered_agent_output(agent_index)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        if let Some(stream) = self.agent_stream_buffers.get(agent_index)
+            && !stream.is_empty()
+            && self.agent_scroll[agent_index].follows_output()
+        {
+            lines.push(format!("{stream}{STREAM_CURSOR}"));
+        }
+        lines
+    }
+
+    /// Refreshes an agent panel's `max_scroll` from its current content.
+    fn refresh_agent_max_scroll(&mut self, agent_index: usize) {
+        let lines = self.agent_panel_lines(agent_index);
+        let scroll = &mut self.agent_scroll[agent_index];
+        let (height, width) = (scroll.panel_height, scroll.panel_width);
+        scroll.update_max_scroll(&lines, height, width);
+    }
+
+    /// Writes all executed trades to `path` as a JSON array, creating
+    /// parent directories if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Io`](crate::LeesonError::Io) if a directory or
+    /// the file cannot be written.
+    pub fn export_trade_history(&self, path: &Path) -> crate::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                crate::LeesonError::io(format!("failed to create {}", parent.display()), e)
+            })?;
+        }
+        let json = serde_json::to_string_pretty(&self.executed_trades_all)?;
+        std::fs::write(path, json)
+            .map_err(|e| crate::LeesonError::io(format!("failed to write {}", path.display()), e))
+    }
+
+    /// Writes the current [`SessionState`] to `path` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Io`](crate::LeesonError::Io) if the file
+    /// cannot be written.
+    pub fn save_session_state(&self, path: &Path) -> crate::Result<()> {
+        let state = SessionState {
+            chart_timeframe: self.chart_timeframe,
+            chart_type: self.chart_type,
+            orders_view: self.orders_view,
+            active_tab: self.active_tab,
+        };
+        let json = serde_json::to_string_pretty(&state)?;
+        std::fs::write(path, json)
+            .map_err(|e| crate::LeesonError::io(format!("failed to write {}", path.display()), e))
+    }
+
+    /// Applies the [`SessionState`] saved at `path`.
+    ///
+    /// The pairs themselves are restored from the configuration's
+    /// selected pairs, so this runs after their tabs have been opened.
+    /// A missing file leaves the app unchanged. An out-of-range tab index
+    /// selects the last tab.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LeesonError::Io`](crate::LeesonError::Io) if the file
+    /// cannot be read, or [`LeesonError::Json`](crate::LeesonError::Json)
+    /// if it is not a valid session state.
+    pub fn restore_session_state(&mut self, path: &Path) -> crate::Result<()> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(crate::LeesonError::io(
+                    format!("failed to read {}", path.display()),
+                    e,
+                ));
+            }
+        };
+        let state: SessionState = serde_json::from_str(&json)?;
+
+        self.chart_timeframe = state.chart_timeframe;
+        self.chart_type = state.chart_type;
+        self.orders_view = state.orders_view;
+        self.active_tab = state.active_tab.min(self.tabs.len() - 1);
+        self.update_focus_for_tab();
+        Ok(())
+    }
+
+    /// Returns the account's total value in USD, using the last traded
+    /// price of each `{ASSET}/USD` ticker.
+    ///
+    /// Assets without a subscribed USD ticker are left out.
+    #[must_use]
+    pub fn total_portfolio_value(&self) -> Decimal {
+        BalanceSnapshot::from(&self.asset_balances, &self.tickers).total_usd_equivalent
+    }
+
+    /// Returns spot holdings as positions keyed by their `{ASSET}/USD` pair.
+    ///
+    /// USD and empty balances are left out.
+    #[must_use]
+    pub fn spot_positions(&self) -> HashMap<String, Decimal> {
+        self.asset_balances
+            .values()
+            .filter(|b| b.asset != "USD" && !b.total.is_zero())
+            .map(|b| (format!("{}/USD", b.asset), b.total))
+            .collect()
+    }
+
+    /// Applies a fill to its pair's [`FillPosition`], adding the P&L
+    /// realized by closing trades (before fees) to `pnl_today` and
+    /// `pnl_total`.
+    ///
+    /// Executions that are not fills, with no `exec_id`, `last_qty` or
+    /// `last_price`, are ignored.
+    pub fn record_fill(&mut self, data: &ExecutionData) {
+        let (Some(_), Some(qty), Some(price)) = (&data.exec_id, data.last_qty, data.last_price)
+        else {
+            return;
+        };
+        if qty <= Decimal::ZERO {
+            return;
+        }
+        let signed_qty = if data.side == "buy" { qty } else { -qty };
+        let position = self.fill_positions.entry(data.symbol.clone()).or_default();
+
+        if data.is_opening_trade(position.qty) {
+            let held = position.qty.abs();
+            position.avg_price = (position.avg_price * held + price * qty) / (held + qty);
+            position.qty += signed_qty;
+        } else if data.is_closing_trade(position.qty) {
+            let closed = qty.min(position.qty.abs());
+            let per_unit = if position.qty > Decimal::ZERO {
+                price - position.avg_price
+            } else {
+                position.avg_price - price
+            };
+            let realized = closed * per_unit;
+            self.pnl_today += realized;
+            self.pnl_total += realized;
+            position.qty += signed_qty;
+            if position.qty.is_zero() {
+                position.avg_price = Decimal::ZERO;
+            } else if qty > closed {
+                // Flipped to the other side; the remainder opened at this price
+                position.avg_price = price;
+            }
+        }
+    }
+
+    /// Returns the unrealized P&L of the positions built from fills,
+    /// valuing longs at the live bid and shorts at the live ask.
+    ///
+    /// Pairs without a ticker are left out.
+    #[must_use]
+    pub fn mark_to_market_pnl(&self) -> Decimal {
+        self.fill_positions
+            .iter()
+            .filter_map(|(symbol, position)| {
+                let ticker = self.tickers.get(symbol)?;
+                if position.qty > Decimal::ZERO {
+                    Some((ticker.bid - position.avg_price) * position.qty)
+                } else if position.qty < Decimal::ZERO {
+                    Some((position.avg_price - ticker.ask) * -position.qty)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the available pairs containing `query`, ignoring case.
+    ///
+    /// An empty query matches every pair.
+    pub fn search_pairs(&self, query: &str) -> Vec<&str> {
+        let query = query.to_lowercase();
+        self.available_pairs
+            .iter()
+            .filter(|pair| pair.to_lowercase().contains(&query))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Searches ticker symbols, open order IDs and client order IDs, and
+    /// agent output lines for `query`, ignoring case.
+    ///
+    /// Results are sorted by relevance, best first; ties keep tickers
+    /// before orders before agent output. An empty query matches nothing.
+    pub fn global_search(&self, query: &str) -> Vec<SearchResult> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut results = Vec::new();
+
+        let mut symbols: Vec<&String> = self.tickers.keys().collect();
+        symbols.sort();
+        for symbol in symbols {
+            if let Some(relevance) = match_relevance(symbol, &query) {
+                results.push(SearchResult {
+                    source: SearchSource::Ticker,
+                    text: format!("{symbol} {}", self.tickers[symbol].last),
+                    relevance,
+                });
+            }
+        }
+
+        let mut orders: Vec<&ExecutionData> = self.open_orders.values().flatten().collect();
+        orders.sort_by(|a, b| a.order_id.cmp(&b.order_id));
+        for order in orders {
+            let relevance = std::iter::once(order.order_id.as_str())
+                .chain(order.cl_ord_id.as_deref())
+                .filter_map(|id| match_relevance(id, &query))
+                .reduce(f32::max);
+            if let Some(relevance) = relevance {
+                results.push(SearchResult {
+                    source: SearchSource::Order,
+                    text: format!(
+                        "{} {} {} {}",
+                        order.order_id, order.symbol, order.side, order.order_status
+                    ),
+                    relevance,
+                });
+            }
+        }
+
+        for (index, lines) in self.agent_outputs.iter().enumerate() {
+            for line in lines {
+                if let Some(relevance) = match_relevance(line, &query) {
+                    results.push(SearchResult {
+                        source: SearchSource::AgentOutput,
+                        text: format!("Agent {}: {line}", index + 1),
+                        relevance,
+                    });
+                }
+            }
+        }
+
+        results.sort_by(|a, b| b.relevance.total_cmp(&a.relevance));
+        results
+    }
+
+    /// Returns the pairs shown in the pair selector, filtered by the
+    /// search query while the pair search overlay is open.
+    pub fn visible_pairs(&self) -> Vec<&str> {
+        if self.mode == Mode::PairSearch {
+            self.search_pairs(self.pair_search.as_str())
+        } else {
+            self.available_pairs.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Samples `pnl_today` into the sparkline history, dropping the oldest
+    /// sample beyond [`MAX_PNL_HISTORY`].
+    pub fn record_pnl(&mut self) {
+        if self.pnl_history.len() >= MAX_PNL_HISTORY {
+            self.pnl_history.pop_front();
+        }
+        self.pnl_history.push_back((Instant::now(), self.pnl_today));
+    }
+
+    /// Recomputes `message_rate` from `message_counter` once at least a
+    /// second has passed since the last sample, then resets the counter.
+    pub fn sample_message_rate(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_rate_sample);
+        if elapsed < MESSAGE_RATE_WINDOW {
+            return;
+        }
+        self.message_rate = self.message_counter as f64 / elapsed.as_secs_f64();
+        self.message_counter = 0;
+        self.last_rate_sample = now;
+    }
+
     /// Adds a line to an agent output panel.
     pub fn add_agent_output(&mut self, agent_index: usize, line: String) {
         if agent_index < 3 {
             let output = &mut self.agent_outputs[agent_index];
-            let was_at_max = output.len() >= MAX_AGENT_OUTPUT_LINES;
+            let was_at_max = output.len() >= self.agent_output_lines;
             if was_at_max {
                 output.pop_front();
                 // Approximate offset adjustment when not pinned
                 let scroll = &mut self.agent_scroll[agent_index];
-                if !scroll.pinned {
+                if !scroll.follows_output() {
                     scroll.offset = scroll.offset.saturating_sub(1);
                 }
             }
             output.push_back(line);
             // When pinned, offset is computed at render time from max_scroll
+            if !matches!(self.current_tab(), Tab::Agent) {
+                self.unread_agent_lines[agent_index] += 1;
+            }
         }
     }
 
+    /// Clears an agent output panel, its partial stream line, and its
+    /// scroll position. Does nothing for an invalid index.
+    pub fn clear_agent_output(&mut self, agent_index: usize) {
+        if agent_index < 3 {
+            self.agent_outputs[agent_index].clear();
+            self.agent_stream_buffers[agent_index].clear();
+            self.agent_scroll[agent_index] = ScrollState {
+                hard_pinned: self.agent_scroll[agent_index].hard_pinned,
+                ..ScrollState::default()
+            };
+        }
+    }
+
+    /// Toggles the persistent pin of an agent output panel. While it is
+    /// set, scrolling up or to the top is ignored and the panel keeps
+    /// following new output. Does nothing for an invalid index.
+    pub fn pin_agent_panel(&mut self, agent_index: usize) {
+        if let Some(scroll) = self.agent_scroll.get_mut(agent_index) {
+            scroll.hard_pinned = !scroll.hard_pinned;
+            scroll.pinned = true;
+            scroll.offset = scroll.max_scroll;
+        }
+    }
+
+    /// Toggles whether an agent may submit orders and returns the new
+    /// paused state, or `None` for an invalid index.
+    pub fn toggle_agent_pause(&mut self, agent_index: usize) -> Option<bool> {
+        let paused = self.agent_paused.get_mut(agent_index)?;
+        *paused = !*paused;
+        Some(*paused)
+    }
+
     /// Appends a streaming text delta to an agent's stream buffer.
     ///
     /// Complete lines (terminated by `\n`) are flushed immediately to
@@ -322,8 +944,9 @@ impl App {
     /// Scrolls an agent output panel up by one line.
     pub fn scroll_agent_up(&mut self, agent_index: usize) {
         if agent_index < 3 {
+            self.refresh_agent_max_scroll(agent_index);
             let scroll = &mut self.agent_scroll[agent_index];
-            if scroll.offset > 0 {
+            if scroll.offset > 0 && !scroll.hard_pinned {
                 scroll.offset -= 1;
                 scroll.pinned = false;
             }
@@ -333,6 +956,7 @@ impl App {
     /// Scrolls an agent output panel down by one visual row.
     pub fn scroll_agent_down(&mut self, agent_index: usize) {
         if agent_index < 3 {
+            self.refresh_agent_max_scroll(agent_index);
             let scroll = &mut self.agent_scroll[agent_index];
             if scroll.offset < scroll.max_scroll {
                 scroll.offset += 1;
@@ -346,7 +970,7 @@ impl App {
 
     /// Scrolls an agent output panel to the top.
     pub fn scroll_agent_top(&mut self, agent_index: usize) {
-        if agent_index < 3 {
+        if agent_index < 3 && !self.agent_scroll[agent_index].hard_pinned {
             let scroll = &mut self.agent_scroll[agent_index];
             scroll.offset = 0;
             scroll.pinned = false;
@@ -362,15 +986,42 @@ impl App {
         }
     }
 
-    /// Sets an error message to display.
-    pub fn show_error(&mut self, message: impl Into<String>) {
-        self.error_message = Some(ErrorDisplay {
+    /// Sets a notification to display in the status bar and records it in
+    /// the notification history.
+    pub fn show_notification(&mut self, message: impl Into<String>, level: NotificationLevel) {
+        let notification = ErrorDisplay {
             message: message.into(),
+            level,
             timestamp: Instant::now(),
-        });
+        };
+        if self.notification_history.len() >= MAX_NOTIFICATION_HISTORY {
+            self.notification_history.pop_front();
+        }
+        self.notification_history.push_back(notification.clone());
+        self.error_message = Some(notification);
     }
 
-    /// Clears error messages older than 5 seconds.
+    /// Forgets every past notification.
+    pub fn clear_notification_history(&mut self) {
+        self.notification_history.clear();
+    }
+
+    /// Sets an informational notification to display.
+    pub fn show_info(&mut self, message: impl Into<String>) {
+        self.show_notification(message, NotificationLevel::Info);
+    }
+
+    /// Sets a warning notification to display.
+    pub fn show_warning(&mut self, message: impl Into<String>) {
+        self.show_notification(message, NotificationLevel::Warning);
+    }
+
+    /// Sets an error message to display.
+    pub fn show_error(&mut self, message: impl Into<String>) {
+        self.show_notification(message, NotificationLevel::Error);
+    }
+
+    /// Clears notifications older than 5 seconds, regardless of level.
     pub fn clear_stale_errors(&mut self) {
         if let Some(ref error) = self.error_message
             && error.timestamp.elapsed() > std::time::Duration::from_secs(5)
@@ -422,8 +1073,21 @@ impl Tab {
     }
 }
 
+/// TUI layout saved on quit and restored at the next start.
+///
+/// Selected pairs are not part of it; they are persisted on every change
+/// by [`AppConfig::save_selected_pairs`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub chart_timeframe: Timeframe,
+    pub chart_type: ChartType,
+    pub orders_view: OrdersView,
+    /// Index into the tabs, where 0 is the agent tab.
+    pub active_tab: usize,
+}
+
 /// Chart display type.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ChartType {
     #[default]
     Candle,
@@ -441,7 +1105,7 @@ impl ChartType {
 }
 
 /// Chart timeframe options.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Timeframe {
     #[default]
     M1,
@@ -479,7 +1143,7 @@ impl Timeframe {
 }
 
 /// Orders view mode.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrdersView {
     #[default]
     Open,
@@ -496,6 +1160,68 @@ impl OrdersView {
     }
 }
 
+/// Where a [`SearchResult`] was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchSource {
+    Ticker,
+    Order,
+    AgentOutput,
+}
+
+impl SearchSource {
+    /// Short label shown next to each result.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchSource::Ticker => "ticker",
+            SearchSource::Order => "order",
+            SearchSource::AgentOutput => "agent",
+        }
+    }
+}
+
+/// A single match from [`App::global_search`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult {
+    pub source: SearchSource,
+    /// Display text for the match.
+    pub text: String,
+    /// 1.0 for an exact match, 0.75 for a prefix and 0.5 for a substring.
+    pub relevance: f32,
+}
+
+/// Scores how well `haystack` matches an already lowercased `query`, or
+/// `None` if it does not contain it.
+fn match_relevance(haystack: &str, query: &str) -> Option<f32> {
+    let haystack = haystack.to_lowercase();
+    if haystack == query {
+        Some(1.0)
+    } else if haystack.starts_with(query) {
+        Some(0.75)
+    } else if haystack.contains(query) {
+        Some(0.5)
+    } else {
+        None
+    }
+}
+
+/// Order book display mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BookView {
+    #[default]
+    Depth,
+    Heatmap,
+}
+
+impl BookView {
+    /// Toggles between views.
+    pub fn toggle(&mut self) {
+        *self = match self {
+            BookView::Depth => BookView::Heatmap,
+            BookView::Heatmap => BookView::Depth,
+        };
+    }
+}
+
 /// UI focus targets.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Focus {
@@ -525,6 +1251,12 @@ pub enum Mode {
     Confirm,
     RiskEdit,
     ApiKeys,
+    CancelAfter,
+    PairSearch,
+    Annotate,
+    EditOrder,
+    NotificationLog,
+    GlobalSearch,
 }
 
 /// Authentication token lifecycle state.
@@ -554,13 +1286,14 @@ impl TokenState {
 }
 
 /// WebSocket connection status.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub enum ConnectionStatus {
     #[default]
     Disconnected,
     Connecting,
     Connected,
-    Reconnecting,
+    /// Reconnecting, with the reason the previous connection ended.
+    Reconnecting(String),
 }
 
 impl ConnectionStatus {
@@ -570,7 +1303,7 @@ impl ConnectionStatus {
             ConnectionStatus::Disconnected => "Offline",
             ConnectionStatus::Connecting => "Connecting...",
             ConnectionStatus::Connected => "Online",
-            ConnectionStatus::Reconnecting => "Reconnecting...",
+            ConnectionStatus::Reconnecting(_) => "Reconnecting...",
         }
     }
 }
@@ -596,6 +1329,14 @@ pub struct OrderBookState {
     pub last_resync_request: Option<Instant>,
 }
 
+impl OrderBookState {
+    /// Returns the best bid and best ask, or `None` if either side is empty.
+    #[must_use]
+    pub fn top_of_book(&self) -> Option<(PriceLevel, PriceLevel)> {
+        Some((self.bids.first()?.clone(), self.asks.first()?.clone()))
+    }
+}
+
 /// A historical snapshot of order book state.
 #[derive(Clone, Debug)]
 pub struct OrderBookSnapshot {
@@ -610,7 +1351,7 @@ pub struct OrderBookSnapshot {
 }
 
 /// An executed trade for display in the all-trades table.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ExecutedTrade {
     /// Timestamp of the trade.
     pub timestamp: String,
@@ -688,6 +1429,62 @@ impl RiskEditState {
     }
 }
 
+/// State for the order edit overlay.
+#[derive(Clone, Debug)]
+pub struct EditOrderState {
+    /// Exchange ID of the order being edited.
+    pub order_id: String,
+    /// Trading pair of the order.
+    pub symbol: String,
+    /// Index of the focused field (0 = price, 1 = quantity).
+    pub selected: usize,
+    /// New limit price.
+    pub price: TextInput,
+    /// New order quantity.
+    pub qty: TextInput,
+}
+
+impl EditOrderState {
+    /// Number of editable fields.
+    pub const FIELD_COUNT: usize = 2;
+
+    /// Creates an edit state pre-filled with the order's price and quantity.
+    pub fn new(order: &ExecutionData) -> Self {
+        let prefilled = |value: String| {
+            let mut input = TextInput::new();
+            for c in value.chars() {
+                input.insert(c);
+            }
+            input
+        };
+        Self {
+            order_id: order.order_id.clone(),
+            symbol: order.symbol.clone(),
+            selected: 0,
+            price: prefilled(order.limit_price.map(|p| p.to_string()).unwrap_or_default()),
+            qty: prefilled(order.order_qty.to_string()),
+        }
+    }
+
+    /// Returns the label for the field at the given index.
+    pub fn field_label(index: usize) -> &'static str {
+        match index {
+            0 => "Limit price",
+            1 => "Quantity",
+            _ => "",
+        }
+    }
+
+    /// Returns the input for the focused field.
+    pub fn selected_input(&mut self) -> &mut TextInput {
+        if self.selected == 0 {
+            &mut self.price
+        } else {
+            &mut self.qty
+        }
+    }
+}
+
 /// State for the API keys edit overlay.
 #[derive(Clone, Debug)]
 pub struct ApiKeysEditState {
@@ -770,11 +1567,25 @@ pub struct ApiKeyField {
     pub new_value: Option<String>,
 }
 
-/// Error message with timestamp for auto-clear.
+/// Severity of a status bar notification.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NotificationLevel {
+    /// Confirmation of a completed action.
+    Info,
+    /// Something the operator should look at, but nothing failed.
+    Warning,
+    /// An operation failed.
+    #[default]
+    Error,
+}
+
+/// Notification with timestamp for auto-clear.
 #[derive(Clone, Debug)]
 pub struct ErrorDisplay {
-    /// The error message.
+    /// The notification text.
     pub message: String,
+    /// How severe the notification is.
+    pub level: NotificationLevel,
     /// When the error was shown.
     pub timestamp: Instant,
 }
@@ -816,6 +1627,15 @@ impl TokenUsageStats {
     }
 }
 
+/// Net quantity held in a pair, built from fills.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FillPosition {
+    /// Net quantity; negative when short.
+    pub qty: Decimal,
+    /// Average entry price of the open quantity.
+    pub avg_price: Decimal,
+}
+
 /// Simulation performance statistics for TUI display.
 #[derive(Clone, Debug, Default)]
 pub struct SimulationStats {
@@ -840,8 +1660,50 @@ pub struct ScrollState {
     pub offset: usize,
     /// Whether the view is pinned to the bottom (auto-scrolls on new content).
     pub pinned: bool,
+    /// Persistent pin set by the user; unlike `pinned`, scrolling up does
+    /// not clear it.
+    pub hard_pinned: bool,
     /// Maximum valid scroll offset (updated each render frame).
     pub max_scroll: usize,
+    /// Inner height of the panel at the last update.
+    pub panel_height: usize,
+    /// Inner width of the panel at the last update.
+    pub panel_width: usize,
+}
+
+impl ScrollState {
+    /// Whether the view should stay at the bottom as new content arrives.
+    pub fn follows_output(&self) -> bool {
+        self.hard_pinned || self.pinned
+    }
+
+    /// Recomputes `max_scroll` for `lines` word-wrapped into a panel of
+    /// `panel_height` rows by `panel_width` columns, returning the total
+    /// number of wrapped rows.
+    ///
+    /// The panel size is remembered so scrolling between renders can
+    /// refresh `max_scroll` for content that arrived in the meantime.
+    pub fn update_max_scroll(
+        &mut self,
+        lines: &[String],
+        panel_height: usize,
+        panel_width: usize,
+    ) -> usize {
+        self.panel_height = panel_height;
+        self.panel_width = panel_width;
+        if panel_width == 0 {
+            self.max_scroll = 0;
+            return 0;
+        }
+
+        let text: Vec<Line> = lines.iter().map(|line| Line::from(line.as_str())).collect();
+        let width = u16::try_from(panel_width).unwrap_or(u16::MAX);
+        let total = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .line_count(width);
+        self.max_scroll = total.saturating_sub(panel_height);
+        total
+    }
 }
 
 impl Default for ScrollState {
@@ -849,28 +1711,86 @@ impl Default for ScrollState {
         Self {
             offset: 0,
             pinned: true,
+            hard_pinned: false,
             max_scroll: 0,
+            panel_height: 0,
+            panel_width: 0,
         }
     }
 }
 
-/// Balance for a single asset.
-#[derive(Clone, Debug)]
-pub struct AssetBalance {
-    /// Asset symbol (e.g., "BTC", "USD").
-    pub asset: String,
-    /// Total balance across all wallets.
-    pub total: Decimal,
-    /// Balance in spot wallet.
-    pub spot: Decimal,
-    /// Balance in earn wallet.
-    pub earn: Decimal,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn test_config(agent_output_lines: usize) -> AppConfig {
+        AppConfig {
+            kraken: crate::config::KrakenConfig {
+                websocket_url: String::new(),
+                api_key: None,
+                api_secret: None,
+            },
+            simulation: false,
+            token_input_cost: None,
+            token_output_cost: None,
+            agent_output_lines,
+            max_reconnect_attempts: None,
+            selected_pairs: Vec::new(),
+            selected_pairs_path: std::path::PathBuf::new(),
+            log_level: crate::config::DEFAULT_LOG_LEVEL.to_string(),
+        }
+    }
+
+    #[test]
+    fn new_with_config_restores_selected_pairs() {
+        let mut config = test_config(DEFAULT_AGENT_OUTPUT_LINES);
+        config.selected_pairs = vec![
+            "BTC/USD".to_string(),
+            "ETH/USD".to_string(),
+            "BTC/USD".to_string(),
+        ];
+
+        let app = App::new_with_config(&config);
+
+        assert_eq!(app.selected_pairs, ["BTC/USD", "ETH/USD"]);
+        assert_eq!(app.tabs.len(), 3);
+    }
+
+    #[test]
+    fn agent_output_respects_configured_cap() {
+        let mut app = App::new_with_config(&test_config(10));
+        for i in 0..10 {
+            app.add_agent_output(0, format!("line {i}"));
+        }
+        assert_eq!(app.agent_outputs[0].len(), 10);
+
+        app.add_agent_output(0, "line 10".to_string());
+
+        assert_eq!(app.agent_outputs[0].len(), 10);
+        assert_eq!(
+            app.agent_outputs[0].front().map(String::as_str),
+            Some("line 1")
+        );
+        assert_eq!(
+            app.agent_outputs[0].back().map(String::as_str),
+            Some("line 10")
+        );
+    }
+
+    #[test]
+    fn agent_output_keeps_more_lines_than_default_when_configured() {
+        let mut app = App::new_with_config(&test_config(500));
+        for i in 0..500 {
+            app.add_agent_output(1, format!("line {i}"));
+        }
+
+        assert_eq!(app.agent_outputs[1].len(), 500);
+        assert_eq!(
+            app.agent_outputs[1].front().map(String::as_str),
+            Some("line 0")
+        );
+    }
+
     #[test]
     fn stream_delta_flushes_complete_lines() {
         let mut app = App::new();
@@ -920,6 +1840,311 @@ mod tests {
         assert_eq!(app.agent_outputs[0].len(), 0);
     }
 
+    #[test]
+    fn clear_agent_output_resets_panel() {
+        let mut app = App::new();
+        app.add_agent_output(1, "line".to_string());
+        app.append_stream_delta(1, "partial");
+        app.agent_scroll[1] = ScrollState {
+            offset: 3,
+            pinned: false,
+            hard_pinned: false,
+            max_scroll: 5,
+            panel_height: 10,
+            panel_width: 40,
+        };
+        app.add_agent_output(2, "untouched".to_string());
+
+        app.clear_agent_output(1);
+
+        assert!(app.agent_outputs[1].is_empty());
+        assert!(app.agent_stream_buffers[1].is_empty());
+        assert_eq!(app.agent_scroll[1].offset, 0);
+        assert!(app.agent_scroll[1].pinned);
+        assert_eq!(app.agent_outputs[2].len(), 1);
+    }
+
+    #[test]
+    fn pin_agent_panel_toggles_persistent_pin() {
+        let mut app = App::new();
+        app.agent_scroll[0].pinned = false;
+
+        app.pin_agent_panel(0);
+        assert!(app.agent_scroll[0].hard_pinned);
+        assert!(app.agent_scroll[0].pinned);
+
+        app.pin_agent_panel(0);
+        assert!(!app.agent_scroll[0].hard_pinned);
+
+        app.pin_agent_panel(5);
+        assert!(app.agent_scroll.iter().all(|s| !s.hard_pinned));
+    }
+
+    #[test]
+    fn hard_pinned_panel_follows_new_lines_despite_scrolling() {
+        let mut app = App::new();
+        for i in 0..20 {
+            app.add_agent_output(0, format!("line {i}"));
+        }
+        app.agent_scroll[0].update_max_scroll(&app.agent_panel_lines(0), 5, 40);
+        app.pin_agent_panel(0);
+
+        app.scroll_agent_up(0);
+        app.scroll_agent_top(0);
+        assert!(app.agent_scroll[0].follows_output());
+        assert_eq!(app.agent_scroll[0].offset, app.agent_scroll[0].max_scroll);
+
+        app.add_agent_output(0, "line 20".to_string());
+        app.append_stream_delta(0, "partial");
+        assert!(app.agent_scroll[0].follows_output());
+        assert!(
+            app.agent_panel_lines(0)
+                .last()
+                .unwrap()
+                .starts_with("partial")
+        );
+    }
+
+    #[test]
+    fn clear_agent_output_ignores_invalid_index() {
+        let mut app = App::new();
+        app.add_agent_output(0, "line".to_string());
+
+        app.clear_agent_output(3);
+
+        assert_eq!(app.agent_outputs[0].len(), 1);
+    }
+
+    #[test]
+    fn update_max_scroll_counts_wrapped_rows() {
+        let mut scroll = ScrollState::default();
+        let lines = vec!["short".to_string(), "x".repeat(25)];
+
+        let total = scroll.update_max_scroll(&lines, 2, 10);
+
+        assert_eq!(total, 4);
+        assert_eq!(scroll.max_scroll, 2);
+    }
+
+    #[test]
+    fn scroll_down_sees_output_added_since_last_render() {
+        let mut app = App::new();
+        app.add_agent_output(0, "first".to_string());
+        let lines = app.agent_panel_lines(0);
+        app.agent_scroll[0].update_max_scroll(&lines, 2, 10);
+        app.scroll_agent_top(0);
+        assert_eq!(app.agent_scroll[0].max_scroll, 0);
+
+        // A long line wraps onto three extra rows before the next render
+        app.add_agent_output(0, "y".repeat(30));
+        app.scroll_agent_down(0);
+
+        assert_eq!(app.agent_scroll[0].max_scroll, 2);
+        assert_eq!(app.agent_scroll[0].offset, 1);
+        assert!(!app.agent_scroll[0].pinned);
+    }
+
+    #[test]
+    fn agent_panel_lines_include_stream_only_when_pinned() {
+        let mut app = App::new();
+        app.add_agent_output(2, "done".to_string());
+        app.append_stream_delta(2, "partial");
+
+        assert_eq!(
+            app.agent_panel_lines(2),
+            vec!["done".to_string(), format!("partial{STREAM_CURSOR}")]
+        );
+
+        app.agent_scroll[2].pinned = false;
+        assert_eq!(app.agent_panel_lines(2), vec!["done".to_string()]);
+    }
+
+    #[test]
+    fn filtered_agent_output_matches_subset() {
+        let mut app = App::new();
+        app.add_agent_output(1, "BTC/USD spread widening".to_string());
+        app.add_agent_output(1, "ETH/USD quiet".to_string());
+        app.add_agent_output(1, "btc volume spike".to_string());
+
+        app.agent_output_filter[1] = Some("BTC".to_string());
+        assert_eq!(
+            app.filtered_agent_output(1),
+            vec!["BTC/USD spread widening", "btc volume spike"]
+        );
+
+        app.agent_output_filter[1] = Some(String::new());
+        assert_eq!(app.filtered_agent_output(1).len(), 3);
+    }
+
+    #[test]
+    fn export_trade_history_writes_json_array() {
+        let mut app = App::new();
+        app.executed_trades_all.push_back(ExecutedTrade {
+            timestamp: "2024-01-01T12:00:00Z".to_string(),
+            symbol: "BTC/USD".to_string(),
+            side: "buy".to_string(),
+            qty: Decimal::new(5, 1),
+            price: Decimal::new(42000, 0),
+            pnl: None,
+        });
+        app.executed_trades_all.push_back(ExecutedTrade {
+            timestamp: "2024-01-01T13:00:00Z".to_string(),
+            symbol: "BTC/USD".to_string(),
+            side: "sell".to_string(),
+            qty: Decimal::new(5, 1),
+            price: Decimal::new(43000, 0),
+            pnl: Some(Decimal::new(500, 0)),
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("exports/session/trades.json");
+        app.export_trade_history(&path).unwrap();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        let trades: Vec<ExecutedTrade> = serde_json::from_str(&json).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].side, "buy");
+        assert_eq!(trades[0].price, Decimal::new(42000, 0));
+        assert_eq!(trades[0].pnl, None);
+        assert_eq!(trades[1].timestamp, "2024-01-01T13:00:00Z");
+        assert_eq!(trades[1].pnl, Some(Decimal::new(500, 0)));
+    }
+
+    fn backdate_notification(app: &mut App, secs: u64) {
+        let notification = app.error_message.as_mut().unwrap();
+        notification.timestamp = Instant::now() - std::time::Duration::from_secs(secs);
+    }
+
+    #[test]
+    fn clear_stale_errors_keeps_fresh_notifications_of_any_level() {
+        let mut app = App::new();
+        for level in [
+            NotificationLevel::Info,
+            NotificationLevel::Warning,
+            NotificationLevel::Error,
+        ] {
+            app.show_notification("fresh", level);
+            backdate_notification(&mut app, 4);
+            app.clear_stale_errors();
+            assert_eq!(app.error_message.as_ref().map(|e| e.level), Some(level));
+        }
+    }
+
+    #[test]
+    fn clear_stale_errors_removes_old_notifications_of_any_level() {
+        let mut app = App::new();
+
+        app.show_info("saved");
+        backdate_notification(&mut app, 6);
+        app.clear_stale_errors();
+        assert!(app.error_message.is_none());
+
+        app.show_warning("unchanged");
+        backdate_notification(&mut app, 6);
+        app.clear_stale_errors();
+        assert!(app.error_message.is_none());
+
+        app.show_error("failed");
+        backdate_notification(&mut app, 6);
+        app.clear_stale_errors();
+        assert!(app.error_message.is_none());
+    }
+
+    #[test]
+    fn newer_notification_replaces_older_level() {
+        let mut app = App::new();
+        app.show_error("failed");
+        app.show_info("saved");
+
+        let notification = app.error_message.as_ref().unwrap();
+        assert_eq!(notification.level, NotificationLevel::Info);
+        assert_eq!(notification.message, "saved");
+    }
+
+    #[test]
+    fn search_pairs_ignores_case() {
+        let app = App::new();
+        assert_eq!(app.search_pairs("eth"), vec!["ETH/USD"]);
+        assert_eq!(app.search_pairs("Do"), vec!["DOGE/USD", "DOT/USD"]);
+    }
+
+    #[test]
+    fn search_pairs_empty_query_matches_all() {
+        let app = App::new();
+        assert_eq!(app.search_pairs("").len(), app.available_pairs.len());
+    }
+
+    #[test]
+    fn search_pairs_without_match_is_empty() {
+        let app = App::new();
+        assert!(app.search_pairs("EUR").is_empty());
+    }
+
+    #[test]
+    fn global_search_ranks_matches_across_sources() {
+        let ticker = |symbol: &str, last: &str| -> TickerData {
+            serde_json::from_value(serde_json::json!({
+                "symbol": symbol,
+                "bid": last, "bid_qty": "1", "ask": last, "ask_qty": "1", "last": last
+            }))
+            .unwrap()
+        };
+        let mut app = App::new();
+        app.update_ticker("BTC/USD".to_string(), ticker("BTC/USD", "50000"));
+        app.update_ticker("ETH/USD".to_string(), ticker("ETH/USD", "3000"));
+        app.open_orders.insert(
+            "BTC/USD".to_string(),
+            vec![
+                serde_json::from_value(serde_json::json!({
+                    "exec_type": "new",
+                    "order_id": "OABC-123",
+                    "cl_ord_id": "btc-entry",
+                    "symbol": "BTC/USD",
+                    "side": "buy",
+                    "order_type": "limit",
+                    "order_qty": "1",
+                    "order_status": "new",
+                    "timestamp": "2024-01-01T00:00:00Z"
+                }))
+                .unwrap(),
+            ],
+        );
+        app.add_agent_output(0, "watching btc/usd for a breakout".to_string());
+
+        let results = app.global_search("BTC/USD");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].source, SearchSource::Ticker);
+        assert_eq!(results[0].relevance, 1.0);
+        assert_eq!(results[1].source, SearchSource::AgentOutput);
+
+        let results = app.global_search("btc");
+        let sources: Vec<_> = results.iter().map(|r| r.source).collect();
+        assert_eq!(
+            sources,
+            [
+                SearchSource::Ticker,
+                SearchSource::Order,
+                SearchSource::AgentOutput
+            ]
+        );
+        assert_eq!(results[1].relevance, 0.75);
+
+        assert_eq!(app.global_search("abc-1")[0].source, SearchSource::Order);
+        assert!(app.global_search("  ").is_empty());
+        assert!(app.global_search("xrp").is_empty());
+    }
+
+    #[test]
+    fn visible_pairs_filtered_only_while_searching() {
+        let mut app = App::new();
+        app.pair_search.insert('s');
+        app.pair_search.insert('o');
+        assert_eq!(app.visible_pairs().len(), app.available_pairs.len());
+
+        app.mode = Mode::PairSearch;
+        assert_eq!(app.visible_pairs(), vec!["SOL/USD"]);
+    }
+
     #[test]
     fn stream_delta_ignores_invalid_agent_index() {
         let mut app = App::new();
@@ -929,4 +2154,385 @@ mod tests {
             assert!(app.agent_outputs[i].is_empty());
         }
     }
+
+    #[test]
+    fn annotations_are_keyed_by_symbol_and_candle() {
+        let mut app = App::new();
+        app.add_annotation("BTC/USD", "2024-01-01T00:00:00Z", "breakout entry");
+
+        assert_eq!(
+            app.get_annotation("BTC/USD", "2024-01-01T00:00:00Z"),
+            Some("breakout entry")
+        );
+        assert_eq!(app.get_annotation("ETH/USD", "2024-01-01T00:00:00Z"), None);
+        assert_eq!(app.get_annotation("BTC/USD", "2024-01-01T00:01:00Z"), None);
+
+        // Adding again replaces the note
+        app.add_annotation("BTC/USD", "2024-01-01T00:00:00Z", "exit");
+        assert_eq!(
+            app.get_annotation("BTC/USD", "2024-01-01T00:00:00Z"),
+            Some("exit")
+        );
+        assert_eq!(app.annotation_store.len(), 1);
+    }
+
+    #[test]
+    fn remove_annotation_returns_note() {
+        let mut app = App::new();
+        app.add_annotation("BTC/USD", "2024-01-01T00:00:00Z", "entry");
+
+        assert_eq!(
+            app.remove_annotation("BTC/USD", "2024-01-01T00:00:00Z"),
+            Some("entry".to_string())
+        );
+        assert_eq!(app.get_annotation("BTC/USD", "2024-01-01T00:00:00Z"), None);
+        assert_eq!(
+            app.remove_annotation("BTC/USD", "2024-01-01T00:00:00Z"),
+            None
+        );
+    }
+
+    #[test]
+    fn message_rate_is_sampled_once_per_window() {
+        let mut app = App::new();
+        let start = app.last_rate_sample;
+        app.message_counter = 30;
+
+        app.sample_message_rate(start + std::time::Duration::from_millis(500));
+        assert_eq!(app.message_rate, 0.0);
+        assert_eq!(app.message_counter, 30);
+
+        app.sample_message_rate(start + std::time::Duration::from_secs(2));
+        assert_eq!(app.message_rate, 15.0);
+        assert_eq!(app.message_counter, 0);
+        assert_eq!(
+            app.last_rate_sample,
+            start + std::time::Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn pnl_history_keeps_most_recent_samples() {
+        let mut app = App::new();
+        for i in 0..MAX_PNL_HISTORY + 5 {
+            app.pnl_today = Decimal::from(i);
+            app.record_pnl();
+        }
+
+        assert_eq!(app.pnl_history.len(), MAX_PNL_HISTORY);
+        assert_eq!(
+            app.pnl_history.front().map(|(_, pnl)| *pnl),
+            Some(Decimal::from(5))
+        );
+        assert_eq!(
+            app.pnl_history.back().map(|(_, pnl)| *pnl),
+            Some(Decimal::from(MAX_PNL_HISTORY + 4))
+        );
+    }
+
+    #[test]
+    fn open_order_id_batches_respect_batch_cancel_limit() {
+        let mut app = App::new();
+        let orders = (0..120)
+            .map(|i| {
+                serde_json::from_value(serde_json::json!({
+                    "order_id": format!("O{i:05}"),
+                    "symbol": "BTC/USD",
+                    "side": "sell",
+                    "order_type": "limit",
+                    "order_qty": "1",
+                    "order_status": "new",
+                    "exec_type": "new",
+                    "timestamp": "2024-01-01T00:00:00Z"
+                }))
+                .unwrap()
+            })
+            .collect();
+        app.open_orders.insert("BTC/USD".to_string(), orders);
+
+        let batches = app.open_order_id_batches("BTC/USD");
+
+        let sizes: Vec<usize> = batches.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [50, 50, 20]);
+        assert_eq!(batches[2].last().map(String::as_str), Some("O00119"));
+        assert!(app.open_order_id_batches("ETH/USD").is_empty());
+    }
+
+    #[test]
+    fn unread_agent_lines_reset_when_agent_tab_is_focused() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+
+        // Output seen on the agent tab is already read
+        app.add_agent_output(0, "seen".to_string());
+        assert_eq!(app.unread_agent_lines, [0, 0, 0]);
+
+        app.select_pair_tab(0);
+        app.add_agent_output(0, "one".to_string());
+        app.add_agent_output(2, "two".to_string());
+        app.add_agent_output(2, "three".to_string());
+        assert_eq!(app.unread_agent_lines, [1, 0, 2]);
+        assert_eq!(app.tab_badge(&Tab::Agent), 3);
+
+        app.next_tab();
+        assert_eq!(app.current_tab(), &Tab::Agent);
+        assert_eq!(app.tab_badge(&Tab::Agent), 0);
+    }
+
+    #[test]
+    fn pair_tab_badge_counts_open_orders() {
+        let mut app = App::new();
+        let order = serde_json::from_value::<ExecutionData>(serde_json::json!({
+            "order_id": "O1",
+            "symbol": "BTC/USD",
+            "side": "buy",
+            "order_type": "limit",
+            "order_qty": "1",
+            "order_status": "new",
+            "exec_type": "new",
+            "timestamp": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        app.open_orders
+            .insert("BTC/USD".to_string(), vec![order.clone(), order]);
+
+        assert_eq!(app.tab_badge(&Tab::TradingPair("BTC/USD".to_string())), 2);
+        assert_eq!(app.tab_badge(&Tab::TradingPair("ETH/USD".to_string())), 0);
+    }
+
+    #[test]
+    fn notification_history_keeps_most_recent() {
+        let mut app = App::new();
+        for i in 0..MAX_NOTIFICATION_HISTORY + 3 {
+            app.show_info(format!("note {i}"));
+        }
+
+        assert_eq!(app.notification_history.len(), MAX_NOTIFICATION_HISTORY);
+        assert_eq!(
+            app.notification_history.front().map(|n| n.message.as_str()),
+            Some("note 3")
+        );
+
+        app.clear_notification_history();
+        assert!(app.notification_history.is_empty());
+        // The status bar notification is unaffected
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn close_current_pair_tab_removes_pair_and_tab() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.toggle_pair("ETH/USD");
+        app.select_pair_tab(0);
+
+        assert_eq!(app.close_current_pair_tab().as_deref(), Some("BTC/USD"));
+        assert_eq!(app.selected_pairs, ["ETH/USD"]);
+        assert_eq!(app.tabs.len(), 2);
+        assert_eq!(app.current_tab(), &Tab::TradingPair("ETH/USD".to_string()));
+        assert_eq!(app.focus, Focus::OrderBook);
+
+        // Closing the last pair falls back to the agent tab
+        assert_eq!(app.close_current_pair_tab().as_deref(), Some("ETH/USD"));
+        assert_eq!(app.current_tab(), &Tab::Agent);
+        assert_eq!(app.focus, Focus::AgentInput);
+    }
+
+    #[test]
+    fn close_current_pair_tab_keeps_agent_tab() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+
+        assert!(app.close_current_pair_tab().is_none());
+        assert_eq!(app.tabs.len(), 2);
+        assert_eq!(app.selected_pairs, ["BTC/USD"]);
+    }
+
+    #[test]
+    fn record_fill_realizes_pnl_on_closing_trades() {
+        let fill = |side: &str, qty: &str, price: &str| -> ExecutionData {
+            serde_json::from_value(serde_json::json!({
+                "order_id": "O1",
+                "exec_id": "E1",
+                "symbol": "BTC/USD",
+                "side": side,
+                "order_type": "market",
+                "order_qty": qty,
+                "order_status": "filled",
+                "exec_type": "trade",
+                "last_qty": qty,
+                "last_price": price,
+                "timestamp": "2024-01-01T00:00:00Z"
+            }))
+            .unwrap()
+        };
+        let mut app = App::new();
+
+        app.record_fill(&fill("buy", "1", "100"));
+        app.record_fill(&fill("buy", "1", "200"));
+        assert_eq!(app.pnl_total, Decimal::ZERO);
+        assert_eq!(
+            app.fill_positions["BTC/USD"],
+            FillPosition {
+                qty: Decimal::from(2),
+                avg_price: Decimal::from(150),
+            }
+        );
+
+        // Sells 2 long at +50 each, then opens 1 short at 200
+        app.record_fill(&fill("sell", "3", "200"));
+        assert_eq!(app.pnl_total, Decimal::from(100));
+        assert_eq!(app.pnl_today, Decimal::from(100));
+        assert_eq!(
+            app.fill_positions["BTC/USD"],
+            FillPosition {
+                qty: Decimal::from(-1),
+                avg_price: Decimal::from(200),
+            }
+        );
+
+        // Covering the short above its entry loses money
+        app.record_fill(&fill("buy", "1", "250"));
+        assert_eq!(app.pnl_total, Decimal::from(50));
+        assert_eq!(app.fill_positions["BTC/USD"], FillPosition::default());
+    }
+
+    #[test]
+    fn mark_to_market_values_longs_at_bid_and_shorts_at_ask() {
+        let fill = |symbol: &str, side: &str, qty: &str, price: &str| -> ExecutionData {
+            serde_json::from_value(serde_json::json!({
+                "order_id": "O1",
+                "exec_id": "E1",
+                "symbol": symbol,
+                "side": side,
+                "order_type": "market",
+                "order_qty": qty,
+                "order_status": "filled",
+                "exec_type": "filled",
+                "last_qty": qty,
+                "last_price": price,
+                "timestamp": "2024-01-01T00:00:00Z"
+            }))
+            .unwrap()
+        };
+        let ticker = |symbol: &str, bid: &str, ask: &str| -> TickerData {
+            serde_json::from_value(serde_json::json!({
+                "symbol": symbol,
+                "bid": bid, "bid_qty": "1", "ask": ask, "ask_qty": "1", "last": bid
+            }))
+            .unwrap()
+        };
+        let mut app = App::new();
+        app.record_fill(&fill("BTC/USD", "buy", "1", "100"));
+        app.record_fill(&fill("BTC/USD", "buy", "1", "200"));
+        app.record_fill(&fill("ETH/USD", "sell", "2", "50"));
+        app.record_fill(&fill("SOL/USD", "buy", "10", "20"));
+        assert_eq!(app.fill_positions["BTC/USD"].qty, Decimal::from(2));
+
+        app.update_ticker("BTC/USD".to_string(), ticker("BTC/USD", "160", "170"));
+        app.update_ticker("ETH/USD".to_string(), ticker("ETH/USD", "40", "45"));
+
+        // BTC: (160 - 150) * 2 = 20, ETH: (50 - 45) * 2 = 10, SOL has no ticker
+        assert_eq!(app.mark_to_market_pnl(), Decimal::from(30));
+    }
+
+    #[test]
+    fn session_state_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.toggle_pair("ETH/USD");
+        app.chart_timeframe = Timeframe::H4;
+        app.chart_type = ChartType::Line;
+        app.orders_view = OrdersView::Executed;
+        app.active_tab = 2;
+        app.save_session_state(&path).unwrap();
+
+        let mut restored = App::new();
+        restored.toggle_pair("BTC/USD");
+        restored.toggle_pair("ETH/USD");
+        restored.restore_session_state(&path).unwrap();
+
+        assert_eq!(restored.current_tab(), &Tab::TradingPair("ETH/USD".into()));
+        assert_eq!(restored.focus, Focus::OrderBook);
+        assert_eq!(restored.chart_timeframe, Timeframe::H4);
+        assert_eq!(restored.chart_type, ChartType::Line);
+        assert_eq!(restored.orders_view, OrdersView::Executed);
+    }
+
+    #[test]
+    fn missing_session_state_starts_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new();
+
+        app.restore_session_state(&dir.path().join("session.json"))
+            .unwrap();
+
+        assert!(app.selected_pairs.is_empty());
+        assert_eq!(app.current_tab(), &Tab::Agent);
+        assert_eq!(app.chart_timeframe, Timeframe::M1);
+    }
+
+    #[test]
+    fn toggle_pair_never_duplicates_a_pair() {
+        let mut app = App::new();
+
+        app.toggle_pair("BTC/USD");
+        app.toggle_pair("BTC/USD");
+        assert!(app.selected_pairs.is_empty());
+        assert_eq!(app.tabs, [Tab::Agent]);
+
+        app.toggle_pair("BTC/USD");
+        app.toggle_pair("ETH/USD");
+        app.toggle_pair("BTC/USD");
+        app.toggle_pair("BTC/USD");
+        assert_eq!(app.selected_pairs, ["ETH/USD", "BTC/USD"]);
+        let btc_tabs = app
+            .tabs
+            .iter()
+            .filter(|t| matches!(t, Tab::TradingPair(s) if s == "BTC/USD"))
+            .count();
+        assert_eq!(btc_tabs, 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "has a tab but was not selected")]
+    fn toggle_pair_asserts_on_stray_tab() {
+        let mut app = App::new();
+        app.tabs.push(Tab::TradingPair("BTC/USD".to_string()));
+
+        app.toggle_pair("BTC/USD");
+    }
+
+    #[test]
+    fn estimated_cost_sums_both_rates() {
+        let usage = TokenUsageStats {
+            input_tokens: 2_000_000,
+            output_tokens: 500_000,
+            input_cost_per_million: Some(Decimal::from(3)),
+            output_cost_per_million: Some(Decimal::from(15)),
+        };
+
+        assert_eq!(usage.estimated_cost(), Some(Decimal::new(135, 1)));
+    }
+
+    #[test]
+    fn estimated_cost_with_one_rate_prices_that_side_only() {
+        let usage = TokenUsageStats {
+            input_tokens: 2_000_000,
+            output_tokens: 500_000,
+            input_cost_per_million: None,
+            output_cost_per_million: Some(Decimal::from(15)),
+        };
+        assert_eq!(usage.estimated_cost(), Some(Decimal::new(75, 1)));
+
+        let unpriced = TokenUsageStats {
+            input_cost_per_million: None,
+            output_cost_per_million: None,
+            ..usage
+        };
+        assert_eq!(unpriced.estimated_cost(), None);
+    }
 }
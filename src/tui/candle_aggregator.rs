@@ -0,0 +1,190 @@
+//! Client-side down-sampling of 1-minute candles into higher timeframes.
+//!
+//! Used when the chart shows a timeframe above 1m but the candle feed
+//! still delivers 1-minute bars.
+
+use std::collections::VecDeque;
+
+use rust_decimal::Decimal;
+
+use super::app::Timeframe;
+use crate::models::candle::CandleData;
+
+/// Merges 1-minute candles into bars of a coarser [`Timeframe`].
+pub struct CandleAggregator;
+
+impl CandleAggregator {
+    /// Groups `candles` (oldest first) into `timeframe` bars.
+    ///
+    /// Bars are aligned to the start of the UTC day, so a 5m bar covers
+    /// minutes 00-04, 05-09 and so on. Repeated updates of the same minute
+    /// replace each other rather than being counted twice. Candles already
+    /// at or above the target interval, or with an unparseable
+    /// `interval_begin`, are passed through unchanged.
+    #[must_use]
+    pub fn aggregate(candles: &VecDeque<CandleData>, timeframe: Timeframe) -> Vec<CandleData> {
+        let target = u64::try_from(timeframe.interval()).unwrap_or(1);
+        let mut bars: Vec<CandleData> = Vec::new();
+        // The minute candles merged into the last bar, latest update only
+        let mut minutes: Vec<&CandleData> = Vec::new();
+        let mut current: Option<(String, u64)> = None;
+
+        for candle in candles {
+            let bucket = (candle.interval < target)
+                .then(|| bucket_start(&candle.interval_begin, target))
+                .flatten();
+            let Some(bucket) = bucket else {
+                current = None;
+                minutes.clear();
+                bars.push(candle.clone());
+                continue;
+            };
+
+            if current.as_ref() != Some(&bucket) {
+                current = Some(bucket.clone());
+                minutes.clear();
+                bars.push(candle.clone());
+            }
+
+            match minutes.last_mut() {
+                Some(last) if last.interval_begin == candle.interval_begin => *last = candle,
+                _ => minutes.push(candle),
+            }
+
+            let bar = bars.last_mut().expect("a bar was pushed for this bucket");
+            *bar = merge(&minutes, &bucket, target);
+        }
+
+        bars
+    }
+}
+
+/// Returns the date and bar-start minute of the `target`-minute bar
+/// containing `interval_begin` (e.g. `2024-01-01T14:37:00Z`).
+fn bucket_start(interval_begin: &str, target: u64) -> Option<(String, u64)> {
+    let date = interval_begin.get(..10)?;
+    let hours: u64 = interval_begin.get(11..13)?.parse().ok()?;
+    let minutes: u64 = interval_begin.get(14..16)?.parse().ok()?;
+    let minute_of_day = hours * 60 + minutes;
+    Some((date.to_string(), minute_of_day - minute_of_day % target))
+}
+
+/// Merges the minute candles of one bar into a single OHLCV candle.
+fn merge(minutes: &[&CandleData], bucket: &(String, u64), target: u64) -> CandleData {
+    let first = minutes[0];
+    let last = minutes[minutes.len() - 1];
+    let volume: Decimal = minutes.iter().map(|c| c.volume).sum();
+    let vwap = if volume.is_zero() {
+        last.vwap
+    } else {
+        minutes.iter().map(|c| c.vwap * c.volume).sum::<Decimal>() / volume
+    };
+    let (date, start) = bucket;
+
+    CandleData {
+        symbol: first.symbol.clone(),
+        open: first.open,
+        high: minutes.iter().map(|c| c.high).max().unwrap_or(first.high),
+        low: minutes.iter().map(|c| c.low).min().unwrap_or(first.low),
+        close: last.close,
+        vwap,
+        trades: minutes.iter().map(|c| c.trades).sum(),
+        volume,
+        interval_begin: format!("{date}T{:02}:{:02}:00.000000000Z", start / 60, start % 60),
+        interval: target,
+        timestamp: last.timestamp.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn minute(begin: &str, open: &str, high: &str, low: &str, close: &str) -> CandleData {
+        serde_json::from_value(serde_json::json!({
+            "symbol": "BTC/USD",
+            "open": open, "high": high, "low": low, "close": close,
+            "vwap": close, "trades": 2, "volume": "1",
+            "interval_begin": begin,
+            "interval": 1,
+            "timestamp": begin
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn five_minutes_make_one_m5_bar() {
+        let candles: VecDeque<_> = [
+            minute("2024-01-01T10:00:00.000000000Z", "100", "104", "99", "103"),
+            minute("2024-01-01T10:01:00.000000000Z", "103", "108", "102", "107"),
+            minute("2024-01-01T10:02:00.000000000Z", "107", "107", "95", "96"),
+            minute("2024-01-01T10:03:00.000000000Z", "96", "101", "96", "100"),
+            minute("2024-01-01T10:04:00.000000000Z", "100", "102", "98", "101"),
+        ]
+        .into();
+
+        let bars = CandleAggregator::aggregate(&candles, Timeframe::M5);
+
+        assert_eq!(bars.len(), 1);
+        let bar = &bars[0];
+        assert_eq!(bar.open, dec!(100));
+        assert_eq!(bar.high, dec!(108));
+        assert_eq!(bar.low, dec!(95));
+        assert_eq!(bar.close, dec!(101));
+        assert_eq!(bar.volume, dec!(5));
+        assert_eq!(bar.trades, 10);
+        assert_eq!(bar.vwap, dec!(101.4));
+        assert_eq!(bar.interval, 5);
+        assert_eq!(bar.interval_begin, "2024-01-01T10:00:00.000000000Z");
+        assert_eq!(bar.timestamp, "2024-01-01T10:04:00.000000000Z");
+    }
+
+    #[test]
+    fn bars_split_on_timeframe_boundaries() {
+        let candles: VecDeque<_> = [
+            minute("2024-01-01T10:03:00.000000000Z", "1", "1", "1", "1"),
+            minute("2024-01-01T10:04:00.000000000Z", "2", "2", "2", "2"),
+            minute("2024-01-01T10:05:00.000000000Z", "3", "3", "3", "3"),
+        ]
+        .into();
+
+        let bars = CandleAggregator::aggregate(&candles, Timeframe::M5);
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].interval_begin, "2024-01-01T10:00:00.000000000Z");
+        assert_eq!(bars[0].close, dec!(2));
+        assert_eq!(bars[1].interval_begin, "2024-01-01T10:05:00.000000000Z");
+        assert_eq!(bars[1].open, dec!(3));
+    }
+
+    #[test]
+    fn updates_of_the_same_minute_are_not_double_counted() {
+        let candles: VecDeque<_> = [
+            minute("2024-01-01T10:00:00.000000000Z", "100", "101", "100", "101"),
+            minute("2024-01-01T10:00:00.000000000Z", "100", "102", "99", "102"),
+        ]
+        .into();
+
+        let bars = CandleAggregator::aggregate(&candles, Timeframe::M5);
+
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].volume, dec!(1));
+        assert_eq!(bars[0].low, dec!(99));
+        assert_eq!(bars[0].close, dec!(102));
+    }
+
+    #[test]
+    fn m1_timeframe_passes_candles_through() {
+        let candles: VecDeque<_> = [
+            minute("2024-01-01T10:00:00.000000000Z", "1", "1", "1", "1"),
+            minute("2024-01-01T10:01:00.000000000Z", "2", "2", "2", "2"),
+        ]
+        .into();
+
+        let bars = CandleAggregator::aggregate(&candles, Timeframe::M1);
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[1].interval_begin, "2024-01-01T10:01:00.000000000Z");
+    }
+}
@@ -0,0 +1,135 @@
+//! Confirmation modal component.
+//!
+//! A centered box with a highlighted prompt, one `key: value` row per
+//! detail and the `[Y] Confirm [N] Cancel` hint underneath. Keys are padded
+//! to a common width so the values line up.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Flex, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Width of the modal as a percentage of `area`.
+const WIDTH_PERCENT: u16 = 60;
+
+/// Height of the modal as a percentage of `area`.
+const HEIGHT_PERCENT: u16 = 40;
+
+/// Draws the modal centered in `area`, clearing whatever is behind it.
+pub fn render(frame: &mut Frame, area: Rect, prompt: &str, details: &[(&str, &str)]) {
+    let dialog = centered(area);
+    frame.render_widget(Clear, dialog);
+
+    let key_width = details.iter().map(|(key, _)| key.len()).max().unwrap_or(0) + 2;
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            prompt.to_string(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(details.iter().map(|(key, value)| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<key_width$}", format!("{key}:")),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(value.to_string()),
+        ])
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "[Y] ",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("Confirm   "),
+        Span::styled(
+            "[N] ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("Cancel"),
+    ]));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Confirm ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, dialog);
+}
+
+/// Returns the modal's rectangle, centered in `area`.
+fn centered(area: Rect) -> Rect {
+    let [row] = Layout::vertical([Constraint::Percentage(HEIGHT_PERCENT)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [dialog] = Layout::horizontal([Constraint::Percentage(WIDTH_PERCENT)])
+        .flex(Flex::Center)
+        .areas(row);
+    dialog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn draw(width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| {
+                render(
+                    frame,
+                    frame.area(),
+                    "Order Confirmation Required",
+                    &[("Symbol", "BTC/USD"), ("Qty", "0.5")],
+                )
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_at_any_size() {
+        for (width, height) in [(0, 0), (1, 1), (5, 3), (20, 4), (80, 24), (300, 100)] {
+            draw(width, height);
+        }
+    }
+
+    #[test]
+    fn aligns_detail_values() {
+        let rows = draw(80, 30);
+
+        let symbol = rows.iter().find(|row| row.contains("Symbol:")).unwrap();
+        let qty = rows.iter().find(|row| row.contains("Qty:")).unwrap();
+        assert_eq!(symbol.find("BTC/USD"), qty.find("0.5"));
+        assert!(rows.iter().any(|row| row.contains("[Y] Confirm")));
+    }
+
+    #[test]
+    fn modal_is_centered() {
+        let dialog = centered(Rect::new(0, 0, 100, 50));
+        assert_eq!(dialog, Rect::new(20, 15, 60, 20));
+    }
+}
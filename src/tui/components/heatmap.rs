@@ -0,0 +1,193 @@
+//! Order book heat-map component.
+//!
+//! Divides the price range covered by the book into buckets, one row each
+//! with the highest prices on top, and shades every row by the quantity
+//! resting in it: dark blue for thin buckets up to bright red for the
+//! thickest one.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::models::book::PriceLevel;
+
+/// Hue of the thinnest bucket (blue), in degrees.
+const COLD_HUE: f64 = 240.0;
+
+/// Lightness of the thinnest bucket.
+const COLD_LIGHTNESS: f64 = 0.2;
+
+/// Lightness of the thickest bucket.
+const HOT_LIGHTNESS: f64 = 0.5;
+
+/// Renders `resolution` price buckets spanning every bid and ask level.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    bids: &[PriceLevel],
+    asks: &[PriceLevel],
+    resolution: usize,
+) {
+    let Some((low, high)) = price_range(bids, asks) else {
+        return;
+    };
+    let bid_qty = bucket_quantities(bids, low, high, resolution);
+    let ask_qty = bucket_quantities(asks, low, high, resolution);
+    let totals: Vec<Decimal> = bid_qty.iter().zip(&ask_qty).map(|(b, a)| b + a).collect();
+    let max_qty = totals.iter().copied().max().unwrap_or(Decimal::ZERO);
+    let width = usize::from(area.width).saturating_sub(13);
+
+    let lines: Vec<Line> = (0..resolution)
+        .rev()
+        .map(|bucket| {
+            let side_color = if ask_qty[bucket] > bid_qty[bucket] {
+                Color::Red
+            } else {
+                Color::Green
+            };
+            let fraction = if max_qty.is_zero() {
+                0.0
+            } else {
+                (totals[bucket] / max_qty).to_f64().unwrap_or(0.0)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>12.2} ", bucket_floor(low, high, resolution, bucket)),
+                    Style::default().fg(side_color),
+                ),
+                Span::styled(
+                    format!("{:>width$.4}", totals[bucket]),
+                    Style::default().fg(Color::White).bg(heat_color(fraction)),
+                ),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Lowest and highest price across both sides, or `None` for an empty book.
+fn price_range(bids: &[PriceLevel], asks: &[PriceLevel]) -> Option<(Decimal, Decimal)> {
+    let prices = bids.iter().chain(asks).map(|level| level.price);
+    let low = prices.clone().min()?;
+    let high = prices.max()?;
+    Some((low, high))
+}
+
+/// Sums the quantity of `levels` into `resolution` equal-width buckets
+/// between `low` and `high`, lowest price first.
+///
+/// The top price falls into the last bucket. A flat range puts everything
+/// in the first one.
+fn bucket_quantities(
+    levels: &[PriceLevel],
+    low: Decimal,
+    high: Decimal,
+    resolution: usize,
+) -> Vec<Decimal> {
+    let mut buckets = vec![Decimal::ZERO; resolution];
+    if resolution == 0 {
+        return buckets;
+    }
+    let range = high - low;
+
+    for level in levels {
+        let index = if range.is_zero() {
+            0
+        } else {
+            ((level.price - low) / range * Decimal::from(resolution))
+                .floor()
+                .to_usize()
+                .unwrap_or(0)
+                .min(resolution - 1)
+        };
+        buckets[index] += level.qty;
+    }
+    buckets
+}
+
+/// Lowest price of `bucket`.
+fn bucket_floor(low: Decimal, high: Decimal, resolution: usize, bucket: usize) -> Decimal {
+    (high - low) / Decimal::from(resolution) * Decimal::from(bucket) + low
+}
+
+/// Interpolates in HSL from dark blue (`fraction` 0.0) to bright red (1.0).
+fn heat_color(fraction: f64) -> Color {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let hue = COLD_HUE * (1.0 - fraction);
+    let lightness = COLD_LIGHTNESS + (HOT_LIGHTNESS - COLD_LIGHTNESS) * fraction;
+    let (r, g, b) = hsl_to_rgb(hue, 1.0, lightness);
+    Color::Rgb(r, g, b)
+}
+
+/// Converts a hue in degrees with saturation and lightness in 0.0..=1.0 to
+/// RGB channels.
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, qty: Decimal) -> PriceLevel {
+        PriceLevel { price, qty }
+    }
+
+    #[test]
+    fn quantities_accumulate_per_bucket() {
+        let levels = [
+            level(dec!(100), dec!(1)),
+            level(dec!(101), dec!(2)),
+            level(dec!(105), dec!(0.5)),
+            level(dec!(110), dec!(4)),
+        ];
+
+        assert_eq!(
+            bucket_quantities(&levels, dec!(100), dec!(110), 2),
+            [dec!(3), dec!(4.5)]
+        );
+        assert_eq!(
+            bucket_quantities(&levels, dec!(100), dec!(110), 1),
+            [dec!(7.5)]
+        );
+    }
+
+    #[test]
+    fn flat_or_empty_ranges_do_not_divide_by_zero() {
+        let levels = [level(dec!(100), dec!(1)), level(dec!(100), dec!(2))];
+
+        assert_eq!(
+            bucket_quantities(&levels, dec!(100), dec!(100), 3),
+            [dec!(3), dec!(0), dec!(0)]
+        );
+        assert!(bucket_quantities(&levels, dec!(100), dec!(100), 0).is_empty());
+        assert_eq!(price_range(&[], &[]), None);
+    }
+
+    #[test]
+    fn heat_runs_from_dark_blue_to_bright_red() {
+        assert_eq!(heat_color(0.0), Color::Rgb(0, 0, 102));
+        assert_eq!(heat_color(1.0), Color::Rgb(255, 0, 0));
+    }
+}
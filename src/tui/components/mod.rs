@@ -1,4 +1,8 @@
 //! UI components for the TUI.
 
+pub mod confirmation_modal;
+pub mod heatmap;
+pub mod price_ladder;
+pub mod sparkline;
 pub mod status_bar;
 pub mod tab_bar;
@@ -0,0 +1,199 @@
+//! Price ladder component.
+//!
+//! A wider variant of the order book depth view with a `CUM` column holding
+//! the running quantity from the spread outward. The cumulative bar and its
+//! color intensity scale with that running total, so walls further from the
+//! spread stand out.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::models::book::PriceLevel;
+
+/// Smallest width at which the ladder fits all of its columns.
+pub const MIN_WIDTH: u16 = 52;
+
+/// Longest cumulative depth bar, in cells.
+const BAR_WIDTH: usize = 15;
+
+/// Dimmest channel value used for the shallowest level.
+const MIN_INTENSITY: f64 = 90.0;
+
+/// Renders up to `max_levels` asks above the spread and bids below it,
+/// each with its cumulative quantity.
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    bids: &[PriceLevel],
+    asks: &[PriceLevel],
+    max_levels: usize,
+) {
+    let ask_cums = cumulative(asks, max_levels);
+    let bid_cums = cumulative(bids, max_levels);
+    // Both sides share one scale so their depth can be compared
+    let max_cum = ask_cums
+        .last()
+        .copied()
+        .max(bid_cums.last().copied())
+        .unwrap_or(Decimal::ZERO);
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    lines.push(header("ASK", Color::Red));
+    // Reversed so the best ask sits just above the spread
+    for (level, cum) in asks.iter().zip(&ask_cums).rev() {
+        lines.push(level_line(level, *cum, max_cum, Side::Ask));
+    }
+
+    if let (Some(best_bid), Some(best_ask)) = (bids.first(), asks.first()) {
+        let spread = best_ask.price - best_bid.price;
+        let spread_pct = if best_bid.price.is_zero() {
+            Decimal::ZERO
+        } else {
+            (spread / best_bid.price) * Decimal::from(100)
+        };
+        lines.push(Line::from(Span::styled(
+            format!("─── Spread: {:.2} ({:.3}%) ───", spread, spread_pct),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    lines.push(header("BID", Color::Green));
+    for (level, cum) in bids.iter().zip(&bid_cums) {
+        lines.push(level_line(level, *cum, max_cum, Side::Bid));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Which side of the book a ladder row belongs to.
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    Bid,
+    Ask,
+}
+
+/// Side label followed by the column headings.
+fn header(label: &str, color: Color) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{label:<4}"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("{:>8} {:>10} {:>11}", "PRICE", "QTY", "CUM"),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
+/// One price level with its cumulative quantity and depth bar.
+fn level_line(level: &PriceLevel, cum: Decimal, max_cum: Decimal, side: Side) -> Line<'static> {
+    let fraction = if max_cum.is_zero() {
+        0.0
+    } else {
+        (cum / max_cum).to_f64().unwrap_or(0.0)
+    };
+    let base = match side {
+        Side::Bid => Color::Green,
+        Side::Ask => Color::Red,
+    };
+    let shade = intensity(side, fraction);
+    let bar_len = ((fraction * BAR_WIDTH as f64).round() as usize).clamp(1, BAR_WIDTH);
+
+    Line::from(vec![
+        Span::styled(format!("{:>12.2} ", level.price), Style::default().fg(base)),
+        Span::raw(format!("{:>10.4} ", level.qty)),
+        Span::styled(format!("{cum:>11.4} "), Style::default().fg(shade)),
+        Span::styled("█".repeat(bar_len), Style::default().fg(shade)),
+    ])
+}
+
+/// Running totals of the first `max_levels` quantities, best price first.
+fn cumulative(levels: &[PriceLevel], max_levels: usize) -> Vec<Decimal> {
+    levels
+        .iter()
+        .take(max_levels)
+        .scan(Decimal::ZERO, |total, level| {
+            *total += level.qty;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// Side color whose brightness grows with `fraction` (0.0 to 1.0) of the
+/// deepest cumulative quantity.
+fn intensity(side: Side, fraction: f64) -> Color {
+    let level = (MIN_INTENSITY + (255.0 - MIN_INTENSITY) * fraction.clamp(0.0, 1.0)) as u8;
+    match side {
+        Side::Bid => Color::Rgb(0, level, 0),
+        Side::Ask => Color::Rgb(level, 0, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, qty: Decimal) -> PriceLevel {
+        PriceLevel { price, qty }
+    }
+
+    #[test]
+    fn cumulative_sums_from_the_spread_outward() {
+        let levels = [
+            level(dec!(100), dec!(1)),
+            level(dec!(99), dec!(2.5)),
+            level(dec!(98), dec!(0.5)),
+        ];
+
+        assert_eq!(cumulative(&levels, 10), [dec!(1), dec!(3.5), dec!(4)]);
+        assert_eq!(cumulative(&levels, 2), [dec!(1), dec!(3.5)]);
+    }
+
+    #[test]
+    fn intensity_grows_with_cumulative_quantity() {
+        let shallow = intensity(Side::Bid, 0.1);
+        let deep = intensity(Side::Bid, 1.0);
+
+        match (shallow, deep) {
+            (Color::Rgb(0, low, 0), Color::Rgb(0, high, 0)) => {
+                assert!(low < high);
+                assert_eq!(high, 255);
+            }
+            other => panic!("unexpected colors {other:?}"),
+        }
+    }
+
+    #[test]
+    fn renders_cum_column() {
+        let bids = [level(dec!(100), dec!(1)), level(dec!(99), dec!(2))];
+        let asks = [level(dec!(101), dec!(0.5))];
+        let mut terminal = Terminal::new(TestBackend::new(MIN_WIDTH, 8)).unwrap();
+
+        terminal
+            .draw(|frame| render(frame, frame.area(), &bids, &asks, 10))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect();
+        assert!(rows[0].contains("CUM"));
+        assert!(rows[1].contains("0.5000"));
+        assert!(rows[5].trim_end().contains("3.0000"));
+    }
+}
@@ -0,0 +1,75 @@
+//! One-row P&L sparkline component.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Paragraph,
+};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Bar glyphs from lowest to highest.
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders the most recent values of `history` that fit in `area`, scaled
+/// between the smallest and largest value shown.
+///
+/// Green if the latest value is at or above the first one shown, red
+/// otherwise.
+pub fn render(frame: &mut Frame, area: Rect, history: &VecDeque<(Instant, Decimal)>) {
+    let width = usize::from(area.width);
+    let values: Vec<Decimal> = history
+        .iter()
+        .skip(history.len().saturating_sub(width))
+        .map(|(_, value)| *value)
+        .collect();
+    let (Some(first), Some(last)) = (values.first(), values.last()) else {
+        return;
+    };
+    let color = if last >= first {
+        Color::Green
+    } else {
+        Color::Red
+    };
+
+    frame.render_widget(
+        Paragraph::new(spark(&values)).style(Style::default().fg(color)),
+        area,
+    );
+}
+
+/// Maps each value to a bar glyph. A flat series draws the lowest bar.
+fn spark(values: &[Decimal]) -> String {
+    let min = values.iter().copied().min().unwrap_or(Decimal::ZERO);
+    let max = values.iter().copied().max().unwrap_or(Decimal::ZERO);
+    let range = max - min;
+    let top = LEVELS.len() - 1;
+
+    values
+        .iter()
+        .map(|value| {
+            if range.is_zero() {
+                return LEVELS[0];
+            }
+            let fraction = ((*value - min) / range).to_f64().unwrap_or(0.0);
+            LEVELS[((fraction * top as f64).round() as usize).min(top)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn spark_scales_between_min_and_max() {
+        assert_eq!(spark(&[dec!(-10), dec!(0), dec!(10)]), "▁▅█");
+        assert_eq!(spark(&[dec!(3), dec!(3)]), "▁▁");
+        assert_eq!(spark(&[]), "");
+    }
+}
@@ -2,22 +2,79 @@
 
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Block, Paragraph},
 };
 
-use crate::tui::app::{App, ConnectionStatus};
+use super::sparkline;
+use crate::tui::app::{App, ConnectionStatus, NotificationLevel};
 
-/// Renders the status bar.
+/// Width of the P&L sparkline at the right end of the status bar, in samples.
+const SPARKLINE_WIDTH: u16 = 20;
+
+/// Renders the status bar, with today's P&L and its sparkline on the
+/// right once execution updates have been sampled.
 pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let area = if app.pnl_history.is_empty() {
+        area
+    } else {
+        let pnl_label = format!(" P&L ${:.2} ", app.pnl_today);
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(pnl_label.chars().count() as u16),
+                Constraint::Length(SPARKLINE_WIDTH),
+            ])
+            .split(area);
+        let pnl_color = if app.pnl_today >= rust_decimal::Decimal::ZERO {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        frame.render_widget(
+            Paragraph::new(pnl_label).style(Style::default().fg(pnl_color).bg(Color::DarkGray)),
+            chunks[1],
+        );
+        frame.render_widget(
+            Block::default().style(Style::default().bg(Color::DarkGray)),
+            chunks[2],
+        );
+        sparkline::render(frame, chunks[2], &app.pnl_history);
+        chunks[0]
+    };
+
     let status_color = match app.connection_status {
         ConnectionStatus::Connected => Color::Green,
-        ConnectionStatus::Connecting | ConnectionStatus::Reconnecting => Color::Yellow,
+        ConnectionStatus::Connecting | ConnectionStatus::Reconnecting(_) => Color::Yellow,
         ConnectionStatus::Disconnected => Color::Red,
     };
 
+    // Session uptime and reconnect count while connected
+    let stats_span = match app.connection_stats {
+        Some(ref stats) if app.connection_status == ConnectionStatus::Connected => Span::styled(
+            format!(
+                "up {} ↻{} ",
+                format_uptime(stats.session_uptime()),
+                stats.reconnect_count
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        _ => Span::raw(""),
+    };
+
+    // Market data throughput while connected
+    let rate_span = if app.connection_status == ConnectionStatus::Connected {
+        Span::styled(
+            format!("~ {:.0} msg/s ", app.message_rate),
+            Style::default().fg(Color::Gray),
+        )
+    } else {
+        Span::raw("")
+    };
+
     let auth_label = if !app.authenticated {
         Span::styled(" No Auth ", Style::default().fg(Color::DarkGray))
     } else if app.private_connected {
@@ -26,6 +83,14 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
         Span::styled(" Auth Down ", Style::default().fg(Color::Yellow))
     };
 
+    // Fee tier from credential validation, shown next to the auth label
+    let tier_span = match app.account_tier {
+        Some(ref tier) if app.authenticated => {
+            Span::styled(format!("{tier} "), Style::default().fg(Color::Gray))
+        }
+        _ => Span::raw(""),
+    };
+
     // Show USD balance if available
     let balance_span = if let Some(usd) = app.asset_balances.get("USD") {
         Span::styled(
@@ -36,17 +101,52 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
         Span::raw("")
     };
 
-    let tab_info = format!(" {}/{} ", app.active_tab + 1, app.tabs.len());
+    // Total account value in USD, once balances have arrived
+    let portfolio_span = if app.asset_balances.is_empty() {
+        Span::raw("")
+    } else {
+        Span::styled(
+            format!(" Total ${:.2} ", app.total_portfolio_value()),
+            Style::default().fg(Color::Cyan),
+        )
+    };
 
-    let error_span = if let Some(ref error) = app.error_message {
+    // Unrealized P&L of live positions built from fills
+    let mtm_span = if app.simulation || app.fill_positions.values().all(|p| p.qty.is_zero()) {
+        Span::raw("")
+    } else {
+        let mtm = app.mark_to_market_pnl();
+        let color = if mtm >= rust_decimal::Decimal::ZERO {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        Span::styled(format!(" MTM {mtm:+.2} "), Style::default().fg(color))
+    };
+
+    // Dead man's switch badge: shown while the timer is armed
+    let cancel_after_span = if let Some(timeout) = app.cancel_after_timeout {
         Span::styled(
-            format!(" {} ", error.message),
-            Style::default().fg(Color::Red),
+            format!(" DMS {timeout}s "),
+            Style::default().fg(Color::Yellow),
         )
     } else {
         Span::raw("")
     };
 
+    let tab_info = format!(" {}/{} ", app.active_tab + 1, app.tabs.len());
+
+    let error_span = if let Some(ref error) = app.error_message {
+        let color = match error.level {
+            NotificationLevel::Info => Color::Green,
+            NotificationLevel::Warning => Color::Yellow,
+            NotificationLevel::Error => Color::Red,
+        };
+        Span::styled(format!(" {} ", error.message), Style::default().fg(color))
+    } else {
+        Span::raw("")
+    };
+
     // Simulation badge: yellow background with P&L and trade count
     let sim_spans: Vec<Span> = if app.simulation {
         let total_pnl = app.sim_stats.realized_pnl + app.sim_stats.unrealized_pnl;
@@ -98,11 +198,17 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
             format!(" {} ", app.connection_status.label()),
             Style::default().fg(status_color),
         ),
+        stats_span,
+        rate_span,
         Span::raw("│"),
         auth_label,
+        tier_span,
         Span::raw("│"),
         balance_span,
+        portfolio_span,
+        mtm_span,
         Span::raw("│"),
+        cancel_after_span,
         error_span,
         Span::raw(format!(
             "{:>width$}",
@@ -127,3 +233,15 @@ fn format_token_count(count: u64) -> String {
         format!("{count} tok")
     }
 }
+
+/// Formats an uptime as hours and minutes, or minutes and seconds when
+/// under an hour.
+fn format_uptime(uptime: std::time::Duration) -> String {
+    let secs = uptime.as_secs();
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if h > 0 {
+        format!("{h}h{m:02}m")
+    } else {
+        format!("{m}m{s:02}s")
+    }
+}
@@ -10,7 +10,8 @@ use ratatui::{
 
 use crate::tui::app::App;
 
-/// Renders the tab bar.
+/// Renders the tab bar, with a `[n]` badge on tabs that have unread agent
+/// output or open orders.
 pub fn render(frame: &mut Frame, area: Rect, app: &App) {
     let mut spans: Vec<Span> = Vec::new();
 
@@ -26,7 +27,11 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App) {
             Style::default().fg(Color::White)
         };
 
-        spans.push(Span::styled(format!(" {} ", tab.title()), style));
+        let label = match app.tab_badge(tab) {
+            0 => format!(" {} ", tab.title()),
+            count => format!(" {} [{count}] ", tab.title()),
+        };
+        spans.push(Span::styled(label, style));
         spans.push(Span::raw(" "));
     }
 
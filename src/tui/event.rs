@@ -6,23 +6,27 @@ use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEvent, KeyModi
 use tokio::sync::mpsc;
 
 use crate::models::add_order::AddOrderParams;
-use crate::models::balance::BalanceResponse;
+use crate::models::balance::{AssetBalance, BalanceResponse};
 use crate::models::book::{BookUpdateResponse, calculate_checksum};
 use crate::models::candle::CandleUpdateResponse;
 use crate::models::execution::ExecutionUpdateResponse;
+use crate::models::instrument::InstrumentUpdateResponse;
+use crate::models::orders::OrdersUpdateResponse;
 use crate::models::ticker::TickerUpdateResponse;
 use crate::models::trade::TradeUpdateResponse;
 use crate::models::{
-    AddOrderResponse, AmendOrderResponse, CancelAllResponse, CancelOrderResponse,
-    StatusUpdateResponse,
+    AddOrderResponse, AmendOrderResponse, BatchAddResponse, BatchCancelResponse,
+    CancelAfterResponse, CancelAllResponse, CancelOrderResponse, EditOrderBuilder, EditOrderParams,
+    EditOrderResponse, MAX_TIMEOUT_SECONDS, StatusUpdateResponse, SubscribeResponse,
 };
 
 use crate::risk::config::AgentRiskParams;
 
 use super::app::{
-    ApiKeysEditState, App, AssetBalance, Focus, MAX_BOOK_DEPTH, MAX_ORDERBOOK_HISTORY, Mode,
-    OrderBookSnapshot, RiskEditState, Tab,
+    ApiKeysEditState, App, EditOrderState, Focus, MAX_BOOK_DEPTH, MAX_ORDERBOOK_HISTORY, Mode,
+    OrderBookSnapshot, OrdersView, RiskEditState, Tab, Timeframe,
 };
+use super::input::text_input::TextInput;
 
 /// Maximum length (in bytes) for agent input text.
 ///
@@ -31,6 +35,9 @@ use super::app::{
 /// for a single operator command.
 const MAX_INPUT_LENGTH: usize = 4096;
 
+/// Maximum length (in bytes) for an agent output keyword filter.
+const MAX_FILTER_LENGTH: usize = 64;
+
 /// Maximum open orders tracked per symbol before oldest are dropped.
 const MAX_OPEN_ORDERS_PER_SYMBOL: usize = 200;
 
@@ -57,6 +64,8 @@ pub enum Message {
     Book(BookUpdateResponse),
     /// Trade update from WebSocket.
     Trade(TradeUpdateResponse),
+    /// Level-3 (individual orders) update from WebSocket.
+    Level3(OrdersUpdateResponse),
     /// Candle update from WebSocket.
     Candle(CandleUpdateResponse),
     /// Execution update from WebSocket.
@@ -65,29 +74,57 @@ pub enum Message {
     Balance(BalanceResponse),
     /// Status update from WebSocket.
     Status(StatusUpdateResponse),
+    /// Asset and pair reference data from WebSocket.
+    InstrumentUpdate(InstrumentUpdateResponse),
     /// Heartbeat received.
     Heartbeat,
 
     /// Order placement response.
     OrderPlaced(AddOrderResponse),
+    /// Batch order placement response.
+    BatchOrderResult(BatchAddResponse),
     /// Order cancellation response.
     OrderCancelled(CancelOrderResponse),
     /// Order amendment response.
     OrderAmended(AmendOrderResponse),
+    /// Order edit (cancel-and-replace) response.
+    OrderEdited(EditOrderResponse),
+    /// Batch cancel response.
+    BatchCancelResult(BatchCancelResponse),
     /// Cancel all response.
     AllOrdersCancelled(CancelAllResponse),
+    /// Dead man's switch (cancel_all_orders_after) response.
+    CancelAfterUpdated(CancelAfterResponse),
+    /// An RPC request received no response within the timeout.
+    RequestTimeout { req_id: u64, method: String },
+    /// Queued orders were discarded before reaching the exchange.
+    OrdersNotSent { count: usize, reason: String },
+    /// Acknowledgement of a `subscribe` or `unsubscribe` request.
+    SubscribeAck(SubscribeResponse),
+    /// A public channel subscription was still rejected after retrying.
+    SubscriptionFailed {
+        channel: String,
+        symbol: String,
+        error: String,
+    },
 
     /// WebSocket connected.
     Connected,
-    /// WebSocket disconnected.
-    Disconnected,
-    /// WebSocket reconnecting.
-    Reconnecting,
+    /// WebSocket disconnected, with the cause when known.
+    Disconnected(Option<crate::LeesonError>),
+    /// WebSocket reconnecting, with a short description of why.
+    Reconnecting { reason: String },
+    /// Periodic connection stability report.
+    ConnectionStats(crate::websocket::ConnectionStats),
 
     /// Output line from an agent subprocess.
     AgentOutput { agent_index: usize, line: String },
     /// Agent subprocess signaled readiness.
     AgentReady(usize),
+    /// Agent subprocess announced it can parse structured JSON payloads.
+    AgentSupportsJson(usize),
+    /// Agent subprocess asked to be told when a price level is reached.
+    AgentPriceAlert(crate::agent::PriceAlert),
     /// Agent subprocess exited.
     AgentExited {
         agent_index: usize,
@@ -183,6 +220,13 @@ pub fn spawn_tick_timer(tx: mpsc::Sender<Message>, interval_ms: u64) {
 
 /// Updates application state based on a message.
 pub fn update(app: &mut App, message: Message) -> Option<Action> {
+    if matches!(
+        message,
+        Message::Ticker(_) | Message::Book(_) | Message::Trade(_) | Message::Candle(_)
+    ) {
+        app.message_counter += 1;
+    }
+
     match message {
         Message::Input(event) => handle_input(app, event),
         Message::Ticker(response) => {
@@ -191,6 +235,18 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             }
             None
         }
+        Message::Level3(response) => {
+            let is_snapshot = response.tpe == "snapshot";
+            for data in response.data {
+                let book = app.level3_books.entry(data.symbol.clone()).or_default();
+                if is_snapshot {
+                    book.apply_snapshot(&data);
+                } else {
+                    book.apply_update(&data);
+                }
+            }
+            None
+        }
         Message::Book(response) => {
             const MAX_CHECKSUM_FAILURES: u8 = 3;
             const RESYNC_COOLDOWN: Duration = Duration::from_secs(5);
@@ -307,11 +363,25 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             None
         }
         Message::Candle(response) => {
-            for data in response.data {
+            let interval = u64::try_from(app.chart_timeframe.interval()).unwrap_or_default();
+            // Drop candles still arriving for a previous timeframe, but keep
+            // 1m candles so the chart can aggregate them client-side
+            for data in response
+                .data
+                .into_iter()
+                .filter(|d| d.interval == interval || d.interval == 1)
+            {
                 let candles = app
                     .candles
                     .entry(data.symbol.clone())
                     .or_insert_with(|| std::collections::VecDeque::with_capacity(100));
+                // Never mix feeds: native candles replace 1m ones
+                match candles.front() {
+                    Some(c) if c.interval == data.interval => {}
+                    Some(_) if data.interval == interval => candles.clear(),
+                    Some(_) => continue,
+                    None => {}
+                }
                 if candles.len() >= 100 {
                     candles.pop_front();
                 }
@@ -321,6 +391,7 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
         }
         Message::Execution(response) => {
             for data in response.data {
+                app.record_fill(&data);
                 // Add to open or executed orders based on status
                 let symbol = data.symbol.clone();
                 match data.order_status.as_str() {
@@ -341,6 +412,7 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
                         if let Some(orders) = app.open_orders.get_mut(&symbol) {
                             orders.retain(|o| o.order_id != data.order_id);
                         }
+                        app.deselect_order(&symbol, &data.order_id);
                         // Add to executed orders
                         let executed = app
                             .executed_orders
@@ -354,6 +426,7 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
                     _ => {}
                 }
             }
+            app.record_pnl();
             None
         }
         Message::Balance(response) => {
@@ -393,6 +466,26 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             app.last_heartbeat = Some(std::time::Instant::now());
             None
         }
+        Message::InstrumentUpdate(response) => {
+            // Only the snapshot lists every pair; updates carry changes
+            if response.tpe != "snapshot" {
+                return None;
+            }
+            let mut pairs: Vec<String> = response
+                .data
+                .pairs
+                .into_iter()
+                .filter(|pair| pair.status == "online")
+                .map(|pair| pair.symbol)
+                .collect();
+            if !pairs.is_empty() {
+                pairs.sort();
+                app.available_pairs = pairs;
+                app.pair_selector_index =
+                    app.pair_selector_index.min(app.available_pairs.len() - 1);
+            }
+            None
+        }
         Message::OrderPlaced(response) => {
             if !response.success
                 && let Some(error) = response.error
@@ -401,6 +494,39 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             }
             None
         }
+        Message::BatchOrderResult(response) => {
+            if !response.success {
+                let error = response
+                    .error
+                    .unwrap_or_else(|| "unknown error".to_string());
+                app.add_agent_output(0, format!("[batch rejected] {error}"));
+                app.show_error(format!("Batch order rejected: {error}"));
+                return None;
+            }
+            for result in response.result.unwrap_or_default() {
+                let label = match result.cl_ord_id {
+                    Some(ref cl_ord_id) => format!("{} ({cl_ord_id})", result.order_id),
+                    None => result.order_id.clone(),
+                };
+                app.add_agent_output(0, format!("[batch order placed] {label}"));
+
+                // Fill in client identifiers the execution report may have omitted
+                if let Some(order) = app
+                    .open_orders
+                    .values_mut()
+                    .flatten()
+                    .find(|o| o.order_id == result.order_id)
+                {
+                    if order.cl_ord_id.is_none() {
+                        order.cl_ord_id = result.cl_ord_id;
+                    }
+                    if order.order_userref.is_none() {
+                        order.order_userref = result.order_userref;
+                    }
+                }
+            }
+            None
+        }
         Message::OrderCancelled(response) => {
             if !response.success
                 && let Some(error) = response.error
@@ -409,6 +535,16 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             }
             None
         }
+        Message::BatchCancelResult(response) => {
+            match (response.success, response.result) {
+                (true, Some(result)) => app.show_info(format!("Cancelled {} orders", result.count)),
+                _ => app.show_error(format!(
+                    "Batch cancel rejected: {}",
+                    response.error.as_deref().unwrap_or("unknown error")
+                )),
+            }
+            None
+        }
         Message::OrderAmended(response) => {
             if !response.success
                 && let Some(error) = response.error
@@ -417,18 +553,80 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             }
             None
         }
+        Message::OrderEdited(response) => {
+            match (response.success, response.result) {
+                (true, Some(result)) => app.show_info(format!(
+                    "Order {} replaced by {}",
+                    result.original_order_id, result.order_id
+                )),
+                (true, None) => app.show_info("Order edited"),
+                (false, _) => app.show_error(format!(
+                    "Order edit rejected: {}",
+                    response.error.as_deref().unwrap_or("unknown error")
+                )),
+            }
+            None
+        }
         Message::AllOrdersCancelled(_) => None,
+        Message::CancelAfterUpdated(response) => {
+            if !response.success {
+                app.cancel_after_timeout = None;
+                if let Some(error) = response.error {
+                    app.show_error(format!("Dead man's switch failed: {error}"));
+                }
+            }
+            None
+        }
+        Message::RequestTimeout { req_id, method } => {
+            app.show_error(format!("{method} request {req_id} timed out"));
+            None
+        }
+        Message::OrdersNotSent { count, reason } => {
+            app.show_error(format!("{count} order(s) not sent: {reason}"));
+            None
+        }
+        Message::SubscribeAck(ack) => {
+            // Tracked subscriptions are retried by the connection manager,
+            // which reports them via `SubscriptionFailed` once it gives up
+            if !ack.success && ack.req_id.is_none() {
+                app.show_warning(format!(
+                    "{} {} failed: {}",
+                    ack.method,
+                    ack.symbol().unwrap_or("channel"),
+                    ack.error.as_deref().unwrap_or("unknown error")
+                ));
+            }
+            None
+        }
+        Message::SubscriptionFailed {
+            channel,
+            symbol,
+            error,
+        } => {
+            app.show_error(format!(
+                "{channel} subscription for {symbol} failed: {error}"
+            ));
+            None
+        }
         Message::Connected => {
             app.connection_status = super::app::ConnectionStatus::Connected;
             None
         }
-        Message::Disconnected => {
+        Message::ConnectionStats(stats) => {
+            app.connection_stats = Some(stats);
+            None
+        }
+        Message::Disconnected(error) => {
             app.connection_status = super::app::ConnectionStatus::Disconnected;
             app.private_connected = false;
+            if let Some(error) = error {
+                app.show_error(disconnect_message(&error));
+            }
             None
         }
-        Message::Reconnecting => {
-            app.connection_status = super::app::ConnectionStatus::Reconnecting;
+        Message::Reconnecting { reason } => {
+            app.show_warning(format!("Reconnecting: {reason}"));
+            app.connection_status = super::app::ConnectionStatus::Reconnecting(reason);
             app.private_connected = false;
             None
         }
@@ -452,6 +650,17 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             app.add_agent_output(agent_index, "[agent ready]".to_string());
             None
         }
+        Message::AgentSupportsJson(_) => None,
+        Message::AgentPriceAlert(alert) => {
+            app.add_agent_output(
+                alert.agent_index,
+                format!(
+                    "[alert] {} {} {}",
+                    alert.symbol, alert.direction, alert.threshold
+                ),
+            );
+            None
+        }
         Message::AgentOrderRequest {
             agent_index,
             symbol,
@@ -461,27 +670,33 @@ pub fn update(app: &mut App, message: Message) -> Option<Action> {
             price,
             cl_ord_id,
         } => {
-            use crate::models::add_order::{AddOrderBuilder, OrderSide};
+            use crate::models::add_order::{AddOrderBuilder, OrderSide, OrderType};
             use rust_decimal::Decimal;
             use std::str::FromStr;
 
+            if app.agent_paused.get(agent_index).copied().unwrap_or(false) {
+                app.add_agent_output(
+                    agent_index,
+                    format!("[order rejected] agent paused: {side} {qty} {symbol}"),
+                );
+                return None;
+            }
+
             let parse_result = (|| -> Result<AddOrderParams, String> {
-                let side = match side.to_lowercase().as_str() {
-                    "buy" => OrderSide::Buy,
-                    "sell" => OrderSide::Sell,
-                    other => return Err(format!("invalid side: {other}")),
-                };
+                let side = OrderSide::try_from(side.as_str()).map_err(|e| e.to_string())?;
                 let qty = Decimal::from_str(&qty).map_err(|e| format!("invalid qty: {e}"))?;
 
-                let mut builder = match order_type.to_lowercase().as_str() {
-                    "market" => AddOrderBuilder::market(side, &symbol, qty),
-                    "limit" => {
+                let order_type =
+                    OrderType::try_from(order_type.as_str()).map_err(|e| e.to_string())?;
+                let mut builder = match order_type {
+                    OrderType::Market => AddOrderBuilder::market(side, &symbol, qty),
+                    OrderType::Limit => {
                         let price_str = price.as_deref().ok_or("limit order requires a price")?;
                         let price = Decimal::from_str(price_str)
                             .map_err(|e| format!("invalid price: {e}"))?;
                         AddOrderBuilder::limit(side, &symbol, qty, price)
                     }
-                    other => return Err(format!("unsupported order type: {other}")),
+                    other => return Err(format!("unsupported order type: {other:?}")),
                 };
 
                 if let Some(ref id) = cl_ord_id {
@@ -560,10 +775,24 @@ pub enum Action {
     SubmitOrder(Box<AddOrderParams>),
     /// Operator confirmed a pending order.
     ConfirmOrder,
-    /// Cancel an order.
+    /// Cancel the open order with this exchange ID.
     CancelOrder(String),
+    /// Cancel every open order shown for a trading pair.
+    CancelAllVisible(String),
+    /// Edit the price or quantity of an open order.
+    EditOrder(Box<EditOrderParams>),
+    /// Set the dead man's switch timeout in seconds (0 disables it).
+    SetCancelAfter(u32),
     /// Operator saved updated agent risk parameters.
     SaveRiskParams(AgentRiskParams),
+    /// Re-read the enforced risk limits from disk.
+    ReloadRiskConfig,
+    /// Write the session's executed trades to a JSON file.
+    ExportTradeHistory,
+    /// Stop or resume order submissions from an agent.
+    SetAgentPaused { agent_index: usize, paused: bool },
+    /// Re-subscribe candles for the selected pairs at a new interval.
+    ChangeTimeframe { from: Timeframe, to: Timeframe },
     /// Operator saved API keys from the overlay.
     SaveApiKeys {
         /// New values for each credential (None = unchanged).
@@ -571,6 +800,17 @@ pub enum Action {
     },
 }
 
+/// Formats a disconnect cause for the status bar, e.g. `Kraken: maintenance (1001)`.
+fn disconnect_message(error: &crate::LeesonError) -> String {
+    match error {
+        crate::LeesonError::WebSocketClosed { code, reason } => format!(
+            "Kraken: {}",
+            crate::error::describe_close(*code, reason.as_deref())
+        ),
+        other => format!("Disconnected: {other}"),
+    }
+}
+
 /// Handles input events and updates application state.
 fn handle_input(app: &mut App, event: Event) -> Option<Action> {
     match event {
@@ -578,6 +818,7 @@ fn handle_input(app: &mut App, event: Event) -> Option<Action> {
         Event::Resize(_, _) => None,
         Event::Tick => {
             app.clear_stale_errors();
+            app.sample_message_rate(std::time::Instant::now());
             None
         }
     }
@@ -595,6 +836,31 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Option<Action> {
         return handle_api_keys_mode(app, key);
     }
 
+    // The timeout prompt handles its own Esc (clears the input buffer)
+    if app.mode == Mode::CancelAfter {
+        return handle_cancel_after_mode(app, key);
+    }
+
+    // Pair search captures all typing, including 'q'
+    if app.mode == Mode::PairSearch {
+        return handle_pair_search_mode(app, key);
+    }
+
+    // Global search captures all typing, including 'q'
+    if app.mode == Mode::GlobalSearch {
+        return handle_global_search_mode(app, key);
+    }
+
+    // The annotation prompt captures all typing, including 'q'
+    if app.mode == Mode::Annotate {
+        return handle_annotate_mode(app, key);
+    }
+
+    // The order edit overlay captures all typing, including 'q'
+    if app.mode == Mode::EditOrder {
+        return handle_edit_order_mode(app, key);
+    }
+
     // Global keys (work in any mode)
     match key.code {
         KeyCode::Char('q') if key.modifiers.is_empty() && app.mode == Mode::Normal => {
@@ -602,6 +868,10 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Option<Action> {
             return None;
         }
         KeyCode::Esc => {
+            // Esc on an agent panel also drops its keyword filter
+            if let Some(index) = agent_output_index(app.focus) {
+                app.agent_output_filter[index] = None;
+            }
             app.mode = Mode::Normal;
             return None;
         }
@@ -613,7 +883,14 @@ fn handle_key(app: &mut App, key: KeyEvent) -> Option<Action> {
         Mode::Normal => handle_normal_mode(app, key),
         Mode::Insert => handle_insert_mode(app, key),
         Mode::Confirm => handle_confirm_mode(app, key),
-        Mode::RiskEdit | Mode::ApiKeys => unreachable!(),
+        Mode::RiskEdit
+        | Mode::ApiKeys
+        | Mode::CancelAfter
+        | Mode::PairSearch
+        | Mode::GlobalSearch
+        | Mode::Annotate
+        | Mode::EditOrder => unreachable!(),
+        Mode::NotificationLog => handle_notification_log_mode(app, key),
     }
 }
 
@@ -633,6 +910,11 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
             app.previous_tab();
             None
         }
+        KeyCode::Char(c @ '1'..='9') if key.modifiers.contains(KeyModifiers::ALT) => {
+            let n = c.to_digit(10).expect("matched an ascii digit") as usize;
+            app.select_pair_tab(n - 1);
+            None
+        }
 
         // Help
         KeyCode::Char('?') => {
@@ -647,6 +929,35 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
             None
         }
 
+        // Hot-reload enforced risk limits
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::ReloadRiskConfig)
+        }
+
+        // Export executed trades
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Action::ExportTradeHistory)
+        }
+
+        // Close the active trading-pair tab
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.close_current_pair_tab().map(Action::UnsubscribePair)
+        }
+
+        // Notification log
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.mode = Mode::NotificationLog;
+            None
+        }
+
+        // Global search (terminals without extended key reporting send
+        // Ctrl+/ as Ctrl+7)
+        KeyCode::Char('/' | '7') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.global_search = TextInput::new();
+            app.mode = Mode::GlobalSearch;
+            None
+        }
+
         // API keys overlay
         KeyCode::Char('a') => {
             app.api_keys_edit = Some(ApiKeysEditState::new());
@@ -681,13 +992,40 @@ fn handle_agent_tab_keys(app: &mut App, key: KeyEvent) -> Option<Action> {
             None
         }
 
+        // Toggle the persistent pin of the focused agent output panel
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Some(index) = agent_output_index(app.focus) {
+                app.pin_agent_panel(index);
+            }
+            None
+        }
+
+        // Pause or resume order submissions from the process behind the
+        // focused panel; every panel belongs to the multi-agent process
+        KeyCode::Char('P') => agent_output_index(app.focus).and_then(|_| {
+            let agent_index = crate::agent::MULTI_AGENT_INDEX;
+            let paused = app.toggle_agent_pause(agent_index)?;
+            Some(Action::SetAgentPaused {
+                agent_index,
+                paused,
+            })
+        }),
+
+        // Clear the focused agent output panel
+        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if let Some(index) = agent_output_index(app.focus) {
+                app.clear_agent_output(index);
+            }
+            None
+        }
+
         // Scroll / navigation with j/k
         KeyCode::Char('j') | KeyCode::Down => {
             match app.focus {
-                Focus::PairSelector => {
-                    if app.pair_selector_index < app.available_pairs.len().saturating_sub(1) {
-                        app.pair_selector_index += 1;
-                    }
+                Focus::PairSelector
+                    if app.pair_selector_index < app.available_pairs.len().saturating_sub(1) =>
+                {
+                    app.pair_selector_index += 1;
                 }
                 Focus::AgentOutput1 => app.scroll_agent_down(0),
                 Focus::AgentOutput2 => app.scroll_agent_down(1),
@@ -709,6 +1047,15 @@ fn handle_agent_tab_keys(app: &mut App, key: KeyEvent) -> Option<Action> {
             None
         }
 
+        // Edit the keyword filter of the focused agent panel
+        KeyCode::Char('F') => {
+            if let Some(index) = agent_output_index(app.focus) {
+                app.agent_output_filter[index].get_or_insert_with(String::new);
+                app.mode = Mode::Insert;
+            }
+            None
+        }
+
         // Jump to top/bottom of agent output
         KeyCode::Char('g') => {
             match app.focus {
@@ -745,6 +1092,15 @@ fn handle_agent_tab_keys(app: &mut App, key: KeyEvent) -> Option<Action> {
             None
         }
 
+        // Open the pair search overlay
+        KeyCode::Char('/') => {
+            app.pair_search = TextInput::new();
+            app.pair_selector_index = 0;
+            app.focus = Focus::PairSelector;
+            app.mode = Mode::PairSearch;
+            None
+        }
+
         // Enter insert mode for agent input
         KeyCode::Char('i') | KeyCode::Enter => {
             if app.focus == Focus::AgentInput {
@@ -778,8 +1134,14 @@ fn handle_agent_tab_keys(app: &mut App, key: KeyEvent) -> Option<Action> {
 }
 
 /// Handles keys for trading pair tabs.
-fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, _symbol: &str) -> Option<Action> {
+fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, symbol: &str) -> Option<Action> {
     match key.code {
+        // Toggle the order book heat-map; the book is the leftmost panel
+        KeyCode::Char('h') if app.focus == Focus::OrderBook => {
+            app.book_view.toggle();
+            None
+        }
+
         // Panel focus navigation
         KeyCode::Char('h') | KeyCode::Left => {
             app.focus = match app.focus {
@@ -797,6 +1159,18 @@ fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, _symbol: &str) ->
             };
             None
         }
+        // Move the open order selection; `k` past the first order leaves the panel
+        KeyCode::Char('j') | KeyCode::Down if app.focus == Focus::Orders => {
+            app.move_order_selection(symbol, 1);
+            None
+        }
+        KeyCode::Char('k') | KeyCode::Up
+            if app.focus == Focus::Orders
+                && app.selected_order_index(symbol).is_some_and(|i| i > 0) =>
+        {
+            app.move_order_selection(symbol, -1);
+            None
+        }
         KeyCode::Char('j') | KeyCode::Down => {
             app.focus = match app.focus {
                 Focus::OrderBook => Focus::Trades,
@@ -814,6 +1188,39 @@ fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, _symbol: &str) ->
             None
         }
 
+        // Move the chart candle cursor (older / newer)
+        KeyCode::Char('[') if app.focus == Focus::Chart => {
+            let count = app.chart_candles(symbol).map_or(0, |c| c.len());
+            if count > 0 {
+                app.chart_cursor = Some(app.chart_cursor.map_or(0, |c| c + 1).min(count - 1));
+            }
+            None
+        }
+        KeyCode::Char(']') if app.focus == Focus::Chart => {
+            app.chart_cursor = app.chart_cursor.and_then(|c| c.checked_sub(1));
+            None
+        }
+
+        // Annotate the selected candle
+        KeyCode::Char('m') if app.focus == Focus::Chart => {
+            let Some(candle) = app.selected_candle(symbol) else {
+                app.show_warning("select a candle with [ and ] first");
+                return None;
+            };
+            let timestamp = candle.interval_begin.clone();
+            let existing = app
+                .get_annotation(symbol, &timestamp)
+                .unwrap_or_default()
+                .to_string();
+            app.annotation_input = TextInput::new();
+            for c in existing.chars() {
+                app.annotation_input.insert(c);
+            }
+            app.annotation_target = Some((symbol.to_string(), timestamp));
+            app.mode = Mode::Annotate;
+            None
+        }
+
         // Toggle chart type
         KeyCode::Char('g') => {
             app.chart_type.toggle();
@@ -827,30 +1234,12 @@ fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, _symbol: &str) ->
         }
 
         // Timeframe shortcuts
-        KeyCode::Char('1') => {
-            app.chart_timeframe = super::app::Timeframe::M1;
-            None
-        }
-        KeyCode::Char('2') => {
-            app.chart_timeframe = super::app::Timeframe::M5;
-            None
-        }
-        KeyCode::Char('3') => {
-            app.chart_timeframe = super::app::Timeframe::M15;
-            None
-        }
-        KeyCode::Char('4') => {
-            app.chart_timeframe = super::app::Timeframe::H1;
-            None
-        }
-        KeyCode::Char('5') => {
-            app.chart_timeframe = super::app::Timeframe::H4;
-            None
-        }
-        KeyCode::Char('6') => {
-            app.chart_timeframe = super::app::Timeframe::D1;
-            None
-        }
+        KeyCode::Char('1') => change_timeframe(app, Timeframe::M1),
+        KeyCode::Char('2') => change_timeframe(app, Timeframe::M5),
+        KeyCode::Char('3') => change_timeframe(app, Timeframe::M15),
+        KeyCode::Char('4') => change_timeframe(app, Timeframe::H1),
+        KeyCode::Char('5') => change_timeframe(app, Timeframe::H4),
+        KeyCode::Char('6') => change_timeframe(app, Timeframe::D1),
 
         // New order
         KeyCode::Char('n') => {
@@ -858,15 +1247,58 @@ fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, _symbol: &str) ->
             None
         }
 
-        // Cancel order
+        // Cancel every open order in the orders panel
+        KeyCode::Char('c')
+            if key.modifiers.contains(KeyModifiers::CONTROL) && app.focus == Focus::Orders =>
+        {
+            if app.open_orders.get(symbol).is_none_or(Vec::is_empty) {
+                app.show_warning("no open orders to cancel");
+                return None;
+            }
+            Some(Action::CancelAllVisible(symbol.to_string()))
+        }
+
+        // Cancel the selected open order; Ctrl+C cancels all of them
         KeyCode::Char('c') => {
-            // TODO: Cancel selected order
-            None
+            if app.focus != Focus::Orders || app.orders_view != OrdersView::Open {
+                app.show_warning("select an open order in the orders panel first");
+                return None;
+            }
+            if app.open_orders.get(symbol).is_none_or(Vec::is_empty) {
+                app.show_warning("no open orders to cancel");
+                return None;
+            }
+            let Some(order_id) = app.selected_order_id(symbol) else {
+                app.show_warning("select an order with j and k first");
+                return None;
+            };
+            Some(Action::CancelOrder(order_id))
         }
 
-        // Edit order
+        // Edit the selected open order
         KeyCode::Char('e') => {
-            // TODO: Edit selected order
+            if app.focus != Focus::Orders || app.orders_view != OrdersView::Open {
+                app.show_warning("select an open order in the orders panel first");
+                return None;
+            }
+            let Some(order) = app.selected_open_order(symbol) else {
+                app.show_warning("select an order with j and k first");
+                return None;
+            };
+            app.order_edit = Some(EditOrderState::new(order));
+            app.mode = Mode::EditOrder;
+            None
+        }
+
+        // Dead man's switch timeout prompt
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cancel_after_input = TextInput::new();
+            if let Some(timeout) = app.cancel_after_timeout {
+                for c in timeout.to_string().chars() {
+                    app.cancel_after_input.insert(c);
+                }
+            }
+            app.mode = Mode::CancelAfter;
             None
         }
 
@@ -874,8 +1306,31 @@ fn handle_trading_pair_tab_keys(app: &mut App, key: KeyEvent, _symbol: &str) ->
     }
 }
 
+/// Returns the agent panel index for an agent output focus target.
+fn agent_output_index(focus: Focus) -> Option<usize> {
+    match focus {
+        Focus::AgentOutput1 => Some(0),
+        Focus::AgentOutput2 => Some(1),
+        Focus::AgentOutput3 => Some(2),
+        _ => None,
+    }
+}
+
+/// Handles keys in the notification log (Esc is handled globally).
+fn handle_notification_log_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('c') => app.clear_notification_history(),
+        KeyCode::Enter | KeyCode::Char('q') => app.mode = Mode::Normal,
+        _ => {}
+    }
+    None
+}
+
 /// Handles keys in insert mode (text input).
 fn handle_insert_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    if let Some(index) = agent_output_index(app.focus) {
+        return handle_filter_input(app, key, index);
+    }
     if app.focus != Focus::AgentInput {
         return None;
     }
@@ -885,6 +1340,7 @@ fn handle_insert_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
             let command = sanitize_input(&std::mem::take(&mut app.agent_input));
             app.agent_input_cursor = 0;
             app.mode = Mode::Normal;
+            app.input_history.push(&command);
             if !command.is_empty() {
                 return Some(Action::SendToAgent1(command));
             }
@@ -932,10 +1388,57 @@ fn handle_insert_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
             app.agent_input_cursor = app.agent_input.len();
             None
         }
+        KeyCode::Up => {
+            app.input_history.previous(&mut app.agent_input);
+            app.agent_input_cursor = app.agent_input.len();
+            None
+        }
+        KeyCode::Down => {
+            app.input_history.next(&mut app.agent_input);
+            app.agent_input_cursor = app.agent_input.len();
+            None
+        }
         _ => None,
     }
 }
 
+/// Handles typing into an agent panel's keyword filter.
+///
+/// Enter applies the filter; an empty filter is removed.
+fn handle_filter_input(app: &mut App, key: KeyEvent, index: usize) -> Option<Action> {
+    let filter = app.agent_output_filter[index].get_or_insert_with(String::new);
+    match key.code {
+        KeyCode::Char(c) if !c.is_control() && filter.len() < MAX_FILTER_LENGTH => {
+            filter.push(c);
+        }
+        KeyCode::Backspace => {
+            filter.pop();
+        }
+        KeyCode::Enter => {
+            if filter.is_empty() {
+                app.agent_output_filter[index] = None;
+            }
+            app.mode = Mode::Normal;
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Switches the chart timeframe, discarding candles of the old interval.
+fn change_timeframe(app: &mut App, timeframe: Timeframe) -> Option<Action> {
+    if app.chart_timeframe == timeframe {
+        return None;
+    }
+    let from = std::mem::replace(&mut app.chart_timeframe, timeframe);
+    app.candles.clear();
+    app.chart_cursor = None;
+    Some(Action::ChangeTimeframe {
+        from,
+        to: timeframe,
+    })
+}
+
 /// Strips control characters and trims whitespace from operator input
 /// before it reaches the agent layer.
 fn sanitize_input(raw: &str) -> String {
@@ -946,91 +1449,242 @@ fn sanitize_input(raw: &str) -> String {
         .to_string()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn sanitize_strips_control_characters() {
-        assert_eq!(sanitize_input("hello\x00world"), "helloworld");
-        assert_eq!(sanitize_input("buy\t100\n"), "buy100");
-        assert_eq!(sanitize_input("\x1b[31mred\x1b[0m"), "[31mred[0m");
+/// Handles keys in confirm mode (dialogs).
+fn handle_confirm_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => {
+            app.mode = Mode::Normal;
+            Some(Action::ConfirmOrder)
+        }
+        KeyCode::Char('n') | KeyCode::Esc => {
+            app.pending_order = None;
+            app.mode = Mode::Normal;
+            None
+        }
+        _ => None,
     }
+}
 
-    #[test]
-    fn sanitize_trims_whitespace() {
-        assert_eq!(sanitize_input("  buy 100  "), "buy 100");
-    }
+/// Handles keys in the pair search overlay.
+///
+/// Typing filters the pair selector live; `Enter` subscribes to the
+/// highlighted match.
+fn handle_pair_search_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char(c) if c.is_ascii_graphic() || c == ' ' => {
+            app.pair_search.insert(c);
+            app.pair_selector_index = 0;
+            None
+        }
+        KeyCode::Backspace => {
+            app.pair_search.backspace();
+            app.pair_selector_index = 0;
+            None
+        }
+        KeyCode::Down => {
+            if app.pair_selector_index < app.visible_pairs().len().saturating_sub(1) {
+                app.pair_selector_index += 1;
+            }
+            None
+        }
+        KeyCode::Up => {
+            app.pair_selector_index = app.pair_selector_index.saturating_sub(1);
+            None
+        }
+        KeyCode::Enter => {
+            let symbol = app
+                .visible_pairs()
+                .get(app.pair_selector_index)
+                .map(|s| s.to_string());
+            app.pair_search.take();
+            app.mode = Mode::Normal;
 
-    #[test]
-    fn sanitize_empty_and_whitespace_only() {
-        assert_eq!(sanitize_input(""), "");
-        assert_eq!(sanitize_input("   "), "");
-        assert_eq!(sanitize_input("\n\t\r"), "");
+            let symbol = symbol?;
+            // Keep the cursor on the chosen pair in the unfiltered selector
+            app.pair_selector_index = app
+                .available_pairs
+                .iter()
+                .position(|p| *p == symbol)
+                .unwrap_or(0);
+            if app.is_pair_selected(&symbol) {
+                return None;
+            }
+            app.toggle_pair(&symbol);
+            Some(Action::SubscribePair(symbol))
+        }
+        KeyCode::Esc => {
+            app.pair_search.take();
+            app.pair_selector_index = 0;
+            app.mode = Mode::Normal;
+            None
+        }
+        _ => None,
     }
+}
 
-    #[test]
-    fn sanitize_preserves_valid_unicode() {
-        assert_eq!(sanitize_input("buy BTC/USD 0.5"), "buy BTC/USD 0.5");
-        assert_eq!(sanitize_input("price ≥ 100"), "price ≥ 100");
-    }
-
-    #[test]
-    fn max_input_length_rejects_at_limit() {
-        let mut app = App::new();
-        app.mode = Mode::Insert;
-        app.focus = Focus::AgentInput;
-
-        // Fill to exactly MAX_INPUT_LENGTH with ASCII chars
-        app.agent_input = "a".repeat(MAX_INPUT_LENGTH);
-        app.agent_input_cursor = MAX_INPUT_LENGTH;
-
-        // One more char should be rejected
-        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
-        let action = handle_insert_mode(&mut app, key);
-        assert!(action.is_none());
-        assert_eq!(app.agent_input.len(), MAX_INPUT_LENGTH);
+/// Handles keys in the global search overlay.
+fn handle_global_search_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char(c) if c.is_ascii_graphic() || c == ' ' => {
+            app.global_search.insert(c);
+            None
+        }
+        KeyCode::Backspace => {
+            app.global_search.backspace();
+            None
+        }
+        KeyCode::Esc | KeyCode::Enter => {
+            app.global_search.take();
+            app.mode = Mode::Normal;
+            None
+        }
+        _ => None,
     }
+}
 
-    #[test]
-    fn max_input_length_allows_under_limit() {
-        let mut app = App::new();
-        app.mode = Mode::Insert;
-        app.focus = Focus::AgentInput;
-
-        app.agent_input = "a".repeat(MAX_INPUT_LENGTH - 1);
-        app.agent_input_cursor = MAX_INPUT_LENGTH - 1;
-
-        let key = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE);
-        let action = handle_insert_mode(&mut app, key);
-        assert!(action.is_none());
-        assert_eq!(app.agent_input.len(), MAX_INPUT_LENGTH);
-        assert!(app.agent_input.ends_with('z'));
+/// Handles keys in the dead man's switch timeout prompt.
+fn handle_cancel_after_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            app.cancel_after_input.insert(c);
+            None
+        }
+        KeyCode::Backspace => {
+            app.cancel_after_input.backspace();
+            None
+        }
+        KeyCode::Left => {
+            app.cancel_after_input.move_left();
+            None
+        }
+        KeyCode::Right => {
+            app.cancel_after_input.move_right();
+            None
+        }
+        KeyCode::Enter => {
+            let timeout = match app.cancel_after_input.as_str().parse::<u32>() {
+                Ok(t) if t <= MAX_TIMEOUT_SECONDS => t,
+                _ => {
+                    app.show_error(format!(
+                        "timeout must be between 0 and {MAX_TIMEOUT_SECONDS} seconds"
+                    ));
+                    return None;
+                }
+            };
+            app.cancel_after_input.take();
+            app.mode = Mode::Normal;
+            if !app.private_connected {
+                app.show_error("dead man's switch requires an authenticated connection");
+                return None;
+            }
+            app.cancel_after_timeout = (timeout > 0).then_some(timeout);
+            Some(Action::SetCancelAfter(timeout))
+        }
+        KeyCode::Esc => {
+            app.cancel_after_input.take();
+            app.mode = Mode::Normal;
+            None
+        }
+        _ => None,
     }
+}
 
-    #[test]
-    fn control_characters_rejected_during_input() {
-        let mut app = App::new();
-        app.mode = Mode::Insert;
-        app.focus = Focus::AgentInput;
-
-        // Try inserting a null byte
-        let key = KeyEvent::new(KeyCode::Char('\0'), KeyModifiers::NONE);
-        let action = handle_insert_mode(&mut app, key);
-        assert!(action.is_none());
-        assert!(app.agent_input.is_empty());
+/// Handles keys in the candle annotation prompt.
+///
+/// Saving an empty note removes the annotation.
+fn handle_annotate_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char(c) => {
+            app.annotation_input.insert(c);
+            None
+        }
+        KeyCode::Backspace => {
+            app.annotation_input.backspace();
+            None
+        }
+        KeyCode::Left => {
+            app.annotation_input.move_left();
+            None
+        }
+        KeyCode::Right => {
+            app.annotation_input.move_right();
+            None
+        }
+        KeyCode::Enter => {
+            let note = sanitize_input(&app.annotation_input.take());
+            app.mode = Mode::Normal;
+            let (symbol, timestamp) = app.annotation_target.take()?;
+            if note.is_empty() {
+                app.remove_annotation(&symbol, &timestamp);
+            } else {
+                app.add_annotation(&symbol, &timestamp, note);
+            }
+            None
+        }
+        KeyCode::Esc => {
+            app.annotation_input.take();
+            app.annotation_target = None;
+            app.mode = Mode::Normal;
+            None
+        }
+        _ => None,
     }
 }
 
-/// Handles keys in confirm mode (dialogs).
-fn handle_confirm_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+/// Handles keys in the order edit overlay.
+fn handle_edit_order_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
+    let state = app.order_edit.as_mut()?;
+
     match key.code {
-        KeyCode::Char('y') | KeyCode::Enter => {
+        KeyCode::Tab | KeyCode::Down | KeyCode::Up | KeyCode::BackTab => {
+            state.selected = (state.selected + 1) % EditOrderState::FIELD_COUNT;
+            None
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+            state.selected_input().insert(c);
+            None
+        }
+        KeyCode::Backspace => {
+            state.selected_input().backspace();
+            None
+        }
+        KeyCode::Left => {
+            state.selected_input().move_left();
+            None
+        }
+        KeyCode::Right => {
+            state.selected_input().move_right();
+            None
+        }
+        KeyCode::Enter => {
+            let parse = |input: &TextInput| {
+                input
+                    .as_str()
+                    .parse::<rust_decimal::Decimal>()
+                    .ok()
+                    .filter(|v| v.is_sign_positive() && !v.is_zero())
+            };
+            let (Some(price), Some(qty)) = (parse(&state.price), parse(&state.qty)) else {
+                app.show_error("price and quantity must be positive numbers");
+                return None;
+            };
+            let params = EditOrderBuilder::new(&state.order_id, &state.symbol)
+                .with_limit_price(price)
+                .with_order_qty(qty)
+                // Stamped with the live token by the connection manager
+                .build_params("pending");
+            app.order_edit = None;
             app.mode = Mode::Normal;
-            Some(Action::ConfirmOrder)
+            match params {
+                Ok(params) => Some(Action::EditOrder(Box::new(params))),
+                Err(e) => {
+                    app.show_error(format!("invalid order edit: {e}"));
+                    None
+                }
+            }
         }
-        KeyCode::Char('n') | KeyCode::Esc => {
-            app.pending_order = None;
+        KeyCode::Esc => {
+            app.order_edit = None;
             app.mode = Mode::Normal;
             None
         }
@@ -1080,6 +1734,10 @@ fn handle_risk_edit_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
 
         // Save and close
         KeyCode::Char('s') => {
+            if let Err(e) = state.params.validate() {
+                app.show_error(format!("invalid risk params: {e}"));
+                return None;
+            }
             let params = state.params.clone();
             app.agent_risk_params = params.clone();
             app.risk_edit = None;
@@ -1211,7 +1869,7 @@ fn handle_api_keys_mode(app: &mut App, key: KeyEvent) -> Option<Action> {
                 .count();
 
             if unchanged > 0 && values.iter().all(|v| v.is_none()) {
-                app.show_error(format!("{unchanged} key(s) unchanged (already set)"));
+                app.show_warning(format!("{unchanged} key(s) unchanged (already set)"));
                 app.api_keys_edit = None;
                 app.mode = Mode::Normal;
                 return None;
@@ -1297,3 +1955,975 @@ fn handle_api_key_field_edit(app: &mut App, key: KeyEvent) -> Option<Action> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instrument_message(tpe: &str, pairs: &[(&str, &str)]) -> Message {
+        let pairs: Vec<serde_json::Value> = pairs
+            .iter()
+            .map(|(symbol, status)| {
+                serde_json::json!({
+                    "symbol": symbol,
+                    "base": "X",
+                    "quote": "USD",
+                    "status": status,
+                    "qty_precision": 8,
+                    "qty_increment": "0.00000001",
+                    "price_precision": 1,
+                    "price_increment": "0.1",
+                    "cost_precision": 5,
+                    "cost_min": "0.5",
+                    "qty_min": "0.0001",
+                    "marginable": false,
+                    "margin_initial": null,
+                    "position_limit_long": null,
+                    "position_limit_short": null,
+                    "has_index": true
+                })
+            })
+            .collect();
+        Message::InstrumentUpdate(
+            serde_json::from_value(serde_json::json!({
+                "channel": "instrument",
+                "type": tpe,
+                "data": { "assets": [], "pairs": pairs }
+            }))
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn instrument_snapshot_replaces_available_pairs() {
+        let mut app = App::new();
+        app.pair_selector_index = 7;
+
+        update(
+            &mut app,
+            instrument_message(
+                "snapshot",
+                &[
+                    ("XBT/EUR", "online"),
+                    ("AAVE/USD", "online"),
+                    ("LUNA/USD", "delisted"),
+                ],
+            ),
+        );
+
+        assert_eq!(app.available_pairs, ["AAVE/USD", "XBT/EUR"]);
+        assert_eq!(app.pair_selector_index, 1);
+    }
+
+    #[test]
+    fn instrument_updates_and_empty_snapshots_keep_available_pairs() {
+        let mut app = App::new();
+        let defaults = app.available_pairs.clone();
+
+        update(
+            &mut app,
+            instrument_message("update", &[("AAVE/USD", "online")]),
+        );
+        update(
+            &mut app,
+            instrument_message("snapshot", &[("LUNA/USD", "delisted")]),
+        );
+
+        assert_eq!(app.available_pairs, defaults);
+    }
+
+    #[test]
+    fn sanitize_strips_control_characters() {
+        assert_eq!(sanitize_input("hello\x00world"), "helloworld");
+        assert_eq!(sanitize_input("buy\t100\n"), "buy100");
+        assert_eq!(sanitize_input("\x1b[31mred\x1b[0m"), "[31mred[0m");
+    }
+
+    #[test]
+    fn sanitize_trims_whitespace() {
+        assert_eq!(sanitize_input("  buy 100  "), "buy 100");
+    }
+
+    #[test]
+    fn sanitize_empty_and_whitespace_only() {
+        assert_eq!(sanitize_input(""), "");
+        assert_eq!(sanitize_input("   "), "");
+        assert_eq!(sanitize_input("\n\t\r"), "");
+    }
+
+    #[test]
+    fn sanitize_preserves_valid_unicode() {
+        assert_eq!(sanitize_input("buy BTC/USD 0.5"), "buy BTC/USD 0.5");
+        assert_eq!(sanitize_input("price ≥ 100"), "price ≥ 100");
+    }
+
+    #[test]
+    fn max_input_length_rejects_at_limit() {
+        let mut app = App::new();
+        app.mode = Mode::Insert;
+        app.focus = Focus::AgentInput;
+
+        // Fill to exactly MAX_INPUT_LENGTH with ASCII chars
+        app.agent_input = "a".repeat(MAX_INPUT_LENGTH);
+        app.agent_input_cursor = MAX_INPUT_LENGTH;
+
+        // One more char should be rejected
+        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        let action = handle_insert_mode(&mut app, key);
+        assert!(action.is_none());
+        assert_eq!(app.agent_input.len(), MAX_INPUT_LENGTH);
+    }
+
+    #[test]
+    fn max_input_length_allows_under_limit() {
+        let mut app = App::new();
+        app.mode = Mode::Insert;
+        app.focus = Focus::AgentInput;
+
+        app.agent_input = "a".repeat(MAX_INPUT_LENGTH - 1);
+        app.agent_input_cursor = MAX_INPUT_LENGTH - 1;
+
+        let key = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE);
+        let action = handle_insert_mode(&mut app, key);
+        assert!(action.is_none());
+        assert_eq!(app.agent_input.len(), MAX_INPUT_LENGTH);
+        assert!(app.agent_input.ends_with('z'));
+    }
+
+    #[test]
+    fn control_characters_rejected_during_input() {
+        let mut app = App::new();
+        app.mode = Mode::Insert;
+        app.focus = Focus::AgentInput;
+
+        // Try inserting a null byte
+        let key = KeyEvent::new(KeyCode::Char('\0'), KeyModifiers::NONE);
+        let action = handle_insert_mode(&mut app, key);
+        assert!(action.is_none());
+        assert!(app.agent_input.is_empty());
+    }
+
+    #[test]
+    fn ctrl_d_prompt_sets_cancel_after() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.next_tab();
+        app.private_connected = true;
+
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert!(handle_key(&mut app, ctrl_d).is_none());
+        assert_eq!(app.mode, Mode::CancelAfter);
+
+        for c in ['6', '0'] {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            );
+        }
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let action = handle_key(&mut app, enter);
+
+        assert!(matches!(action, Some(Action::SetCancelAfter(60))));
+        assert_eq!(app.cancel_after_timeout, Some(60));
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn cancel_after_prompt_rejects_timeout_above_max() {
+        let mut app = App::new();
+        app.mode = Mode::CancelAfter;
+        app.private_connected = true;
+        for c in (MAX_TIMEOUT_SECONDS + 1).to_string().chars() {
+            app.cancel_after_input.insert(c);
+        }
+
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(handle_key(&mut app, enter).is_none());
+        assert_eq!(app.mode, Mode::CancelAfter);
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn batch_order_result_reports_each_order() {
+        let mut app = App::new();
+        let response: BatchAddResponse = serde_json::from_value(serde_json::json!({
+            "method": "batch_add",
+            "result": [
+                { "order_id": "OAAAAA-AAAAA-AAAAA1", "cl_ord_id": "first" },
+                { "order_id": "OAAAAA-AAAAA-AAAAA2" }
+            ],
+            "success": true,
+            "time_in": "2022-06-13T08:09:10.123456Z",
+            "time_out": "2022-06-13T08:09:10.789012Z"
+        }))
+        .unwrap();
+
+        assert!(update(&mut app, Message::BatchOrderResult(response)).is_none());
+
+        let lines: Vec<&String> = app.agent_outputs[0].iter().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "[batch order placed] OAAAAA-AAAAA-AAAAA1 (first)",
+                "[batch order placed] OAAAAA-AAAAA-AAAAA2",
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_order_rejection_shows_error() {
+        let mut app = App::new();
+        let response: BatchAddResponse = serde_json::from_value(serde_json::json!({
+            "method": "batch_add",
+            "success": false,
+            "error": "EOrder:Insufficient funds",
+            "time_in": "2022-06-13T08:09:10.123456Z",
+            "time_out": "2022-06-13T08:09:10.789012Z"
+        }))
+        .unwrap();
+
+        update(&mut app, Message::BatchOrderResult(response));
+
+        assert_eq!(
+            app.agent_outputs[0].back().map(String::as_str),
+            Some("[batch rejected] EOrder:Insufficient funds")
+        );
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn pair_search_subscribes_highlighted_match() {
+        let mut app = App::new();
+        let slash = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert!(handle_key(&mut app, slash).is_none());
+        assert_eq!(app.mode, Mode::PairSearch);
+
+        // 'q' is part of the query, not the quit key
+        for c in ['q', 'u'] {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            );
+        }
+        assert!(!app.should_quit);
+        assert!(app.visible_pairs().is_empty());
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+        );
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+        );
+        for c in ['d', 'o'] {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            );
+        }
+        handle_key(&mut app, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let action = handle_key(&mut app, enter);
+
+        assert!(matches!(action, Some(Action::SubscribePair(ref s)) if s == "DOT/USD"));
+        assert!(app.is_pair_selected("DOT/USD"));
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.available_pairs[app.pair_selector_index], "DOT/USD");
+    }
+
+    #[test]
+    fn disconnect_shows_close_code_and_reason() {
+        let mut app = App::new();
+        let error = crate::LeesonError::WebSocketClosed {
+            code: Some(1001),
+            reason: Some("maintenance".to_string()),
+        };
+
+        update(&mut app, Message::Disconnected(Some(error)));
+
+        assert_eq!(
+            app.error_message.as_ref().map(|e| e.message.as_str()),
+            Some("Kraken: maintenance (1001)")
+        );
+    }
+
+    #[test]
+    fn alt_digit_jumps_to_pair_tab() {
+        let mut app = App::new();
+        for symbol in ["BTC/USD", "ETH/USD", "SOL/USD"] {
+            app.toggle_pair(symbol);
+        }
+        let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+        assert_eq!(app.selected_pair_index(), None);
+
+        handle_key(&mut app, alt('3'));
+        assert_eq!(app.selected_pair_index(), Some(2));
+        assert_eq!(app.current_tab(), &Tab::TradingPair("SOL/USD".to_string()));
+        assert_eq!(app.focus, Focus::OrderBook);
+
+        handle_key(&mut app, alt('1'));
+        assert_eq!(app.selected_pair_index(), Some(0));
+        assert_eq!(app.current_tab(), &Tab::TradingPair("BTC/USD".to_string()));
+
+        // Out of range: stay on the current tab
+        handle_key(&mut app, alt('4'));
+        assert_eq!(app.selected_pair_index(), Some(0));
+    }
+
+    #[test]
+    fn h_toggles_heatmap_only_in_order_book() {
+        use crate::tui::app::BookView;
+
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.next_tab();
+        let h = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(app.focus, Focus::OrderBook);
+
+        handle_key(&mut app, h);
+        assert_eq!(app.book_view, BookView::Heatmap);
+
+        // Elsewhere `h` still moves focus left
+        app.focus = Focus::Chart;
+        handle_key(&mut app, h);
+        assert_eq!(app.focus, Focus::OrderBook);
+        assert_eq!(app.book_view, BookView::Heatmap);
+    }
+
+    fn candle_at(interval_begin: &str) -> crate::models::candle::CandleData {
+        serde_json::from_value(serde_json::json!({
+            "symbol": "BTC/USD",
+            "open": "100", "high": "110", "low": "90", "close": "105",
+            "vwap": "102", "trades": 3, "volume": "1.5",
+            "interval_begin": interval_begin,
+            "interval": 1,
+            "timestamp": interval_begin
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn annotate_selected_candle() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+        app.focus = Focus::Chart;
+        app.candles.insert(
+            "BTC/USD".to_string(),
+            ["t0", "t1", "t2"].into_iter().map(candle_at).collect(),
+        );
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // Nothing selected yet
+        handle_key(&mut app, key('m'));
+        assert_eq!(app.mode, Mode::Normal);
+
+        // Select the second-newest candle and annotate it
+        handle_key(&mut app, key('['));
+        handle_key(&mut app, key('['));
+        assert_eq!(app.chart_cursor, Some(1));
+        handle_key(&mut app, key('m'));
+        assert_eq!(app.mode, Mode::Annotate);
+        for c in "entry q".chars() {
+            handle_key(&mut app, key(c));
+        }
+        handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.get_annotation("BTC/USD", "t1"), Some("entry q"));
+
+        // Saving an empty note removes it
+        handle_key(&mut app, key('m'));
+        for _ in 0.."entry q".len() {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+            );
+        }
+        handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.get_annotation("BTC/USD", "t1"), None);
+    }
+
+    fn open_order(order_id: &str) -> crate::models::execution::ExecutionData {
+        serde_json::from_value(serde_json::json!({
+            "order_id": order_id,
+            "symbol": "BTC/USD",
+            "side": "buy",
+            "order_type": "limit",
+            "order_qty": "0.5",
+            "order_status": "new",
+            "limit_price": "30000",
+            "exec_type": "new",
+            "timestamp": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn edit_selected_open_order() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+        app.open_orders.insert(
+            "BTC/USD".to_string(),
+            vec![open_order("OAAAAA"), open_order("OBBBBB")],
+        );
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // Only opens from the orders panel
+        handle_key(&mut app, key('e'));
+        assert_eq!(app.mode, Mode::Normal);
+
+        app.focus = Focus::Orders;
+        handle_key(&mut app, key('j'));
+        handle_key(&mut app, key('j'));
+        handle_key(&mut app, key('j'));
+        assert_eq!(app.selected_order_id("BTC/USD").as_deref(), Some("OBBBBB"));
+        handle_key(&mut app, key('e'));
+        assert_eq!(app.mode, Mode::EditOrder);
+
+        let state = app.order_edit.as_ref().unwrap();
+        assert_eq!(state.order_id, "OBBBBB");
+        assert_eq!(state.price.as_str(), "30000");
+        assert_eq!(state.qty.as_str(), "0.5");
+
+        // Bump the price, then the quantity
+        handle_key(&mut app, key('1'));
+        handle_key(&mut app, KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE),
+        );
+        handle_key(&mut app, key('7'));
+
+        let action = handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let Some(Action::EditOrder(params)) = action else {
+            panic!("expected EditOrder, got {action:?}");
+        };
+        assert_eq!(params.order_id, "OBBBBB");
+        assert_eq!(params.symbol, "BTC/USD");
+        assert_eq!(params.limit_price, Some(rust_decimal_macros::dec!(300001)));
+        assert_eq!(params.order_qty, Some(rust_decimal_macros::dec!(0.7)));
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.order_edit.is_none());
+    }
+
+    #[test]
+    fn ctrl_c_in_orders_panel_cancels_all_visible() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+        app.focus = Focus::Orders;
+        assert!(handle_key(&mut app, ctrl_c).is_none());
+        assert!(app.error_message.is_some());
+
+        app.open_orders.insert(
+            "BTC/USD".to_string(),
+            vec![open_order("OAAAAA"), open_order("OBBBBB")],
+        );
+        let action = handle_key(&mut app, ctrl_c);
+        assert!(matches!(action, Some(Action::CancelAllVisible(ref s)) if s == "BTC/USD"));
+    }
+
+    #[test]
+    fn c_cancels_only_the_selected_order() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+        app.open_orders.insert(
+            "BTC/USD".to_string(),
+            vec![open_order("OAAAAA"), open_order("OBBBBB")],
+        );
+        let c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+
+        assert!(handle_key(&mut app, c).is_none());
+
+        // Without a selection nothing is cancelled
+        app.focus = Focus::Orders;
+        assert!(handle_key(&mut app, c).is_none());
+        assert_eq!(
+            app.error_message.as_ref().map(|e| e.message.as_str()),
+            Some("select an order with j and k first")
+        );
+
+        app.selected_orders
+            .insert("BTC/USD".to_string(), "OBBBBB".to_string());
+        let action = handle_key(&mut app, c);
+        assert!(matches!(
+            action,
+            Some(Action::CancelOrder(ref id)) if id == "OBBBBB"
+        ));
+    }
+
+    #[test]
+    fn level3_messages_maintain_book() {
+        let mut app = App::new();
+        let message = |tpe: &str, event: Option<&str>, qty: &str| {
+            Message::Level3(
+                serde_json::from_value(serde_json::json!({
+                    "channel": "level3",
+                    "type": tpe,
+                    "data": [{
+                        "symbol": "BTC/USD",
+                        "bids": [{
+                            "event": event,
+                            "order_id": "OBID01",
+                            "limit_price": "50000",
+                            "order_qty": qty,
+                            "timestamp": "2024-01-01T00:00:00Z"
+                        }],
+                        "asks": [],
+                        "checksum": 0,
+                        "timestamp": "2024-01-01T00:00:00Z"
+                    }]
+                }))
+                .unwrap(),
+            )
+        };
+
+        update(&mut app, message("snapshot", None, "1"));
+        update(&mut app, message("update", Some("modify"), "0.25"));
+
+        let book = &app.level3_books["BTC/USD"];
+        assert_eq!(
+            book.best_bid(),
+            Some((
+                rust_decimal_macros::dec!(50000),
+                rust_decimal_macros::dec!(0.25)
+            ))
+        );
+
+        update(&mut app, message("update", Some("delete"), "0"));
+        assert!(app.level3_books["BTC/USD"].is_empty());
+    }
+
+    #[test]
+    fn ctrl_n_opens_notification_log() {
+        let mut app = App::new();
+        app.show_error("order rejected");
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(app.mode, Mode::NotificationLog);
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE),
+        );
+        assert!(app.notification_history.is_empty());
+
+        handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Normal);
+    }
+
+    #[test]
+    fn ctrl_slash_opens_global_search_and_captures_typing() {
+        let mut app = App::new();
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('/'), KeyModifiers::CONTROL),
+        );
+        assert_eq!(app.mode, Mode::GlobalSearch);
+
+        for c in ['q', 'b'] {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            );
+        }
+        assert!(!app.should_quit);
+        assert_eq!(app.global_search.as_str(), "qb");
+
+        handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.global_search.is_empty());
+    }
+
+    #[test]
+    fn batch_cancel_result_shows_count() {
+        let mut app = App::new();
+        let response = serde_json::from_value(serde_json::json!({
+            "method": "batch_cancel",
+            "success": true,
+            "result": { "count": 2 },
+            "time_in": "2024-01-01T00:00:00Z",
+            "time_out": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+
+        update(&mut app, Message::BatchCancelResult(response));
+
+        assert_eq!(
+            app.error_message.as_ref().map(|e| e.message.as_str()),
+            Some("Cancelled 2 orders")
+        );
+    }
+
+    #[test]
+    fn edit_order_rejects_zero_quantity() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+        app.focus = Focus::Orders;
+        app.open_orders
+            .insert("BTC/USD".to_string(), vec![open_order("OAAAAA")]);
+        app.selected_orders
+            .insert("BTC/USD".to_string(), "OAAAAA".to_string());
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE),
+        );
+        let state = app.order_edit.as_mut().unwrap();
+        state.qty = TextInput::new();
+        state.qty.insert('0');
+
+        let action = handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(action.is_none());
+        assert_eq!(app.mode, Mode::EditOrder);
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn order_selection_follows_the_order_as_the_list_changes() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+        app.focus = Focus::Orders;
+        app.open_orders.insert(
+            "BTC/USD".to_string(),
+            vec![open_order("OAAAAA"), open_order("OBBBBB")],
+        );
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let closed = |order_id: &str| {
+            let mut order = open_order(order_id);
+            order.order_status = "filled".to_string();
+            Message::Execution(ExecutionUpdateResponse {
+                channel: "executions".to_string(),
+                tpe: "update".to_string(),
+                sequence: 1,
+                data: vec![order],
+            })
+        };
+
+        handle_key(&mut app, key('j'));
+        handle_key(&mut app, key('j'));
+        assert_eq!(app.selected_order_id("BTC/USD").as_deref(), Some("OBBBBB"));
+
+        // An order above the selection fills: the selection stays on its order
+        update(&mut app, closed("OAAAAA"));
+        assert_eq!(app.selected_order_id("BTC/USD").as_deref(), Some("OBBBBB"));
+        assert_eq!(app.selected_order_index("BTC/USD"), Some(0));
+
+        // The selected order fills: nothing is selected
+        update(&mut app, closed("OBBBBB"));
+        assert_eq!(app.selected_order_id("BTC/USD"), None);
+
+        // `k` without a selection returns focus to the chart
+        handle_key(&mut app, key('k'));
+        assert_eq!(app.focus, Focus::Chart);
+    }
+
+    #[test]
+    fn switching_tabs_clears_order_selection() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.toggle_pair("ETH/USD");
+        app.select_pair_tab(0);
+        app.focus = Focus::Orders;
+        app.open_orders
+            .insert("BTC/USD".to_string(), vec![open_order("OAAAAA")]);
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE),
+        );
+        assert!(app.selected_order_id("BTC/USD").is_some());
+
+        app.select_pair_tab(1);
+        app.select_pair_tab(0);
+
+        assert_eq!(app.selected_order_id("BTC/USD"), None);
+    }
+
+    #[test]
+    fn order_edited_reports_replacement_id() {
+        let mut app = App::new();
+        let response: EditOrderResponse = serde_json::from_value(serde_json::json!({
+            "method": "edit_order",
+            "result": { "order_id": "ONEW00", "original_order_id": "OOLD00" },
+            "success": true
+        }))
+        .unwrap();
+
+        update(&mut app, Message::OrderEdited(response));
+        let notification = app.error_message.as_ref().unwrap();
+        assert_eq!(notification.message, "Order OOLD00 replaced by ONEW00");
+    }
+
+    #[test]
+    fn reconnecting_shows_reason() {
+        use crate::tui::app::{ConnectionStatus, NotificationLevel};
+
+        let mut app = App::new();
+        app.private_connected = true;
+
+        update(
+            &mut app,
+            Message::Reconnecting {
+                reason: "token expired".to_string(),
+            },
+        );
+
+        assert_eq!(
+            app.connection_status,
+            ConnectionStatus::Reconnecting("token expired".to_string())
+        );
+        assert!(!app.private_connected);
+        let notification = app.error_message.as_ref().unwrap();
+        assert_eq!(notification.message, "Reconnecting: token expired");
+        assert_eq!(notification.level, NotificationLevel::Warning);
+    }
+
+    #[test]
+    fn connection_stats_message_is_stored() {
+        let mut app = App::new();
+        let stats = crate::websocket::ConnectionStats {
+            reconnect_count: 2,
+            messages_received: 40,
+            ..Default::default()
+        };
+
+        update(&mut app, Message::ConnectionStats(stats.clone()));
+
+        assert_eq!(app.connection_stats, Some(stats));
+    }
+
+    #[test]
+    fn minute_candles_are_aggregated_until_native_candles_arrive() {
+        let mut app = App::new();
+        app.chart_timeframe = Timeframe::M5;
+        let candle = |begin: &str, interval: u64| {
+            serde_json::from_value(serde_json::json!({
+                "symbol": "BTC/USD",
+                "open": "100", "high": "110", "low": "90", "close": "105",
+                "vwap": "102", "trades": 3, "volume": "1.5",
+                "interval_begin": begin,
+                "interval": interval,
+                "timestamp": begin
+            }))
+            .unwrap()
+        };
+        let response = |data| CandleUpdateResponse {
+            channel: "ohlc".to_string(),
+            tpe: "update".to_string(),
+            timestamp: String::new(),
+            data,
+        };
+
+        update(
+            &mut app,
+            Message::Candle(response(vec![
+                candle("2024-01-01T10:00:00.000000000Z", 1),
+                candle("2024-01-01T10:01:00.000000000Z", 1),
+                candle("2024-01-01T10:05:00.000000000Z", 1),
+            ])),
+        );
+        let bars = app.chart_candles("BTC/USD").unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].volume, rust_decimal_macros::dec!(3.0));
+
+        // A native 5m candle replaces the 1m feed
+        update(
+            &mut app,
+            Message::Candle(response(vec![candle("2024-01-01T10:10:00.000000000Z", 5)])),
+        );
+        update(
+            &mut app,
+            Message::Candle(response(vec![candle("2024-01-01T10:11:00.000000000Z", 1)])),
+        );
+        assert_eq!(app.candles["BTC/USD"].len(), 1);
+        assert_eq!(app.chart_candles("BTC/USD").unwrap()[0].interval, 5);
+        assert_eq!(app.message_counter, 3);
+    }
+
+    #[test]
+    fn timeframe_key_requests_candle_resubscription() {
+        let mut app = App::new();
+        app.tabs.push(Tab::TradingPair("BTC/USD".to_string()));
+        app.active_tab = 1;
+        app.candles
+            .insert("BTC/USD".to_string(), Default::default());
+
+        let action = handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE),
+        );
+
+        assert!(matches!(
+            action,
+            Some(Action::ChangeTimeframe {
+                from: Timeframe::M1,
+                to: Timeframe::H1
+            })
+        ));
+        assert_eq!(app.chart_timeframe, Timeframe::H1);
+        assert!(app.candles.is_empty());
+
+        // Selecting the active timeframe again is a no-op
+        let action = handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('4'), KeyModifiers::NONE),
+        );
+        assert!(action.is_none());
+    }
+
+    #[test]
+    fn ctrl_k_clears_focused_agent_output() {
+        let mut app = App::new();
+        app.add_agent_output(0, "keep".to_string());
+        app.add_agent_output(2, "clear me".to_string());
+        app.focus = Focus::AgentOutput3;
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL),
+        );
+
+        assert!(app.agent_outputs[2].is_empty());
+        assert_eq!(app.agent_outputs[0].len(), 1);
+    }
+
+    #[test]
+    fn shift_p_pauses_owning_agent_and_holds_its_orders() {
+        let mut app = App::new();
+        app.focus = Focus::AgentOutput2;
+        let shift_p = KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        let order = |agent_index| Message::AgentOrderRequest {
+            agent_index,
+            symbol: "BTC/USD".to_string(),
+            side: "buy".to_string(),
+            order_type: "market".to_string(),
+            qty: "0.1".to_string(),
+            price: None,
+            cl_ord_id: None,
+        };
+
+        // Panel 2 shows a sub-agent of the multi-agent process, which is paused
+        let action = handle_key(&mut app, shift_p);
+        assert!(matches!(
+            action,
+            Some(Action::SetAgentPaused {
+                agent_index: 0,
+                paused: true
+            })
+        ));
+        assert_eq!(app.agent_paused, [true, false, false]);
+
+        assert!(update(&mut app, order(0)).is_none());
+        assert!(app.agent_outputs[0][0].starts_with("[order rejected] agent paused"));
+
+        let action = handle_key(&mut app, shift_p);
+        assert!(matches!(
+            action,
+            Some(Action::SetAgentPaused { paused: false, .. })
+        ));
+        assert!(matches!(
+            update(&mut app, order(0)),
+            Some(Action::SubmitOrder(_))
+        ));
+    }
+
+    #[test]
+    fn agent_output_filter_prompt() {
+        let mut app = App::new();
+        app.focus = Focus::AgentOutput2;
+
+        handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT),
+        );
+        assert_eq!(app.mode, Mode::Insert);
+        for c in ['b', 't', 'c'] {
+            handle_key(
+                &mut app,
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE),
+            );
+        }
+        handle_key(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(app.mode, Mode::Normal);
+        assert_eq!(app.agent_output_filter[1].as_deref(), Some("btc"));
+        assert_eq!(app.agent_input, "");
+
+        handle_key(&mut app, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.agent_output_filter[1], None);
+    }
+
+    #[test]
+    fn stream_deltas_build_partial_line_with_cursor() {
+        let mut app = App::new();
+        for delta in ["Buy", "ing BTC", "\nwaiting for ", "fill"] {
+            update(
+                &mut app,
+                Message::AgentStreamDelta {
+                    agent_index: 0,
+                    delta: delta.to_string(),
+                },
+            );
+        }
+
+        assert_eq!(
+            app.agent_panel_lines(0),
+            vec![
+                "Buying BTC".to_string(),
+                format!("waiting for fill{}", crate::tui::app::STREAM_CURSOR),
+            ]
+        );
+
+        update(&mut app, Message::AgentStreamEnd { agent_index: 0 });
+        assert_eq!(
+            app.agent_panel_lines(0),
+            vec!["Buying BTC".to_string(), "waiting for fill".to_string()]
+        );
+    }
+
+    #[test]
+    fn up_recalls_submitted_command_in_insert_mode() {
+        let mut app = App::new();
+        app.mode = Mode::Insert;
+        app.focus = Focus::AgentInput;
+        app.agent_input = "status".to_string();
+        handle_insert_mode(&mut app, KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        app.mode = Mode::Insert;
+        handle_insert_mode(&mut app, KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(app.agent_input, "status");
+        assert_eq!(app.agent_input_cursor, "status".len());
+
+        handle_insert_mode(&mut app, KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert!(app.agent_input.is_empty());
+        assert_eq!(app.agent_input_cursor, 0);
+    }
+
+    #[test]
+    fn ctrl_w_closes_pair_tab_and_unsubscribes() {
+        let mut app = App::new();
+        app.toggle_pair("BTC/USD");
+        app.select_pair_tab(0);
+
+        let action = handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL),
+        );
+
+        assert!(matches!(action, Some(Action::UnsubscribePair(ref s)) if s == "BTC/USD"));
+        assert!(app.selected_pairs.is_empty());
+        assert_eq!(app.current_tab(), &Tab::Agent);
+    }
+}
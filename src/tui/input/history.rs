@@ -0,0 +1,159 @@
+//! Command history for the agent input field.
+
+use std::collections::VecDeque;
+
+/// Maximum number of submitted commands remembered.
+pub const MAX_INPUT_HISTORY: usize = 50;
+
+/// Submitted commands with an optional navigation cursor.
+///
+/// Navigating copies an entry into the caller's buffer, so editing a
+/// recalled command never changes the stored history.
+#[derive(Clone, Debug, Default)]
+pub struct InputHistory {
+    /// Submitted commands, oldest first.
+    entries: VecDeque<String>,
+    /// Index of the entry currently loaded, or `None` when editing a new
+    /// command.
+    cursor: Option<usize>,
+}
+
+impl InputHistory {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a submitted command and ends navigation. Empty commands and
+    /// repeats of the newest entry are not stored.
+    pub fn push(&mut self, command: &str) {
+        self.cursor = None;
+        if command.is_empty() || self.entries.back().is_some_and(|last| last == command) {
+            return;
+        }
+        if self.entries.len() >= MAX_INPUT_HISTORY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(command.to_string());
+    }
+
+    /// Loads the entry before the current one into `buffer`, starting from
+    /// the newest. Stays on the oldest entry once reached.
+    pub fn previous(&mut self, buffer: &mut String) {
+        let index = match self.cursor {
+            None => self.entries.len().checked_sub(1),
+            Some(i) => Some(i.saturating_sub(1)),
+        };
+        if let Some(index) = index {
+            self.cursor = Some(index);
+            buffer.clone_from(&self.entries[index]);
+        }
+    }
+
+    /// Loads the entry after the current one into `buffer`. Moving past the
+    /// newest entry clears `buffer` for a new command.
+    pub fn next(&mut self, buffer: &mut String) {
+        let Some(i) = self.cursor else {
+            return;
+        };
+        if i + 1 < self.entries.len() {
+            self.cursor = Some(i + 1);
+            buffer.clone_from(&self.entries[i + 1]);
+        } else {
+            self.cursor = None;
+            buffer.clear();
+        }
+    }
+
+    /// Returns the index of the loaded entry, if navigating.
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Returns the number of stored commands.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no commands are stored.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(commands: &[&str]) -> InputHistory {
+        let mut history = InputHistory::new();
+        for command in commands {
+            history.push(command);
+        }
+        history
+    }
+
+    #[test]
+    fn up_and_down_walk_the_history() {
+        let mut history = history(&["first", "second", "third"]);
+        let mut buffer = String::new();
+
+        history.previous(&mut buffer);
+        assert_eq!(buffer, "third");
+        history.previous(&mut buffer);
+        history.previous(&mut buffer);
+        assert_eq!(buffer, "first");
+        assert_eq!(history.cursor(), Some(0));
+
+        // Stays on the oldest entry
+        history.previous(&mut buffer);
+        assert_eq!(buffer, "first");
+
+        history.next(&mut buffer);
+        assert_eq!(buffer, "second");
+        history.next(&mut buffer);
+        history.next(&mut buffer);
+        assert_eq!(buffer, "");
+        assert_eq!(history.cursor(), None);
+    }
+
+    #[test]
+    fn down_without_navigation_keeps_buffer() {
+        let mut history = history(&["first"]);
+        let mut buffer = "typing".to_string();
+
+        history.next(&mut buffer);
+
+        assert_eq!(buffer, "typing");
+    }
+
+    #[test]
+    fn editing_a_recalled_entry_leaves_history_intact() {
+        let mut history = history(&["buy btc"]);
+        let mut buffer = String::new();
+
+        history.previous(&mut buffer);
+        buffer.push_str(" now");
+        history.next(&mut buffer);
+        history.previous(&mut buffer);
+
+        assert_eq!(buffer, "buy btc");
+    }
+
+    #[test]
+    fn push_caps_length_and_skips_repeats() {
+        let mut history = InputHistory::new();
+        for i in 0..MAX_INPUT_HISTORY + 5 {
+            history.push(&format!("cmd {i}"));
+        }
+        history.push(&format!("cmd {}", MAX_INPUT_HISTORY + 4));
+        history.push("");
+
+        assert_eq!(history.len(), MAX_INPUT_HISTORY);
+        let mut buffer = String::new();
+        for _ in 0..MAX_INPUT_HISTORY {
+            history.previous(&mut buffer);
+        }
+        assert_eq!(buffer, "cmd 5");
+    }
+}
@@ -1,3 +1,4 @@
 //! Input handling utilities.
 
+pub mod history;
 pub mod text_input;
@@ -4,6 +4,7 @@
 //! order management, and agent interaction.
 
 pub mod app;
+pub mod candle_aggregator;
 pub mod components;
 pub mod event;
 pub mod input;
@@ -13,5 +14,5 @@ pub mod ui;
 
 pub use app::App;
 pub use event::{Event, Message};
-pub use terminal::{Tui, restore_terminal, setup_terminal};
+pub use terminal::Tui;
 pub use ui::render;
@@ -8,9 +8,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap},
 };
 
-use crate::tui::app::{App, Focus, Mode};
+use crate::agent::MULTI_AGENT_INDEX;
+use crate::tui::app::{App, Focus, Mode, STREAM_CURSOR};
 use crate::tui::components::{status_bar, tab_bar};
 
+/// Width of the token usage panel beside the agent input.
+const TOKEN_USAGE_WIDTH: u16 = 64;
+
 /// Renders the Agent tab.
 pub fn render(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
@@ -52,8 +56,13 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Pair selector
     render_pair_selector(frame, main_layout[6], app);
 
-    // Agent input
-    render_agent_input(frame, main_layout[7], app);
+    // Agent input with token usage to its right
+    let input_row = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(20), Constraint::Length(TOKEN_USAGE_WIDTH)])
+        .split(main_layout[7]);
+    render_agent_input(frame, input_row[0], app);
+    render_token_usage(frame, input_row[1], app);
 
     // Keybindings help
     render_keybindings(frame, main_layout[8], app);
@@ -93,10 +102,15 @@ fn render_agent_outputs(frame: &mut Frame, area: Rect, app: &mut App) {
         let inner_width = col.width.saturating_sub(2);
 
         // Snapshot output data to avoid borrow conflicts with scroll mutation
-        let output_lines: Vec<String> = app.agent_outputs[i].iter().cloned().collect();
+        let output_lines: Vec<String> = app
+            .filtered_agent_output(i)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let panel_lines = app.agent_panel_lines(i);
         let stream_buf = app.agent_stream_buffers[i].clone();
         let has_stream = !stream_buf.is_empty();
-        let is_pinned = app.agent_scroll[i].pinned;
+        let is_pinned = app.agent_scroll[i].follows_output();
 
         // Build text lines from agent output
         let mut lines: Vec<Line> = output_lines
@@ -108,22 +122,25 @@ fn render_agent_outputs(frame: &mut Frame, area: Rect, app: &mut App) {
         if has_stream && is_pinned {
             lines.push(Line::from(vec![
                 Span::raw(stream_buf.as_str()),
-                Span::styled("\u{258d}", Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    STREAM_CURSOR.to_string(),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::SLOW_BLINK),
+                ),
             ]));
         }
 
         let text = Text::from(lines);
         let paragraph = Paragraph::new(text).wrap(Wrap { trim: false });
 
-        // Compute total visual rows after wrapping
-        let total_visual = paragraph.line_count(inner_width);
-        let max_scroll = total_visual.saturating_sub(inner_height);
-
-        // Update scroll state
+        // Compute total visual rows after wrapping and update scroll state
         let scroll = &mut app.agent_scroll[i];
-        scroll.max_scroll = max_scroll;
+        let total_visual =
+            scroll.update_max_scroll(&panel_lines, inner_height, usize::from(inner_width));
+        let max_scroll = scroll.max_scroll;
 
-        let visual_offset = if scroll.pinned {
+        let visual_offset = if scroll.follows_output() {
             max_scroll
         } else {
             scroll.offset.min(max_scroll)
@@ -132,7 +149,7 @@ fn render_agent_outputs(frame: &mut Frame, area: Rect, app: &mut App) {
 
         // Build title with scroll indicator
         let title = if total_visual > inner_height {
-            let at_bottom = scroll.pinned || visual_offset >= max_scroll;
+            let at_bottom = scroll.follows_output() || visual_offset >= max_scroll;
             let indicator = if at_bottom {
                 "end".to_string()
             } else {
@@ -143,6 +160,24 @@ fn render_agent_outputs(frame: &mut Frame, area: Rect, app: &mut App) {
         } else {
             base_titles[i].to_string()
         };
+        let title = if scroll.hard_pinned {
+            format!("{title}📌 ")
+        } else {
+            title
+        };
+        // Every panel shows a sub-agent of the multi-agent process
+        let title = if app.agent_paused[MULTI_AGENT_INDEX] {
+            format!("{title}[PAUSED] ")
+        } else {
+            title
+        };
+        let title = match app.agent_output_filter[i] {
+            Some(ref filter) if is_focused && app.mode == Mode::Insert => {
+                format!("{title}filter: {filter}▏ ")
+            }
+            Some(ref filter) if !filter.is_empty() => format!("{title}filter: {filter} "),
+            _ => title,
+        };
 
         let block = Block::default()
             .title(title)
@@ -516,7 +551,8 @@ fn render_pair_selector(frame: &mut Frame, area: Rect, app: &App) {
     let pairs_per_row = 4;
     let mut lines: Vec<Line> = Vec::new();
 
-    for (i, chunk) in app.available_pairs.chunks(pairs_per_row).enumerate() {
+    let pairs = app.visible_pairs();
+    for (i, chunk) in pairs.chunks(pairs_per_row).enumerate() {
         let spans: Vec<Span> = chunk
             .iter()
             .enumerate()
@@ -546,6 +582,26 @@ fn render_pair_selector(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(para, inner);
 }
 
+/// Renders the tokens used by agents so far and their estimated cost.
+fn render_token_usage(frame: &mut Frame, area: Rect, app: &App) {
+    let usage = &app.token_usage;
+    let block = Block::default()
+        .title(" Token Usage ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+
+    let cost = usage
+        .estimated_cost()
+        .map_or("N/A".to_string(), |cost| format!("${cost:.2}"));
+    let line = Line::from(vec![
+        Span::raw(format!("Input: {} tokens / ", usage.input_tokens)),
+        Span::raw(format!("Output: {} tokens / ", usage.output_tokens)),
+        Span::styled(format!("Cost: {cost}"), Style::default().fg(Color::Magenta)),
+    ]);
+
+    frame.render_widget(Paragraph::new(line).block(block), area);
+}
+
 /// Renders the agent input field for Agent 1.
 fn render_agent_input(frame: &mut Frame, area: Rect, app: &App) {
     let is_focused = app.focus == Focus::AgentInput;
@@ -588,13 +644,22 @@ fn render_agent_input(frame: &mut Frame, area: Rect, app: &App) {
 /// Renders the keybindings help line.
 fn render_keybindings(frame: &mut Frame, area: Rect, app: &App) {
     let help = match app.mode {
-        Mode::Insert => "[Esc]normal [Enter]send to Agent 1",
+        Mode::Insert if app.focus != Focus::AgentInput => {
+            "[type]filter [Enter]apply [Esc]clear filter"
+        }
+        Mode::Insert => "[Esc]normal [Enter]send to Agent 1 [Up/Down]history",
         Mode::Normal => {
-            "[Tab]switch tab [Space]toggle pair [i]Agent 1 input [1-3]focus agent [j/k]scroll [g/G]top/bottom [r]risk [a]api keys [q]quit"
+            "[Tab]switch tab [Alt+1-9]jump to pair [Space]toggle pair [/]search pairs [i]Agent 1 input [1-3]focus agent [j/k]scroll [g/G]top/bottom [F]filter [^K]clear [^P]pin [P]pause [r]risk [^L]reload limits [^E]export trades [^N]notifications [^/]search [a]api keys [q]quit"
         }
         Mode::Confirm => "[y]yes [n]no",
         Mode::RiskEdit => "[j/k]navigate [Space]toggle [Enter]edit [s]save [Esc]cancel",
         Mode::ApiKeys => "[j/k]navigate [Enter]edit [s]save [Esc]cancel",
+        Mode::CancelAfter => "[0-9]timeout [Enter]set [Esc]cancel",
+        Mode::Annotate => "[type]note [Enter]save (empty removes) [Esc]cancel",
+        Mode::EditOrder => "[Tab]next field [Enter]submit [Esc]cancel",
+        Mode::PairSearch => "[type]filter [↑/↓]move [Enter]subscribe [Esc]cancel",
+        Mode::NotificationLog => "[c]clear [Esc]close",
+        Mode::GlobalSearch => "[type]search [Esc]close",
     };
 
     let para = Paragraph::new(help).style(Style::default().fg(Color::DarkGray));
@@ -7,10 +7,25 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
+use std::collections::VecDeque;
+
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::models::book::PriceLevel;
+use crate::models::trade::{TradeBar, TradeData};
+use crate::tui::app::{App, BookView, ChartType, Focus, Mode, OrdersView};
+use crate::tui::components::{heatmap, price_ladder, status_bar, tab_bar};
+
+/// Length of each interval in the trade volume bars.
+const VOLUME_BAR_SECS: u64 = 60;
+
+/// Rows given to the trade volume bars (header included).
+const VOLUME_PANEL_ROWS: u16 = 4;
 
-use crate::tui::app::{App, ChartType, Focus, Mode, OrdersView};
-use crate::tui::components::{status_bar, tab_bar};
+/// Smallest trades panel height that fits both the trade list and the
+/// volume bars.
+const VOLUME_PANEL_MIN_HEIGHT: u16 = 8;
 
 /// Renders a trading pair tab.
 pub fn render(frame: &mut Frame, app: &App, symbol: &str) {
@@ -20,12 +35,12 @@ pub fn render(frame: &mut Frame, app: &App, symbol: &str) {
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Tab bar
-            Constraint::Length(1), // Status bar
-            Constraint::Length(1), // Ticker header
-            Constraint::Min(10),   // Main content (order book + chart)
-            Constraint::Length(8), // Bottom content (trades + orders)
-            Constraint::Length(1), // Keybindings help
+            Constraint::Length(1),  // Tab bar
+            Constraint::Length(1),  // Status bar
+            Constraint::Length(1),  // Ticker header
+            Constraint::Min(10),    // Main content (order book + chart)
+            Constraint::Length(12), // Bottom content (trades + orders)
+            Constraint::Length(1),  // Keybindings help
         ])
         .split(area);
 
@@ -65,16 +80,9 @@ fn render_ticker_header(frame: &mut Frame, area: Rect, app: &App, symbol: &str)
     let ticker = app.tickers.get(symbol);
 
     let content = if let Some(t) = ticker {
-        let change_color = if t.change >= Decimal::ZERO {
-            Color::Green
-        } else {
-            Color::Red
-        };
-        let arrow = if t.change >= Decimal::ZERO {
-            "▲"
-        } else {
-            "▼"
-        };
+        let rising = t.change.is_none_or(|change| change >= Decimal::ZERO);
+        let change_color = if rising { Color::Green } else { Color::Red };
+        let arrow = if rising { "▲" } else { "▼" };
 
         Line::from(vec![
             Span::styled(
@@ -93,9 +101,15 @@ fn render_ticker_header(frame: &mut Frame, area: Rect, app: &App, symbol: &str)
             Span::raw("Ask: "),
             Span::styled(format!("{:.2} ", t.ask), Style::default().fg(Color::Red)),
             Span::styled(
-                format!("{:+.2}%", t.change_pct),
+                t.change_pct
+                    .map_or_else(|| "--% ".to_string(), |pct| format!("{pct:+.2}% ")),
                 Style::default().fg(change_color),
             ),
+            Span::raw("VWAP: "),
+            Span::styled(
+                format!("{:+.2}%", t.vwap_deviation()),
+                Style::default().fg(Color::Cyan),
+            ),
         ])
     } else {
         Line::from(vec![
@@ -146,35 +160,62 @@ fn render_orderbook(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
     render_orderbook_history(frame, orderbook_layout[1], app, symbol);
 }
 
-/// Renders the order book depth (bids/asks).
+/// Renders the order book depth (bids/asks), switching to the price
+/// ladder with cumulative quantities when the panel is wide enough, or
+/// to the heat-map when that view is selected.
 fn render_orderbook_depth(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
     let orderbook = app.orderbooks.get(symbol);
 
-    let mut lines: Vec<Line> = Vec::new();
-
     // Calculate how many levels to show per side
     // Reserve 3 lines for: ASK header, spread, BID header
     let available_height = area.height.saturating_sub(3) as usize;
     let levels_per_side = (available_height / 2).clamp(1, 10);
 
+    if let Some(ob) = orderbook
+        && app.book_view == BookView::Heatmap
+    {
+        heatmap::render(frame, area, &ob.bids, &ob.asks, usize::from(area.height));
+        return;
+    }
+
+    if let Some(ob) = orderbook
+        && area.width >= price_ladder::MIN_WIDTH
+    {
+        price_ladder::render(frame, area, &ob.bids, &ob.asks, levels_per_side);
+        return;
+    }
+
+    let mut lines: Vec<Line> = Vec::new();
+
     // ASK header
     lines.push(Line::from(Span::styled(
         "ASK",
         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
     )));
 
+    // On USD-quoted pairs the bars scale with notional so levels compare
+    // by dollar value rather than by raw quantity
+    let bar_size = |level: &PriceLevel| {
+        if is_usd_pair(symbol) {
+            level.notional()
+        } else {
+            level.qty
+        }
+    };
+
     if let Some(ob) = orderbook {
         // Show asks (reversed so lowest ask is at bottom, closest to spread)
-        let max_qty = ob
+        let max_size = ob
             .asks
             .iter()
             .take(levels_per_side)
-            .map(|a| a.qty)
+            .map(bar_size)
             .max()
+            .filter(|size| !size.is_zero())
             .unwrap_or(Decimal::ONE);
 
         for ask in ob.asks.iter().take(levels_per_side).rev() {
-            let bar_len = ((ask.qty / max_qty) * Decimal::from(15))
+            let bar_len = ((bar_size(ask) / max_size) * Decimal::from(15))
                 .to_string()
                 .parse::<usize>()
                 .unwrap_or(1);
@@ -208,16 +249,17 @@ fn render_orderbook_depth(frame: &mut Frame, area: Rect, app: &App, symbol: &str
                 .add_modifier(Modifier::BOLD),
         )));
 
-        let max_qty = ob
+        let max_size = ob
             .bids
             .iter()
             .take(levels_per_side)
-            .map(|b| b.qty)
+            .map(bar_size)
             .max()
+            .filter(|size| !size.is_zero())
             .unwrap_or(Decimal::ONE);
 
         for bid in ob.bids.iter().take(levels_per_side) {
-            let bar_len = ((bid.qty / max_qty) * Decimal::from(15))
+            let bar_len = ((bar_size(bid) / max_size) * Decimal::from(15))
                 .to_string()
                 .parse::<usize>()
                 .unwrap_or(1);
@@ -243,6 +285,13 @@ fn render_orderbook_depth(frame: &mut Frame, area: Rect, app: &App, symbol: &str
     frame.render_widget(para, area);
 }
 
+/// Returns `true` when `symbol` is quoted in US dollars (e.g. `BTC/USD`).
+fn is_usd_pair(symbol: &str) -> bool {
+    symbol
+        .split_once('/')
+        .is_some_and(|(_, quote)| quote == "USD")
+}
+
 /// Extracts time portion (HH:MM:SS) from an RFC3339 timestamp.
 fn extract_time(timestamp: &str) -> &str {
     // RFC3339 format: "2024-01-15T12:34:56.789Z" or "2024-01-15T12:34:56.789000Z"
@@ -376,9 +425,9 @@ fn render_chart(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let candles = app.candles.get(symbol);
+    let candles = app.chart_candles(symbol);
 
-    if let Some(candle_data) = candles {
+    if let Some(candle_data) = candles.as_deref() {
         if candle_data.is_empty() {
             let para = Paragraph::new("No candle data").style(Style::default().fg(Color::DarkGray));
             frame.render_widget(para, inner);
@@ -394,9 +443,23 @@ fn render_chart(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
                 });
 
             let price_range = max_price - min_price;
-            let height = inner.height.saturating_sub(2) as usize;
+            // Reserve rows for the annotation markers and timeframe selector
+            let height = inner.height.saturating_sub(3) as usize;
+            let visible = (inner.width as usize).saturating_sub(12);
 
             if price_range > Decimal::ZERO && height > 0 {
+                // Annotation markers above annotated candles
+                let mut marker_row: Vec<Span> = vec![Span::raw(" ".repeat(12))];
+                for candle in candle_data.iter().rev().take(visible) {
+                    let marker = if app.get_annotation(symbol, &candle.interval_begin).is_some() {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    marker_row.push(Span::styled(marker, Style::default().fg(Color::Yellow)));
+                }
+                lines.push(Line::from(marker_row));
+
                 // Build chart rows
                 for row in 0..height {
                     let price_level =
@@ -405,9 +468,13 @@ fn render_chart(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
                     let mut row_chars: Vec<Span> = Vec::new();
                     row_chars.push(Span::raw(format!("{:>10.2} │", price_level)));
 
-                    for candle in candle_data.iter().rev().take(inner.width as usize - 12) {
+                    for (offset, candle) in candle_data.iter().rev().take(visible).enumerate() {
                         let is_bullish = candle.close >= candle.open;
                         let color = if is_bullish { Color::Green } else { Color::Red };
+                        let mut style = Style::default().fg(color);
+                        if is_focused && app.chart_cursor == Some(offset) {
+                            style = style.bg(Color::DarkGray);
+                        }
 
                         let body_top = candle.open.max(candle.close);
                         let body_bottom = candle.open.min(candle.close);
@@ -422,14 +489,14 @@ fn render_chart(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
                             " "
                         };
 
-                        row_chars.push(Span::styled(char, Style::default().fg(color)));
+                        row_chars.push(Span::styled(char, style));
                     }
 
                     lines.push(Line::from(row_chars));
                 }
 
                 // Timeframe selector
-                let tf_line = Line::from(vec![
+                let mut tf_line = Line::from(vec![
                     Span::raw("           "),
                     Span::styled(
                         " 1m ",
@@ -480,6 +547,15 @@ fn render_chart(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
                         },
                     ),
                 ]);
+                if let Some(note) = app
+                    .selected_candle(symbol)
+                    .and_then(|c| app.get_annotation(symbol, &c.interval_begin))
+                {
+                    tf_line.push_span(Span::styled(
+                        format!("  * {note}"),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
                 lines.push(tf_line);
             }
 
@@ -509,13 +585,27 @@ fn render_trades(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let trades = app.recent_trades.get(symbol);
+
+    // Volume bars below the trade list, when there is room for both
+    let list_area = if inner.height >= VOLUME_PANEL_MIN_HEIGHT {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(VOLUME_PANEL_ROWS)])
+            .split(inner);
+        if let Some(trade_list) = trades {
+            render_trade_volume(frame, rows[1], trade_list);
+        }
+        rows[0]
+    } else {
+        inner
+    };
+
     // Split into two columns: BUY | SELL
     let columns = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(inner);
-
-    let trades = app.recent_trades.get(symbol);
+        .split(list_area);
 
     // Separate trades by side
     let (buy_trades, sell_trades): (Vec<_>, Vec<_>) = if let Some(trade_list) = trades {
@@ -534,13 +624,60 @@ fn render_trades(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
     render_trades_column(frame, columns[1], "SELL", Color::Red, &sell_trades);
 }
 
+/// Renders recent trade volume per [`VOLUME_BAR_SECS`] interval, newest
+/// first, labelled with each interval's closing price.
+///
+/// Bars are split into buy (green) and sell (red) volume and scaled to the
+/// busiest interval shown.
+fn render_trade_volume(frame: &mut Frame, area: Rect, trades: &VecDeque<TradeData>) {
+    let trades: Vec<TradeData> = trades.iter().cloned().collect();
+    let bars = TradeBar::from_trades(&trades, VOLUME_BAR_SECS);
+    // First row is the header
+    let shown: Vec<&TradeBar> = bars
+        .iter()
+        .rev()
+        .take(area.height.saturating_sub(1) as usize)
+        .collect();
+    let max_volume = shown
+        .iter()
+        .map(|b| b.volume)
+        .max()
+        .unwrap_or(Decimal::ZERO);
+    let width = area.width.saturating_sub(13) as usize;
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(" {:>10}  Volume ({VOLUME_BAR_SECS}s)", "Close"),
+        Style::default().fg(Color::DarkGray),
+    ))];
+    for bar in shown {
+        let scale = |qty: Decimal| {
+            if max_volume.is_zero() {
+                return 0;
+            }
+            (qty / max_volume * Decimal::from(width))
+                .round()
+                .to_usize()
+                .unwrap_or(0)
+        };
+        let buy = scale(bar.buy_volume);
+        let sell = scale(bar.volume).saturating_sub(buy);
+        lines.push(Line::from(vec![
+            Span::raw(format!(" {:>10.2}  ", bar.close)),
+            Span::styled("█".repeat(buy), Style::default().fg(Color::Green)),
+            Span::styled("█".repeat(sell), Style::default().fg(Color::Red)),
+        ]));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
 /// Renders a single trades column (BUY or SELL).
 fn render_trades_column(
     frame: &mut Frame,
     area: Rect,
     title: &str,
     color: Color,
-    trades: &[&crate::models::trade::TradeData],
+    trades: &[&TradeData],
 ) {
     let mut lines: Vec<Line> = Vec::new();
 
@@ -560,12 +697,16 @@ fn render_trades_column(
         Style::default().fg(Color::DarkGray),
     )]));
 
-    // Trade rows
+    // Trade rows, with market-order (aggressive) trades in bold
     let max_rows = area.height.saturating_sub(2) as usize;
     for trade in trades.iter().take(max_rows) {
+        let mut style = Style::default().fg(color);
+        if trade.is_aggressive() {
+            style = style.add_modifier(Modifier::BOLD);
+        }
         lines.push(Line::from(vec![Span::styled(
             format!(" {:>10.2}  {:>8.4}", trade.price, trade.qty),
-            Style::default().fg(color),
+            style,
         )]));
     }
 
@@ -621,6 +762,7 @@ fn render_orders(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
         Style::default().add_modifier(Modifier::BOLD),
     )));
 
+    let selected = app.selected_open_order(symbol).map(|o| o.order_id.as_str());
     if let Some(order_list) = orders {
         for order in order_list
             .iter()
@@ -640,7 +782,7 @@ fn render_orders(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
 
             let price = order.limit_price.unwrap_or(Decimal::ZERO);
 
-            lines.push(Line::from(vec![
+            let line = Line::from(vec![
                 Span::raw(format!("{:<12} ", id_short)),
                 Span::styled(
                     format!("{:<6} ", order.side.to_uppercase()),
@@ -649,7 +791,13 @@ fn render_orders(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
                 Span::raw(format!("{:<8} ", order.order_type)),
                 Span::raw(format!("{:>12.2} ", price)),
                 Span::raw(format!("{:>10.4}", order.order_qty)),
-            ]));
+            ]);
+            // Highlight the order that `e` would edit
+            if is_focused && selected == Some(order.order_id.as_str()) {
+                lines.push(line.style(Style::default().add_modifier(Modifier::REVERSED)));
+            } else {
+                lines.push(line);
+            }
         }
     }
 
@@ -703,8 +851,13 @@ fn render_orders(frame: &mut Frame, area: Rect, app: &App, symbol: &str) {
 fn render_keybindings(frame: &mut Frame, area: Rect, app: &App) {
     let help = match app.mode {
         Mode::RiskEdit => "[j/k]navigate [Space]toggle [Enter]edit [s]save [Esc]cancel",
+        Mode::CancelAfter => "[0-9]timeout [Enter]set [Esc]cancel",
+        Mode::Annotate => "[type]note [Enter]save (empty removes) [Esc]cancel",
+        Mode::EditOrder => "[Tab]next field [Enter]submit [Esc]cancel",
+        Mode::NotificationLog => "[c]clear [Esc]close",
+        Mode::GlobalSearch => "[type]search [Esc]close",
         _ => {
-            "[n]ew order [c]ancel [e]dit [g]chart type [o]orders view [1-6]timeframe [r]risk [Tab]switch tab [?]help [q]quit"
+            "[n]ew order [c]ancel [e]dit [g]chart type [h]heat-map (book) [j/k]select order [^C]cancel all [[/]]select candle [m]annotate [o]orders view [1-6]timeframe [r]risk [^D]dead man's switch [^E]export trades [^N]notifications [^/]search [^W]close tab [Tab]switch tab [Alt+1-9]jump to pair [?]help [q]quit"
         }
     };
 
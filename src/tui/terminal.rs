@@ -1,67 +1,262 @@
-//! Terminal setup and teardown utilities.
+//! Terminal setup and teardown.
+//!
+//! [`Tui`] owns the terminal while the TUI runs and restores it when
+//! dropped, so an early return or a panic never leaves the shell in raw
+//! mode on the alternate screen. A panic hook restores it before the panic
+//! message is printed, so the message lands on the main screen.
 
 use std::io::{self, IsTerminal, Stdout};
+use std::sync::Once;
 
 use crossterm::{
+    cursor::Show,
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use ratatui::{Terminal, backend::CrosstermBackend};
+use ratatui::{Frame, Terminal, backend::CrosstermBackend};
 
 use crate::Result;
 
-/// Type alias for our terminal backend.
-pub type Tui = Terminal<CrosstermBackend<Stdout>>;
-
-/// Initializes the terminal for TUI rendering.
-///
-/// Enables raw mode and switches to the alternate screen buffer.
-/// Returns a configured Terminal instance.
-///
-/// # Errors
+/// The terminal in raw mode on the alternate screen, restored on drop.
 ///
-/// Returns an error if terminal initialization fails or if stdout is not a TTY.
-pub fn setup_terminal() -> Result<Tui> {
-    // Check if stdout is a terminal
-    if !io::stdout().is_terminal() {
-        return Err(crate::LeesonError::Io(
-            "TUI requires an interactive terminal (TTY). Cannot run in a non-interactive environment.".to_string()
-        ));
+/// Output goes to stdout unless a test supplies another writer.
+pub struct Tui<W: io::Write = Stdout> {
+    terminal: Terminal<CrosstermBackend<W>>,
+    /// Set once [`restore`](Self::restore) has run.
+    restored: bool,
+}
+
+impl Tui {
+    /// Initializes the terminal for TUI rendering.
+    ///
+    /// Enables raw mode, switches to the alternate screen buffer and
+    /// installs the panic hook that restores the terminal.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if terminal initialization fails or if stdout is
+    /// not a TTY.
+    pub fn new() -> Result<Self> {
+        // Check if stdout is a terminal
+        if !io::stdout().is_terminal() {
+            return Err(crate::LeesonError::io(
+                "TUI requires an interactive terminal (TTY)",
+                io::Error::new(io::ErrorKind::Unsupported, "stdout is not a terminal"),
+            ));
+        }
+
+        enable_raw_mode().map_err(|e| crate::LeesonError::io("failed to enable raw mode", e))?;
+
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen).map_err(|e| {
+            // Try to restore terminal state before returning error
+            let _ = disable_raw_mode();
+            crate::LeesonError::io("failed to enter alternate screen", e)
+        })?;
+
+        let backend = CrosstermBackend::new(stdout);
+        let terminal = Terminal::new(backend).map_err(|e| {
+            // Try to restore terminal state before returning error
+            let _ = execute!(io::stdout(), LeaveAlternateScreen);
+            let _ = disable_raw_mode();
+            crate::LeesonError::io("failed to create terminal", e)
+        })?;
+
+        install_panic_hook();
+
+        Ok(Self {
+            terminal,
+            restored: false,
+        })
+    }
+}
+
+impl<W: io::Write> Tui<W> {
+    /// Draws one frame with `render`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing the frame to the terminal fails.
+    pub fn draw<F: FnOnce(&mut Frame)>(&mut self, render: F) -> Result<()> {
+        self.terminal
+            .draw(render)
+            .map_err(|e| crate::LeesonError::io("failed to draw frame", e))?;
+        Ok(())
     }
 
-    enable_raw_mode()
-        .map_err(|e| crate::LeesonError::Io(format!("failed to enable raw mode: {e}")))?;
+    /// Restores the terminal to its original state.
+    ///
+    /// Disables raw mode, returns to the main screen buffer and shows the
+    /// cursor. Every step is tried even if an earlier one fails. Once it has
+    /// succeeded further calls do nothing; dropping the `Tui` calls it until
+    /// then, logging instead of returning any error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error if any step fails.
+    pub fn restore(&mut self) -> Result<()> {
+        if self.restored {
+            return Ok(());
+        }
 
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen).map_err(|e| {
-        // Try to restore terminal state before returning error
-        let _ = disable_raw_mode();
-        crate::LeesonError::Io(format!("failed to enter alternate screen: {e}"))
-    })?;
+        let raw_mode =
+            disable_raw_mode().map_err(|e| crate::LeesonError::io("failed to disable raw mode", e));
+        let screen = execute!(self.terminal.backend_mut(), LeaveAlternateScreen)
+            .map_err(|e| crate::LeesonError::io("failed to leave alternate screen", e));
+        let cursor = self
+            .terminal
+            .show_cursor()
+            .map_err(|e| crate::LeesonError::io("failed to show cursor", e));
 
-    let backend = CrosstermBackend::new(stdout);
-    let terminal = Terminal::new(backend).map_err(|e| {
-        // Try to restore terminal state before returning error
-        let _ = disable_raw_mode();
-        crate::LeesonError::Io(format!("failed to create terminal: {e}"))
-    })?;
+        let result = raw_mode.and(screen).and(cursor);
+        self.restored = result.is_ok();
+        result
+    }
+}
 
-    Ok(terminal)
+/// Chains a panic hook, once per process, that restores the terminal on
+/// stdout before the previous hook prints the panic message.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            // Best effort: the panic is reported either way
+            let _ = disable_raw_mode();
+            let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+            previous(info);
+        }));
+    });
 }
 
-/// Restores the terminal to its original state.
-///
-/// Disables raw mode and returns to the main screen buffer.
-///
-/// # Errors
-///
-/// Returns an error if terminal restoration fails.
-pub fn restore_terminal(terminal: &mut Tui) -> Result<()> {
-    disable_raw_mode().map_err(|e| crate::LeesonError::Io(e.to_string()))?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)
-        .map_err(|e| crate::LeesonError::Io(e.to_string()))?;
-    terminal
-        .show_cursor()
-        .map_err(|e| crate::LeesonError::Io(e.to_string()))?;
-    Ok(())
+impl<W: io::Write> Drop for Tui<W> {
+    fn drop(&mut self) {
+        if let Err(e) = self.restore() {
+            tracing::warn!("failed to restore terminal: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{TerminalOptions, Viewport, layout::Rect};
+    use std::sync::{Arc, Mutex};
+
+    /// Writer whose output stays readable after the `Tui` is dropped.
+    #[derive(Clone, Default)]
+    struct SharedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedOutput {
+        fn contains(&self, needle: &[u8]) -> bool {
+            self.0
+                .lock()
+                .unwrap()
+                .windows(needle.len())
+                .any(|window| window == needle)
+        }
+    }
+
+    /// A `Tui` writing to `output`, with a fixed viewport so no real
+    /// terminal is queried.
+    fn test_tui(output: SharedOutput) -> Tui<SharedOutput> {
+        let terminal = Terminal::with_options(
+            CrosstermBackend::new(output),
+            TerminalOptions {
+                viewport: Viewport::Fixed(Rect::new(0, 0, 20, 5)),
+            },
+        )
+        .unwrap();
+        Tui {
+            terminal,
+            restored: false,
+        }
+    }
+
+    const LEAVE_ALTERNATE_SCREEN: &[u8] = b"\x1b[?1049l";
+    const SHOW_CURSOR: &[u8] = b"\x1b[?25h";
+
+    #[test]
+    fn drop_restores_terminal_after_panic() {
+        let output = SharedOutput::default();
+        let tui_output = output.clone();
+
+        let result = std::panic::catch_unwind(move || {
+            let mut tui = test_tui(tui_output);
+            tui.draw(|_| {}).unwrap();
+            panic!("main loop failed");
+        });
+
+        assert!(result.is_err());
+        assert!(output.contains(LEAVE_ALTERNATE_SCREEN));
+    }
+
+    #[test]
+    fn restore_runs_once() {
+        let output = SharedOutput::default();
+        let mut tui = test_tui(output.clone());
+
+        tui.restore().unwrap();
+        output.0.lock().unwrap().clear();
+        drop(tui);
+
+        assert!(!output.contains(LEAVE_ALTERNATE_SCREEN));
+    }
+
+    /// Writer whose first write fails.
+    struct FailsOnce {
+        output: SharedOutput,
+        failed: bool,
+    }
+
+    impl io::Write for FailsOnce {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if !self.failed {
+                self.failed = true;
+                return Err(io::Error::other("write failed"));
+            }
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn restore_tries_every_step_and_reports_failure() {
+        let output = SharedOutput::default();
+        let terminal = Terminal::with_options(
+            CrosstermBackend::new(FailsOnce {
+                output: output.clone(),
+                failed: false,
+            }),
+            TerminalOptions {
+                viewport: Viewport::Fixed(Rect::new(0, 0, 20, 5)),
+            },
+        )
+        .unwrap();
+        let mut tui = Tui {
+            terminal,
+            restored: false,
+        };
+
+        assert!(tui.restore().is_err());
+        assert!(output.contains(SHOW_CURSOR));
+
+        // Not marked restored, so the next attempt runs again
+        assert!(tui.restore().is_ok());
+        assert!(output.contains(LEAVE_ALTERNATE_SCREEN));
+    }
 }
@@ -6,7 +6,11 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
-use super::app::{ApiKeysEditState, App, FieldStatus, Mode, RiskEditState, Tab};
+use super::app::{
+    ApiKeysEditState, App, EditOrderState, FieldStatus, Mode, NotificationLevel, PendingOrder,
+    RiskEditState, SearchSource, Tab,
+};
+use super::components::confirmation_modal;
 use super::tabs::{agent, trading_pair};
 
 /// Renders the entire application UI.
@@ -20,7 +24,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     if app.mode == Mode::Confirm
         && let Some(ref pending) = app.pending_order
     {
-        render_confirm_overlay(frame, app, pending);
+        render_confirm_overlay(frame, pending);
     }
 
     // Render risk parameters edit overlay
@@ -36,81 +40,63 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     {
         render_api_keys_overlay(frame, state);
     }
-}
 
-/// Renders a centered confirmation dialog overlay.
-fn render_confirm_overlay(frame: &mut Frame, _app: &App, pending: &super::app::PendingOrder) {
-    let area = frame.area();
-    let dialog = centered_rect(60, 40, area);
+    // Render dead man's switch timeout prompt
+    if app.mode == Mode::CancelAfter {
+        render_cancel_after_overlay(frame, app);
+    }
 
-    // Clear the area behind the dialog
-    frame.render_widget(Clear, dialog);
+    // Render candle annotation prompt
+    if app.mode == Mode::Annotate {
+        render_annotation_overlay(frame, app);
+    }
 
-    let params = &pending.params;
-    let price_str = params
-        .limit_price
-        .map_or("market".to_string(), |p| p.to_string());
+    // Render order edit overlay
+    if app.mode == Mode::EditOrder
+        && let Some(ref state) = app.order_edit
+    {
+        render_edit_order_overlay(frame, state);
+    }
 
-    let lines = vec![
-        Line::from(Span::styled(
-            "Order Confirmation Required",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Reason: ", Style::default().fg(Color::Yellow)),
-            Span::raw(&pending.reason),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Symbol: ", Style::default().fg(Color::Cyan)),
-            Span::raw(&params.symbol),
-        ]),
-        Line::from(vec![
-            Span::styled("Side:   ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{:?}", params.side)),
-        ]),
-        Line::from(vec![
-            Span::styled("Type:   ", Style::default().fg(Color::Cyan)),
-            Span::raw(format!("{:?}", params.order_type)),
-        ]),
-        Line::from(vec![
-            Span::styled("Qty:    ", Style::default().fg(Color::Cyan)),
-            Span::raw(params.order_qty.to_string()),
-        ]),
-        Line::from(vec![
-            Span::styled("Price:  ", Style::default().fg(Color::Cyan)),
-            Span::raw(price_str),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled(
-                "[Y] ",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("Confirm   "),
-            Span::styled(
-                "[N] ",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Span::raw("Cancel"),
-        ]),
-    ];
+    // Render pair search input (the pair selector itself shows the matches)
+    if app.mode == Mode::PairSearch {
+        render_pair_search_overlay(frame, app);
+    }
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .title(" Confirm Order ");
+    // Render recent notifications
+    if app.mode == Mode::NotificationLog {
+        render_notification_log_overlay(frame, app);
+    }
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false });
+    // Render global search results
+    if app.mode == Mode::GlobalSearch {
+        render_global_search_overlay(frame, app);
+    }
+}
 
-    frame.render_widget(paragraph, dialog);
+/// Renders the order confirmation dialog overlay.
+fn render_confirm_overlay(frame: &mut Frame, pending: &PendingOrder) {
+    let params = &pending.params;
+    let side = format!("{:?}", params.side);
+    let order_type = format!("{:?}", params.order_type);
+    let qty = params.order_qty.to_string();
+    let price = params
+        .limit_price
+        .map_or("market".to_string(), |p| p.to_string());
+
+    confirmation_modal::render(
+        frame,
+        frame.area(),
+        "Order Confirmation Required",
+        &[
+            ("Reason", &pending.reason),
+            ("Symbol", &params.symbol),
+            ("Side", &side),
+            ("Type", &order_type),
+            ("Qty", &qty),
+            ("Price", &price),
+        ],
+    );
 }
 
 /// Renders the risk parameters edit overlay.
@@ -356,6 +342,361 @@ fn render_api_keys_overlay(frame: &mut Frame, state: &ApiKeysEditState) {
     frame.render_widget(paragraph, dialog);
 }
 
+/// Renders the dead man's switch timeout prompt.
+fn render_cancel_after_overlay(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let dialog = centered_rect(50, 30, area);
+
+    frame.render_widget(Clear, dialog);
+
+    let current = app
+        .cancel_after_timeout
+        .map_or("off".to_string(), |t| format!("{t}s"));
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Dead Man's Switch",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Current: ", Style::default().fg(Color::Cyan)),
+            Span::raw(current),
+        ]),
+        Line::from(vec![
+            Span::styled("Timeout (s): ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}▏", app.cancel_after_input.as_str()),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        ]),
+        Line::from(Span::styled(
+            "All orders are cancelled if the timer is not refreshed. 0 disables.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "[Enter] ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("set  "),
+            Span::styled(
+                "[Esc] ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("cancel"),
+        ]),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Cancel After ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, dialog);
+}
+
+/// Renders the notification history, newest first.
+fn render_notification_log_overlay(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let dialog = centered_rect(70, 60, area);
+
+    frame.render_widget(Clear, dialog);
+
+    let mut lines: Vec<Line> = app
+        .notification_history
+        .iter()
+        .rev()
+        .map(|notification| {
+            let color = match notification.level {
+                NotificationLevel::Info => Color::Green,
+                NotificationLevel::Warning => Color::Yellow,
+                NotificationLevel::Error => Color::Red,
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>6} ", format_age(notification.timestamp.elapsed())),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(notification.message.clone(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No notifications",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Notifications ")
+        .title_bottom(" [c] clear  [Esc] close ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, dialog);
+}
+
+/// Formats an elapsed time as a compact age, e.g. `42s` or `3m`.
+fn format_age(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
+
+/// Renders the note prompt for the selected chart candle.
+fn render_annotation_overlay(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let dialog = centered_rect(50, 30, area);
+
+    frame.render_widget(Clear, dialog);
+
+    let candle = app
+        .annotation_target
+        .as_ref()
+        .map_or(String::new(), |(symbol, timestamp)| {
+            format!("{symbol} @ {timestamp}")
+        });
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Annotate Candle",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Candle: ", Style::default().fg(Color::Cyan)),
+            Span::raw(candle),
+        ]),
+        Line::from(vec![
+            Span::styled("Note: ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}▏", app.annotation_input.as_str()),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::UNDERLINED),
+            ),
+        ]),
+        Line::from(Span::styled(
+            "Saving an empty note removes the annotation.",
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "[Enter] ",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("save  "),
+            Span::styled(
+                "[Esc] ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("cancel"),
+        ]),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Annotation ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, dialog);
+}
+
+/// Renders the order edit overlay.
+fn render_edit_order_overlay(frame: &mut Frame, state: &EditOrderState) {
+    let area = frame.area();
+    let dialog = centered_rect(50, 30, area);
+
+    frame.render_widget(Clear, dialog);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Edit Order",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Order: ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{} ({})", state.order_id, state.symbol)),
+        ]),
+    ];
+
+    for (i, input) in [&state.price, &state.qty].into_iter().enumerate() {
+        let is_selected = i == state.selected;
+        let label = EditOrderState::field_label(i);
+
+        let (marker, label_style, value_str, value_style) = if is_selected {
+            (
+                "▸ ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+                format!("{}▏", input.as_str()),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::UNDERLINED),
+            )
+        } else {
+            (
+                "  ",
+                Style::default().fg(Color::Cyan),
+                input.as_str().to_string(),
+                Style::default().fg(Color::Gray),
+            )
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(marker, label_style),
+            Span::styled(format!("{label}: "), label_style),
+            Span::styled(value_str, value_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            "[Tab] ",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("next field  "),
+        Span::styled(
+            "[Enter] ",
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("submit  "),
+        Span::styled(
+            "[Esc] ",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("cancel"),
+    ]));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(" Edit Order ");
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, dialog);
+}
+
+/// Renders the pair search input box.
+fn render_pair_search_overlay(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let dialog = centered_rect(40, 20, area);
+    let dialog = Rect {
+        height: dialog.height.min(4),
+        ..dialog
+    };
+
+    frame.render_widget(Clear, dialog);
+
+    let matches = app.visible_pairs().len();
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("/ ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}▏", app.pair_search.as_str()),
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!("{matches} match(es)"),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Search Pairs ");
+
+    frame.render_widget(Paragraph::new(lines).block(block), dialog);
+}
+
+/// Renders the global search query and its matches, best first.
+fn render_global_search_overlay(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let dialog = centered_rect(70, 60, area);
+
+    frame.render_widget(Clear, dialog);
+
+    let results = app.global_search(app.global_search.as_str());
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("/ ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("{}▏", app.global_search.as_str()),
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!("{} match(es)", results.len()),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    lines.extend(results.iter().map(|result| {
+        let color = match result.source {
+            SearchSource::Ticker => Color::Cyan,
+            SearchSource::Order => Color::Yellow,
+            SearchSource::AgentOutput => Color::Magenta,
+        };
+        Line::from(vec![
+            Span::styled(
+                format!("{:<7}", result.source.label()),
+                Style::default().fg(color),
+            ),
+            Span::raw(result.text.clone()),
+        ])
+    }));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(" Search ")
+        .title_bottom(" [Esc] close ");
+
+    frame.render_widget(Paragraph::new(lines).block(block), dialog);
+}
+
 /// Returns a centered rectangle of the given percentage of the parent area.
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::default()
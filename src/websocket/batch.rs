@@ -0,0 +1,244 @@
+//! Outgoing order batching.
+//!
+//! [`BatchOrderManager`] holds orders submitted within a short window and
+//! sends orders for the same pair together as a single `batch_add` request.
+//! Orders left on their own are sent as a plain `add_order`.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::{RequestIds, WsWriter, add_order, batch_add};
+use crate::Result;
+use crate::models::{
+    AddOrderParams, AddOrderRequest, BatchAddRequest, BatchOrderEntry, MAX_BATCH_SIZE,
+    MIN_BATCH_SIZE,
+};
+
+/// How long the first queued order waits for company before the queue is flushed.
+pub const BATCH_WINDOW: Duration = Duration::from_millis(50);
+
+/// Queues outgoing orders and sends them as `batch_add` requests when possible.
+///
+/// The owner is responsible for calling [`flush`](Self::flush) once the
+/// queue [`is_full`](Self::is_full) or its [`deadline`](Self::deadline)
+/// has passed.
+#[derive(Debug, Default)]
+pub struct BatchOrderManager {
+    queue: Vec<AddOrderParams>,
+    first_queued_at: Option<Instant>,
+    request_ids: RequestIds,
+    sent: Vec<(u64, &'static str)>,
+}
+
+impl BatchOrderManager {
+    /// Creates an empty manager that stamps requests from `request_ids`.
+    #[must_use]
+    pub fn new(request_ids: RequestIds) -> Self {
+        Self {
+            request_ids,
+            ..Self::default()
+        }
+    }
+
+    /// Queues an order for the next flush.
+    pub fn submit(&mut self, params: AddOrderParams) {
+        self.first_queued_at.get_or_insert_with(Instant::now);
+        self.queue.push(params);
+    }
+
+    /// Returns `true` once the queue holds [`MAX_BATCH_SIZE`] orders.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.queue.len() >= MAX_BATCH_SIZE
+    }
+
+    /// Returns the number of queued orders.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no orders are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns when the queue is due to be flushed, or `None` if it is empty.
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.first_queued_at.map(|queued| queued + BATCH_WINDOW)
+    }
+
+    /// Discards all queued orders, returning how many were dropped.
+    pub fn clear(&mut self) -> usize {
+        self.first_queued_at = None;
+        std::mem::take(&mut self.queue).len()
+    }
+
+    /// Sends every queued order on `writer`.
+    ///
+    /// Orders for the same symbol are combined into `batch_add` requests of
+    /// up to [`MAX_BATCH_SIZE`]; an order with no partner is sent as
+    /// `add_order`. Orders using `validate` or `no_mpp` are always sent on
+    /// their own since `batch_add` has no per-order equivalent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LeesonError`](crate::LeesonError) if sending a request
+    /// fails. Orders not yet sent at that point are discarded.
+    pub async fn flush(&mut self, writer: &mut WsWriter) -> Result<()> {
+        self.first_queued_at = None;
+
+        for mut group in group_orders(std::mem::take(&mut self.queue)) {
+            let req_id = self.request_ids.next();
+            if group.len() < MIN_BATCH_SIZE {
+                let params = group.pop().expect("order groups are never empty");
+                add_order(writer, AddOrderRequest::new(params, Some(req_id))).await?;
+                self.sent.push((req_id, "add_order"));
+            } else {
+                let symbol = group[0].symbol.clone();
+                let token = group[0].token.as_str().to_owned();
+                let orders = group.into_iter().map(BatchOrderEntry::from).collect();
+                let request = BatchAddRequest::new(&symbol, orders, &token, Some(req_id));
+                batch_add(writer, request).await?;
+                self.sent.push((req_id, "batch_add"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the `(req_id, method)` of each request sent since the last call.
+    pub fn take_sent(&mut self) -> Vec<(u64, &'static str)> {
+        std::mem::take(&mut self.sent)
+    }
+}
+
+/// Returns `true` if the order can share a `batch_add` request.
+fn is_batchable(params: &AddOrderParams) -> bool {
+    params.validate.is_none() && params.no_mpp.is_none()
+}
+
+/// Splits orders into per-symbol groups of at most [`MAX_BATCH_SIZE`],
+/// preserving submission order within each group.
+fn group_orders(orders: Vec<AddOrderParams>) -> Vec<Vec<AddOrderParams>> {
+    let mut groups: Vec<Vec<AddOrderParams>> = Vec::new();
+
+    for params in orders {
+        let group = if is_batchable(&params) {
+            groups.iter_mut().find(|group| {
+                group.len() < MAX_BATCH_SIZE
+                    && is_batchable(&group[0])
+                    && group[0].symbol == params.symbol
+            })
+        } else {
+            None
+        };
+
+        match group {
+            Some(group) => group.push(params),
+            None => groups.push(vec![params]),
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AddOrderBuilder, OrderSide};
+    use crate::websocket::test_support::loopback;
+    use futures_util::StreamExt;
+    use rust_decimal_macros::dec;
+    use tungstenite::Message as WsMessage;
+
+    fn order(symbol: &str) -> AddOrderParams {
+        AddOrderBuilder::limit(OrderSide::Buy, symbol, dec!(0.1), dec!(100))
+            .build("test_token")
+            .unwrap()
+    }
+
+    #[test]
+    fn full_at_max_batch_size() {
+        let mut batch = BatchOrderManager::default();
+        for _ in 0..MAX_BATCH_SIZE - 1 {
+            batch.submit(order("BTC/USD"));
+        }
+        assert!(!batch.is_full());
+
+        batch.submit(order("BTC/USD"));
+        assert!(batch.is_full());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn deadline_follows_first_submission() {
+        let mut batch = BatchOrderManager::default();
+        assert_eq!(batch.deadline(), None);
+
+        let first = Instant::now();
+        batch.submit(order("BTC/USD"));
+        tokio::time::advance(Duration::from_millis(20)).await;
+        batch.submit(order("BTC/USD"));
+
+        assert_eq!(batch.deadline(), Some(first + BATCH_WINDOW));
+        assert_eq!(batch.clear(), 2);
+        assert_eq!(batch.deadline(), None);
+    }
+
+    #[test]
+    fn groups_by_symbol_and_isolates_validate_orders() {
+        let mut validate = order("BTC/USD");
+        validate.validate = Some(true);
+        let orders = vec![
+            order("BTC/USD"),
+            order("ETH/USD"),
+            validate,
+            order("BTC/USD"),
+        ];
+
+        let sizes: Vec<(String, usize)> = group_orders(orders)
+            .iter()
+            .map(|g| (g[0].symbol.clone(), g.len()))
+            .collect();
+
+        assert_eq!(
+            sizes,
+            vec![
+                ("BTC/USD".to_string(), 2),
+                ("ETH/USD".to_string(), 1),
+                ("BTC/USD".to_string(), 1),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn flush_sends_full_batch_as_single_batch_add() {
+        let (mut write, _read, server) = loopback(|mut ws| async move {
+            match ws.next().await {
+                Some(Ok(WsMessage::Text(text))) => {
+                    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    value["method"] == "batch_add"
+                        && value["params"]["symbol"] == "BTC/USD"
+                        && value["params"]["token"] == "test_token"
+                        && value["params"]["orders"].as_array().map(Vec::len)
+                            == Some(MAX_BATCH_SIZE)
+                }
+                _ => false,
+            }
+        })
+        .await;
+
+        let mut batch = BatchOrderManager::default();
+        while !batch.is_full() {
+            batch.submit(order("BTC/USD"));
+        }
+        batch.flush(&mut write).await.unwrap();
+
+        assert!(batch.is_empty());
+        assert_eq!(batch.take_sent(), vec![(1, "batch_add")]);
+        assert!(server.await.unwrap(), "server did not receive the batch");
+    }
+}
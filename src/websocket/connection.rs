@@ -1,31 +1,40 @@
 //! WebSocket connection lifecycle management.
 //!
 //! [`ConnectionManager`] handles connecting, reading messages, automatic
-//! reconnection with exponential backoff, token refresh before expiry,
-//! and re-subscription to all active channels after each reconnect.
+//! reconnection with exponential backoff, ping-based liveness checks, token
+//! refresh before expiry, and re-subscription to all active channels after
+//! each reconnect. A drop of only the public or only the private connection
+//! reconnects just that one.
 //!
 //! Maintains two connections:
 //! - Public: `wss://ws.kraken.com/v2` for market data (ticker, book, ohlc, trade)
 //! - Private: `wss://ws-auth.kraken.com/v2` for authenticated channels (executions, balances)
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
-use futures_util::StreamExt;
+use futures_util::{SinkExt, StreamExt};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 use tungstenite::Message as WsMessage;
 use zeroize::Zeroizing;
 
 use super::{
-    WsReader, WsWriter, connect, ping, subscribe, subscribe_balances, subscribe_book,
-    subscribe_executions, subscribe_instrument,
+    BatchOrderManager, WsReader, WsWriter, batch_cancel, cancel_after, cancel_order, connect,
+    edit_order, ping, subscribe_balances, subscribe_executions, subscribe_instrument,
 };
-use crate::auth::get_websocket_token;
-use crate::models::Channel;
+use crate::LeesonError;
+use crate::auth::{CredentialInfo, TOKEN_LIFETIME, get_websocket_token};
 use crate::models::book::BookDepth;
+use crate::models::{
+    AddOrderParams, BatchCancelBuilder, BookSubscribeRequest, CancelAfterRequest,
+    CancelOrderBuilder, CancelOrderParams, CancelOrderRequest, Channel, EditOrderParams,
+    EditOrderRequest, MIN_BATCH_CANCEL_SIZE, RedactedToken, SubscribeRequest, SubscribeResponse,
+};
 use crate::tui::Message;
-use crate::tui::app::TokenState;
+use crate::tui::app::{Timeframe, TokenState};
 
 /// Refresh token after 12 minutes (3-minute buffer before 15-min expiry).
 const TOKEN_REFRESH_INTERVAL: Duration = Duration::from_secs(12 * 60);
@@ -39,12 +48,49 @@ const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
 /// Maximum backoff duration between reconnection attempts.
 const MAX_BACKOFF: Duration = Duration::from_secs(60);
 
+/// How often to probe each connection with a `ping`.
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long to wait for a `pong` (or any data) after a `ping` before
+/// treating the connection as dead.
+const PONG_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long an RPC request may go unanswered before it is reported as timed out.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often pending RPC requests are checked for timeouts.
+const REQUEST_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many times a rejected public subscription is retried before giving up.
+const SUBSCRIBE_MAX_RETRIES: u8 = 3;
+
+/// Delay before a rejected public subscription is retried.
+const SUBSCRIBE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Public channels subscribed for every selected trading pair.
+const PUBLIC_CHANNELS: [Channel; 4] = [
+    Channel::Ticker,
+    Channel::Book,
+    Channel::Candles,
+    Channel::Trades,
+];
+
+/// How often connection statistics are reported to the TUI while connected.
+const STATS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Maximum time to wait for the server to acknowledge a close frame.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Public WebSocket endpoint for market data.
 const PUBLIC_WS_URL: &str = "wss://ws.kraken.com/v2";
 
 /// Private WebSocket endpoint for authenticated channels.
 const PRIVATE_WS_URL: &str = "wss://ws-auth.kraken.com/v2";
 
+/// Capacity of the channel behind
+/// [`ConnectionManager::subscribe_channel_sender`].
+const EXTERNAL_COMMAND_CAPACITY: usize = 32;
+
 /// Commands sent from the main loop to the connection manager.
 pub enum ConnectionCommand {
     /// A trading pair was subscribed in the UI.
@@ -58,12 +104,225 @@ pub enum ConnectionCommand {
         api_key: Option<Zeroizing<String>>,
         api_secret: Option<Zeroizing<String>>,
     },
+    /// The chart timeframe changed; candles are re-subscribed at this
+    /// interval after reconnecting.
+    SetTimeframe(Timeframe),
+    /// Arm the dead man's switch with a timeout in seconds (0 disables it).
+    SetCancelAfter(u32),
+    /// Queue an order for submission on the private connection.
+    ///
+    /// Orders are batched for [`BATCH_WINDOW`](super::BATCH_WINDOW) and
+    /// stamped with the current auth token before sending.
+    SubmitOrder(Box<AddOrderParams>),
+    /// Send an `edit_order` request on the private connection, stamped with
+    /// the current auth token.
+    EditOrder(Box<EditOrderParams>),
+    /// Send a `cancel_order` request on the private connection, stamped
+    /// with the current auth token.
+    CancelOrder(Box<CancelOrderParams>),
+    /// Cancel up to [`MAX_BATCH_CANCEL_SIZE`](crate::models::MAX_BATCH_CANCEL_SIZE)
+    /// orders by ID in one request on the private connection.
+    ///
+    /// A single order is sent as `cancel_order`, since `batch_cancel`
+    /// needs at least [`MIN_BATCH_CANCEL_SIZE`] orders.
+    BatchCancel(Vec<String>),
+    /// Subscribe the symbols to a public channel on the public connection,
+    /// for callers driving subscriptions without the UI.
+    ///
+    /// Rejected subscriptions are retried like those of selected pairs.
+    SubscribeChannel(Channel, Vec<String>),
+}
+
+/// Shared source of `req_id` values for correlating RPC requests with
+/// their responses.
+///
+/// Cloning is cheap; all clones draw from the same counter, so IDs are
+/// unique across every task that sends requests.
+#[derive(Debug, Clone, Default)]
+pub struct RequestIds(Arc<AtomicU64>);
+
+impl RequestIds {
+    /// Returns the next request ID (starting at 1).
+    #[must_use]
+    pub fn next(&self) -> u64 {
+        self.0.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+/// An RPC request that has been sent but not yet answered.
+struct PendingRequest {
+    method: &'static str,
+    sent_at: Instant,
+}
+
+/// RPC requests awaiting a response, keyed by `req_id`.
+#[derive(Default)]
+struct PendingRequests {
+    requests: HashMap<u64, PendingRequest>,
+}
+
+impl PendingRequests {
+    /// Starts tracking a request sent at `sent_at`.
+    fn insert(&mut self, req_id: u64, method: &'static str, sent_at: Instant) {
+        self.requests
+            .insert(req_id, PendingRequest { method, sent_at });
+    }
+
+    /// Stops tracking a request once its response arrives.
+    fn complete(&mut self, req_id: u64) -> bool {
+        self.requests.remove(&req_id).is_some()
+    }
+
+    /// Removes and returns every request older than [`REQUEST_TIMEOUT`].
+    fn drain_expired(&mut self, now: Instant) -> Vec<(u64, &'static str)> {
+        let mut expired: Vec<(u64, &'static str)> = self
+            .requests
+            .iter()
+            .filter(|(_, req)| now.duration_since(req.sent_at) >= REQUEST_TIMEOUT)
+            .map(|(&id, req)| (id, req.method))
+            .collect();
+        expired.sort_unstable_by_key(|&(id, _)| id);
+        for (id, _) in &expired {
+            self.requests.remove(id);
+        }
+        expired
+    }
+}
+
+/// A public channel subscription for a single symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PublicSubscription {
+    channel: Channel,
+    symbol: String,
+    /// How many times this subscription has already been retried.
+    retries: u8,
+}
+
+/// What happened to a subscription the server rejected.
+#[derive(Debug, PartialEq, Eq)]
+enum SubscriptionOutcome {
+    /// The subscription will be sent again after [`SUBSCRIBE_RETRY_DELAY`].
+    Retrying(PublicSubscription),
+    /// The subscription was retried [`SUBSCRIBE_MAX_RETRIES`] times and dropped.
+    GaveUp(PublicSubscription),
+}
+
+/// Public subscriptions awaiting a response or scheduled for retry.
+#[derive(Default)]
+struct SubscriptionTracker {
+    /// Sent subscriptions keyed by `req_id`.
+    pending: HashMap<u64, PublicSubscription>,
+    /// Rejected subscriptions and when to send them again.
+    retries: Vec<(tokio::time::Instant, PublicSubscription)>,
+}
+
+impl SubscriptionTracker {
+    /// Starts tracking a subscription sent with `req_id`.
+    fn sent(&mut self, req_id: u64, subscription: PublicSubscription) {
+        self.pending.insert(req_id, subscription);
+    }
+
+    /// Stops tracking a subscription the server accepted.
+    fn acknowledge(&mut self, req_id: u64) {
+        self.pending.remove(&req_id);
+    }
+
+    /// Schedules a rejected subscription for retry, or gives up on it once
+    /// it has been retried [`SUBSCRIBE_MAX_RETRIES`] times.
+    ///
+    /// Returns `None` if `req_id` is not a tracked subscription.
+    fn reject(&mut self, req_id: u64, now: tokio::time::Instant) -> Option<SubscriptionOutcome> {
+        let mut subscription = self.pending.remove(&req_id)?;
+        if subscription.retries >= SUBSCRIBE_MAX_RETRIES {
+            return Some(SubscriptionOutcome::GaveUp(subscription));
+        }
+        subscription.retries += 1;
+        self.retries
+            .push((now + SUBSCRIBE_RETRY_DELAY, subscription.clone()));
+        Some(SubscriptionOutcome::Retrying(subscription))
+    }
+
+    /// Returns when the next retry is due, if any are scheduled.
+    fn next_retry(&self) -> Option<tokio::time::Instant> {
+        self.retries.iter().map(|(due, _)| *due).min()
+    }
+
+    /// Removes and returns every subscription whose retry is due.
+    fn take_due(&mut self, now: tokio::time::Instant) -> Vec<PublicSubscription> {
+        let (due, waiting) = std::mem::take(&mut self.retries)
+            .into_iter()
+            .partition(|(at, _)| *at <= now);
+        self.retries = waiting;
+        due.into_iter()
+            .map(|(_, subscription)| subscription)
+            .collect()
+    }
+
+    /// Stops tracking and retrying subscriptions for a deselected pair.
+    fn forget(&mut self, symbol: &str) {
+        self.pending.retain(|_, sub| sub.symbol != symbol);
+        self.retries.retain(|(_, sub)| sub.symbol != symbol);
+    }
+
+    /// Forgets all subscriptions, e.g. before re-subscribing on a new connection.
+    fn clear(&mut self) {
+        self.pending.clear();
+        self.retries.clear();
+    }
+}
+
+/// Connection stability counters maintained by the [`ConnectionManager`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionStats {
+    /// Successful connections after the first one.
+    pub reconnect_count: u64,
+    /// Time spent connected in sessions that have ended.
+    pub total_uptime: Duration,
+    /// When the current session was established, if connected.
+    pub current_session_start: Option<Instant>,
+    /// Text messages received on the public and private connections.
+    pub messages_received: u64,
+}
+
+impl ConnectionStats {
+    /// Returns how long the current session has been connected.
+    #[must_use]
+    pub fn session_uptime(&self) -> Duration {
+        self.current_session_start
+            .map_or(Duration::ZERO, |start| start.elapsed())
+    }
+
+    /// Returns the time spent connected across all sessions, including the
+    /// current one.
+    #[must_use]
+    pub fn uptime(&self) -> Duration {
+        self.total_uptime + self.session_uptime()
+    }
+
+    /// Records the start of a session, counting it as a reconnect unless it
+    /// is the first.
+    fn session_started(&mut self, now: Instant, reconnect: bool) {
+        if reconnect {
+            self.reconnect_count += 1;
+        }
+        self.current_session_start = Some(now);
+    }
+
+    /// Adds the current session's duration to the total uptime.
+    fn session_ended(&mut self, now: Instant) {
+        if let Some(start) = self.current_session_start.take() {
+            self.total_uptime += now.duration_since(start);
+        }
+    }
 }
 
 /// Why the reader loop exited.
 enum DisconnectReason {
-    /// The connection was lost or errored.
-    ConnectionError,
+    /// The public connection was lost or errored, with the cause when known.
+    PublicConnectionLost(Option<LeesonError>),
+    /// The private connection was lost or errored, with the cause when
+    /// known. The public connection is still healthy.
+    PrivateConnectionLost(Option<LeesonError>),
     /// The auth token is about to expire and needs refreshing.
     TokenExpired,
     /// API credentials were updated; reconnect with new creds.
@@ -72,6 +331,77 @@ enum DisconnectReason {
     Shutdown,
 }
 
+/// Counts consecutive reconnect attempts against an optional limit.
+#[derive(Debug, Default)]
+struct ReconnectBudget {
+    /// Attempts allowed before giving up; `None` retries forever.
+    max_attempts: Option<u32>,
+    /// Attempts made since the last successful connection.
+    attempts: u32,
+}
+
+impl ReconnectBudget {
+    /// Records a reconnect attempt, returning `false` once the limit is used up.
+    fn try_attempt(&mut self) -> bool {
+        if self.max_attempts.is_some_and(|max| self.attempts >= max) {
+            return false;
+        }
+        self.attempts += 1;
+        true
+    }
+
+    /// Starts counting afresh after a successful connection.
+    fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
+/// Reconnect schedule for one connection.
+///
+/// The next attempt is a deadline the read loop waits on alongside
+/// everything else, so the other connection and the command channel keep
+/// being served while this one backs off.
+#[derive(Debug)]
+struct Reconnect {
+    /// Attempts left before this connection gives up.
+    budget: ReconnectBudget,
+    /// Delay before the next attempt, doubled after each one.
+    backoff: Duration,
+    /// When the next attempt is due; `None` while connected or given up.
+    due: Option<tokio::time::Instant>,
+}
+
+impl Default for Reconnect {
+    fn default() -> Self {
+        Self {
+            budget: ReconnectBudget::default(),
+            backoff: INITIAL_BACKOFF,
+            due: None,
+        }
+    }
+}
+
+impl Reconnect {
+    /// Schedules the next attempt after the current backoff and doubles it,
+    /// returning `false` once the budget is used up.
+    fn schedule(&mut self) -> bool {
+        if !self.budget.try_attempt() {
+            self.due = None;
+            return false;
+        }
+        self.due = Some(tokio::time::Instant::now() + self.backoff);
+        self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+        true
+    }
+
+    /// Resets the backoff and budget after a successful connection.
+    fn succeeded(&mut self) {
+        self.budget.reset();
+        self.backoff = INITIAL_BACKOFF;
+        self.due = None;
+    }
+}
+
 /// Manages the WebSocket connection lifecycle including reconnection
 /// with exponential backoff and token refresh before expiry.
 ///
@@ -85,9 +415,46 @@ pub struct ConnectionManager {
     tx: mpsc::Sender<Message>,
     writer: Arc<tokio::sync::Mutex<Option<WsWriter>>>,
     cmd_rx: mpsc::Receiver<ConnectionCommand>,
+    /// Pairs the server has confirmed a subscription for.
     subscribed_pairs: Vec<String>,
+    /// Pairs selected in the UI whose subscription is not yet confirmed.
+    pending_pairs: Vec<String>,
     /// When the current token was last used for an authenticated operation.
     token_last_used: Option<Instant>,
+    /// Counter used to stamp outgoing RPC requests.
+    request_ids: RequestIds,
+    /// Sent RPC requests that have not been answered yet.
+    pending_requests: PendingRequests,
+    /// Active dead man's switch timeout in seconds, refreshed while connected.
+    cancel_after: Option<u32>,
+    /// Orders waiting to be sent as a batch.
+    batch: BatchOrderManager,
+    /// Public subscriptions awaiting a response or a retry.
+    subscriptions: SubscriptionTracker,
+    /// Candle interval used when subscribing to the `ohlc` channel.
+    timeframe: Timeframe,
+    /// Reconnect, uptime and message counters.
+    stats: ConnectionStats,
+    /// Token from credential validation, used for the first connection.
+    initial_token: Option<CredentialInfo>,
+    /// Backoff for the public connection; the manager gives up once its
+    /// budget is used up.
+    public_reconnect: Reconnect,
+    /// Backoff for the private connection; only the private side gives up
+    /// once its budget is used up.
+    private_reconnect: Reconnect,
+    /// Endpoint for market data.
+    public_url: String,
+    /// Endpoint for authenticated channels.
+    private_url: String,
+    /// Messages forwarded to the TUI as if read from a connection.
+    inject_channel: Option<mpsc::Receiver<Message>>,
+    /// When a rate-limited token fetch may be retried, as asked by Kraken.
+    token_retry_at: Option<Instant>,
+    /// Handed out by [`subscribe_channel_sender`](Self::subscribe_channel_sender).
+    external_tx: mpsc::Sender<ConnectionCommand>,
+    /// Commands from [`external_tx`](Self::external_tx) clones.
+    external_rx: mpsc::Receiver<ConnectionCommand>,
 }
 
 impl ConnectionManager {
@@ -102,6 +469,8 @@ impl ConnectionManager {
         writer: Arc<tokio::sync::Mutex<Option<WsWriter>>>,
         cmd_rx: mpsc::Receiver<ConnectionCommand>,
     ) -> Self {
+        let request_ids = RequestIds::default();
+        let (external_tx, external_rx) = mpsc::channel(EXTERNAL_COMMAND_CAPACITY);
         Self {
             tls_config,
             api_key,
@@ -110,10 +479,431 @@ impl ConnectionManager {
             writer,
             cmd_rx,
             subscribed_pairs: Vec::new(),
+            pending_pairs: Vec::new(),
             token_last_used: None,
+            batch: BatchOrderManager::new(request_ids.clone()),
+            request_ids,
+            pending_requests: PendingRequests::default(),
+            cancel_after: None,
+            subscriptions: SubscriptionTracker::default(),
+            timeframe: Timeframe::default(),
+            stats: ConnectionStats::default(),
+            initial_token: None,
+            public_reconnect: Reconnect::default(),
+            private_reconnect: Reconnect::default(),
+            public_url: PUBLIC_WS_URL.to_string(),
+            private_url: PRIVATE_WS_URL.to_string(),
+            inject_channel: None,
+            token_retry_at: None,
+            external_tx,
+            external_rx,
+        }
+    }
+
+    /// Forwards messages received on `rx` from the read loop, so tests can
+    /// feed it without a server sending them.
+    #[cfg(test)]
+    fn with_injector(mut self, rx: mpsc::Receiver<Message>) -> Self {
+        self.inject_channel = Some(rx);
+        self
+    }
+
+    /// Points the manager at other endpoints, such as loopback servers.
+    #[cfg(test)]
+    fn with_endpoints(mut self, public_url: String, private_url: String) -> Self {
+        self.public_url = public_url;
+        self.private_url = private_url;
+        self
+    }
+
+    /// Limits how many consecutive reconnect attempts are made after the
+    /// connection drops. `None` (the default) retries forever.
+    #[must_use]
+    pub fn with_max_reconnect_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.public_reconnect.budget.max_attempts = max_attempts;
+        self.private_reconnect.budget.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the candle interval used for the first `ohlc` subscriptions,
+    /// such as the one restored from the previous session.
+    #[must_use]
+    pub fn with_timeframe(mut self, timeframe: Timeframe) -> Self {
+        self.timeframe = timeframe;
+        self
+    }
+
+    /// Reuses the token fetched by
+    /// [`validate_credentials`](crate::auth::validate_credentials) for the
+    /// first connection instead of requesting another one.
+    #[must_use]
+    pub fn with_credential_info(mut self, info: CredentialInfo) -> Self {
+        self.initial_token = Some(info);
+        self
+    }
+
+    /// Replaces the pairs to subscribe to once connected, such as those
+    /// already selected in the UI before the manager was spawned.
+    ///
+    /// They stay pending until the server acknowledges them.
+    pub fn set_subscribed_pairs(&mut self, pairs: Vec<String>) {
+        self.subscribed_pairs.clear();
+        self.pending_pairs = pairs;
+    }
+
+    /// Returns a sender for driving the manager from outside the TUI, e.g.
+    /// with [`ConnectionCommand::SubscribeChannel`].
+    ///
+    /// Commands sent on it are handled like those from the main loop, but
+    /// dropping every clone does not shut the manager down.
+    #[must_use]
+    pub fn subscribe_channel_sender(&self) -> mpsc::Sender<ConnectionCommand> {
+        self.external_tx.clone()
+    }
+
+    /// Returns a snapshot of the connection stability counters.
+    #[must_use]
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.stats.clone()
+    }
+
+    /// Marks the RPC request answered by `value` as complete.
+    fn complete_request(&mut self, value: &serde_json::Value) {
+        if value.get("method").is_some()
+            && let Some(req_id) = value.get("req_id").and_then(|id| id.as_u64())
+            && self.pending_requests.complete(req_id)
+        {
+            debug!(req_id, "RPC response received");
+        }
+    }
+
+    /// Stops refreshing the dead man's switch if the exchange rejected it.
+    fn observe_cancel_after(&mut self, value: &serde_json::Value) {
+        if value.get("method").and_then(|m| m.as_str()) == Some("cancel_all_orders_after")
+            && value.get("success").and_then(|s| s.as_bool()) == Some(false)
+            && self.cancel_after.take().is_some()
+        {
+            warn!("dead man's switch rejected, no longer refreshing");
+        }
+    }
+
+    /// Sends a `cancel_all_orders_after` request on the private connection.
+    ///
+    /// Logs and returns without sending when there is no private connection
+    /// or auth token.
+    async fn send_cancel_after(
+        &mut self,
+        write: Option<&mut WsWriter>,
+        token: Option<&str>,
+        timeout: u32,
+    ) {
+        let (Some(write), Some(token)) = (write, token) else {
+            warn!("dead man's switch requires an authenticated connection");
+            return;
+        };
+        let req_id = self.request_ids.next();
+        let request = CancelAfterRequest::new(timeout, token, Some(req_id));
+        match cancel_after(write, request).await {
+            Ok(()) => {
+                self.token_last_used = Some(Instant::now());
+                self.pending_requests
+                    .insert(req_id, "cancel_all_orders_after", Instant::now());
+            }
+            Err(e) => warn!("Failed to send cancel_after: {e}"),
         }
     }
 
+    /// Sends an `edit_order` request on the private connection.
+    ///
+    /// Without a private connection or auth token the edit is dropped and
+    /// the TUI told it was not sent.
+    async fn send_edit_order(
+        &mut self,
+        write: Option<&mut WsWriter>,
+        token: Option<&str>,
+        mut params: EditOrderParams,
+    ) {
+        let (Some(write), Some(token)) = (write, token) else {
+            warn!("no private connection, dropping order edit");
+            self.try_send(Message::OrdersNotSent {
+                count: 1,
+                reason: "no authenticated connection".to_string(),
+            });
+            return;
+        };
+        params.token = RedactedToken::new(token);
+        let req_id = self.request_ids.next();
+        match edit_order(write, EditOrderRequest::new(params, Some(req_id))).await {
+            Ok(()) => {
+                self.token_last_used = Some(Instant::now());
+                self.pending_requests
+                    .insert(req_id, "edit_order", Instant::now());
+            }
+            Err(e) => {
+                warn!("Failed to send edit_order: {e}");
+                self.try_send(Message::OrdersNotSent {
+                    count: 1,
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Sends a `cancel_order` request, stamped with the current auth token.
+    async fn send_cancel_order(
+        &mut self,
+        write: Option<&mut WsWriter>,
+        token: Option<&str>,
+        mut params: CancelOrderParams,
+    ) {
+        let count = params.order_id.as_ref().map_or(1, Vec::len);
+        let (Some(write), Some(token)) = (write, token) else {
+            warn!("no private connection, dropping order cancel");
+            self.try_send(Message::OrdersNotSent {
+                count,
+                reason: "no authenticated connection".to_string(),
+            });
+            return;
+        };
+        params.token = RedactedToken::new(token);
+        let req_id = self.request_ids.next();
+        match cancel_order(write, CancelOrderRequest::new(params, Some(req_id))).await {
+            Ok(()) => {
+                self.token_last_used = Some(Instant::now());
+                self.pending_requests
+                    .insert(req_id, "cancel_order", Instant::now());
+            }
+            Err(e) => {
+                warn!("Failed to send cancel_order: {e}");
+                self.try_send(Message::OrdersNotSent {
+                    count,
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Sends a `batch_cancel` (or `cancel_order` for a single order) for
+    /// `order_ids`, stamped with the current auth token.
+    async fn send_batch_cancel(
+        &mut self,
+        write: Option<&mut WsWriter>,
+        token: Option<&str>,
+        order_ids: Vec<String>,
+    ) {
+        let count = order_ids.len();
+        let (Some(write), Some(token)) = (write, token) else {
+            warn!("no private connection, dropping batch cancel");
+            self.try_send(Message::OrdersNotSent {
+                count,
+                reason: "no authenticated connection".to_string(),
+            });
+            return;
+        };
+        let req_id = self.request_ids.next();
+        let method = if count < MIN_BATCH_CANCEL_SIZE {
+            "cancel_order"
+        } else {
+            "batch_cancel"
+        };
+        let sent: crate::Result<()> = async {
+            if count < MIN_BATCH_CANCEL_SIZE {
+                let request = CancelOrderBuilder::by_order_id(order_ids)
+                    .with_req_id(req_id)
+                    .build_request(token)?;
+                cancel_order(write, request).await
+            } else {
+                let request = BatchCancelBuilder::with_orders(order_ids)
+                    .with_req_id(req_id)
+                    .build(token)?;
+                batch_cancel(write, request).await
+            }
+        }
+        .await;
+        match sent {
+            Ok(()) => {
+                self.token_last_used = Some(Instant::now());
+                self.pending_requests.insert(req_id, method, Instant::now());
+            }
+            Err(e) => {
+                warn!("Failed to send {method}: {e}");
+                self.try_send(Message::OrdersNotSent {
+                    count,
+                    reason: e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Updates the subscribed pairs from a server acknowledgement and retries
+    /// or gives up on tracked subscriptions the server rejected.
+    fn observe_subscription(&mut self, ack: &SubscribeResponse) {
+        let symbol = ack.symbol().map(str::to_string);
+
+        if ack.is_unsubscribe() {
+            if ack.success
+                && let Some(symbol) = symbol
+            {
+                self.subscribed_pairs.retain(|s| s != &symbol);
+            }
+            return;
+        }
+
+        if ack.success {
+            if let Some(req_id) = ack.req_id {
+                self.subscriptions.acknowledge(req_id);
+            }
+            if let Some(symbol) = symbol {
+                self.pending_pairs.retain(|s| s != &symbol);
+                if !self.subscribed_pairs.contains(&symbol) {
+                    self.subscribed_pairs.push(symbol);
+                }
+            }
+            return;
+        }
+
+        let error = ack
+            .error
+            .clone()
+            .unwrap_or_else(|| "unknown error".to_string());
+        let outcome = ack.req_id.and_then(|req_id| {
+            self.subscriptions
+                .reject(req_id, tokio::time::Instant::now())
+        });
+        match outcome {
+            Some(SubscriptionOutcome::Retrying(sub)) => {
+                warn!(
+                    channel = sub.channel.as_str(),
+                    symbol = %sub.symbol,
+                    attempt = sub.retries,
+                    "subscription failed, retrying: {error}"
+                );
+            }
+            Some(SubscriptionOutcome::GaveUp(sub)) => {
+                error!(
+                    channel = sub.channel.as_str(),
+                    symbol = %sub.symbol,
+                    "subscription failed permanently: {error}"
+                );
+                self.pending_pairs.retain(|s| s != &sub.symbol);
+                self.try_send(Message::SubscriptionFailed {
+                    channel: sub.channel.as_str().to_string(),
+                    symbol: sub.symbol,
+                    error,
+                });
+            }
+            None => {
+                warn!(req_id = ?ack.req_id, symbol = ?symbol, "subscription failed: {error}");
+                if let Some(symbol) = symbol {
+                    self.pending_pairs.retain(|s| s != &symbol);
+                }
+            }
+        }
+    }
+
+    /// Sends a public subscription stamped with a fresh `req_id` and tracks
+    /// it until the server responds.
+    async fn send_subscription(&mut self, write: &mut WsWriter, subscription: PublicSubscription) {
+        let req_id = self.request_ids.next();
+        let symbols = std::slice::from_ref(&subscription.symbol);
+        let json = match subscription.channel {
+            Channel::Book => serde_json::to_string(
+                &BookSubscribeRequest::new(symbols, BookDepth::D25, None).with_req_id(req_id),
+            ),
+            Channel::Candles => serde_json::to_string(
+                &SubscribeRequest::new(&Channel::Candles, symbols, None)
+                    .with_interval(self.timeframe.interval())
+                    .with_req_id(req_id),
+            ),
+            channel => serde_json::to_string(
+                &SubscribeRequest::new(&channel, symbols, None).with_req_id(req_id),
+            ),
+        }
+        .expect("subscribe request serialization should not fail");
+
+        match write.send(WsMessage::Text(json.into())).await {
+            Ok(()) => {
+                debug!(
+                    req_id,
+                    channel = subscription.channel.as_str(),
+                    symbol = %subscription.symbol,
+                    "Subscribed to channel"
+                );
+                self.subscriptions.sent(req_id, subscription);
+            }
+            Err(e) => warn!(
+                channel = subscription.channel.as_str(),
+                symbol = %subscription.symbol,
+                "Failed to subscribe: {e}"
+            ),
+        }
+    }
+
+    /// Subscribes each of `symbols` to `channel` on the public connection.
+    async fn subscribe_channel(&mut self, channel: Channel, symbols: Vec<String>) {
+        let writer = self.writer.clone();
+        let mut guard = writer.lock().await;
+        let Some(write) = guard.as_mut() else {
+            warn!(
+                channel = channel.as_str(),
+                "no public connection, dropping subscription"
+            );
+            return;
+        };
+        for symbol in symbols {
+            let subscription = PublicSubscription {
+                channel,
+                symbol,
+                retries: 0,
+            };
+            self.send_subscription(write, subscription).await;
+        }
+    }
+
+    /// Re-sends rejected subscriptions whose retry delay has passed.
+    async fn retry_subscriptions(&mut self) {
+        let due = self.subscriptions.take_due(tokio::time::Instant::now());
+        let writer = self.writer.clone();
+        let mut guard = writer.lock().await;
+        let Some(write) = guard.as_mut() else {
+            return;
+        };
+        for subscription in due {
+            self.send_subscription(write, subscription).await;
+        }
+    }
+
+    /// Sends queued orders on the private connection and tracks their responses.
+    ///
+    /// Without a private connection the queue is discarded and the TUI told
+    /// how many orders were lost.
+    async fn flush_orders(&mut self, write: Option<&mut WsWriter>) {
+        let Some(write) = write else {
+            let count = self.batch.clear();
+            warn!(count, "no private connection, dropping queued orders");
+            self.try_send(Message::OrdersNotSent {
+                count,
+                reason: "no authenticated connection".to_string(),
+            });
+            return;
+        };
+
+        let queued = self.batch.len();
+        if let Err(e) = self.batch.flush(write).await {
+            warn!("Failed to send queued orders: {e}");
+            self.try_send(Message::OrdersNotSent {
+                count: queued,
+                reason: e.to_string(),
+            });
+        }
+
+        let now = Instant::now();
+        for (req_id, method) in self.batch.take_sent() {
+            self.pending_requests.insert(req_id, method, now);
+        }
+        self.token_last_used = Some(now);
+    }
+
     /// Sends a message to the TUI, logging a warning when the channel is full.
     fn try_send(&self, message: Message) {
         match self.tx.try_send(message) {
@@ -135,12 +925,27 @@ impl ConnectionManager {
         )
     }
 
-    /// Fetches a fresh auth token, or `None` if no credentials.
-    async fn fetch_token(&self) -> Option<Zeroizing<String>> {
+    /// Returns an auth token and when it was issued, or `None` if no
+    /// credentials.
+    ///
+    /// The token from credential validation is used once if it is not yet
+    /// due for refresh; otherwise a fresh token is fetched.
+    async fn fetch_token(&mut self) -> Option<(Zeroizing<String>, Instant)> {
+        self.token_retry_at = None;
         if !self.has_credentials() {
             return None;
         }
 
+        if let Some(info) = self.initial_token.take() {
+            let issued_at = info.expires_at.checked_sub(TOKEN_LIFETIME);
+            if let Some(issued_at) = issued_at
+                && issued_at.elapsed() < TOKEN_REFRESH_INTERVAL
+            {
+                info!("Using authentication token from credential validation");
+                return Some((info.token, issued_at));
+            }
+        }
+
         let key = self.api_key.as_ref().unwrap();
         let secret = self.api_secret.as_ref().unwrap();
         let tls = (*self.tls_config).clone();
@@ -148,7 +953,20 @@ impl ConnectionManager {
         match get_websocket_token(key, secret, tls).await {
             Ok(token) => {
                 info!("Fetched authentication token");
-                Some(token)
+                Some((token, Instant::now()))
+            }
+            Err(
+                e @ LeesonError::TokenRefreshFailed {
+                    retry_after: Some(retry_after),
+                    ..
+                },
+            ) => {
+                warn!(
+                    retry_after_secs = retry_after.as_secs(),
+                    "Failed to fetch auth token: {e}"
+                );
+                self.token_retry_at = Some(Instant::now() + retry_after);
+                None
             }
             Err(e) => {
                 error!("Failed to fetch auth token: {e}");
@@ -158,17 +976,30 @@ impl ConnectionManager {
     }
 
     /// Subscribes to public channels (market data) on the given writer.
-    async fn subscribe_public(&self, write: &mut WsWriter) {
+    ///
+    /// Both confirmed and still-pending pairs are re-subscribed; they stay
+    /// pending until the new connection acknowledges them. Each pair
+    /// subscription is tracked so that rejections can be retried.
+    async fn subscribe_public(&mut self, write: &mut WsWriter) {
         if let Err(e) = subscribe_instrument(write).await {
             warn!("Failed to subscribe to instruments: {e}");
         }
 
-        for symbol in &self.subscribed_pairs {
-            let symbols = vec![symbol.clone()];
-            let _ = subscribe(write, &Channel::Ticker, &symbols, None).await;
-            let _ = subscribe_book(write, &symbols, BookDepth::D25, None).await;
-            let _ = subscribe(write, &Channel::Candles, &symbols, None).await;
-            let _ = subscribe(write, &Channel::Trades, &symbols, None).await;
+        for symbol in std::mem::take(&mut self.subscribed_pairs) {
+            if !self.pending_pairs.contains(&symbol) {
+                self.pending_pairs.push(symbol);
+            }
+        }
+        self.subscriptions.clear();
+        for symbol in self.pending_pairs.clone() {
+            for channel in PUBLIC_CHANNELS {
+                let subscription = PublicSubscription {
+                    channel,
+                    symbol: symbol.clone(),
+                    retries: 0,
+                };
+                self.send_subscription(write, subscription).await;
+            }
         }
     }
 
@@ -182,20 +1013,98 @@ impl ConnectionManager {
         }
     }
 
-    /// Runs the connection manager loop indefinitely.
+    /// Connects to the public endpoint, pings it and subscribes to market
+    /// data.
+    ///
+    /// On failure returns why, for the TUI's reconnect notice, along with
+    /// the error.
+    async fn connect_public(
+        &mut self,
+    ) -> std::result::Result<(WsWriter, WsReader), (&'static str, LeesonError)> {
+        info!(url = %self.public_url, "Connecting to public WebSocket");
+        let (mut write, read) = match connect(&self.public_url, self.tls_config.clone()).await {
+            Ok(pair) => pair,
+            Err(e) => {
+                error!("Public connection failed: {e}");
+                return Err(("connection failed", e));
+            }
+        };
+
+        if let Err(e) = ping(&mut write).await {
+            warn!("Public ping failed: {e}");
+            return Err(("ping failed", e));
+        }
+
+        self.subscribe_public(&mut write).await;
+        info!("Public WebSocket connected and subscribed");
+        Ok((write, read))
+    }
+
+    /// Connects to the private endpoint and subscribes to the authenticated
+    /// channels with `token`.
+    ///
+    /// Returns `None` if the connection could not be set up; the session
+    /// then continues with the public connection only.
+    async fn connect_private(&self, token: &str) -> Option<(WsWriter, WsReader)> {
+        info!(url = %self.private_url, "Connecting to private WebSocket");
+        match connect(&self.private_url, self.tls_config.clone()).await {
+            Ok((mut write, read)) => {
+                if let Err(e) = ping(&mut write).await {
+                    warn!("Private ping failed: {e}");
+                    self.try_send(Message::PrivateChannelStatus(false));
+                    None
+                } else {
+                    self.subscribe_private(&mut write, token).await;
+                    info!("Private WebSocket connected and subscribed");
+                    self.try_send(Message::PrivateChannelStatus(true));
+                    Some((write, read))
+                }
+            }
+            Err(e) => {
+                warn!("Private connection failed (continuing with public only): {e}");
+                self.try_send(Message::PrivateChannelStatus(false));
+                None
+            }
+        }
+    }
+
+    /// Hands the public writer to the main loop and reports the new
+    /// session to the TUI.
+    async fn public_connected(&mut self, write: WsWriter, reconnect: bool) {
+        {
+            let mut guard = self.writer.lock().await;
+            *guard = Some(write);
+        }
+        self.try_send(Message::Connected);
+        self.stats.session_started(Instant::now(), reconnect);
+        self.try_send(Message::ConnectionStats(self.connection_stats()));
+    }
+
+    /// Runs the connection manager loop until shutdown.
     ///
     /// Connects to both public and private WebSocket endpoints,
     /// reads messages, and automatically reconnects with exponential
-    /// backoff on disconnection. Refreshes the auth token before it expires.
+    /// backoff on disconnection. When only one connection drops, only that
+    /// one is reconnected, from within the read loop, so the other keeps its
+    /// session and the auth token is not refreshed early. Refreshes the auth
+    /// token before it expires. Gives up once the public reconnect limit, if
+    /// any, has been reached; running out of private reconnects only leaves
+    /// the session public-only until the next token refresh.
     pub async fn run(mut self) {
-        let mut backoff = INITIAL_BACKOFF;
+        let mut connected_before = false;
+        let mut reason = "initial connection";
 
         loop {
-            // Notify UI we're reconnecting
-            self.try_send(Message::Reconnecting);
+            // Notify UI we're reconnecting, and why
+            self.try_send(Message::Reconnecting {
+                reason: reason.to_string(),
+            });
 
             // Fetch a token if we have credentials (for private connection)
-            let token = self.fetch_token().await;
+            let (token, token_fetched_at) = match self.fetch_token().await {
+                Some((token, fetched_at)) => (Some(token), fetched_at),
+                None => (None, Instant::now()),
+            };
             if token.is_some() {
                 self.try_send(Message::TokenState(TokenState::Valid));
             } else {
@@ -206,128 +1115,222 @@ impl ConnectionManager {
             }
 
             // Connect to PUBLIC endpoint for market data
-            info!(url = %PUBLIC_WS_URL, "Connecting to public WebSocket");
-            let public_result = connect(PUBLIC_WS_URL, self.tls_config.clone()).await;
-            let (mut public_write, public_read) = match public_result {
+            let (public_write, public_read) = match self.connect_public().await {
                 Ok(pair) => pair,
-                Err(e) => {
-                    error!("Public connection failed: {e}");
-                    self.try_send(Message::Disconnected);
-                    info!(backoff_secs = backoff.as_secs(), "Backing off before retry");
-                    tokio::time::sleep(backoff).await;
-                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                Err((failure, e)) => {
+                    self.try_send(Message::Disconnected(Some(e)));
+                    reason = failure;
+                    if let Some(DisconnectReason::Shutdown) = self.back_off().await {
+                        return;
+                    }
                     continue;
                 }
             };
+            let mut public_read = Some(public_read);
 
-            // Ping and subscribe on public connection
-            if let Err(e) = ping(&mut public_write).await {
-                warn!("Public ping failed: {e}");
-                self.try_send(Message::Disconnected);
-                tokio::time::sleep(backoff).await;
-                backoff = (backoff * 2).min(MAX_BACKOFF);
-                continue;
-            }
+            // Connect to PRIVATE endpoint if we have credentials
+            let private_connection = match token {
+                Some(ref token_str) => self.connect_private(token_str).await,
+                None => None,
+            };
 
-            self.subscribe_public(&mut public_write).await;
-            info!("Public WebSocket connected and subscribed");
+            // Hand the public writer to the main loop (for sending subscriptions)
+            self.public_connected(public_write, connected_before).await;
+            connected_before = true;
 
-            // Connect to PRIVATE endpoint if we have credentials
-            let private_connection = if let Some(ref token_str) = token {
-                info!(url = %PRIVATE_WS_URL, "Connecting to private WebSocket");
-                match connect(PRIVATE_WS_URL, self.tls_config.clone()).await {
-                    Ok((mut private_write, private_read)) => {
-                        if let Err(e) = ping(&mut private_write).await {
-                            warn!("Private ping failed: {e}");
-                            self.try_send(Message::PrivateChannelStatus(false));
-                            None
-                        } else {
-                            self.subscribe_private(&mut private_write, token_str).await;
-                            info!("Private WebSocket connected and subscribed");
-                            self.try_send(Message::PrivateChannelStatus(true));
-                            Some((private_write, private_read))
+            // Reset backoff and the reconnect budgets on successful connection
+            self.public_reconnect.succeeded();
+            self.private_reconnect.succeeded();
+
+            // Keep the private writer so the connection can be closed cleanly
+            let (mut private_write, mut private_read) = match private_connection {
+                Some((write, read)) => (Some(write), Some(read)),
+                None => {
+                    if token.is_some() {
+                        self.schedule_private_reconnect();
+                    }
+                    (None, None)
+                }
+            };
+
+            // Enter reader loop; a single dropped connection is reconnected
+            // in the background while the other one keeps being read
+            let disconnect = loop {
+                let disconnect = self
+                    .read_loop(
+                        &mut public_read,
+                        &mut private_write,
+                        &mut private_read,
+                        token.as_deref().map(String::as_str),
+                        token_fetched_at,
+                    )
+                    .await;
+
+                match disconnect {
+                    DisconnectReason::PublicConnectionLost(error) => {
+                        // Clear the writer so the main loop doesn't use a stale one
+                        self.writer.lock().await.take();
+                        self.stats.session_ended(Instant::now());
+                        self.try_send(Message::Disconnected(error));
+                        public_read = None;
+                        if !self.schedule_public_reconnect() {
+                            break DisconnectReason::Shutdown;
                         }
                     }
-                    Err(e) => {
-                        warn!("Private connection failed (continuing with public only): {e}");
+                    DisconnectReason::PrivateConnectionLost(error) => {
+                        match error {
+                            Some(e) => warn!("Private connection lost: {e}"),
+                            None => warn!("Private connection lost"),
+                        }
                         self.try_send(Message::PrivateChannelStatus(false));
-                        None
+                        private_write = None;
+                        private_read = None;
+                        self.schedule_private_reconnect();
                     }
+                    other => break other,
                 }
-            } else {
-                None
             };
 
-            // Hand the public writer to the main loop (for sending subscriptions)
-            {
-                let mut guard = self.writer.lock().await;
-                *guard = Some(public_write);
-            }
-            self.try_send(Message::Connected);
-
-            // Reset backoff on successful connection
-            backoff = INITIAL_BACKOFF;
-
-            // Enter reader loop
-            let token_fetched_at = Instant::now();
-            let reason = self
-                .read_loop(
-                    public_read,
-                    private_connection,
-                    token.is_some(),
-                    token_fetched_at,
-                )
-                .await;
-
             // Clear the writer so the main loop doesn't use a stale one
-            {
-                let mut guard = self.writer.lock().await;
-                *guard = None;
+            let public_write = self.writer.lock().await.take();
+            if public_write.is_some() {
+                self.stats.session_ended(Instant::now());
             }
 
-            match reason {
+            match disconnect {
                 DisconnectReason::TokenExpired | DisconnectReason::CredentialsUpdated => {
+                    reason = match disconnect {
+                        DisconnectReason::TokenExpired if token.is_none() => "retrying token fetch",
+                        DisconnectReason::TokenExpired => "token expired",
+                        _ => "credentials updated",
+                    };
                     self.try_send(Message::TokenState(TokenState::Refreshing));
                     info!("Token expiring or credentials updated, reconnecting");
                     // No backoff for planned refresh or credential update
                 }
-                DisconnectReason::ConnectionError => {
-                    self.try_send(Message::Disconnected);
-                    self.try_send(Message::TokenState(TokenState::Refreshing));
-                    info!(
-                        backoff_secs = backoff.as_secs(),
-                        "Connection lost, backing off"
-                    );
-                    tokio::time::sleep(backoff).await;
-                    backoff = (backoff * 2).min(MAX_BACKOFF);
-                }
-                DisconnectReason::Shutdown => {
+                // Lost connections are reconnected above or gave up as a shutdown
+                DisconnectReason::PublicConnectionLost(_)
+                | DisconnectReason::PrivateConnectionLost(_)
+                | DisconnectReason::Shutdown => {
                     info!("Connection manager shutting down");
+                    Self::close_connections(
+                        public_write.map(|write| (write, public_read)),
+                        private_write.map(|write| (write, private_read)),
+                    )
+                    .await;
                     return;
                 }
             }
         }
     }
 
-    /// Reads messages from both WebSocket connections until disconnection,
-    /// token expiry, or shutdown.
+    /// Schedules the next public reconnect attempt.
+    ///
+    /// Returns `false` instead, after reporting
+    /// [`LeesonError::MaxRetriesExceeded`] to the TUI, once the reconnect
+    /// limit has been reached.
+    fn schedule_public_reconnect(&mut self) -> bool {
+        if !self.public_reconnect.schedule() {
+            let attempts = self.public_reconnect.budget.attempts;
+            error!(attempts, "Reconnect limit reached, giving up");
+            self.try_send(Message::Disconnected(Some(
+                LeesonError::MaxRetriesExceeded { attempts },
+            )));
+            return false;
+        }
+        info!(
+            backoff_secs = self.public_reconnect.backoff.as_secs(),
+            "Public connection lost, backing off"
+        );
+        true
+    }
+
+    /// Schedules the next private reconnect attempt.
+    ///
+    /// Once the reconnect limit has been reached only the private side
+    /// gives up; the public feed keeps running and the next token refresh
+    /// tries the private connection afresh.
+    fn schedule_private_reconnect(&mut self) {
+        if self.private_reconnect.schedule() {
+            info!(
+                backoff_secs = self.private_reconnect.backoff.as_secs(),
+                "Private connection lost, backing off"
+            );
+        } else {
+            error!(
+                attempts = self.private_reconnect.budget.attempts,
+                "Private reconnect limit reached, continuing with public only"
+            );
+        }
+    }
+
+    /// Sleeps until the next public reconnect attempt is due.
+    ///
+    /// Used only while neither connection is up; returns
+    /// [`DisconnectReason::Shutdown`] once the reconnect limit has been
+    /// reached.
+    async fn back_off(&mut self) -> Option<DisconnectReason> {
+        if !self.schedule_public_reconnect() {
+            return Some(DisconnectReason::Shutdown);
+        }
+        if let Some(due) = self.public_reconnect.due.take() {
+            tokio::time::sleep_until(due).await;
+        }
+        None
+    }
+
+    /// Sends a close frame on each open connection and waits for the
+    /// server to complete the close handshake.
+    ///
+    /// Both connections are closed concurrently; each gives up after
+    /// [`CLOSE_TIMEOUT`] so an unresponsive server cannot stall shutdown.
+    async fn close_connections(
+        public: Option<(WsWriter, Option<WsReader>)>,
+        private: Option<(WsWriter, Option<WsReader>)>,
+    ) {
+        tokio::join!(
+            async {
+                if let Some((write, read)) = public {
+                    close_connection("public", write, read).await;
+                }
+            },
+            async {
+                if let Some((write, read)) = private {
+                    close_connection("private", write, read).await;
+                }
+            },
+        );
+    }
+
+    /// Reads messages from both WebSocket connections until either one
+    /// drops, the token expires, or shutdown.
+    ///
+    /// Each connection is pinged every [`PING_INTERVAL`] and counts as lost
+    /// if nothing arrives on it within [`PONG_TIMEOUT`]. A connection that
+    /// is down is reconnected here once its backoff has elapsed, so the
+    /// other one and the command channel are served in the meantime.
+    ///
+    /// While the dead man's switch is armed it is refreshed on the private
+    /// connection every half timeout, starting immediately on entry so the
+    /// switch survives reconnects.
     async fn read_loop(
         &mut self,
-        mut public_read: WsReader,
-        private_connection: Option<(WsWriter, WsReader)>,
-        has_token: bool,
+        public_read: &mut Option<WsReader>,
+        private_write: &mut Option<WsWriter>,
+        private_read: &mut Option<WsReader>,
+        token: Option<&str>,
         token_fetched_at: Instant,
     ) -> DisconnectReason {
-        // Split private connection if available
-        let mut private_read = private_connection.map(|(_, read)| read);
+        let has_token = token.is_some();
 
         // Build the token refresh deadline and warning deadline
+        // Without a token, retry the fetch once a rate limit has passed
         let refresh_deadline = if has_token {
             Some(tokio::time::Instant::from_std(
                 token_fetched_at + TOKEN_REFRESH_INTERVAL,
             ))
         } else {
-            None
+            self.token_retry_at.map(tokio::time::Instant::from_std)
         };
         let warning_deadline = if has_token {
             Some(tokio::time::Instant::from_std(
@@ -353,16 +1356,53 @@ impl ConnectionManager {
         };
         tokio::pin!(warning_sleep);
 
+        // Liveness probe: ping periodically and expect a reply within PONG_TIMEOUT
+        let mut ping_timer =
+            tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+        let mut last_ping_sent: Option<tokio::time::Instant> = None;
+        let mut last_private_ping_sent: Option<tokio::time::Instant> = None;
+
+        let mut request_sweep = tokio::time::interval(REQUEST_SWEEP_INTERVAL);
+
+        let mut cancel_after_refresh =
+            tokio::time::interval(cancel_after_refresh_period(self.cancel_after.unwrap_or(0)));
+
+        let mut stats_report =
+            tokio::time::interval_at(tokio::time::Instant::now() + STATS_INTERVAL, STATS_INTERVAL);
+
         loop {
+            let cancel_after_armed = self.cancel_after.is_some();
+            let batch_deadline = self.batch.deadline();
+            let subscription_retry = self.subscriptions.next_retry();
+            let public_reconnect = self.public_reconnect.due;
+            let private_reconnect = self.private_reconnect.due;
+
             tokio::select! {
-                // Read from public connection
-                msg = public_read.next() => {
+                // Read from public connection (while connected)
+                msg = async {
+                    match public_read {
+                        Some(read) => read.next().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    // Any frame from the server proves the connection is alive
+                    if matches!(msg, Some(Ok(_))) {
+                        last_ping_sent = None;
+                    }
                     match msg {
                         Some(Ok(WsMessage::Text(text))) => {
                             debug!("Public WS message: {}", text);
-                            if let Ok(value) = serde_json::from_str::<serde_json::Value>(&text)
-                                && let Some(message) = parse_ws_message(value)
-                            {
+                            self.stats.messages_received += 1;
+                            let value = serde_json::from_str::<serde_json::Value>(&text).ok();
+                            if let Some(ref value) = value {
+                                self.complete_request(value);
+                                self.observe_cancel_after(value);
+                            }
+                            let message = value.and_then(parse_ws_message);
+                            if let Some(Message::SubscribeAck(ref ack)) = message {
+                                self.observe_subscription(ack);
+                            }
+                            if let Some(message) = message {
                                 match self.tx.try_send(message) {
                                     Ok(()) => {}
                                     Err(mpsc::error::TrySendError::Full(_)) => {
@@ -374,31 +1414,43 @@ impl ConnectionManager {
                                 }
                             }
                         }
-                        Some(Ok(_)) => {} // Binary/Ping/Pong/Close frames
+                        Some(Ok(WsMessage::Close(frame))) => {
+                            let error = LeesonError::from_close_frame(frame.as_ref());
+                            warn!("Public WebSocket closed by server: {error}");
+                            return DisconnectReason::PublicConnectionLost(Some(error));
+                        }
+                        Some(Ok(_)) => {} // Binary/Ping/Pong frames
                         Some(Err(e)) => {
                             warn!("Public WebSocket error: {e}");
-                            return DisconnectReason::ConnectionError;
+                            return DisconnectReason::PublicConnectionLost(Some(e.into()));
                         }
                         None => {
                             warn!("Public WebSocket stream ended");
-                            return DisconnectReason::ConnectionError;
+                            return DisconnectReason::PublicConnectionLost(None);
                         }
                     }
                 }
 
                 // Read from private connection (if available)
                 msg = async {
-                    match &mut private_read {
+                    match private_read {
                         Some(read) => read.next().await,
                         None => std::future::pending().await,
                     }
                 } => {
+                    if matches!(msg, Some(Ok(_))) {
+                        last_private_ping_sent = None;
+                    }
                     match msg {
                         Some(Ok(WsMessage::Text(text))) => {
                             debug!("Private WS message: {}", text);
-                            if let Ok(value) = serde_json::from_str::<serde_json::Value>(&text)
-                                && let Some(message) = parse_ws_message(value)
-                            {
+                            self.stats.messages_received += 1;
+                            let value = serde_json::from_str::<serde_json::Value>(&text).ok();
+                            if let Some(ref value) = value {
+                                self.complete_request(value);
+                                self.observe_cancel_after(value);
+                            }
+                            if let Some(message) = value.and_then(parse_ws_message) {
                                 match self.tx.try_send(message) {
                                     Ok(()) => {}
                                     Err(mpsc::error::TrySendError::Full(_)) => {
@@ -410,30 +1462,48 @@ impl ConnectionManager {
                                 }
                             }
                         }
-                        Some(Ok(_)) => {} // Binary/Ping/Pong/Close frames
+                        Some(Ok(WsMessage::Close(frame))) => {
+                            let error = LeesonError::from_close_frame(frame.as_ref());
+                            warn!("Private WebSocket closed by server: {error}");
+                            return DisconnectReason::PrivateConnectionLost(Some(error));
+                        }
+                        Some(Ok(_)) => {} // Binary/Ping/Pong frames
                         Some(Err(e)) => {
                             warn!("Private WebSocket error: {e}");
-                            // Don't fail completely, just log and continue with public
-                            private_read = None;
-                            self.try_send(Message::PrivateChannelStatus(false));
+                            return DisconnectReason::PrivateConnectionLost(Some(e.into()));
                         }
                         None => {
                             warn!("Private WebSocket stream ended");
-                            private_read = None;
-                            self.try_send(Message::PrivateChannelStatus(false));
+                            return DisconnectReason::PrivateConnectionLost(None);
                         }
                     }
                 }
 
-                cmd = self.cmd_rx.recv() => {
+                Some(message) = async {
+                    match self.inject_channel.as_mut() {
+                        Some(rx) => rx.recv().await,
+                        None => std::future::pending().await,
+                    }
+                } => {
+                    if let Err(mpsc::error::TrySendError::Closed(_)) = self.tx.try_send(message) {
+                        return DisconnectReason::Shutdown;
+                    }
+                }
+
+                cmd = next_command(&mut self.cmd_rx, &mut self.external_rx) => {
                     match cmd {
                         Some(ConnectionCommand::PairSubscribed(symbol)) => {
-                            if !self.subscribed_pairs.contains(&symbol) {
-                                self.subscribed_pairs.push(symbol);
+                            // Confirmed once the server acknowledges it
+                            if !self.subscribed_pairs.contains(&symbol)
+                                && !self.pending_pairs.contains(&symbol)
+                            {
+                                self.pending_pairs.push(symbol);
                             }
                         }
                         Some(ConnectionCommand::PairUnsubscribed(symbol)) => {
-                            self.subscribed_pairs.retain(|s| s != &symbol);
+                            // Removed from `subscribed_pairs` by the unsubscribe ack
+                            self.pending_pairs.retain(|s| s != &symbol);
+                            self.subscriptions.forget(&symbol);
                         }
                         Some(ConnectionCommand::TokenUsed) => {
                             self.token_last_used = Some(Instant::now());
@@ -452,6 +1522,44 @@ impl ConnectionManager {
                             info!("credentials updated, reconnecting");
                             return DisconnectReason::CredentialsUpdated;
                         }
+                        Some(ConnectionCommand::SubmitOrder(mut params)) => {
+                            if let Some(token) = token {
+                                params.token = RedactedToken::new(token);
+                            }
+                            self.batch.submit(*params);
+                            if self.batch.is_full() {
+                                self.flush_orders(private_write.as_mut()).await;
+                            }
+                        }
+                        Some(ConnectionCommand::EditOrder(params)) => {
+                            self.send_edit_order(private_write.as_mut(), token, *params).await;
+                        }
+                        Some(ConnectionCommand::CancelOrder(params)) => {
+                            self.send_cancel_order(private_write.as_mut(), token, *params)
+                                .await;
+                        }
+                        Some(ConnectionCommand::BatchCancel(order_ids)) => {
+                            self.send_batch_cancel(private_write.as_mut(), token, order_ids)
+                                .await;
+                        }
+                        Some(ConnectionCommand::SubscribeChannel(channel, symbols)) => {
+                            self.subscribe_channel(channel, symbols).await;
+                        }
+                        Some(ConnectionCommand::SetTimeframe(timeframe)) => {
+                            self.timeframe = timeframe;
+                        }
+                        Some(ConnectionCommand::SetCancelAfter(timeout)) => {
+                            if timeout == 0 {
+                                self.cancel_after = None;
+                                self.send_cancel_after(private_write.as_mut(), token, 0).await;
+                            } else {
+                                // The refresh timer fires immediately, arming the switch
+                                self.cancel_after = Some(timeout);
+                                cancel_after_refresh = tokio::time::interval(
+                                    cancel_after_refresh_period(timeout),
+                                );
+                            }
+                        }
                         None => {
                             // Command channel closed, app is shutting down
                             return DisconnectReason::Shutdown;
@@ -472,11 +1580,195 @@ impl ConnectionManager {
                 () = &mut token_sleep => {
                     return DisconnectReason::TokenExpired;
                 }
+
+                _ = request_sweep.tick() => {
+                    for (req_id, method) in self.pending_requests.drain_expired(Instant::now()) {
+                        warn!(req_id, method, "RPC request timed out");
+                        self.try_send(Message::RequestTimeout {
+                            req_id,
+                            method: method.to_string(),
+                        });
+                    }
+                }
+
+                () = async {
+                    match batch_deadline {
+                        Some(deadline) => tokio::time::sleep_until(deadline).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    self.flush_orders(private_write.as_mut()).await;
+                }
+
+                () = async {
+                    match subscription_retry {
+                        Some(due) => tokio::time::sleep_until(due).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    self.retry_subscriptions().await;
+                }
+
+                _ = stats_report.tick() => {
+                    self.try_send(Message::ConnectionStats(self.connection_stats()));
+                }
+
+                _ = cancel_after_refresh.tick(), if cancel_after_armed => {
+                    if let Some(timeout) = self.cancel_after {
+                        self.send_cancel_after(private_write.as_mut(), token, timeout).await;
+                    }
+                }
+
+                _ = ping_timer.tick() => {
+                    let mut guard = self.writer.lock().await;
+                    if let Some(write) = guard.as_mut() {
+                        if let Err(e) = ping(write).await {
+                            warn!("Health-check ping failed: {e}");
+                            return DisconnectReason::PublicConnectionLost(Some(e));
+                        }
+                        // Keep the original send time if a ping is still outstanding
+                        last_ping_sent.get_or_insert_with(tokio::time::Instant::now);
+                    }
+                    drop(guard);
+                    if let Some(write) = private_write.as_mut() {
+                        if let Err(e) = ping(write).await {
+                            warn!("Private health-check ping failed: {e}");
+                            return DisconnectReason::PrivateConnectionLost(Some(e));
+                        }
+                        last_private_ping_sent.get_or_insert_with(tokio::time::Instant::now);
+                    }
+                }
+
+                () = async {
+                    match last_ping_sent {
+                        Some(sent) => tokio::time::sleep_until(sent + PONG_TIMEOUT).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    warn!(
+                        timeout_secs = PONG_TIMEOUT.as_secs(),
+                        "No pong received from public WebSocket"
+                    );
+                    return DisconnectReason::PublicConnectionLost(None);
+                }
+
+                () = async {
+                    match last_private_ping_sent {
+                        Some(sent) => tokio::time::sleep_until(sent + PONG_TIMEOUT).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    warn!(
+                        timeout_secs = PONG_TIMEOUT.as_secs(),
+                        "No pong received from private WebSocket"
+                    );
+                    return DisconnectReason::PrivateConnectionLost(None);
+                }
+
+                () = async {
+                    match public_reconnect {
+                        Some(due) => tokio::time::sleep_until(due).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    self.public_reconnect.due = None;
+                    self.try_send(Message::Reconnecting {
+                        reason: "public connection lost".to_string(),
+                    });
+                    match self.connect_public().await {
+                        Ok((write, read)) => {
+                            self.public_connected(write, true).await;
+                            *public_read = Some(read);
+                            last_ping_sent = None;
+                            self.public_reconnect.succeeded();
+                            // The TUI marks the private channel down on any disconnect
+                            if private_write.is_some() {
+                                self.try_send(Message::PrivateChannelStatus(true));
+                            }
+                        }
+                        Err((_, e)) => {
+                            self.try_send(Message::Disconnected(Some(e)));
+                            if !self.schedule_public_reconnect() {
+                                return DisconnectReason::Shutdown;
+                            }
+                        }
+                    }
+                }
+
+                () = async {
+                    match private_reconnect {
+                        Some(due) => tokio::time::sleep_until(due).await,
+                        None => std::future::pending::<()>().await,
+                    }
+                } => {
+                    self.private_reconnect.due = None;
+                    // Reuse the current token; the public session is unaffected
+                    let connection = match token {
+                        Some(token) => self.connect_private(token).await,
+                        None => None,
+                    };
+                    match connection {
+                        Some((write, read)) => {
+                            *private_write = Some(write);
+                            *private_read = Some(read);
+                            last_private_ping_sent = None;
+                            self.private_reconnect.succeeded();
+                        }
+                        None => self.schedule_private_reconnect(),
+                    }
+                }
             }
         }
     }
 }
 
+/// Waits for the next command from the main loop or from a
+/// [`subscribe_channel_sender`](ConnectionManager::subscribe_channel_sender)
+/// handle.
+///
+/// Returns `None` once the main loop's channel closes; the manager keeps
+/// its own external sender, so the other channel never does.
+async fn next_command(
+    cmd_rx: &mut mpsc::Receiver<ConnectionCommand>,
+    external_rx: &mut mpsc::Receiver<ConnectionCommand>,
+) -> Option<ConnectionCommand> {
+    tokio::select! {
+        cmd = cmd_rx.recv() => cmd,
+        Some(cmd) = external_rx.recv() => Some(cmd),
+    }
+}
+
+/// How often to refresh a dead man's switch armed with `timeout` seconds.
+///
+/// Refreshing at half the timeout leaves room for one lost request before
+/// the exchange cancels all orders.
+fn cancel_after_refresh_period(timeout: u32) -> Duration {
+    Duration::from_secs(u64::from(timeout / 2).max(1))
+}
+
+/// Sends a close frame on a single connection and drains the reader until
+/// the server echoes the close, the stream ends, or [`CLOSE_TIMEOUT`] elapses.
+async fn close_connection(name: &str, mut write: WsWriter, read: Option<WsReader>) {
+    let handshake = async {
+        if let Err(e) = write.send(WsMessage::Close(None)).await {
+            debug!("Failed to send {name} close frame: {e}");
+            return;
+        }
+        if let Some(mut read) = read {
+            while let Some(msg) = read.next().await {
+                if matches!(msg, Ok(WsMessage::Close(_)) | Err(_)) {
+                    break;
+                }
+            }
+        }
+    };
+
+    match tokio::time::timeout(CLOSE_TIMEOUT, handshake).await {
+        Ok(()) => info!("{name} WebSocket closed"),
+        Err(_) => warn!("Timed out waiting for {name} WebSocket close handshake"),
+    }
+}
+
 /// Parses a WebSocket JSON message into a TUI [`Message`].
 fn parse_ws_message(value: serde_json::Value) -> Option<Message> {
     let method = value.get("method").and_then(|m| m.as_str());
@@ -487,26 +1779,29 @@ fn parse_ws_message(value: serde_json::Value) -> Option<Message> {
     if let Some(method) = method {
         return match method {
             "pong" => None,
-            "subscribe" => {
-                // Log subscription responses but don't forward
-                if let Some(success) = value.get("success").and_then(|s| s.as_bool())
-                    && !success
-                    && let Some(error) = value.get("error").and_then(|e| e.as_str())
-                {
-                    warn!("Subscription failed: {}", error);
-                }
-                None
-            }
+            "subscribe" | "unsubscribe" => serde_json::from_value(value)
+                .ok()
+                .map(Message::SubscribeAck),
             "add_order" => serde_json::from_value(value).ok().map(Message::OrderPlaced),
+            "batch_add" => serde_json::from_value(value)
+                .ok()
+                .map(Message::BatchOrderResult),
             "cancel_order" => serde_json::from_value(value)
                 .ok()
                 .map(Message::OrderCancelled),
             "amend_order" => serde_json::from_value(value)
                 .ok()
                 .map(Message::OrderAmended),
+            "edit_order" => serde_json::from_value(value).ok().map(Message::OrderEdited),
+            "batch_cancel" => serde_json::from_value(value)
+                .ok()
+                .map(Message::BatchCancelResult),
             "cancel_all" => serde_json::from_value(value)
                 .ok()
                 .map(Message::AllOrdersCancelled),
+            "cancel_all_orders_after" => serde_json::from_value(value)
+                .ok()
+                .map(Message::CancelAfterUpdated),
             _ => None,
         };
     }
@@ -516,12 +1811,14 @@ fn parse_ws_message(value: serde_json::Value) -> Option<Message> {
         // Channels that need both snapshots and updates
         // - ticker: snapshot for initial price, updates for changes
         // - book: snapshot for initial order book, updates for changes
+        // - level3: snapshot for resting orders, updates for order events
         // - ohlc: snapshot for historical candles, updates for current candle
         // - executions/balances: authenticated channels need both
+        // - instrument: snapshot lists every tradable pair
         // - trade: only updates (real-time trades as they happen)
         let needs_snapshot = matches!(
             channel,
-            "ticker" | "book" | "ohlc" | "executions" | "balances"
+            "ticker" | "book" | "level3" | "ohlc" | "executions" | "balances" | "instrument"
         );
 
         // Skip snapshots for channels that only need updates (trade)
@@ -532,10 +1829,24 @@ fn parse_ws_message(value: serde_json::Value) -> Option<Message> {
         return match channel {
             "heartbeat" => Some(Message::Heartbeat),
             "status" => serde_json::from_value(value).ok().map(Message::Status),
+            "instrument" => match serde_json::from_value(value) {
+                Ok(v) => Some(Message::InstrumentUpdate(v)),
+                Err(e) => {
+                    warn!("Failed to parse instrument: {e}");
+                    None
+                }
+            },
             "ticker" => {
                 debug!("Received ticker: {:?}", value);
-                match serde_json::from_value(value.clone()) {
-                    Ok(v) => Some(Message::Ticker(v)),
+                match serde_json::from_value::<crate::models::ticker::TickerUpdateResponse>(
+                    value.clone(),
+                ) {
+                    Ok(v) => {
+                        for ticker in v.data.iter().filter(|t| !t.is_complete()) {
+                            warn!(symbol = %ticker.symbol, "Ticker is missing 24-hour statistics");
+                        }
+                        Some(Message::Ticker(v))
+                    }
                     Err(e) => {
                         warn!("Failed to parse ticker: {e}");
                         debug!("Raw ticker: {}", value);
@@ -565,6 +1876,17 @@ fn parse_ws_message(value: serde_json::Value) -> Option<Message> {
                     }
                 }
             }
+            "level3" => {
+                debug!("Received level3: {:?}", value);
+                match serde_json::from_value(value.clone()) {
+                    Ok(v) => Some(Message::Level3(v)),
+                    Err(e) => {
+                        warn!("Failed to parse level3: {e}");
+                        debug!("Raw level3: {}", value);
+                        None
+                    }
+                }
+            }
             "ohlc" => {
                 debug!("Received ohlc: {:?}", value);
                 match serde_json::from_value(value.clone()) {
@@ -596,3 +1918,1033 @@ fn parse_ws_message(value: serde_json::Value) -> Option<Message> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::test_support::loopback;
+
+    /// Builds a manager whose shared writer holds `write`.
+    ///
+    /// The returned channel ends must be kept alive, otherwise the read
+    /// loop treats their closure as an application shutdown.
+    fn test_manager(
+        write: WsWriter,
+    ) -> (
+        ConnectionManager,
+        mpsc::Receiver<Message>,
+        mpsc::Sender<ConnectionCommand>,
+    ) {
+        let (tx, rx) = mpsc::channel(64);
+        let (cmd_tx, cmd_rx) = mpsc::channel(1);
+        let tls = Arc::new(crate::tls::build_tls_config().unwrap());
+        let writer = Arc::new(tokio::sync::Mutex::new(Some(write)));
+        let manager = ConnectionManager::new(String::new(), tls, None, None, tx, writer, cmd_rx);
+        (manager, rx, cmd_tx)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_loop_disconnects_when_pong_never_arrives() {
+        let (write, read, _server) = loopback(|mut ws| async move {
+            // Swallow everything, never reply
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+
+        let reason = manager
+            .read_loop(&mut Some(read), &mut None, &mut None, None, Instant::now())
+            .await;
+
+        assert!(matches!(
+            reason,
+            DisconnectReason::PublicConnectionLost(None)
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_loop_reports_server_close_frame() {
+        let (write, read, _server) = loopback(|mut ws| async move {
+            let frame = tungstenite::protocol::CloseFrame {
+                code: tungstenite::protocol::frame::coding::CloseCode::Away,
+                reason: "maintenance".into(),
+            };
+            ws.close(Some(frame)).await.is_ok()
+        })
+        .await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+
+        let reason = manager
+            .read_loop(&mut Some(read), &mut None, &mut None, None, Instant::now())
+            .await;
+
+        match reason {
+            DisconnectReason::PublicConnectionLost(Some(LeesonError::WebSocketClosed {
+                code,
+                reason,
+            })) => {
+                assert_eq!(code, Some(1001));
+                assert_eq!(reason.as_deref(), Some("maintenance"));
+            }
+            _ => panic!("expected a WebSocketClosed disconnect"),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_loop_stays_connected_while_pongs_arrive() {
+        let (write, read, _server) = loopback(|mut ws| async move {
+            while let Some(Ok(msg)) = ws.next().await {
+                if let WsMessage::Text(text) = msg
+                    && text.contains("ping")
+                {
+                    let pong = r#"{"method":"pong","time_in":"","time_out":""}"#;
+                    if ws.send(WsMessage::Text(pong.into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            true
+        })
+        .await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+
+        let result = tokio::time::timeout(
+            PING_INTERVAL * 4,
+            manager.read_loop(&mut Some(read), &mut None, &mut None, None, Instant::now()),
+        )
+        .await;
+
+        assert!(result.is_err(), "read loop exited despite healthy pongs");
+    }
+
+    #[tokio::test]
+    async fn injected_message_reaches_the_tui() {
+        let (write, read, _server) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (manager, mut rx, _cmd_tx) = test_manager(write);
+        let (inject_tx, inject_rx) = mpsc::channel(1);
+        let mut manager = manager.with_injector(inject_rx);
+        let ticker = serde_json::from_value(serde_json::json!({
+            "channel": "ticker",
+            "type": "update",
+            "data": [{
+                "symbol": "BTC/USD",
+                "bid": "100", "bid_qty": "1", "ask": "101", "ask_qty": "1",
+                "last": "100.5", "volume": "10", "vwap": "100",
+                "low": "99", "high": "102", "change": "1", "change_pct": "1"
+            }]
+        }))
+        .unwrap();
+        inject_tx.send(Message::Ticker(ticker)).await.unwrap();
+
+        let (mut read, mut private_write, mut private_read) = (Some(read), None, None);
+        tokio::select! {
+            _ = manager.read_loop(
+                &mut read,
+                &mut private_write,
+                &mut private_read,
+                None,
+                Instant::now(),
+            ) => {
+                panic!("read loop exited early");
+            }
+            message = rx.recv() => match message {
+                Some(Message::Ticker(response)) => assert_eq!(response.data[0].symbol, "BTC/USD"),
+                other => panic!("expected the injected ticker, got {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn read_loop_reports_private_close_separately() {
+        let (public_write, public_read, _public) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (private_write, private_read, _private) =
+            loopback(|mut ws| async move { ws.close(None).await.is_ok() }).await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(public_write);
+
+        let reason = manager
+            .read_loop(
+                &mut Some(public_read),
+                &mut Some(private_write),
+                &mut Some(private_read),
+                Some("test_token"),
+                Instant::now(),
+            )
+            .await;
+
+        assert!(matches!(reason, DisconnectReason::PrivateConnectionLost(_)));
+        assert!(manager.writer.lock().await.is_some());
+    }
+
+    /// Accepts the next WebSocket client on `listener`.
+    async fn accept(
+        listener: &tokio::net::TcpListener,
+    ) -> tokio_tungstenite::WebSocketStream<tokio::net::TcpStream> {
+        let (tcp, _) = listener.accept().await.unwrap();
+        tokio_tungstenite::accept_async(tcp).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn public_drop_reconnects_without_touching_private_session() {
+        let public = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let private = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (tx, mut rx) = mpsc::channel(64);
+        let (_cmd_tx, cmd_rx) = mpsc::channel(1);
+        let tls = Arc::new(crate::tls::build_tls_config().unwrap());
+        let writer = Arc::new(tokio::sync::Mutex::new(None));
+        let manager = ConnectionManager::new(
+            String::new(),
+            tls,
+            Some(Zeroizing::new("key".to_string())),
+            Some(Zeroizing::new("secret".to_string())),
+            tx,
+            writer,
+            cmd_rx,
+        )
+        .with_credential_info(CredentialInfo {
+            token: Zeroizing::new("token".to_string()),
+            expires_at: Instant::now() + TOKEN_LIFETIME,
+            tier: String::new(),
+        })
+        .with_endpoints(
+            format!("ws://{}", public.local_addr().unwrap()),
+            format!("ws://{}", private.local_addr().unwrap()),
+        );
+        let run = tokio::spawn(manager.run());
+
+        let mut first_public = accept(&public).await;
+        let _private_session = accept(&private).await;
+        first_public.close(None).await.unwrap();
+        let _second_public = accept(&public).await;
+
+        let mut connected = 0;
+        let mut private_status = Vec::new();
+        while connected < 2 {
+            match tokio::time::timeout(Duration::from_secs(5), rx.recv()).await {
+                Ok(Some(Message::Connected)) => connected += 1,
+                Ok(Some(Message::PrivateChannelStatus(up))) => private_status.push(up),
+                Ok(Some(_)) => {}
+                _ => panic!("manager never reported the public reconnect"),
+            }
+        }
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), private.accept())
+                .await
+                .is_err(),
+            "private connection was reopened"
+        );
+        assert!(!private_status.contains(&false));
+        run.abort();
+    }
+
+    #[tokio::test]
+    async fn private_drop_reconnects_without_touching_public_session() {
+        let public = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let private = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (tx, mut rx) = mpsc::channel(64);
+        let (_cmd_tx, cmd_rx) = mpsc::channel(1);
+        let tls = Arc::new(crate::tls::build_tls_config().unwrap());
+        let writer = Arc::new(tokio::sync::Mutex::new(None));
+        let manager = ConnectionManager::new(
+            String::new(),
+            tls,
+            Some(Zeroizing::new("key".to_string())),
+            Some(Zeroizing::new("secret".to_string())),
+            tx,
+            writer,
+            cmd_rx,
+        )
+        .with_credential_info(CredentialInfo {
+            token: Zeroizing::new("token".to_string()),
+            expires_at: Instant::now() + TOKEN_LIFETIME,
+            tier: String::new(),
+        })
+        .with_endpoints(
+            format!("ws://{}", public.local_addr().unwrap()),
+            format!("ws://{}", private.local_addr().unwrap()),
+        );
+        let run = tokio::spawn(manager.run());
+
+        let _public_session = accept(&public).await;
+        let mut first_private = accept(&private).await;
+        first_private.close(None).await.unwrap();
+        let _second_private = accept(&private).await;
+
+        let mut private_status = Vec::new();
+        while private_status.last() != Some(&true) || private_status.len() < 3 {
+            match tokio::time::timeout(Duration::from_secs(5), rx.recv()).await {
+                Ok(Some(Message::PrivateChannelStatus(up))) => private_status.push(up),
+                Ok(Some(Message::Disconnected(_))) => panic!("public session was dropped"),
+                Ok(Some(_)) => {}
+                _ => panic!("manager never reported the private reconnect"),
+            }
+        }
+        assert_eq!(private_status, vec![true, false, true]);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), public.accept())
+                .await
+                .is_err(),
+            "public connection was reopened"
+        );
+        run.abort();
+    }
+
+    #[tokio::test]
+    async fn private_reconnect_limit_leaves_public_feed_running() {
+        let public = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let private = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let private_url = format!("ws://{}", private.local_addr().unwrap());
+        let (tx, mut rx) = mpsc::channel(64);
+        let (_cmd_tx, cmd_rx) = mpsc::channel(1);
+        let tls = Arc::new(crate::tls::build_tls_config().unwrap());
+        let writer = Arc::new(tokio::sync::Mutex::new(None));
+        let manager = ConnectionManager::new(
+            String::new(),
+            tls,
+            Some(Zeroizing::new("key".to_string())),
+            Some(Zeroizing::new("secret".to_string())),
+            tx,
+            writer.clone(),
+            cmd_rx,
+        )
+        .with_credential_info(CredentialInfo {
+            token: Zeroizing::new("token".to_string()),
+            expires_at: Instant::now() + TOKEN_LIFETIME,
+            tier: String::new(),
+        })
+        .with_endpoints(
+            format!("ws://{}", public.local_addr().unwrap()),
+            private_url,
+        )
+        .with_max_reconnect_attempts(Some(1));
+        let run = tokio::spawn(manager.run());
+
+        let _public_session = accept(&public).await;
+        accept(&private).await.close(None).await.unwrap();
+        // Refuse the single retry
+        drop(private);
+
+        let mut private_status = Vec::new();
+        while private_status.len() < 3 {
+            match tokio::time::timeout(Duration::from_secs(5), rx.recv()).await {
+                Ok(Some(Message::PrivateChannelStatus(up))) => private_status.push(up),
+                Ok(Some(Message::Disconnected(_))) => panic!("public session was dropped"),
+                Ok(Some(_)) => {}
+                _ => panic!("manager never retried the private connection"),
+            }
+        }
+        assert_eq!(private_status, vec![true, false, false]);
+        assert!(!run.is_finished());
+        assert!(writer.lock().await.is_some());
+        run.abort();
+    }
+
+    #[tokio::test]
+    async fn read_loop_serves_private_connection_during_public_backoff() {
+        let (public_write, _public_read, _public) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (private_write, private_read, _private) = loopback(|mut ws| async move {
+            let heartbeat = r#"{"channel":"heartbeat","type":"update"}"#;
+            ws.send(WsMessage::Text(heartbeat.into())).await.is_ok() && ws.next().await.is_some()
+        })
+        .await;
+        let (mut manager, mut rx, _cmd_tx) = test_manager(public_write);
+        manager.writer.lock().await.take();
+        manager.public_reconnect.due = Some(tokio::time::Instant::now() + MAX_BACKOFF);
+
+        let (mut public_read, mut private_write, mut private_read) =
+            (None, Some(private_write), Some(private_read));
+        tokio::select! {
+            _ = manager.read_loop(
+                &mut public_read,
+                &mut private_write,
+                &mut private_read,
+                Some("test_token"),
+                Instant::now(),
+            ) => panic!("read loop exited early"),
+            message = rx.recv() => assert!(matches!(message, Some(Message::Heartbeat))),
+            () = tokio::time::sleep(Duration::from_secs(5)) => {
+                panic!("private message waited for the public backoff");
+            }
+        }
+    }
+
+    #[test]
+    fn request_ids_are_unique_across_clones() {
+        let ids = RequestIds::default();
+        let other = ids.clone();
+        assert_eq!(ids.next(), 1);
+        assert_eq!(other.next(), 2);
+        assert_eq!(ids.next(), 3);
+    }
+
+    #[test]
+    fn pending_requests_expire_after_timeout() {
+        let start = Instant::now();
+        let mut pending = PendingRequests::default();
+        pending.insert(1, "add_order", start);
+        pending.insert(2, "cancel_order", start + Duration::from_secs(5));
+
+        assert!(
+            pending
+                .drain_expired(start + Duration::from_secs(9))
+                .is_empty()
+        );
+
+        let expired = pending.drain_expired(start + REQUEST_TIMEOUT);
+        assert_eq!(expired, vec![(1, "add_order")]);
+
+        // Expired requests are reported only once
+        assert!(pending.drain_expired(start + REQUEST_TIMEOUT).is_empty());
+    }
+
+    #[test]
+    fn completed_requests_never_expire() {
+        let start = Instant::now();
+        let mut pending = PendingRequests::default();
+        pending.insert(7, "amend_order", start);
+
+        assert!(pending.complete(7));
+        assert!(!pending.complete(7));
+        assert!(
+            pending
+                .drain_expired(start + REQUEST_TIMEOUT * 2)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn set_cancel_after_arms_switch_on_private_connection() {
+        let (public_write, public_read, _public) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (private_write, private_read, private) = loopback(|mut ws| async move {
+            while let Some(Ok(msg)) = ws.next().await {
+                if let WsMessage::Text(text) = msg {
+                    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                    return value["method"] == "cancel_all_orders_after"
+                        && value["params"]["timeout"] == 60
+                        && value["params"]["token"] == "test_token"
+                        && value["req_id"] == 1;
+                }
+            }
+            false
+        })
+        .await;
+        let (mut manager, _rx, cmd_tx) = test_manager(public_write);
+        cmd_tx
+            .send(ConnectionCommand::SetCancelAfter(60))
+            .await
+            .unwrap();
+
+        let mut private_write = Some(private_write);
+        let mut private_read = Some(private_read);
+        let mut public_read = Some(public_read);
+        // The private server hangs up once it has answered, ending the read loop
+        tokio::select! {
+            biased;
+            received = private => {
+                assert!(received.unwrap(), "server never received cancel_after request");
+            }
+            _ = manager.read_loop(
+                &mut public_read,
+                &mut private_write,
+                &mut private_read,
+                Some("test_token"),
+                Instant::now(),
+            ) => panic!("read loop exited early"),
+        }
+        assert_eq!(manager.cancel_after, Some(60));
+        assert!(manager.pending_requests.complete(1));
+    }
+
+    fn pending_order() -> Box<AddOrderParams> {
+        let params = crate::models::AddOrderBuilder::market(
+            crate::models::OrderSide::Buy,
+            "BTC/USD",
+            rust_decimal::Decimal::ONE,
+        )
+        .build("pending")
+        .unwrap();
+        Box::new(params)
+    }
+
+    /// Returns the first text message received by a loopback server.
+    async fn first_text(
+        ws: &mut tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+    ) -> Option<serde_json::Value> {
+        while let Some(Ok(msg)) = ws.next().await {
+            if let WsMessage::Text(text) = msg {
+                return serde_json::from_str(&text).ok();
+            }
+        }
+        None
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn submitted_order_is_sent_after_batch_window() {
+        let (public_write, public_read, _public) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (private_write, private_read, private) = loopback(|mut ws| async move {
+            first_text(&mut ws).await.is_some_and(|value| {
+                value["method"] == "add_order" && value["params"]["token"] == "fresh_token"
+            })
+        })
+        .await;
+        let (mut manager, _rx, cmd_tx) = test_manager(public_write);
+        cmd_tx
+            .send(ConnectionCommand::SubmitOrder(pending_order()))
+            .await
+            .unwrap();
+
+        let started = tokio::time::Instant::now();
+        let mut private_write = Some(private_write);
+        let mut private_read = Some(private_read);
+        let mut public_read = Some(public_read);
+        // The private server hangs up once it has answered, ending the read loop
+        tokio::select! {
+            biased;
+            received = private => {
+                assert!(received.unwrap(), "server never received add_order");
+            }
+            _ = manager.read_loop(
+                &mut public_read,
+                &mut private_write,
+                &mut private_read,
+                Some("fresh_token"),
+                Instant::now(),
+            ) => panic!("read loop exited early"),
+        }
+        assert!(started.elapsed() >= crate::websocket::BATCH_WINDOW);
+        assert!(manager.batch.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn full_batch_is_sent_without_waiting() {
+        let (public_write, public_read, _public) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+        let (private_write, private_read, private) = loopback(|mut ws| async move {
+            first_text(&mut ws).await.is_some_and(|value| {
+                value["method"] == "batch_add"
+                    && value["params"]["orders"].as_array().map(Vec::len)
+                        == Some(crate::models::MAX_BATCH_SIZE)
+            })
+        })
+        .await;
+        let (mut manager, _rx, cmd_tx) = test_manager(public_write);
+        for _ in 1..crate::models::MAX_BATCH_SIZE {
+            manager.batch.submit(*pending_order());
+        }
+        cmd_tx
+            .send(ConnectionCommand::SubmitOrder(pending_order()))
+            .await
+            .unwrap();
+
+        let mut private_write = Some(private_write);
+        let mut private_read = Some(private_read);
+        let mut public_read = Some(public_read);
+        // The private server hangs up once it has answered, ending the read loop
+        tokio::select! {
+            biased;
+            received = private => {
+                assert!(received.unwrap(), "server never received a full batch_add");
+            }
+            _ = manager.read_loop(
+                &mut public_read,
+                &mut private_write,
+                &mut private_read,
+                Some("fresh_token"),
+                Instant::now(),
+            ) => panic!("read loop exited early"),
+        }
+        assert!(manager.pending_requests.complete(1));
+    }
+
+    fn ticker_subscription(symbol: &str) -> PublicSubscription {
+        PublicSubscription {
+            channel: Channel::Ticker,
+            symbol: symbol.to_string(),
+            retries: 0,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rejected_subscription_is_retried_then_dropped() {
+        let mut tracker = SubscriptionTracker::default();
+        let start = tokio::time::Instant::now();
+        tracker.sent(1, ticker_subscription("BTC/USD"));
+
+        for attempt in 1..=SUBSCRIBE_MAX_RETRIES {
+            let req_id = u64::from(attempt);
+            match tracker.reject(req_id, start) {
+                Some(SubscriptionOutcome::Retrying(sub)) => assert_eq!(sub.retries, attempt),
+                other => panic!("expected a retry, got {other:?}"),
+            }
+            assert_eq!(tracker.next_retry(), Some(start + SUBSCRIBE_RETRY_DELAY));
+            assert!(tracker.take_due(start).is_empty());
+
+            let due = tracker.take_due(start + SUBSCRIBE_RETRY_DELAY);
+            assert_eq!(due.len(), 1);
+            tracker.sent(req_id + 1, due.into_iter().next().unwrap());
+        }
+
+        let last = u64::from(SUBSCRIBE_MAX_RETRIES) + 1;
+        assert!(matches!(
+            tracker.reject(last, start),
+            Some(SubscriptionOutcome::GaveUp(_))
+        ));
+        assert_eq!(tracker.next_retry(), None);
+    }
+
+    #[test]
+    fn acknowledged_subscription_is_not_retried() {
+        let mut tracker = SubscriptionTracker::default();
+        tracker.sent(5, ticker_subscription("ETH/USD"));
+        tracker.acknowledge(5);
+
+        assert_eq!(tracker.reject(5, tokio::time::Instant::now()), None);
+        assert_eq!(tracker.next_retry(), None);
+    }
+
+    #[test]
+    fn parse_subscribe_failure_response() {
+        let value = serde_json::json!({
+            "method": "subscribe",
+            "req_id": 3,
+            "success": false,
+            "error": "Currency pair not supported",
+            "symbol": "FOO/BAR"
+        });
+
+        match parse_ws_message(value) {
+            Some(Message::SubscribeAck(ack)) => {
+                assert!(!ack.success);
+                assert_eq!(ack.req_id, Some(3));
+                assert_eq!(ack.symbol(), Some("FOO/BAR"));
+                assert_eq!(ack.error.as_deref(), Some("Currency pair not supported"));
+            }
+            other => panic!("expected SubscribeAck, got {other:?}"),
+        }
+    }
+
+    fn ack(method: &str, symbol: &str, success: bool) -> SubscribeResponse {
+        let value = if success {
+            serde_json::json!({
+                "method": method,
+                "result": { "channel": "ticker", "symbol": symbol, "snapshot": true },
+                "success": true,
+                "time_in": "2023-09-25T09:04:31.742599Z",
+                "time_out": "2023-09-25T09:04:31.742648Z"
+            })
+        } else {
+            serde_json::json!({
+                "method": method,
+                "success": false,
+                "error": "Currency pair not supported",
+                "symbol": symbol
+            })
+        };
+        match parse_ws_message(value) {
+            Some(Message::SubscribeAck(ack)) => ack,
+            other => panic!("expected SubscribeAck, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribe_ack_confirms_pending_pair() {
+        let (write, _read, _server) = loopback(|_ws| async move { true }).await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+        manager.pending_pairs.push("BTC/USD".to_string());
+
+        let ack = ack("subscribe", "BTC/USD", true);
+        assert_eq!(ack.channel(), Some("ticker"));
+        manager.observe_subscription(&ack);
+
+        assert!(manager.pending_pairs.is_empty());
+        assert_eq!(manager.subscribed_pairs, vec!["BTC/USD".to_string()]);
+
+        // Further channel acks for the same pair do not duplicate it
+        manager.observe_subscription(&ack);
+        assert_eq!(manager.subscribed_pairs.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn preloaded_pairs_are_subscribed_on_connect() {
+        let (write, _read, server) = loopback(|mut ws| async move {
+            let mut ticker_symbols = Vec::new();
+            while let Some(Ok(WsMessage::Text(text))) = ws.next().await {
+                let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+                if value["params"]["channel"] == "ticker" {
+                    ticker_symbols.push(value["params"]["symbol"][0].as_str().unwrap().to_string());
+                }
+                if ticker_symbols.len() == 2 {
+                    break;
+                }
+            }
+            ticker_symbols == ["BTC/USD", "ETH/USD"]
+        })
+        .await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+        manager.set_subscribed_pairs(vec!["BTC/USD".to_string(), "ETH/USD".to_string()]);
+
+        let mut write = manager.writer.lock().await.take().unwrap();
+        manager.subscribe_public(&mut write).await;
+
+        assert!(server.await.unwrap());
+        assert_eq!(manager.pending_pairs, ["BTC/USD", "ETH/USD"]);
+    }
+
+    #[tokio::test]
+    async fn subscribe_channel_command_is_sent_on_public_connection() {
+        let (public_write, public_read, public) = loopback(|mut ws| async move {
+            first_text(&mut ws).await.is_some_and(|value| {
+                value["method"] == "subscribe"
+                    && value["params"]["channel"] == "trade"
+                    && value["params"]["symbol"][0] == "SOL/USD"
+            })
+        })
+        .await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(public_write);
+        manager
+            .subscribe_channel_sender()
+            .send(ConnectionCommand::SubscribeChannel(
+                Channel::Trades,
+                vec!["SOL/USD".to_string()],
+            ))
+            .await
+            .unwrap();
+        let (mut public_read, mut private_write, mut private_read) =
+            (Some(public_read), None, None);
+
+        tokio::select! {
+            biased;
+            received = public => {
+                assert!(received.unwrap(), "server never received the subscription");
+            }
+            _ = manager.read_loop(
+                &mut public_read,
+                &mut private_write,
+                &mut private_read,
+                None,
+                Instant::now(),
+            ) => panic!("read loop exited early"),
+        }
+        assert_eq!(manager.subscriptions.pending.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejected_untracked_subscription_is_not_confirmed() {
+        let (write, _read, _server) = loopback(|_ws| async move { true }).await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+        manager.pending_pairs.push("FOO/BAR".to_string());
+
+        manager.observe_subscription(&ack("subscribe", "FOO/BAR", false));
+
+        assert!(manager.pending_pairs.is_empty());
+        assert!(manager.subscribed_pairs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_ack_removes_pair() {
+        let (write, _read, _server) = loopback(|_ws| async move { true }).await;
+        let (mut manager, _rx, _cmd_tx) = test_manager(write);
+        manager.subscribed_pairs.push("ETH/USD".to_string());
+
+        manager.observe_subscription(&ack("unsubscribe", "ETH/USD", true));
+
+        assert!(manager.subscribed_pairs.is_empty());
+    }
+
+    #[test]
+    fn forgotten_pair_is_not_retried() {
+        let mut tracker = SubscriptionTracker::default();
+        let now = tokio::time::Instant::now();
+        tracker.sent(1, ticker_subscription("BTC/USD"));
+        tracker.sent(2, ticker_subscription("ETH/USD"));
+        tracker.reject(1, now);
+
+        tracker.forget("BTC/USD");
+
+        assert_eq!(tracker.next_retry(), None);
+        assert_eq!(tracker.pending.len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn read_loop_reports_subscription_after_retries() {
+        let (write, read, _server) = loopback(|mut ws| async move {
+            while let Some(value) = first_text(&mut ws).await {
+                if value["method"] == "subscribe" {
+                    let reply = serde_json::json!({
+                        "method": "subscribe",
+                        "req_id": value["req_id"],
+                        "success": false,
+                        "error": "Currency pair not supported",
+                        "symbol": "BTC/USD"
+                    });
+                    if ws
+                        .send(WsMessage::Text(reply.to_string().into()))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+            true
+        })
+        .await;
+        let (mut manager, mut rx, _cmd_tx) = test_manager(write);
+
+        let writer = manager.writer.clone();
+        {
+            let mut guard = writer.lock().await;
+            let write = guard.as_mut().unwrap();
+            manager
+                .send_subscription(write, ticker_subscription("BTC/USD"))
+                .await;
+        }
+
+        let started = tokio::time::Instant::now();
+        let failure = async {
+            while let Some(message) = rx.recv().await {
+                if let Message::SubscriptionFailed {
+                    channel,
+                    symbol,
+                    error,
+                } = message
+                {
+                    return (channel, symbol, error);
+                }
+            }
+            panic!("message channel closed");
+        };
+        let (mut read, mut private_write, mut private_read) = (Some(read), None, None);
+        tokio::select! {
+            _ = manager.read_loop(
+                &mut read,
+                &mut private_write,
+                &mut private_read,
+                None,
+                Instant::now(),
+            ) => panic!("read loop exited early"),
+            (channel, symbol, error) = failure => {
+                assert_eq!(channel, "ticker");
+                assert_eq!(symbol, "BTC/USD");
+                assert_eq!(error, "Currency pair not supported");
+            }
+        }
+        assert!(started.elapsed() >= SUBSCRIBE_RETRY_DELAY * u32::from(SUBSCRIBE_MAX_RETRIES));
+        assert!(manager.subscriptions.pending.is_empty());
+    }
+
+    #[test]
+    fn parse_batch_add_response() {
+        let value = serde_json::json!({
+            "method": "batch_add",
+            "req_id": 7,
+            "result": [{ "order_id": "OABC12-DEF34-GHI56" }],
+            "success": true,
+            "time_in": "2022-06-13T08:09:10.123456Z",
+            "time_out": "2022-06-13T08:09:10.789012Z"
+        });
+
+        match parse_ws_message(value) {
+            Some(Message::BatchOrderResult(response)) => {
+                assert_eq!(response.req_id, Some(7));
+                assert_eq!(response.result.unwrap().len(), 1);
+            }
+            other => panic!("expected BatchOrderResult, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_edit_order_response() {
+        let value = serde_json::json!({
+            "method": "edit_order",
+            "req_id": 9,
+            "result": { "order_id": "ONEW00", "original_order_id": "OOLD00" },
+            "success": true
+        });
+
+        match parse_ws_message(value) {
+            Some(Message::OrderEdited(response)) => {
+                assert_eq!(response.req_id, Some(9));
+                assert_eq!(response.result.unwrap().original_order_id, "OOLD00");
+            }
+            other => panic!("expected OrderEdited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_batch_cancel_response() {
+        let value = serde_json::json!({
+            "method": "batch_cancel",
+            "req_id": 4,
+            "result": { "count": 3 },
+            "success": true,
+            "time_in": "2024-01-01T00:00:00.000000Z",
+            "time_out": "2024-01-01T00:00:00.000100Z"
+        });
+
+        match parse_ws_message(value) {
+            Some(Message::BatchCancelResult(response)) => {
+                assert_eq!(response.req_id, Some(4));
+                assert_eq!(response.result.unwrap().count, 3);
+            }
+            other => panic!("expected BatchCancelResult, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_level3_snapshot() {
+        let value = serde_json::json!({
+            "channel": "level3",
+            "type": "snapshot",
+            "data": [{
+                "symbol": "BTC/USD",
+                "bids": [{
+                    "order_id": "OBID01",
+                    "limit_price": "50000.0",
+                    "order_qty": "0.5",
+                    "timestamp": "2024-01-01T00:00:00.000000Z"
+                }],
+                "asks": [],
+                "checksum": 281_556_642_u64,
+                "timestamp": "2024-01-01T00:00:00.000000Z"
+            }]
+        });
+
+        match parse_ws_message(value) {
+            Some(Message::Level3(response)) => {
+                assert_eq!(response.tpe, "snapshot");
+                assert_eq!(response.data[0].bids[0].order_id, "OBID01");
+            }
+            other => panic!("expected Level3, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn connection_stats_accumulate_session_uptime() {
+        let mut stats = ConnectionStats::default();
+        let start = Instant::now();
+
+        stats.session_started(start, false);
+        stats.session_ended(start + Duration::from_secs(90));
+        assert_eq!(stats.reconnect_count, 0);
+        assert_eq!(stats.total_uptime, Duration::from_secs(90));
+        assert_eq!(stats.current_session_start, None);
+
+        stats.session_started(start + Duration::from_secs(100), true);
+        stats.session_ended(start + Duration::from_secs(130));
+        assert_eq!(stats.reconnect_count, 1);
+        assert_eq!(stats.total_uptime, Duration::from_secs(120));
+
+        // Ending without an active session changes nothing
+        stats.session_ended(start + Duration::from_secs(200));
+        assert_eq!(stats.total_uptime, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn cancel_after_refresh_period_is_half_timeout() {
+        assert_eq!(cancel_after_refresh_period(60), Duration::from_secs(30));
+        assert_eq!(cancel_after_refresh_period(1), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn close_connections_completes_handshake() {
+        let (write, read, server) = loopback(|mut ws| async move {
+            // Reading the close frame makes tungstenite queue the reply
+            while let Some(msg) = ws.next().await {
+                if matches!(msg, Ok(WsMessage::Close(_))) {
+                    return true;
+                }
+            }
+            false
+        })
+        .await;
+
+        let started = Instant::now();
+        ConnectionManager::close_connections(Some((write, Some(read))), None).await;
+
+        assert!(started.elapsed() < CLOSE_TIMEOUT);
+        assert!(server.await.unwrap(), "server never received a close frame");
+    }
+
+    #[tokio::test]
+    async fn close_connections_times_out_on_silent_server() {
+        let (write, read, _server) = loopback(|ws| async move {
+            // Hold the connection open without ever reading the close frame
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            drop(ws);
+            true
+        })
+        .await;
+
+        let started = Instant::now();
+        ConnectionManager::close_connections(None, Some((write, Some(read)))).await;
+
+        let elapsed = started.elapsed();
+        assert!(elapsed >= CLOSE_TIMEOUT);
+        assert!(elapsed < CLOSE_TIMEOUT + Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn max_reconnect_attempts_of_one_allows_a_single_retry() {
+        let (tx, mut rx) = mpsc::channel(64);
+        let (_cmd_tx, cmd_rx) = mpsc::channel(1);
+        let tls = Arc::new(crate::tls::build_tls_config().unwrap());
+        let writer = Arc::new(tokio::sync::Mutex::new(None));
+        let mut manager =
+            ConnectionManager::new(String::new(), tls, None, None, tx, writer, cmd_rx)
+                .with_max_reconnect_attempts(Some(1));
+
+        assert!(manager.back_off().await.is_none());
+        assert_eq!(manager.public_reconnect.backoff, INITIAL_BACKOFF * 2);
+        assert!(rx.try_recv().is_err());
+
+        assert!(matches!(
+            manager.back_off().await,
+            Some(DisconnectReason::Shutdown)
+        ));
+        match rx.try_recv() {
+            Ok(Message::Disconnected(Some(LeesonError::MaxRetriesExceeded { attempts }))) => {
+                assert_eq!(attempts, 1);
+            }
+            _ => panic!("expected a max retries exceeded disconnect"),
+        }
+    }
+
+    #[test]
+    fn reconnect_budget_resets_after_success() {
+        let mut budget = ReconnectBudget {
+            max_attempts: Some(2),
+            attempts: 0,
+        };
+        assert!(budget.try_attempt());
+        assert!(budget.try_attempt());
+        assert!(!budget.try_attempt());
+
+        budget.reset();
+        assert!(budget.try_attempt());
+        assert!(ReconnectBudget::default().try_attempt());
+    }
+}
@@ -20,269 +20,262 @@ use crate::models::{
     PongResponse, StatusUpdateResponse,
 };
 
-/// Reads and dispatches incoming WebSocket messages indefinitely.
+/// Receives parsed messages from [`process_messages_with_sink`].
 ///
-/// Messages are parsed and logged via `tracing`. The function runs until
-/// the WebSocket connection closes or an error occurs.
-///
-/// # Errors
-///
-/// Returns a [`LeesonError`](crate::LeesonError) if reading from the
-/// WebSocket fails, or if a message cannot be deserialized into the
-/// expected response type.
-pub async fn process_messages(read: &mut WsReader) -> Result<()> {
-    while let Some(msg) = read.next().await {
-        let msg = msg?;
+/// Every method defaults to ignoring the message, so implementors only
+/// override the ones they care about. Data channel snapshots are passed on
+/// as well as updates; check the response's `tpe` to tell them apart.
+pub trait MessageSink {
+    /// Called with a `pong` reply to a ping.
+    fn on_pong(&mut self, _response: PongResponse) {}
 
-        if let Message::Text(text) = msg {
-            let value: serde_json::Value = serde_json::from_str(&text)
-                .map_err(|e| LeesonError::MalformedMessage(e.to_string()))?;
+    /// Called with an `add_order` response.
+    fn on_add_order(&mut self, _response: AddOrderResponse) {}
 
-            dispatch_message(value)?;
-        }
-    }
+    /// Called with a `batch_add` response.
+    fn on_batch_add(&mut self, _response: BatchAddResponse) {}
 
-    Ok(())
-}
+    /// Called with a `batch_cancel` response.
+    fn on_batch_cancel(&mut self, _response: BatchCancelResponse) {}
 
-/// Routes a parsed JSON message to the appropriate handler.
-fn dispatch_message(value: serde_json::Value) -> Result<()> {
-    // Extract routing fields as owned strings to avoid borrow conflicts
-    let msg_method = value
-        .get("method")
-        .and_then(|m| m.as_str())
-        .map(String::from);
-    let msg_type = value.get("type").and_then(|t| t.as_str()).map(String::from);
-    let msg_channel = value
-        .get("channel")
-        .and_then(|c| c.as_str())
-        .map(String::from);
+    /// Called with a `cancel_order` response.
+    fn on_cancel_order(&mut self, _response: CancelOrderResponse) {}
 
-    // Handle RPC responses first (method-based routing)
-    if let Some(ref method) = msg_method {
-        return handle_rpc_response(method, value);
-    }
+    /// Called with a `cancel_all` response.
+    fn on_cancel_all(&mut self, _response: CancelAllResponse) {}
 
-    // Handle channel messages
-    if let Some(ref channel) = msg_channel {
-        return handle_channel_message(channel, msg_type.as_deref(), value);
-    }
+    /// Called with a `cancel_all_orders_after` (dead man's switch) response.
+    fn on_cancel_after(&mut self, _response: CancelAfterResponse) {}
 
-    Ok(())
+    /// Called with an `amend_order` response.
+    fn on_amend_order(&mut self, _response: AmendOrderResponse) {}
+
+    /// Called with an `edit_order` response.
+    fn on_edit_order(&mut self, _response: EditOrderResponse) {}
+
+    /// Called on every heartbeat.
+    fn on_heartbeat(&mut self) {}
+
+    /// Called with a `status` channel message.
+    fn on_status(&mut self, _response: StatusUpdateResponse) {}
+
+    /// Called with an `executions` channel snapshot or update.
+    fn on_execution(&mut self, _response: ExecutionUpdateResponse) {}
+
+    /// Called with a snapshot or update from the `ticker` channel.
+    fn on_ticker(&mut self, _response: TickerUpdateResponse) {}
+
+    /// Called with a snapshot or update from the `book` channel.
+    fn on_book(&mut self, _response: BookUpdateResponse) {}
+
+    /// Called with a snapshot or update from the `ohlc` channel.
+    fn on_candle(&mut self, _response: CandleUpdateResponse) {}
+
+    /// Called with a snapshot or update from the `trade` channel.
+    fn on_trade(&mut self, _response: TradeUpdateResponse) {}
+
+    /// Called with a snapshot or update from the `instrument` channel.
+    fn on_instrument(&mut self, _response: InstrumentUpdateResponse) {}
+
+    /// Called with a snapshot or update from the `level3` orders channel.
+    fn on_orders(&mut self, _response: OrdersUpdateResponse) {}
 }
 
-/// Handles RPC-style responses (method-based).
-fn handle_rpc_response(method: &str, value: serde_json::Value) -> Result<()> {
-    match method {
-        "pong" => {
-            let response: PongResponse = serde_json::from_value(value)?;
-            debug!(
-                method = response.method,
-                time_in = response.time_in,
-                time_out = response.time_out,
-                "Received pong"
-            );
-        }
-        "add_order" => {
-            let response: AddOrderResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        order_id = result.order_id,
-                        cl_ord_id = ?result.cl_ord_id,
-                        req_id = ?response.req_id,
-                        "Order placed successfully"
-                    );
-                }
-            } else {
-                warn!(
+/// [`MessageSink`] that logs every message via `tracing`.
+///
+/// Data channel snapshots are skipped; executions are logged in full.
+#[derive(Debug, Default)]
+pub struct LoggingSink;
+
+impl MessageSink for LoggingSink {
+    fn on_pong(&mut self, response: PongResponse) {
+        debug!(
+            method = response.method,
+            time_in = response.time_in,
+            time_out = response.time_out,
+            "Received pong"
+        );
+    }
+
+    fn on_add_order(&mut self, response: AddOrderResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    order_id = result.order_id,
+                    cl_ord_id = ?result.cl_ord_id,
                     req_id = ?response.req_id,
-                    "Order placement failed"
+                    "Order placed successfully"
                 );
             }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Order placement failed"
+            );
         }
-        "batch_add" => {
-            let response: BatchAddResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref results) = response.result {
-                    info!(
-                        method = response.method,
-                        order_count = results.len(),
-                        req_id = ?response.req_id,
-                        "Batch orders placed successfully"
-                    );
-                    for result in results {
-                        debug!(
-                            order_id = result.order_id,
-                            cl_ord_id = ?result.cl_ord_id,
-                            order_userref = ?result.order_userref,
-                            "Batch order"
-                        );
-                    }
-                }
-            } else {
-                warn!(
+    }
+
+    fn on_batch_add(&mut self, response: BatchAddResponse) {
+        if response.success {
+            if let Some(ref results) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    order_count = results.len(),
                     req_id = ?response.req_id,
-                    "Batch order placement failed"
+                    "Batch orders placed successfully"
                 );
-            }
-        }
-        "batch_cancel" => {
-            let response: BatchCancelResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        count = result.count,
-                        req_id = ?response.req_id,
-                        "Batch orders cancelled successfully"
+                for result in results {
+                    debug!(
+                        order_id = result.order_id,
+                        cl_ord_id = ?result.cl_ord_id,
+                        order_userref = ?result.order_userref,
+                        "Batch order"
                     );
                 }
-            } else {
-                warn!(
+            }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Batch order placement failed"
+            );
+        }
+    }
+
+    fn on_batch_cancel(&mut self, response: BatchCancelResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    count = result.count,
                     req_id = ?response.req_id,
-                    "Batch order cancellation failed"
+                    "Batch orders cancelled successfully"
                 );
             }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Batch order cancellation failed"
+            );
         }
-        "cancel_order" => {
-            let response: CancelOrderResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        order_id = result.order_id,
-                        cl_ord_id = ?result.cl_ord_id,
-                        req_id = ?response.req_id,
-                        "Order cancelled successfully"
-                    );
-                }
-            } else {
-                warn!(
+    }
+
+    fn on_cancel_order(&mut self, response: CancelOrderResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    order_id = result.order_id,
+                    cl_ord_id = ?result.cl_ord_id,
                     req_id = ?response.req_id,
-                    "Order cancellation failed"
+                    "Order cancelled successfully"
                 );
             }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Order cancellation failed"
+            );
         }
-        "cancel_all" => {
-            let response: CancelAllResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        count = result.count,
-                        req_id = ?response.req_id,
-                        "All orders cancelled successfully"
-                    );
-                }
-            } else {
-                warn!(
+    }
+
+    fn on_cancel_all(&mut self, response: CancelAllResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    count = result.count,
                     req_id = ?response.req_id,
-                    "Cancel all orders failed"
+                    "All orders cancelled successfully"
                 );
             }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Cancel all orders failed"
+            );
         }
-        "cancel_all_orders_after" => {
-            let response: CancelAfterResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        current_time = result.current_time,
-                        trigger_time = result.trigger_time,
-                        req_id = ?response.req_id,
-                        "Dead man's switch set successfully"
-                    );
-                }
-            } else {
-                warn!(
+    }
+
+    fn on_cancel_after(&mut self, response: CancelAfterResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    current_time = result.current_time,
+                    trigger_time = result.trigger_time,
                     req_id = ?response.req_id,
-                    "Dead man's switch failed"
+                    "Dead man's switch set successfully"
                 );
             }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Dead man's switch failed"
+            );
         }
-        "amend_order" => {
-            let response: AmendOrderResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        amend_id = result.amend_id,
-                        order_id = ?result.order_id,
-                        cl_ord_id = ?result.cl_ord_id,
-                        req_id = ?response.req_id,
-                        "Order amended successfully"
-                    );
-                }
-            } else {
-                warn!(
+    }
+
+    fn on_amend_order(&mut self, response: AmendOrderResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    amend_id = result.amend_id,
+                    order_id = ?result.order_id,
+                    cl_ord_id = ?result.cl_ord_id,
                     req_id = ?response.req_id,
-                    "Order amendment failed"
+                    "Order amended successfully"
                 );
             }
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Order amendment failed"
+            );
         }
-        "edit_order" => {
-            let response: EditOrderResponse = serde_json::from_value(value)?;
-            if response.success {
-                if let Some(ref result) = response.result {
-                    info!(
-                        method = response.method,
-                        order_id = result.order_id,
-                        original_order_id = result.original_order_id,
-                        req_id = ?response.req_id,
-                        "Order edited successfully"
-                    );
-                }
-            } else {
-                warn!(
+    }
+
+    fn on_edit_order(&mut self, response: EditOrderResponse) {
+        if response.success {
+            if let Some(ref result) = response.result {
+                info!(
                     method = response.method,
-                    error = ?response.error,
+                    order_id = result.order_id,
+                    original_order_id = result.original_order_id,
                     req_id = ?response.req_id,
-                    "Order edit failed"
+                    "Order edited successfully"
                 );
             }
-        }
-        _ => {
-            warn!(method = method, "Unknown RPC method");
+        } else {
+            warn!(
+                method = response.method,
+                error = ?response.error,
+                req_id = ?response.req_id,
+                "Order edit failed"
+            );
         }
     }
 
-    Ok(())
-}
-
-/// Handles channel-based messages (subscriptions).
-fn handle_channel_message(
-    channel: &str,
-    msg_type: Option<&str>,
-    value: serde_json::Value,
-) -> Result<()> {
-    // Check for unknown RPC-style messages that slipped through
-    if value.get("method").is_some() {
-        return Ok(());
-    }
-    // System channels (always process)
-    if channel == Channel::Heartbeat.as_str() {
+    fn on_heartbeat(&mut self) {
         debug!("Received heartbeat");
-        return Ok(());
     }
 
-    if channel == Channel::Status.as_str() {
-        let response: StatusUpdateResponse = serde_json::from_value(value)?;
+    fn on_status(&mut self, response: StatusUpdateResponse) {
         for status in &response.data {
             info!(
                 channel = response.channel,
@@ -294,12 +287,9 @@ fn handle_channel_message(
                 "Status update"
             );
         }
-        return Ok(());
     }
 
-    // Executions channel (process both snapshots and updates)
-    if channel == Channel::Executions.as_str() {
-        let response: ExecutionUpdateResponse = serde_json::from_value(value)?;
+    fn on_execution(&mut self, response: ExecutionUpdateResponse) {
         for exec in &response.data {
             info!(
                 channel = response.channel,
@@ -321,171 +311,292 @@ fn handle_channel_message(
                 "Execution"
             );
         }
-        return Ok(());
     }
 
-    // Data channels (only process updates, skip snapshots)
-    if msg_type != Some("update") {
-        debug!(msg_type = msg_type, "Skipping non-update message");
-        return Ok(());
+    fn on_ticker(&mut self, response: TickerUpdateResponse) {
+        if response.tpe != "update" {
+            debug!(msg_type = response.tpe, "Skipping non-update message");
+            return;
+        }
+        for tick in &response.data {
+            info!(
+                channel = response.channel,
+                symbol = tick.symbol,
+                bid = %tick.bid,
+                bid_qty = %tick.bid_qty,
+                ask = %tick.ask,
+                ask_qty = %tick.ask_qty,
+                last = %tick.last,
+                volume = ?tick.volume,
+                vwap = ?tick.vwap,
+                low = ?tick.low,
+                high = ?tick.high,
+                change = ?tick.change,
+                change_pct = ?tick.change_pct,
+                "Ticker update"
+            );
+        }
     }
 
-    handle_data_channel_update(channel, value)
-}
-
-/// Handles data channel update messages.
-fn handle_data_channel_update(channel: &str, value: serde_json::Value) -> Result<()> {
-    match channel {
-        ch if ch == Channel::Ticker.as_str() => {
-            let response: TickerUpdateResponse = serde_json::from_value(value)?;
-            for tick in &response.data {
-                info!(
-                    channel = response.channel,
-                    symbol = tick.symbol,
-                    bid = %tick.bid,
-                    bid_qty = %tick.bid_qty,
-                    ask = %tick.ask,
-                    ask_qty = %tick.ask_qty,
-                    last = %tick.last,
-                    volume = %tick.volume,
-                    vwap = %tick.vwap,
-                    low = %tick.low,
-                    high = %tick.high,
-                    change = %tick.change,
-                    change_pct = %tick.change_pct,
-                    "Ticker update"
-                );
-            }
+    fn on_book(&mut self, response: BookUpdateResponse) {
+        if response.tpe != "update" {
+            debug!(msg_type = response.tpe, "Skipping non-update message");
+            return;
         }
-        ch if ch == Channel::Book.as_str() => {
-            let response: BookUpdateResponse = serde_json::from_value(value)?;
-            for entry in &response.data {
-                info!(
-                    channel = response.channel,
-                    symbol = entry.symbol,
-                    checksum = entry.checksum,
-                    timestamp = entry.timestamp,
-                    bids = entry.bids.len(),
-                    asks = entry.asks.len(),
-                    "Book update"
-                );
-                for bid in &entry.bids {
-                    debug!(price = %bid.price, qty = %bid.qty, "Bid");
-                }
-                for ask in &entry.asks {
-                    debug!(price = %ask.price, qty = %ask.qty, "Ask");
-                }
+        for entry in &response.data {
+            info!(
+                channel = response.channel,
+                symbol = entry.symbol,
+                checksum = entry.checksum,
+                timestamp = entry.timestamp,
+                bids = entry.bids.len(),
+                asks = entry.asks.len(),
+                "Book update"
+            );
+            for bid in &entry.bids {
+                debug!(price = %bid.price, qty = %bid.qty, "Bid");
             }
-        }
-        ch if ch == Channel::Candles.as_str() => {
-            let response: CandleUpdateResponse = serde_json::from_value(value)?;
-            for candle in &response.data {
-                info!(
-                    channel = response.channel,
-                    symbol = candle.symbol,
-                    open = %candle.open,
-                    high = %candle.high,
-                    low = %candle.low,
-                    close = %candle.close,
-                    vwap = %candle.vwap,
-                    volume = %candle.volume,
-                    trades = candle.trades,
-                    interval = candle.interval,
-                    interval_begin = candle.interval_begin,
-                    timestamp = candle.timestamp,
-                    "Candle update"
-                );
+            for ask in &entry.asks {
+                debug!(price = %ask.price, qty = %ask.qty, "Ask");
             }
         }
-        ch if ch == Channel::Trades.as_str() => {
-            let response: TradeUpdateResponse = serde_json::from_value(value)?;
-            for trade in &response.data {
-                info!(
-                    channel = response.channel,
-                    symbol = trade.symbol,
-                    side = trade.side,
-                    price = %trade.price,
-                    qty = %trade.qty,
-                    ord_type = trade.ord_type,
-                    trade_id = trade.trade_id,
-                    timestamp = trade.timestamp,
-                    "Trade update"
-                );
-            }
+    }
+
+    fn on_candle(&mut self, response: CandleUpdateResponse) {
+        if response.tpe != "update" {
+            debug!(msg_type = response.tpe, "Skipping non-update message");
+            return;
         }
-        ch if ch == Channel::Instruments.as_str() => {
-            let response: InstrumentUpdateResponse = serde_json::from_value(value)?;
+        for candle in &response.data {
+            info!(
+                channel = response.channel,
+                symbol = candle.symbol,
+                open = %candle.open,
+                high = %candle.high,
+                low = %candle.low,
+                close = %candle.close,
+                vwap = %candle.vwap,
+                volume = %candle.volume,
+                trades = candle.trades,
+                interval = candle.interval,
+                interval_begin = candle.interval_begin,
+                timestamp = candle.timestamp,
+                "Candle update"
+            );
+        }
+    }
+
+    fn on_trade(&mut self, response: TradeUpdateResponse) {
+        if response.tpe != "update" {
+            debug!(msg_type = response.tpe, "Skipping non-update message");
+            return;
+        }
+        for trade in &response.data {
             info!(
                 channel = response.channel,
-                assets = response.data.assets.len(),
-                pairs = response.data.pairs.len(),
-                "Instrument update"
+                symbol = trade.symbol,
+                side = trade.side,
+                price = %trade.price,
+                qty = %trade.qty,
+                ord_type = trade.ord_type,
+                trade_id = trade.trade_id,
+                timestamp = trade.timestamp,
+                "Trade update"
+            );
+        }
+    }
+
+    fn on_instrument(&mut self, response: InstrumentUpdateResponse) {
+        if response.tpe != "update" {
+            debug!(msg_type = response.tpe, "Skipping non-update message");
+            return;
+        }
+        info!(
+            channel = response.channel,
+            assets = response.data.assets.len(),
+            pairs = response.data.pairs.len(),
+            "Instrument update"
+        );
+        for asset in &response.data.assets {
+            debug!(
+                id = asset.id,
+                status = asset.status,
+                precision = asset.precision,
+                precision_display = asset.precision_display,
+                borrowable = asset.borrowable,
+                collateral_value = %asset.collateral_value,
+                margin_rate = %asset.margin_rate,
+                "Asset"
             );
-            for asset in &response.data.assets {
+        }
+        for pair in &response.data.pairs {
+            debug!(
+                symbol = pair.symbol,
+                base = pair.base,
+                quote = pair.quote,
+                status = pair.status,
+                qty_precision = pair.qty_precision,
+                qty_increment = %pair.qty_increment,
+                price_precision = pair.price_precision,
+                price_increment = %pair.price_increment,
+                cost_precision = pair.cost_precision,
+                cost_min = %pair.cost_min,
+                qty_min = %pair.qty_min,
+                marginable = pair.marginable,
+                has_index = pair.has_index,
+                "Pair"
+            );
+        }
+    }
+
+    fn on_orders(&mut self, response: OrdersUpdateResponse) {
+        if response.tpe != "update" {
+            debug!(msg_type = response.tpe, "Skipping non-update message");
+            return;
+        }
+        for entry in &response.data {
+            info!(
+                channel = response.channel,
+                symbol = entry.symbol,
+                checksum = entry.checksum,
+                timestamp = entry.timestamp,
+                bids = entry.bids.len(),
+                asks = entry.asks.len(),
+                "Orders update"
+            );
+            for order in &entry.bids {
                 debug!(
-                    id = asset.id,
-                    status = asset.status,
-                    precision = asset.precision,
-                    precision_display = asset.precision_display,
-                    borrowable = asset.borrowable,
-                    collateral_value = %asset.collateral_value,
-                    margin_rate = %asset.margin_rate,
-                    "Asset"
+                    event = order.event,
+                    order_id = order.order_id,
+                    limit_price = %order.limit_price,
+                    order_qty = %order.order_qty,
+                    timestamp = order.timestamp,
+                    "Order bid"
                 );
             }
-            for pair in &response.data.pairs {
+            for order in &entry.asks {
                 debug!(
-                    symbol = pair.symbol,
-                    base = pair.base,
-                    quote = pair.quote,
-                    status = pair.status,
-                    qty_precision = pair.qty_precision,
-                    qty_increment = %pair.qty_increment,
-                    price_precision = pair.price_precision,
-                    price_increment = %pair.price_increment,
-                    cost_precision = pair.cost_precision,
-                    cost_min = %pair.cost_min,
-                    qty_min = %pair.qty_min,
-                    marginable = pair.marginable,
-                    has_index = pair.has_index,
-                    "Pair"
+                    event = order.event,
+                    order_id = order.order_id,
+                    limit_price = %order.limit_price,
+                    order_qty = %order.order_qty,
+                    timestamp = order.timestamp,
+                    "Order ask"
                 );
             }
         }
-        ch if ch == Channel::Orders.as_str() => {
-            let response: OrdersUpdateResponse = serde_json::from_value(value)?;
-            for entry in &response.data {
-                info!(
-                    channel = response.channel,
-                    symbol = entry.symbol,
-                    checksum = entry.checksum,
-                    timestamp = entry.timestamp,
-                    bids = entry.bids.len(),
-                    asks = entry.asks.len(),
-                    "Orders update"
-                );
-                for order in &entry.bids {
-                    debug!(
-                        event = order.event,
-                        order_id = order.order_id,
-                        limit_price = %order.limit_price,
-                        order_qty = %order.order_qty,
-                        timestamp = order.timestamp,
-                        "Order bid"
-                    );
-                }
-                for order in &entry.asks {
-                    debug!(
-                        event = order.event,
-                        order_id = order.order_id,
-                        limit_price = %order.limit_price,
-                        order_qty = %order.order_qty,
-                        timestamp = order.timestamp,
-                        "Order ask"
-                    );
-                }
-            }
+    }
+}
+
+/// Reads and dispatches incoming WebSocket messages indefinitely.
+///
+/// Messages are parsed and logged via `tracing`. The function runs until
+/// the WebSocket connection closes or an error occurs.
+///
+/// # Errors
+///
+/// Returns a [`LeesonError`](crate::LeesonError) if reading from the
+/// WebSocket fails, or if a message cannot be deserialized into the
+/// expected response type.
+pub async fn process_messages(read: &mut WsReader) -> Result<()> {
+    process_messages_with_sink(read, &mut LoggingSink).await
+}
+
+/// Reads incoming WebSocket messages until the connection closes, handing
+/// each parsed message to `sink`.
+///
+/// # Errors
+///
+/// Returns a [`LeesonError`](crate::LeesonError) if reading from the
+/// WebSocket fails, or if a message cannot be deserialized into the
+/// expected response type.
+pub async fn process_messages_with_sink(
+    read: &mut WsReader,
+    sink: &mut impl MessageSink,
+) -> Result<()> {
+    while let Some(msg) = read.next().await {
+        let msg = msg?;
+
+        if let Message::Text(text) = msg {
+            let value: serde_json::Value = serde_json::from_str(&text)
+                .map_err(|e| LeesonError::MalformedMessage(e.to_string()))?;
+
+            dispatch_message(value, sink)?;
         }
+    }
+
+    Ok(())
+}
+
+/// Routes a parsed JSON message to the matching `sink` method.
+fn dispatch_message(value: serde_json::Value, sink: &mut impl MessageSink) -> Result<()> {
+    // Extract routing fields as owned strings to avoid borrow conflicts
+    let msg_method = value
+        .get("method")
+        .and_then(|m| m.as_str())
+        .map(String::from);
+    let msg_channel = value
+        .get("channel")
+        .and_then(|c| c.as_str())
+        .map(String::from);
+
+    // Handle RPC responses first (method-based routing)
+    if let Some(ref method) = msg_method {
+        return handle_rpc_response(method, value, sink);
+    }
+
+    // Handle channel messages
+    if let Some(ref channel) = msg_channel {
+        return handle_channel_message(channel, value, sink);
+    }
+
+    Ok(())
+}
+
+/// Handles RPC-style responses (method-based).
+fn handle_rpc_response(
+    method: &str,
+    value: serde_json::Value,
+    sink: &mut impl MessageSink,
+) -> Result<()> {
+    match method {
+        "pong" => sink.on_pong(serde_json::from_value(value)?),
+        "add_order" => sink.on_add_order(serde_json::from_value(value)?),
+        "batch_add" => sink.on_batch_add(serde_json::from_value(value)?),
+        "batch_cancel" => sink.on_batch_cancel(serde_json::from_value(value)?),
+        "cancel_order" => sink.on_cancel_order(serde_json::from_value(value)?),
+        "cancel_all" => sink.on_cancel_all(serde_json::from_value(value)?),
+        "cancel_all_orders_after" => sink.on_cancel_after(serde_json::from_value(value)?),
+        "amend_order" => sink.on_amend_order(serde_json::from_value(value)?),
+        "edit_order" => sink.on_edit_order(serde_json::from_value(value)?),
+        _ => {
+            warn!(method = method, "Unknown RPC method");
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles channel-based messages (subscriptions).
+fn handle_channel_message(
+    channel: &str,
+    value: serde_json::Value,
+    sink: &mut impl MessageSink,
+) -> Result<()> {
+    match channel {
+        ch if ch == Channel::Heartbeat.as_str() => sink.on_heartbeat(),
+        ch if ch == Channel::Status.as_str() => sink.on_status(serde_json::from_value(value)?),
+        ch if ch == Channel::Executions.as_str() => {
+            sink.on_execution(serde_json::from_value(value)?)
+        }
+        ch if ch == Channel::Ticker.as_str() => sink.on_ticker(serde_json::from_value(value)?),
+        ch if ch == Channel::Book.as_str() => sink.on_book(serde_json::from_value(value)?),
+        ch if ch == Channel::Candles.as_str() => sink.on_candle(serde_json::from_value(value)?),
+        ch if ch == Channel::Trades.as_str() => sink.on_trade(serde_json::from_value(value)?),
+        ch if ch == Channel::Instruments.as_str() => {
+            sink.on_instrument(serde_json::from_value(value)?)
+        }
+        ch if ch == Channel::Orders.as_str() => sink.on_orders(serde_json::from_value(value)?),
         _ => {
             warn!(channel = channel, "Unknown channel");
         }
@@ -493,3 +604,73 @@ fn handle_data_channel_update(channel: &str, value: serde_json::Value) -> Result
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::test_support::loopback;
+    use futures_util::SinkExt;
+
+    /// Records the symbols of ticker messages and counts heartbeats.
+    #[derive(Default)]
+    struct RecordingSink {
+        tickers: Vec<(String, String)>,
+        heartbeats: usize,
+    }
+
+    impl MessageSink for RecordingSink {
+        fn on_ticker(&mut self, response: TickerUpdateResponse) {
+            for tick in response.data {
+                self.tickers.push((response.tpe.clone(), tick.symbol));
+            }
+        }
+
+        fn on_heartbeat(&mut self) {
+            self.heartbeats += 1;
+        }
+    }
+
+    fn ticker_message(tpe: &str, symbol: &str) -> Message {
+        Message::Text(
+            serde_json::json!({
+                "channel": "ticker",
+                "type": tpe,
+                "data": [{
+                    "symbol": symbol, "bid": "100", "bid_qty": "1", "ask": "101",
+                    "ask_qty": "1", "last": "100.5", "volume": "10", "vwap": "100",
+                    "low": "99", "high": "102", "change": "1", "change_pct": "1"
+                }]
+            })
+            .to_string()
+            .into(),
+        )
+    }
+
+    #[tokio::test]
+    async fn sink_receives_parsed_messages_until_close() {
+        let (_write, mut read, _server) = loopback(|mut ws| async move {
+            ws.send(ticker_message("snapshot", "BTC/USD")).await.is_ok()
+                && ws
+                    .send(Message::Text(r#"{"channel":"heartbeat"}"#.into()))
+                    .await
+                    .is_ok()
+                && ws.send(ticker_message("update", "ETH/USD")).await.is_ok()
+                && ws.close(None).await.is_ok()
+        })
+        .await;
+        let mut sink = RecordingSink::default();
+
+        process_messages_with_sink(&mut read, &mut sink)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sink.tickers,
+            [
+                ("snapshot".to_string(), "BTC/USD".to_string()),
+                ("update".to_string(), "ETH/USD".to_string()),
+            ]
+        );
+        assert_eq!(sink.heartbeats, 1);
+    }
+}
@@ -4,11 +4,15 @@
 //! This module is organized by domain:
 //! - [`subscription`] - Channel subscribe/unsubscribe operations
 //! - [`trading`] - Order management RPC operations
+//! - [`batch`] - Batching of outgoing orders into `batch_add` requests
 //! - [`handler`] - Incoming message processing
 
+pub mod batch;
 pub mod connection;
 mod handler;
 mod subscription;
+#[cfg(test)]
+mod test_support;
 mod trading;
 
 use std::sync::Arc;
@@ -26,11 +30,13 @@ use crate::Result;
 use crate::models::PingRequest;
 
 // Re-export submodule functions at the crate level for convenience
-pub use connection::{ConnectionCommand, ConnectionManager};
-pub use handler::process_messages;
+pub use batch::{BATCH_WINDOW, BatchOrderManager};
+pub use connection::{ConnectionCommand, ConnectionManager, ConnectionStats, RequestIds};
+pub use handler::{LoggingSink, MessageSink, process_messages, process_messages_with_sink};
 pub use subscription::{
-    subscribe, subscribe_balances, subscribe_book, subscribe_executions, subscribe_instrument,
-    unsubscribe, unsubscribe_balances, unsubscribe_executions, unsubscribe_instrument,
+    subscribe, subscribe_balances, subscribe_book, subscribe_book_with_snapshot, subscribe_candles,
+    subscribe_executions, subscribe_instrument, unsubscribe, unsubscribe_balances,
+    unsubscribe_candles, unsubscribe_executions, unsubscribe_instrument,
 };
 pub use trading::{
     add_order, amend_order, batch_add, batch_cancel, cancel_after, cancel_all, cancel_order,
@@ -1,16 +1,19 @@
 //! Channel subscription and unsubscription operations.
 
-use futures_util::SinkExt;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
 use tracing::{debug, info};
 use tungstenite::Message;
 
-use super::WsWriter;
-use crate::Result;
-use crate::models::book::BookDepth;
+use super::{WsReader, WsWriter};
+use crate::models::book::{BookDepth, BookUpdateResponse};
 use crate::models::{
     BalancesSubscribeRequest, BalancesUnsubscribeRequest, BookSubscribeRequest, Channel,
     ExecutionsSubscribeRequest, ExecutionsUnsubscribeRequest, SubscribeRequest, UnsubscribeRequest,
 };
+use crate::tui::app::Timeframe;
+use crate::{LeesonError, Result};
 
 /// Subscribes to a symbol-based channel (e.g., ticker, book, trades).
 ///
@@ -63,6 +66,105 @@ pub async fn subscribe_book(
     Ok(())
 }
 
+/// Subscribes to the book channel and waits for the first snapshot of one
+/// of `symbols`.
+///
+/// Reads from `read` until a book `snapshot` for a requested symbol
+/// arrives, so callers can initialize their book state before applying any
+/// update. Every other message read in the meantime is discarded.
+///
+/// # Errors
+///
+/// Returns [`LeesonError::Timeout`] if no snapshot arrives within
+/// `timeout`, [`LeesonError::WebSocketClosed`] if the connection closes
+/// first, or any error from sending the subscription or reading the socket.
+pub async fn subscribe_book_with_snapshot(
+    write: &mut WsWriter,
+    read: &mut WsReader,
+    symbols: &[String],
+    depth: BookDepth,
+    timeout: Duration,
+) -> Result<BookUpdateResponse> {
+    subscribe_book(write, symbols, depth, None).await?;
+
+    let wait_for_snapshot = async {
+        while let Some(msg) = read.next().await {
+            let text = match msg? {
+                Message::Text(text) => text,
+                Message::Close(frame) => return Err(LeesonError::from_close_frame(frame.as_ref())),
+                _ => continue,
+            };
+            let Ok(response) = serde_json::from_str::<BookUpdateResponse>(&text) else {
+                continue;
+            };
+            if response.channel == Channel::Book.as_str()
+                && response.tpe == "snapshot"
+                && response.data.iter().any(|d| symbols.contains(&d.symbol))
+            {
+                return Ok(response);
+            }
+        }
+        Err(LeesonError::from_close_frame(None))
+    };
+
+    tokio::time::timeout(timeout, wait_for_snapshot)
+        .await
+        .map_err(|_| {
+            LeesonError::Timeout(format!(
+                "no book snapshot for {symbols:?} within {}ms",
+                timeout.as_millis()
+            ))
+        })?
+}
+
+/// Subscribes to the `ohlc` channel with candles of the given timeframe.
+///
+/// # Errors
+///
+/// Returns a [`LeesonError`](crate::LeesonError) if sending the subscription message fails.
+pub async fn subscribe_candles(
+    write: &mut WsWriter,
+    symbols: &[String],
+    timeframe: Timeframe,
+) -> Result<()> {
+    let request =
+        SubscribeRequest::new(&Channel::Candles, symbols, None).with_interval(timeframe.interval());
+    let json = serde_json::to_string(&request)?;
+    debug!("Sending candles subscribe request: {}", json);
+    write.send(Message::Text(json.into())).await?;
+    info!(
+        channel = Channel::Candles.as_str(),
+        interval = timeframe.interval(),
+        ?symbols,
+        "Subscribed to candles channel"
+    );
+
+    Ok(())
+}
+
+/// Unsubscribes from `ohlc` candles of the given timeframe.
+///
+/// # Errors
+///
+/// Returns a [`LeesonError`](crate::LeesonError) if sending the unsubscribe message fails.
+pub async fn unsubscribe_candles(
+    write: &mut WsWriter,
+    symbols: &[String],
+    timeframe: Timeframe,
+) -> Result<()> {
+    let request = UnsubscribeRequest::new(&Channel::Candles, symbols, None)
+        .with_interval(timeframe.interval());
+    let json = serde_json::to_string(&request)?;
+    write.send(Message::Text(json.into())).await?;
+    info!(
+        channel = Channel::Candles.as_str(),
+        interval = timeframe.interval(),
+        "Unsubscribed from candles channel"
+    );
+
+    Ok(())
+}
+
 /// Subscribes to the instrument channel (no symbol parameter required).
 ///
 /// # Errors
@@ -194,3 +296,74 @@ pub async fn unsubscribe(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::test_support::loopback;
+
+    fn book_message(tpe: &str, symbol: &str) -> Message {
+        let json = serde_json::json!({
+            "channel": "book",
+            "type": tpe,
+            "data": [{
+                "symbol": symbol,
+                "bids": [{ "price": "50000.0", "qty": "1.0" }],
+                "asks": [{ "price": "50010.0", "qty": "2.0" }],
+                "checksum": 0,
+                "timestamp": "2024-01-01T00:00:00.000000Z"
+            }]
+        });
+        Message::Text(json.to_string().into())
+    }
+
+    #[tokio::test]
+    async fn subscribe_book_with_snapshot_skips_early_messages() {
+        let (mut write, mut read, _server) = loopback(|mut ws| async move {
+            // Wait for the subscription before replying
+            let _ = ws.next().await;
+            let ack = r#"{"method":"subscribe","success":true,"result":{"channel":"book","symbol":"BTC/USD"}}"#;
+            ws.send(Message::Text(ack.into())).await.is_ok()
+                && ws.send(book_message("update", "BTC/USD")).await.is_ok()
+                && ws.send(book_message("snapshot", "ETH/USD")).await.is_ok()
+                && ws.send(book_message("snapshot", "BTC/USD")).await.is_ok()
+                && ws.next().await.is_some()
+        })
+        .await;
+
+        let snapshot = subscribe_book_with_snapshot(
+            &mut write,
+            &mut read,
+            &["BTC/USD".to_string()],
+            BookDepth::D10,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(snapshot.tpe, "snapshot");
+        assert_eq!(snapshot.data[0].symbol, "BTC/USD");
+        assert_eq!(snapshot.data[0].asks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_book_with_snapshot_times_out() {
+        let (mut write, mut read, _server) = loopback(|mut ws| async move {
+            while ws.next().await.is_some() {}
+            true
+        })
+        .await;
+
+        let err = subscribe_book_with_snapshot(
+            &mut write,
+            &mut read,
+            &["BTC/USD".to_string()],
+            BookDepth::D10,
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(err, LeesonError::Timeout(_)), "{err}");
+    }
+}
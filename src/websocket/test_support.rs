@@ -0,0 +1,32 @@
+//! Helpers shared by the WebSocket unit tests.
+
+use futures_util::StreamExt;
+use tokio::net::TcpListener;
+use tokio_tungstenite::MaybeTlsStream;
+
+use super::{WsReader, WsWriter};
+
+/// Connects a client to a loopback WebSocket server driven by `server`.
+pub(crate) async fn loopback<F, Fut>(
+    server: F,
+) -> (WsWriter, WsReader, tokio::task::JoinHandle<bool>)
+where
+    F: FnOnce(tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = bool> + Send,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+        server(ws).await
+    });
+
+    let tcp = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (ws, _) =
+        tokio_tungstenite::client_async(format!("ws://{addr}"), MaybeTlsStream::Plain(tcp))
+            .await
+            .unwrap();
+    let (write, read) = ws.split();
+    (write, read, handle)
+}
@@ -41,12 +41,12 @@ fn test_ticker_update_response_deserializes() {
     assert_eq!(ticker.ask, dec!(42155.0));
     assert_eq!(ticker.ask_qty, dec!(0.75));
     assert_eq!(ticker.last, dec!(42152.0));
-    assert_eq!(ticker.volume, dec!(1250.5));
-    assert_eq!(ticker.vwap, dec!(42100.25));
-    assert_eq!(ticker.low, dec!(41800.0));
-    assert_eq!(ticker.high, dec!(42500.0));
-    assert_eq!(ticker.change, dec!(352.0));
-    assert_eq!(ticker.change_pct, dec!(0.84));
+    assert_eq!(ticker.volume, Some(dec!(1250.5)));
+    assert_eq!(ticker.vwap, Some(dec!(42100.25)));
+    assert_eq!(ticker.low, Some(dec!(41800.0)));
+    assert_eq!(ticker.high, Some(dec!(42500.0)));
+    assert_eq!(ticker.change, Some(dec!(352.0)));
+    assert_eq!(ticker.change_pct, Some(dec!(0.84)));
 }
 
 #[test]
@@ -0,0 +1,81 @@
+"""Exercises the risk guard through the `leeson` Python extension.
+
+Build the module first:
+
+    maturin develop --features python,extension-module
+    python -m pytest tests/python_bindings_test.py
+"""
+
+from decimal import Decimal
+
+import leeson
+
+RISK_JSON = """{
+  "defaults": {
+    "max_order_qty": "1.0",
+    "max_notional_value": "100000",
+    "confirm_above_notional": "50000",
+    "max_trades_per_day": 1,
+    "max_trades_per_week": 10,
+    "max_trades_per_month": 20
+  }
+}"""
+
+
+def limit_buy(qty: str, price: str) -> leeson.AddOrder:
+    return leeson.AddOrder(
+        order_type=leeson.OrderType.Limit,
+        side=leeson.OrderSide.Buy,
+        symbol="BTC/USD",
+        order_qty=Decimal(qty),
+        limit_price=Decimal(price),
+    )
+
+
+def guard() -> leeson.RiskGuard:
+    return leeson.RiskGuard(leeson.RiskConfig.from_json(RISK_JSON))
+
+
+def test_small_order_is_approved():
+    result = guard().check_order(limit_buy("0.1", "50000"))
+
+    assert result == {"verdict": "approved", "reason": None}
+
+
+def test_large_notional_requires_confirmation():
+    result = guard().check_order(limit_buy("0.9", "60000"))
+
+    assert result["verdict"] == "requires_confirmation"
+    assert "confirmation threshold" in result["reason"]
+
+
+def test_oversized_order_is_rejected():
+    result = guard().check_order(limit_buy("2", "50000"))
+
+    assert result["verdict"] == "rejected"
+    assert "exceeds max" in result["reason"]
+
+
+def test_recorded_submissions_count_against_rate_limit():
+    risk_guard = guard()
+    risk_guard.record_submission("BTC/USD", leeson.OrderSide.Buy)
+
+    result = risk_guard.check_order(limit_buy("0.1", "50000"))
+
+    assert result["verdict"] == "rejected"
+    assert "trades in day" in result["reason"]
+
+
+def test_invalid_config_raises_value_error():
+    try:
+        leeson.RiskConfig.from_json("{}")
+    except ValueError:
+        return
+    raise AssertionError("expected ValueError for a config without defaults")
+
+
+if __name__ == "__main__":
+    for name, test in list(globals().items()):
+        if name.startswith("test_"):
+            test()
+    print("ok")
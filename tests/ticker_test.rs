@@ -40,12 +40,12 @@ fn deserialize_ticker_update_response() {
     assert_eq!(tick.ask, dec!(42155.00));
     assert_eq!(tick.ask_qty, dec!(0.75));
     assert_eq!(tick.last, dec!(42152.30));
-    assert_eq!(tick.volume, dec!(1234.56789));
-    assert_eq!(tick.vwap, dec!(42000.12));
-    assert_eq!(tick.low, dec!(41500.00));
-    assert_eq!(tick.high, dec!(42800.00));
-    assert_eq!(tick.change, dec!(652.30));
-    assert_eq!(tick.change_pct, dec!(1.57));
+    assert_eq!(tick.volume, Some(dec!(1234.56789)));
+    assert_eq!(tick.vwap, Some(dec!(42000.12)));
+    assert_eq!(tick.low, Some(dec!(41500.00)));
+    assert_eq!(tick.high, Some(dec!(42800.00)));
+    assert_eq!(tick.change, Some(dec!(652.30)));
+    assert_eq!(tick.change_pct, Some(dec!(1.57)));
 }
 
 #[test]
@@ -68,8 +68,8 @@ fn deserialize_ticker_data_directly() {
     let tick: TickerData = serde_json::from_str(json).unwrap();
 
     assert_eq!(tick.symbol, "ETH/USD");
-    assert_eq!(tick.change, dec!(-15.45));
-    assert_eq!(tick.change_pct, dec!(-0.68));
+    assert_eq!(tick.change, Some(dec!(-15.45)));
+    assert_eq!(tick.change_pct, Some(dec!(-0.68)));
 }
 
 #[test]
@@ -49,6 +49,48 @@ fn test_subscribe_request_serializes() {
     assert!(value["params"].get("token").is_none());
 }
 
+#[test]
+fn test_subscribe_request_omits_interval_by_default() {
+    let symbols = vec!["BTC/USD".to_string()];
+    let request = SubscribeRequest::new(&Channel::Candles, &symbols, None);
+
+    let json = serde_json::to_string(&request).expect("Failed to serialize subscribe request");
+    let value: serde_json::Value =
+        serde_json::from_str(&json).expect("Failed to parse serialized JSON");
+
+    assert!(value["params"].get("interval").is_none());
+    assert!(value.get("req_id").is_none());
+}
+
+#[test]
+fn test_candles_subscribe_request_with_interval_serializes() {
+    let symbols = vec!["BTC/USD".to_string()];
+    let request = SubscribeRequest::new(&Channel::Candles, &symbols, None)
+        .with_interval(15)
+        .with_req_id(9);
+
+    let json = serde_json::to_string(&request).expect("Failed to serialize subscribe request");
+    let value: serde_json::Value =
+        serde_json::from_str(&json).expect("Failed to parse serialized JSON");
+
+    assert_eq!(value["params"]["channel"], "ohlc");
+    assert_eq!(value["params"]["interval"], 15);
+    assert_eq!(value["req_id"], 9);
+}
+
+#[test]
+fn test_candles_unsubscribe_request_with_interval_serializes() {
+    let symbols = vec!["ETH/USD".to_string()];
+    let request = UnsubscribeRequest::new(&Channel::Candles, &symbols, None).with_interval(60);
+
+    let json = serde_json::to_string(&request).expect("Failed to serialize unsubscribe request");
+    let value: serde_json::Value =
+        serde_json::from_str(&json).expect("Failed to parse serialized JSON");
+
+    assert_eq!(value["method"], "unsubscribe");
+    assert_eq!(value["params"]["interval"], 60);
+}
+
 #[test]
 fn test_unsubscribe_request_serializes() {
     let symbols = vec!["BTC/USD".to_string()];